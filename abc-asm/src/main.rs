@@ -0,0 +1,192 @@
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use swf::avm2::asm::{assemble, disassemble};
+use swf::avm2::read::Reader;
+use swf::avm2::write::Writer;
+use swf::read::Reader as SwfReader;
+use swf::write::write_swf_raw_tags;
+use swf::{decompress_swf, SwfBuf, TagCode};
+
+#[derive(Parser, Debug)]
+#[clap(name = "Ruffle ABC Assembler", author, version)]
+struct Opt {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the ABC tags in a SWF
+    List {
+        /// The SWF file to inspect
+        swf: PathBuf,
+    },
+
+    /// Disassemble an ABC tag of a SWF, or a raw `.abc` file, to text
+    Disassemble {
+        /// The SWF or `.abc` file to disassemble
+        input: PathBuf,
+
+        /// The file to write the disassembly to, instead of stdout
+        output: Option<PathBuf>,
+
+        /// Which ABC tag of the SWF to disassemble, as numbered by `list`
+        #[clap(long, short, default_value_t = 0)]
+        tag: usize,
+    },
+
+    /// Assemble text back into ABC bytecode
+    Assemble {
+        /// The text file to assemble
+        input: PathBuf,
+
+        /// The `.abc` file to write, or the SWF to write when `--swf` is given
+        output: PathBuf,
+
+        /// Write a copy of this SWF with one of its ABC tags replaced, instead
+        /// of a raw `.abc` file
+        #[clap(long)]
+        swf: Option<PathBuf>,
+
+        /// Which ABC tag of the SWF to replace, as numbered by `list`
+        #[clap(long, short, default_value_t = 0)]
+        tag: usize,
+    },
+}
+
+/// The location of a `DoAbc` or `DoAbc2` tag in a decompressed SWF.
+struct AbcTag {
+    /// The name given by a `DoAbc2` tag.
+    name: String,
+    tag_code: u16,
+    /// The whole tag, including its header.
+    tag: Range<usize>,
+    /// The body of the tag, after its header.
+    body_start: usize,
+    /// The ABC data within the body.
+    data: Range<usize>,
+}
+
+fn abc_tags(swf_buf: &SwfBuf) -> Result<Vec<AbcTag>> {
+    let data = &swf_buf.data[..];
+    let mut reader = SwfReader::new(data, swf_buf.header.version());
+    let mut tags = vec![];
+    while !reader.get_ref().is_empty() {
+        let start = data.len() - reader.get_ref().len();
+        let (tag_code, length) = reader.read_tag_code_and_length()?;
+        let body_start = data.len() - reader.get_ref().len();
+        let end = body_start + length;
+        let body = data
+            .get(body_start..end)
+            .ok_or_else(|| anyhow!("Tag at offset {start} is truncated"))?;
+        *reader.get_mut() = &data[end..];
+
+        if tag_code == TagCode::DoAbc as u16 {
+            tags.push(AbcTag {
+                name: String::new(),
+                tag_code,
+                tag: start..end,
+                body_start,
+                data: body_start..end,
+            });
+        } else if tag_code == TagCode::DoAbc2 as u16 {
+            // DoAbc2 starts with 32 bits of flags and a null-terminated name.
+            let name_len = body
+                .get(4..)
+                .and_then(|name| name.iter().position(|b| *b == 0))
+                .ok_or_else(|| anyhow!("DoAbc2 tag at offset {start} is malformed"))?;
+            tags.push(AbcTag {
+                name: String::from_utf8_lossy(&body[4..4 + name_len]).into_owned(),
+                tag_code,
+                tag: start..end,
+                body_start,
+                data: body_start + 4 + name_len + 1..end,
+            });
+        }
+    }
+    Ok(tags)
+}
+
+fn read_swf(path: &Path) -> Result<SwfBuf> {
+    let data = std::fs::read(path).with_context(|| format!("Couldn't read {path:?}"))?;
+    Ok(decompress_swf(&data[..])?)
+}
+
+fn select_tag(tags: Vec<AbcTag>, index: usize) -> Result<AbcTag> {
+    let count = tags.len();
+    tags.into_iter()
+        .nth(index)
+        .ok_or_else(|| anyhow!("There is no ABC tag {index}; the SWF has {count}"))
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+
+    match opt.command {
+        Command::List { swf } => {
+            let swf_buf = read_swf(&swf)?;
+            for (i, tag) in abc_tags(&swf_buf)?.iter().enumerate() {
+                println!("{i}: \"{}\" ({} bytes)", tag.name, tag.data.len());
+            }
+        }
+        Command::Disassemble { input, output, tag } => {
+            let abc = if input.extension().is_some_and(|e| e == "abc") {
+                std::fs::read(&input).with_context(|| format!("Couldn't read {input:?}"))?
+            } else {
+                let swf_buf = read_swf(&input)?;
+                let tag = select_tag(abc_tags(&swf_buf)?, tag)?;
+                swf_buf.data[tag.data].to_vec()
+            };
+            let abc_file = Reader::new(&abc[..]).read()?;
+
+            let mut output: Box<dyn Write> = match &output {
+                Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+            output.write_all(disassemble(&abc_file).as_bytes())?;
+            output.flush()?;
+        }
+        Command::Assemble {
+            input,
+            output,
+            swf,
+            tag,
+        } => {
+            let source = std::fs::read_to_string(&input)
+                .with_context(|| format!("Couldn't read {input:?}"))?;
+            let abc_file = assemble(&source).with_context(|| format!("In {input:?}"))?;
+            let mut abc = vec![];
+            Writer::new(&mut abc).write(abc_file)?;
+
+            match swf {
+                Some(swf) => {
+                    let swf_buf = read_swf(&swf)?;
+                    let tag = select_tag(abc_tags(&swf_buf)?, tag)?;
+
+                    // Copy the tag stream, swapping in the new ABC data and
+                    // rewriting the tag header for its new length.
+                    let data = &swf_buf.data[..];
+                    let prefix = &data[tag.body_start..tag.data.start];
+                    let length = (prefix.len() + abc.len()) as u32;
+
+                    let mut tags = data[..tag.tag.start].to_vec();
+                    tags.extend_from_slice(&((tag.tag_code << 6) | 0b111111).to_le_bytes());
+                    tags.extend_from_slice(&length.to_le_bytes());
+                    tags.extend_from_slice(prefix);
+                    tags.extend_from_slice(&abc);
+                    tags.extend_from_slice(&data[tag.tag.end..]);
+
+                    let file = BufWriter::new(File::create(&output)?);
+                    write_swf_raw_tags(swf_buf.header.swf_header(), &tags, file)?;
+                }
+                None => std::fs::write(&output, abc)?,
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use swf::avm1::decompile::find_scripts;
+use swf::{decompress_swf, parse_swf};
+
+#[derive(Parser, Debug)]
+#[clap(name = "Ruffle AVM1 Decompiler", author, version)]
+struct Opt {
+    /// The SWF file to decompile
+    #[clap(name = "swf")]
+    swf: PathBuf,
+
+    /// The file to write the decompiled source to, instead of stdout
+    #[clap(name = "output")]
+    output_path: Option<PathBuf>,
+
+    /// Only list the scripts in the SWF, without decompiling them
+    #[clap(long, short)]
+    list: bool,
+
+    /// Only decompile scripts whose location or event contains this text,
+    /// such as "frame 1" or "sprite 5"
+    #[clap(long, short)]
+    script: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+
+    let data = std::fs::read(&opt.swf)?;
+    let swf_buf = decompress_swf(&data[..])?;
+    let swf = parse_swf(&swf_buf)?;
+    let version = swf.header.version();
+    if swf.header.is_action_script_3() {
+        return Err(anyhow!("This SWF uses ActionScript 3, which is not AVM1."));
+    }
+
+    let mut output: Box<dyn Write> = match &opt.output_path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    let scripts = find_scripts(&swf.tags, version)
+        .into_iter()
+        .filter(|script| {
+            opt.script
+                .as_ref()
+                .is_none_or(|filter| script.to_string().contains(filter))
+        });
+
+    for (index, script) in scripts.enumerate() {
+        if opt.list {
+            writeln!(output, "{script}")?;
+            continue;
+        }
+
+        if index > 0 {
+            writeln!(output)?;
+        }
+        writeln!(output, "// {script}")?;
+        write!(output, "{}", script.decompile(version))?;
+    }
+
+    output.flush()?;
+    Ok(())
+}
@@ -753,53 +753,67 @@ fn collect_stubs(root: &Path, out_dir: &Path) -> Result<(), Box<dyn std::error::
         .filter(|f| f.path().extension() == Some(OsStr::new("as")))
     {
         let contents = fs::read_to_string(entry.path())?;
+        // Report the location the same way `file!()` does for Rust stubs
+        let file = Path::new("core/src/avm2/globals")
+            .join(entry.path().strip_prefix(root)?)
+            .to_string_lossy()
+            .replace('\\', "/");
         for entry in pattern.captures_iter(&contents) {
             let class = &entry["class"];
             let property = entry.name("property").map(|m| m.as_str());
             let specifics = entry.name("specifics").map(|m| m.as_str());
+            let start = entry.get(0).expect("Match should exist").start();
+            let line = contents[..start].matches('\n').count() as u32 + 1;
 
-            match (&entry["type"], property, specifics) {
-                ("stub_method", Some(property), Some(specifics)) => stubs.push(quote! {
+            let stub = match (&entry["type"], property, specifics) {
+                ("stub_method", Some(property), Some(specifics)) => quote! {
                     crate::stub::Stub::Avm2Method {
                         class: Cow::Borrowed(#class),
                         method: Cow::Borrowed(#property),
-                        specifics: Cow::Borrowed(#specifics)
+                        specifics: Some(Cow::Borrowed(#specifics))
                     }
-                }),
-                ("stub_method", Some(property), None) => stubs.push(quote! {
+                },
+                ("stub_method", Some(property), None) => quote! {
                     crate::stub::Stub::Avm2Method {
                         class: Cow::Borrowed(#class),
                         method: Cow::Borrowed(#property),
                         specifics: None
                     }
-                }),
-                ("stub_getter", Some(property), _) => stubs.push(quote! {
+                },
+                ("stub_getter", Some(property), _) => quote! {
                     crate::stub::Stub::Avm2Getter {
                         class: Cow::Borrowed(#class),
                         property: Cow::Borrowed(#property)
                     }
-                }),
-                ("stub_setter", Some(property), _) => stubs.push(quote! {
+                },
+                ("stub_setter", Some(property), _) => quote! {
                     crate::stub::Stub::Avm2Setter {
                         class: Cow::Borrowed(#class),
                         property: Cow::Borrowed(#property)
                     }
-                }),
-                ("stub_constructor", Some(property), _) => stubs.push(quote! {
+                },
+                ("stub_constructor", Some(property), _) => quote! {
                     // Property is actually specifics here
                     crate::stub::Stub::Avm2Constructor {
                         class: Cow::Borrowed(#class),
                         specifics: Some(Cow::Borrowed(#property))
                     }
-                }),
-                ("stub_constructor", None, _) => stubs.push(quote! {
+                },
+                ("stub_constructor", None, _) => quote! {
                     crate::stub::Stub::Avm2Constructor {
                         class: Cow::Borrowed(#class),
                         specifics: None
                     }
-                }),
+                },
                 _ => panic!("Unsupported stub type {}", &entry["type"]),
-            }
+            };
+            stubs.push(quote! {
+                crate::stub::KnownStub {
+                    stub: &#stub,
+                    file: #file,
+                    line: #line
+                }
+            });
         }
     }
 
@@ -808,7 +822,7 @@ fn collect_stubs(root: &Path, out_dir: &Path) -> Result<(), Box<dyn std::error::
         use std::borrow::Cow;
 
         #[cfg(feature = "known_stubs")]
-        pub static AS_DEFINED_STUBS: &[crate::stub::Stub] = &[
+        pub static AS_DEFINED_STUBS: &[crate::stub::KnownStub] = &[
             #(#stubs,)*
         ];
     };
@@ -11,12 +11,14 @@ mod activation;
 mod callable_value;
 mod clamp;
 mod debug;
+mod decode;
 mod error;
 mod flv;
 mod fscommand;
 pub(crate) mod globals;
 mod object;
 mod object_reference;
+mod op;
 mod property;
 mod property_map;
 mod runtime;
@@ -68,35 +70,26 @@ macro_rules! avm_error {
 #[macro_export]
 macro_rules! avm1_stub {
     ($activation: ident, $class: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm1Constructor { class: $class };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
     ($activation: ident, $class: literal, $method: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm1Method {
             class: $class,
             method: $method,
             specifics: None,
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
     ($activation: ident, $class: literal, $method: literal, $specifics: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm1Method {
             class: $class,
             method: $method,
             specifics: Some($specifics),
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
 }
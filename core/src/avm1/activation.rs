@@ -1,9 +1,10 @@
 use crate::avm1::callable_value::CallableValue;
+use crate::avm1::decode::DecodedActions;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
 use crate::avm1::object::{Object, TObject};
+use crate::avm1::op::{JumpTarget, Op, PushValue};
 use crate::avm1::property::Attribute;
-use crate::avm1::runtime::skip_actions;
 use crate::avm1::scope::{Scope, ScopeClass};
 use crate::avm1::{fscommand, globals, scope, ArrayObject, ScriptObject, Value};
 use crate::backend::navigator::{NavigationMethod, Request};
@@ -27,7 +28,6 @@ use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::*;
 use url::form_urlencoded;
-use web_time::Instant;
 
 use super::object_reference::MovieClipReference;
 
@@ -430,10 +430,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
-        let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
+        let mut actions = self.decoded_actions(&code);
+        let mut ip = 0;
 
         loop {
-            let result = self.do_action(&code, &mut read);
+            let result = self.do_action(&code, &mut actions, &mut ip);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
                 Ok(FrameControl::Continue) => {}
@@ -442,11 +443,22 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
-    /// Run a single action from a given action reader.
-    fn do_action<'b>(
+    /// Returns the decoded actions of `code`, decoding them the first time they run.
+    fn decoded_actions(&mut self, code: &SwfSlice) -> Gc<'gc, DecodedActions<'gc>> {
+        let swf_version = self.swf_version();
+        self.context
+            .avm1
+            .decoded_actions(&mut self.context.strings, code, swf_version)
+    }
+
+    /// Run the action at `ip` in the decoded actions of `data`.
+    ///
+    /// Jumps may switch to the decoded actions of another part of the movie.
+    fn do_action(
         &mut self,
-        data: &'b SwfSlice,
-        reader: &mut Reader<'b>,
+        data: &SwfSlice,
+        actions: &mut Gc<'gc, DecodedActions<'gc>>,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         *self.context.actions_since_timeout_check += 1;
         if *self.context.actions_since_timeout_check >= 2000 {
@@ -456,120 +468,137 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             }
         }
 
-        if reader.get_ref().as_ptr() as usize >= data.as_ref().as_ptr_range().end as usize {
+        let decoded = *actions;
+        let Some(op) = decoded.ops().get(*ip) else {
             //Executing beyond the end of a function constitutes an implicit return.
-            Ok(FrameControl::Return(ReturnType::Implicit))
-        } else {
-            let action = reader.read_action()?;
-            avm_debug!(
-                self.context.avm1,
-                "({}) Action: {action:?}",
-                self.id.depth(),
-            );
+            return Ok(FrameControl::Return(ReturnType::Implicit));
+        };
+        *ip += 1;
+        avm_debug!(self.context.avm1, "({}) Action: {op:?}", self.id.depth());
+
+        match op {
+            Op::Add => self.action_add(),
+            Op::Add2 => self.action_add_2(),
+            Op::And => self.action_and(),
+            Op::AsciiToChar => self.action_ascii_to_char(),
+            Op::BitAnd => self.action_bit_and(),
+            Op::BitLShift => self.action_bit_lshift(),
+            Op::BitOr => self.action_bit_or(),
+            Op::BitRShift => self.action_bit_rshift(),
+            Op::BitURShift => self.action_bit_urshift(),
+            Op::BitXor => self.action_bit_xor(),
+            Op::Call => self.action_call(),
+            Op::CallFunction => self.action_call_function(),
+            Op::CallMethod => self.action_call_method(),
+            Op::CastOp => self.action_cast_op(),
+            Op::CharToAscii => self.action_char_to_ascii(),
+            Op::CloneSprite => self.action_clone_sprite(),
+            Op::ConstantPool(constant_pool) => self.action_constant_pool(*constant_pool),
+            Op::Decrement => self.action_decrement(),
+            Op::DefineLocal => self.action_define_local(),
+            Op::DefineLocal2 => self.action_define_local_2(),
+            Op::Delete => self.action_delete(),
+            Op::Delete2 => self.action_delete_2(),
+            Op::Divide => self.action_divide(),
+            Op::End => self.action_end(),
+            Op::EndDrag => self.action_end_drag(),
+            Op::Enumerate => self.action_enumerate(),
+            Op::Enumerate2 => self.action_enumerate_2(),
+            Op::Equals => self.action_equals(),
+            Op::Equals2 => self.action_equals_2(),
+            Op::Extends => self.action_extends(),
+            Op::GetMember => self.action_get_member(),
+            Op::GetProperty => self.action_get_property(),
+            Op::GetTime => self.action_get_time(),
+            Op::GetVariable => self.action_get_variable(),
+            Op::GetUrl2(action) => self.action_get_url_2(action.clone()),
+            Op::GotoFrame(action) => self.action_goto_frame(action.clone()),
+            Op::GotoFrame2(action) => self.action_goto_frame_2(action.clone()),
+            Op::Greater => self.action_greater(),
+            Op::If { target } => self.action_if(*target, data, actions, ip),
+            Op::Increment => self.action_increment(),
+            Op::InitArray => self.action_init_array(),
+            Op::InitObject => self.action_init_object(),
+            Op::ImplementsOp => self.action_implements_op(),
+            Op::InstanceOf => self.action_instance_of(),
+            Op::Jump { target } => self.action_jump(*target, data, actions, ip),
+            Op::Less => self.action_less(),
+            Op::Less2 => self.action_less_2(),
+            Op::MBAsciiToChar => self.action_mb_ascii_to_char(),
+            Op::MBCharToAscii => self.action_mb_char_to_ascii(),
+            Op::MBStringLength => self.action_mb_string_length(),
+            Op::MBStringExtract => self.action_mb_string_extract(),
+            Op::Modulo => self.action_modulo(),
+            Op::Multiply => self.action_multiply(),
+            Op::NextFrame => self.action_next_frame(),
+            Op::NewMethod => self.action_new_method(),
+            Op::NewObject => self.action_new_object(),
+            Op::Not => self.action_not(),
+            Op::Or => self.action_or(),
+            Op::Play => self.action_play(),
+            Op::Pop => self.action_pop(),
+            Op::PreviousFrame => self.action_prev_frame(),
+            Op::Push(values) => self.action_push(values),
+            Op::PushDuplicate => self.action_push_duplicate(),
+            Op::RandomNumber => self.action_random_number(),
+            Op::RemoveSprite => self.action_remove_sprite(),
+            Op::Return => self.action_return(),
+            Op::SetMember => self.action_set_member(),
+            Op::SetProperty => self.action_set_property(),
+            Op::SetTarget2 => self.action_set_target_2(),
+            Op::SetVariable => self.action_set_variable(),
+            Op::StackSwap => self.action_stack_swap(),
+            Op::StartDrag => self.action_start_drag(),
+            Op::Stop => self.action_stop(),
+            Op::StopSounds => self.action_stop_sounds(),
+            Op::StoreRegister(action) => self.action_store_register(*action),
+            Op::StrictEquals => self.action_strict_equals(),
+            Op::StringAdd => self.action_string_add(),
+            Op::StringEquals => self.action_string_equals(),
+            Op::StringExtract => self.action_string_extract(),
+            Op::StringGreater => self.action_string_greater(),
+            Op::StringLength => self.action_string_length(),
+            Op::StringLess => self.action_string_less(),
+            Op::Subtract => self.action_subtract(),
+            Op::TargetPath => self.action_target_path(),
+            Op::Throw => self.action_throw(),
+            Op::ToggleQuality => self.action_toggle_quality(),
+            Op::ToInteger => self.action_to_integer(),
+            Op::ToNumber => self.action_to_number(),
+            Op::ToString => self.action_to_string(),
+            Op::Trace => self.action_trace(),
+            Op::TypeOf => self.action_type_of(),
+            Op::WaitForFrame { frame, skip_to } => self.action_wait_for_frame(*frame, *skip_to, ip),
+            Op::WaitForFrame2 { skip_to } => self.action_wait_for_frame_2(*skip_to, ip),
+            Op::Undecoded { position } => self.action_undecoded(data, *position),
+        }
+    }
+
+    /// Run an action that wasn't decoded ahead of time, by reading it from
+    /// the movie data again.
+    fn action_undecoded(
+        &mut self,
+        data: &SwfSlice,
+        position: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let mut reader = Reader::new(&data.movie.data()[position as usize..], self.swf_version());
+        let action = reader.read_action()?;
+        avm_debug!(
+            self.context.avm1,
+            "({}) Action: {action:?}",
+            self.id.depth()
+        );
 
-            match action {
-                Action::Add => self.action_add(),
-                Action::Add2 => self.action_add_2(),
-                Action::And => self.action_and(),
-                Action::AsciiToChar => self.action_ascii_to_char(),
-                Action::BitAnd => self.action_bit_and(),
-                Action::BitLShift => self.action_bit_lshift(),
-                Action::BitOr => self.action_bit_or(),
-                Action::BitRShift => self.action_bit_rshift(),
-                Action::BitURShift => self.action_bit_urshift(),
-                Action::BitXor => self.action_bit_xor(),
-                Action::Call => self.action_call(),
-                Action::CallFunction => self.action_call_function(),
-                Action::CallMethod => self.action_call_method(),
-                Action::CastOp => self.action_cast_op(),
-                Action::CharToAscii => self.action_char_to_ascii(),
-                Action::CloneSprite => self.action_clone_sprite(),
-                Action::ConstantPool(action) => self.action_constant_pool(action),
-                Action::Decrement => self.action_decrement(),
-                Action::DefineFunction(action) => self.action_define_function(action.into(), data),
-                Action::DefineFunction2(action) => self.action_define_function(action, data),
-                Action::DefineLocal => self.action_define_local(),
-                Action::DefineLocal2 => self.action_define_local_2(),
-                Action::Delete => self.action_delete(),
-                Action::Delete2 => self.action_delete_2(),
-                Action::Divide => self.action_divide(),
-                Action::End => self.action_end(),
-                Action::EndDrag => self.action_end_drag(),
-                Action::Enumerate => self.action_enumerate(),
-                Action::Enumerate2 => self.action_enumerate_2(),
-                Action::Equals => self.action_equals(),
-                Action::Equals2 => self.action_equals_2(),
-                Action::Extends => self.action_extends(),
-                Action::GetMember => self.action_get_member(),
-                Action::GetProperty => self.action_get_property(),
-                Action::GetTime => self.action_get_time(),
-                Action::GetVariable => self.action_get_variable(),
-                Action::GetUrl(action) => self.action_get_url(action),
-                Action::GetUrl2(action) => self.action_get_url_2(action),
-                Action::GotoFrame(action) => self.action_goto_frame(action),
-                Action::GotoFrame2(action) => self.action_goto_frame_2(action),
-                Action::Greater => self.action_greater(),
-                Action::GotoLabel(action) => self.action_goto_label(action),
-                Action::If(action) => self.action_if(action, reader, data),
-                Action::Increment => self.action_increment(),
-                Action::InitArray => self.action_init_array(),
-                Action::InitObject => self.action_init_object(),
-                Action::ImplementsOp => self.action_implements_op(),
-                Action::InstanceOf => self.action_instance_of(),
-                Action::Jump(action) => self.action_jump(action, reader, data),
-                Action::Less => self.action_less(),
-                Action::Less2 => self.action_less_2(),
-                Action::MBAsciiToChar => self.action_mb_ascii_to_char(),
-                Action::MBCharToAscii => self.action_mb_char_to_ascii(),
-                Action::MBStringLength => self.action_mb_string_length(),
-                Action::MBStringExtract => self.action_mb_string_extract(),
-                Action::Modulo => self.action_modulo(),
-                Action::Multiply => self.action_multiply(),
-                Action::NextFrame => self.action_next_frame(),
-                Action::NewMethod => self.action_new_method(),
-                Action::NewObject => self.action_new_object(),
-                Action::Not => self.action_not(),
-                Action::Or => self.action_or(),
-                Action::Play => self.action_play(),
-                Action::Pop => self.action_pop(),
-                Action::PreviousFrame => self.action_prev_frame(),
-                Action::Push(action) => self.action_push(action),
-                Action::PushDuplicate => self.action_push_duplicate(),
-                Action::RandomNumber => self.action_random_number(),
-                Action::RemoveSprite => self.action_remove_sprite(),
-                Action::Return => self.action_return(),
-                Action::SetMember => self.action_set_member(),
-                Action::SetProperty => self.action_set_property(),
-                Action::SetTarget(action) => self.action_set_target(action),
-                Action::SetTarget2 => self.action_set_target_2(),
-                Action::SetVariable => self.action_set_variable(),
-                Action::StackSwap => self.action_stack_swap(),
-                Action::StartDrag => self.action_start_drag(),
-                Action::Stop => self.action_stop(),
-                Action::StopSounds => self.action_stop_sounds(),
-                Action::StoreRegister(action) => self.action_store_register(action),
-                Action::StrictEquals => self.action_strict_equals(),
-                Action::StringAdd => self.action_string_add(),
-                Action::StringEquals => self.action_string_equals(),
-                Action::StringExtract => self.action_string_extract(),
-                Action::StringGreater => self.action_string_greater(),
-                Action::StringLength => self.action_string_length(),
-                Action::StringLess => self.action_string_less(),
-                Action::Subtract => self.action_subtract(),
-                Action::TargetPath => self.action_target_path(),
-                Action::Throw => self.action_throw(),
-                Action::ToggleQuality => self.action_toggle_quality(),
-                Action::ToInteger => self.action_to_integer(),
-                Action::ToNumber => self.action_to_number(),
-                Action::ToString => self.action_to_string(),
-                Action::Trace => self.action_trace(),
-                Action::Try(action) => self.action_try(&action, data),
-                Action::TypeOf => self.action_type_of(),
-                Action::WaitForFrame(action) => self.action_wait_for_frame(action, reader),
-                Action::WaitForFrame2(action) => self.action_wait_for_frame_2(action, reader),
-                Action::With(action) => self.action_with(action, data),
-                Action::Unknown(action) => self.action_unknown(action),
-            }
+        match action {
+            Action::DefineFunction(action) => self.action_define_function(action.into(), data),
+            Action::DefineFunction2(action) => self.action_define_function(action, data),
+            Action::GetUrl(action) => self.action_get_url(action),
+            Action::GotoLabel(action) => self.action_goto_label(action),
+            Action::SetTarget(action) => self.action_set_target(action),
+            Action::Try(action) => self.action_try(&action, data),
+            Action::With(action) => self.action_with(action, data),
+            Action::Unknown(action) => self.action_unknown(action),
+            _ => unreachable!("Action should have been decoded: {action:?}"),
         }
     }
 
@@ -864,19 +893,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn action_constant_pool(
         &mut self,
-        action: ConstantPool,
+        constant_pool: Gc<'gc, Vec<Value<'gc>>>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let encoding = self.encoding();
-        let constants = action
-            .strings
-            .iter()
-            .map(|s| self.strings().intern_wstr(s.decode(encoding)).into())
-            .collect();
-
-        self.context
-            .avm1
-            .set_constant_pool(Gc::new(self.gc(), constants));
-        self.set_constant_pool(self.context.avm1.constant_pool());
+        self.context.avm1.set_constant_pool(constant_pool);
+        self.set_constant_pool(constant_pool);
 
         Ok(FrameControl::Continue)
     }
@@ -1171,9 +1191,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
+        let time = self.context.clock.elapsed().as_millis() as u32;
         let result = time.wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
@@ -1440,15 +1458,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_if<'b>(
+    fn action_if(
         &mut self,
-        action: If,
-        reader: &mut Reader<'b>,
-        data: &'b SwfSlice,
+        target: JumpTarget,
+        data: &SwfSlice,
+        actions: &mut Gc<'gc, DecodedActions<'gc>>,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let val = self.context.avm1.pop();
         if val.as_bool(self.swf_version()) {
-            reader.seek(data.movie.data(), action.offset);
+            self.jump(target, data, actions, ip);
         }
         Ok(FrameControl::Continue)
     }
@@ -1545,16 +1564,42 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_jump<'b>(
+    fn action_jump(
         &mut self,
-        action: Jump,
-        reader: &mut Reader<'b>,
-        data: &'b SwfSlice,
+        target: JumpTarget,
+        data: &SwfSlice,
+        actions: &mut Gc<'gc, DecodedActions<'gc>>,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        reader.seek(data.movie.data(), action.offset);
+        self.jump(target, data, actions, ip);
         Ok(FrameControl::Continue)
     }
 
+    /// Continues execution at `target`.
+    ///
+    /// Jumps which don't land on the start of an action continue with the
+    /// actions decoded from the target position up to the end of `data`.
+    fn jump(
+        &mut self,
+        target: JumpTarget,
+        data: &SwfSlice,
+        actions: &mut Gc<'gc, DecodedActions<'gc>>,
+        ip: &mut usize,
+    ) {
+        match target {
+            JumpTarget::Index(index) => *ip = index as usize,
+            JumpTarget::Position(position) => {
+                let code = SwfSlice {
+                    movie: data.movie.clone(),
+                    start: position as usize,
+                    end: data.end,
+                };
+                *actions = self.decoded_actions(&code);
+                *ip = 0;
+            }
+        }
+    }
+
     fn action_less(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // AS1 less than
         // If one of the values to compare coerces to `NaN`, the result will be false.
@@ -1805,19 +1850,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_push(&mut self, action: Push) -> Result<FrameControl<'gc>, Error<'gc>> {
-        for value in action.values {
-            use swf::avm1::types::Value as SwfValue;
-            let value = match value {
-                SwfValue::Undefined => Value::Undefined,
-                SwfValue::Null => Value::Null,
-                SwfValue::Bool(v) => v.into(),
-                SwfValue::Int(v) => v.into(),
-                SwfValue::Float(v) => v.into(),
-                SwfValue::Double(v) => v.into(),
-                SwfValue::Str(v) => AvmString::new(self.gc(), v.decode(self.encoding())).into(),
-                SwfValue::Register(v) => self.current_register(v),
-                SwfValue::ConstantPool(i) => {
+    fn action_push(&mut self, values: &[PushValue<'gc>]) -> Result<FrameControl<'gc>, Error<'gc>> {
+        for value in values {
+            let value = match *value {
+                PushValue::Value(value) => value,
+                PushValue::Register(v) => self.current_register(v),
+                PushValue::Constant(i) => {
                     if let Some(value) = self.constant_pool().get(i as usize) {
                         *value
                     } else {
@@ -2297,10 +2335,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn action_wait_for_frame(
         &mut self,
-        action: WaitForFrame,
-        r: &mut Reader<'_>,
+        frame_num: u16,
+        skip_to: u32,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let frame_num = action.frame;
         let loaded = if frame_num > 16000 {
             // Exceeded maximum number of frames.
             false
@@ -2312,9 +2350,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         };
 
         if !loaded {
-            // Note that the offset is given in # of actions, NOT in bytes.
-            // Read the actions and toss them away.
-            skip_actions(r, action.num_actions_to_skip);
+            *ip = skip_to as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -2322,8 +2358,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
     fn action_wait_for_frame_2(
         &mut self,
-        action: WaitForFrame2,
-        r: &mut Reader<'_>,
+        skip_to: u32,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let frame_val = self.context.avm1.pop();
         let frame_num = match frame_val {
@@ -2355,9 +2391,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         };
 
         if !loaded {
-            // Note that the offset is given in # of actions, NOT in bytes.
-            // Read the actions and toss them away.
-            skip_actions(r, action.num_actions_to_skip);
+            *ip = skip_to as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -0,0 +1,293 @@
+//! Lowering of AVM1 bytecode into `Op`s.
+
+use crate::avm1::op::{JumpTarget, Op, PushValue};
+use crate::avm1::Value;
+use crate::string::{AvmString, StringContext, SwfStrExt as _};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Gc};
+use std::sync::Weak;
+use swf::avm1::read::Reader;
+use swf::avm1::types::{Action, Value as SwfValue};
+use swf::SwfStr;
+use weak_table::PtrWeakKeyHashMap;
+
+/// The actions of a `SwfSlice`, decoded once so that running them again
+/// doesn't have to parse the SWF.
+#[derive(Collect, Debug)]
+#[collect(no_drop)]
+pub struct DecodedActions<'gc> {
+    ops: Box<[Op<'gc>]>,
+}
+
+impl<'gc> DecodedActions<'gc> {
+    /// Decodes the actions in `code`, as they would be read by the given SWF
+    /// version.
+    ///
+    /// Like the byte reader, this continues until an action starts at or past
+    /// the end of the slice, so the last action may extend past it.
+    pub fn decode(context: &mut StringContext<'gc>, code: &SwfSlice, swf_version: u8) -> Self {
+        let data = code.movie.data();
+        let encoding = SwfStr::encoding_for_version(swf_version);
+        let mut reader = Reader::new(&data[code.start..], swf_version);
+        let position =
+            |reader: &Reader<'_>| reader.get_ref().as_ptr() as usize - data.as_ptr() as usize;
+
+        // The position of each decoded action, used to resolve jumps.
+        let mut positions = Vec::new();
+        let mut ops = Vec::new();
+
+        loop {
+            let start = position(&reader);
+            if start >= code.end {
+                break;
+            }
+            positions.push(start as u32);
+
+            let Ok(action) = reader.read_action() else {
+                ops.push(Op::Undecoded {
+                    position: start as u32,
+                });
+                break;
+            };
+            let next = position(&reader);
+            let index = ops.len() as u32;
+
+            let op = match action {
+                Action::Add => Op::Add,
+                Action::Add2 => Op::Add2,
+                Action::And => Op::And,
+                Action::AsciiToChar => Op::AsciiToChar,
+                Action::BitAnd => Op::BitAnd,
+                Action::BitLShift => Op::BitLShift,
+                Action::BitOr => Op::BitOr,
+                Action::BitRShift => Op::BitRShift,
+                Action::BitURShift => Op::BitURShift,
+                Action::BitXor => Op::BitXor,
+                Action::Call => Op::Call,
+                Action::CallFunction => Op::CallFunction,
+                Action::CallMethod => Op::CallMethod,
+                Action::CastOp => Op::CastOp,
+                Action::CharToAscii => Op::CharToAscii,
+                Action::CloneSprite => Op::CloneSprite,
+                Action::ConstantPool(action) => {
+                    let constants = action
+                        .strings
+                        .iter()
+                        .map(|s| context.intern_wstr(s.decode(encoding)).into())
+                        .collect();
+                    Op::ConstantPool(Gc::new(context.gc(), constants))
+                }
+                Action::Decrement => Op::Decrement,
+                Action::DefineLocal => Op::DefineLocal,
+                Action::DefineLocal2 => Op::DefineLocal2,
+                Action::Delete => Op::Delete,
+                Action::Delete2 => Op::Delete2,
+                Action::Divide => Op::Divide,
+                Action::End => Op::End,
+                Action::EndDrag => Op::EndDrag,
+                Action::Enumerate => Op::Enumerate,
+                Action::Enumerate2 => Op::Enumerate2,
+                Action::Equals => Op::Equals,
+                Action::Equals2 => Op::Equals2,
+                Action::Extends => Op::Extends,
+                Action::GetMember => Op::GetMember,
+                Action::GetProperty => Op::GetProperty,
+                Action::GetTime => Op::GetTime,
+                Action::GetUrl2(action) => Op::GetUrl2(action),
+                Action::GetVariable => Op::GetVariable,
+                Action::GotoFrame(action) => Op::GotoFrame(action),
+                Action::GotoFrame2(action) => Op::GotoFrame2(action),
+                Action::Greater => Op::Greater,
+                Action::If(action) => Op::If {
+                    target: jump_target(next, action.offset, data.len()),
+                },
+                Action::ImplementsOp => Op::ImplementsOp,
+                Action::Increment => Op::Increment,
+                Action::InitArray => Op::InitArray,
+                Action::InitObject => Op::InitObject,
+                Action::InstanceOf => Op::InstanceOf,
+                Action::Jump(action) => Op::Jump {
+                    target: jump_target(next, action.offset, data.len()),
+                },
+                Action::Less => Op::Less,
+                Action::Less2 => Op::Less2,
+                Action::MBAsciiToChar => Op::MBAsciiToChar,
+                Action::MBCharToAscii => Op::MBCharToAscii,
+                Action::MBStringExtract => Op::MBStringExtract,
+                Action::MBStringLength => Op::MBStringLength,
+                Action::Modulo => Op::Modulo,
+                Action::Multiply => Op::Multiply,
+                Action::NewMethod => Op::NewMethod,
+                Action::NewObject => Op::NewObject,
+                Action::NextFrame => Op::NextFrame,
+                Action::Not => Op::Not,
+                Action::Or => Op::Or,
+                Action::Play => Op::Play,
+                Action::Pop => Op::Pop,
+                Action::PreviousFrame => Op::PreviousFrame,
+                Action::Push(action) => Op::Push(
+                    action
+                        .values
+                        .into_iter()
+                        .map(|value| match value {
+                            SwfValue::Undefined => PushValue::Value(Value::Undefined),
+                            SwfValue::Null => PushValue::Value(Value::Null),
+                            SwfValue::Bool(v) => PushValue::Value(v.into()),
+                            SwfValue::Int(v) => PushValue::Value(v.into()),
+                            SwfValue::Float(v) => PushValue::Value(v.into()),
+                            SwfValue::Double(v) => PushValue::Value(v.into()),
+                            SwfValue::Str(v) => PushValue::Value(
+                                AvmString::new(context.gc(), v.decode(encoding)).into(),
+                            ),
+                            SwfValue::Register(v) => PushValue::Register(v),
+                            SwfValue::ConstantPool(i) => PushValue::Constant(i),
+                        })
+                        .collect(),
+                ),
+                Action::PushDuplicate => Op::PushDuplicate,
+                Action::RandomNumber => Op::RandomNumber,
+                Action::RemoveSprite => Op::RemoveSprite,
+                Action::Return => Op::Return,
+                Action::SetMember => Op::SetMember,
+                Action::SetProperty => Op::SetProperty,
+                Action::SetTarget2 => Op::SetTarget2,
+                Action::SetVariable => Op::SetVariable,
+                Action::StackSwap => Op::StackSwap,
+                Action::StartDrag => Op::StartDrag,
+                Action::Stop => Op::Stop,
+                Action::StopSounds => Op::StopSounds,
+                Action::StoreRegister(action) => Op::StoreRegister(action),
+                Action::StrictEquals => Op::StrictEquals,
+                Action::StringAdd => Op::StringAdd,
+                Action::StringEquals => Op::StringEquals,
+                Action::StringExtract => Op::StringExtract,
+                Action::StringGreater => Op::StringGreater,
+                Action::StringLength => Op::StringLength,
+                Action::StringLess => Op::StringLess,
+                Action::Subtract => Op::Subtract,
+                Action::TargetPath => Op::TargetPath,
+                Action::Throw => Op::Throw,
+                Action::ToInteger => Op::ToInteger,
+                Action::ToNumber => Op::ToNumber,
+                Action::ToString => Op::ToString,
+                Action::ToggleQuality => Op::ToggleQuality,
+                Action::Trace => Op::Trace,
+                Action::TypeOf => Op::TypeOf,
+                // Note that these skip a number of actions, NOT bytes.
+                Action::WaitForFrame(action) => Op::WaitForFrame {
+                    frame: action.frame,
+                    skip_to: index + 1 + u32::from(action.num_actions_to_skip),
+                },
+                Action::WaitForFrame2(action) => Op::WaitForFrame2 {
+                    skip_to: index + 1 + u32::from(action.num_actions_to_skip),
+                },
+                Action::DefineFunction(_)
+                | Action::DefineFunction2(_)
+                | Action::GetUrl(_)
+                | Action::GotoLabel(_)
+                | Action::SetTarget(_)
+                | Action::Try(_)
+                | Action::With(_)
+                | Action::Unknown(_) => Op::Undecoded {
+                    position: start as u32,
+                },
+            };
+            ops.push(op);
+        }
+
+        // Now that every action is known, jumps to the start of an action can be
+        // turned into indices. Skipping past the last action returns, like reading
+        // past the end of the slice does.
+        let len = ops.len() as u32;
+        for op in &mut ops {
+            match op {
+                Op::If { target } | Op::Jump { target } => {
+                    if let JumpTarget::Position(position) = *target {
+                        if position as usize >= code.end {
+                            *target = JumpTarget::Index(len);
+                        } else if let Ok(index) = positions.binary_search(&position) {
+                            *target = JumpTarget::Index(index as u32);
+                        }
+                    }
+                }
+                Op::WaitForFrame { skip_to, .. } | Op::WaitForFrame2 { skip_to } => {
+                    *skip_to = (*skip_to).min(len);
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            ops: ops.into_boxed_slice(),
+        }
+    }
+
+    pub fn ops(&self) -> &[Op<'gc>] {
+        &self.ops
+    }
+}
+
+/// Returns the position a jump from `next` by `offset` bytes lands on.
+///
+/// Like `Reader::seek`, positions outside of the movie data are clamped to its
+/// end.
+fn jump_target(next: usize, offset: i16, data_len: usize) -> JumpTarget {
+    let position = usize::try_from(next as isize + isize::from(offset)).unwrap_or(data_len);
+    JumpTarget::Position(position.min(data_len) as u32)
+}
+
+/// The decoded actions of every `SwfSlice` that was run, by movie.
+///
+/// Slices are decoded the first time they run, with the SWF version of the
+/// activation running them. Entries are dropped along with their movie.
+pub struct ActionCache<'gc> {
+    movies: PtrWeakKeyHashMap<
+        Weak<SwfMovie>,
+        FnvHashMap<(usize, usize, u8), Gc<'gc, DecodedActions<'gc>>>,
+    >,
+}
+
+unsafe impl Collect for ActionCache<'_> {
+    #[inline]
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, actions) in self.movies.iter() {
+            actions.values().for_each(|actions| actions.trace(cc));
+        }
+    }
+}
+
+impl Default for ActionCache<'_> {
+    fn default() -> Self {
+        Self {
+            movies: PtrWeakKeyHashMap::new(),
+        }
+    }
+}
+
+impl<'gc> ActionCache<'gc> {
+    /// Returns the decoded actions of `code`, decoding them if this is the
+    /// first time they're run with this SWF version.
+    pub fn get(
+        &mut self,
+        context: &mut StringContext<'gc>,
+        code: &SwfSlice,
+        swf_version: u8,
+    ) -> Gc<'gc, DecodedActions<'gc>> {
+        // NOTE(Clippy): Cannot use or_default() here as PtrWeakKeyHashMap does not have such a method on its Entry API
+        #[allow(clippy::unwrap_or_default)]
+        let actions = self
+            .movies
+            .entry(code.movie.clone())
+            .or_insert_with(FnvHashMap::default);
+
+        *actions
+            .entry((code.start, code.end, swf_version))
+            .or_insert_with(|| {
+                Gc::new(
+                    context.gc(),
+                    DecodedActions::decode(context, code, swf_version),
+                )
+            })
+    }
+}
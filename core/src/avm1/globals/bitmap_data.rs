@@ -16,7 +16,7 @@ use crate::display_object::DisplayObject;
 use crate::string::StringContext;
 use crate::swf::BlendMode;
 use crate::{avm1_stub, avm_error};
-use gc_arena::{GcCell, Mutation};
+use gc_arena::Mutation;
 use ruffle_render::transform::Transform;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -72,7 +72,7 @@ fn new_bitmap_data<'gc>(
     }
     object.set_native(
         gc_context,
-        NativeObject::BitmapData(BitmapDataWrapper::new(GcCell::new(gc_context, bitmap_data))),
+        NativeObject::BitmapData(BitmapDataWrapper::new(gc_context, bitmap_data)),
     );
     object
 }
@@ -106,10 +106,7 @@ fn constructor<'gc>(
     let bitmap_data = BitmapData::new(width, height, transparency, fill_color);
     this.set_native(
         activation.gc(),
-        NativeObject::BitmapData(BitmapDataWrapper::new(GcCell::new(
-            activation.gc(),
-            bitmap_data,
-        ))),
+        NativeObject::BitmapData(BitmapDataWrapper::new(activation.gc(), bitmap_data)),
     );
     Ok(this.into())
 }
@@ -1494,14 +1491,15 @@ fn load_bitmap<'gc>(
         &activation.target_clip_or_root(),
     );
 
-    let character = library
-        .library_for_movie(movie)
-        .and_then(|l| l.character_by_export_name(name));
+    let Some(movie_library) = library.library_for_movie(movie) else {
+        return Ok(Value::Undefined);
+    };
+    let character = movie_library.character_by_export_name(name);
 
-    let Some((_id, Character::Bitmap { compressed, .. })) = character else {
+    let Some((id, Character::Bitmap { compressed, .. })) = character else {
         return Ok(Value::Undefined);
     };
-    let bitmap = compressed.decode().unwrap();
+    let bitmap = movie_library.decode_bitmap(id, compressed).unwrap();
 
     let transparency = true;
     let bitmap_data = BitmapData::new_with_pixels(
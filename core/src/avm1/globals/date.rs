@@ -3,8 +3,10 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
-use crate::locale::{get_current_date_time, get_timezone};
+use crate::deterministic::Clock;
+use crate::locale::{self, Timezone};
 use crate::string::{AvmString, StringContext};
+use chrono::{DateTime, TimeZone};
 use gc_arena::Gc;
 use std::cell::Cell;
 use std::fmt;
@@ -61,8 +63,8 @@ impl Date {
     }
 
     /// Create from current date and time.
-    fn now() -> Self {
-        Self(get_current_date_time().timestamp_millis() as f64)
+    fn now(clock: &Clock) -> Self {
+        Self(clock.date_time().timestamp_millis() as f64)
     }
 
     /// Get milliseconds since epoch.
@@ -163,24 +165,38 @@ impl Date {
     }
 
     /// ECMA-262 LocalTZA - Get local timezone adjustment in milliseconds.
-    fn local_tza(&self, _is_utc: bool) -> i32 {
-        // TODO: Honor `is_utc` flag.
-        get_timezone().local_minus_utc() * Self::MS_PER_SECOND
+    ///
+    /// `is_utc` tells whether this date is in UTC or in local time, as the
+    /// offset of the timezone depends on the instant.
+    fn local_tza(&self, is_utc: bool, timezone: &Timezone) -> i32 {
+        let Some(time) = DateTime::from_timestamp_millis(self.0 as i64) else {
+            return 0;
+        };
+        let time = time.naive_utc();
+        if is_utc {
+            timezone.offset_from_utc_datetime(&time).local_minus_utc() * Self::MS_PER_SECOND
+        } else {
+            // The offset of the resulting instant may differ from the one used to
+            // resolve a time skipped by a transition, so compare the two times instead.
+            locale::from_local_datetime(timezone, &time).map_or(0, |date| {
+                (time - date.naive_utc()).num_milliseconds() as i32
+            })
+        }
     }
 
     /// ECMA-262 LocalTime - Convert from UTC to local timezone.
-    fn local(self) -> Self {
-        Self(self.0 + f64::from(self.local_tza(true)))
+    fn local(self, timezone: &Timezone) -> Self {
+        Self(self.0 + f64::from(self.local_tza(true, timezone)))
     }
 
     /// ECMA-262 UTC - Convert from local timezone to UTC.
-    fn utc(self) -> Self {
-        Self(self.0 - f64::from(self.local_tza(false)))
+    fn utc(self, timezone: &Timezone) -> Self {
+        Self(self.0 - f64::from(self.local_tza(false, timezone)))
     }
 
     /// Get timezone offset in minutes.
-    fn timezone_offset(&self) -> f64 {
-        (self.0 - self.local().0) / f64::from(Self::MS_PER_MINUTE)
+    fn timezone_offset(&self, timezone: &Timezone) -> f64 {
+        (self.0 - self.local(timezone).0) / f64::from(Self::MS_PER_MINUTE)
     }
 
     /// ECMA-262 HourFromTime - Get hours (0-23).
@@ -264,9 +280,13 @@ impl Date {
     }
 }
 
-impl fmt::Display for Date {
+/// Formats a date in local time, along with the offset of its timezone.
+struct LocalDate(Date, Timezone);
+
+impl fmt::Display for LocalDate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !self.is_valid() {
+        let LocalDate(date, timezone) = self;
+        if !date.is_valid() {
             return write!(f, "Invalid Date");
         }
 
@@ -275,20 +295,21 @@ impl fmt::Display for Date {
             "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
         ];
 
-        let timezone_offset = (-self.timezone_offset()).clamp_to_i32();
+        let timezone_offset = (-date.timezone_offset(timezone)).clamp_to_i32();
+        let date = date.local(timezone);
         write!(
             f,
             "{} {} {} {:02}:{:02}:{:02} GMT{}{:02}{:02} {}",
-            DAYS_OF_WEEK[self.week_day() as usize],
-            MONTHS[self.month() as usize],
-            self.date(),
-            self.hours(),
-            self.minutes(),
-            self.seconds(),
+            DAYS_OF_WEEK[date.week_day() as usize],
+            MONTHS[date.month() as usize],
+            date.date(),
+            date.hours(),
+            date.minutes(),
+            date.seconds(),
             if timezone_offset < 0 { '-' } else { '+' },
-            timezone_offset.abs() / Self::MINUTES_PER_HOUR,
-            timezone_offset.abs() % Self::MINUTES_PER_HOUR,
-            self.year(),
+            timezone_offset.abs() / Date::MINUTES_PER_HOUR,
+            timezone_offset.abs() % Date::MINUTES_PER_HOUR,
+            date.year(),
         )
     }
 }
@@ -352,7 +373,7 @@ fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let date = match args[..] {
         [] => {
-            let date = Date::now();
+            let date = Date::now(&activation.context.clock);
             if activation.swf_version() > 7 {
                 Date(date.time().round())
             } else {
@@ -366,7 +387,8 @@ fn constructor<'gc>(
             let minute = args.get(4).copied().unwrap_or(0.0);
             let second = args.get(5).copied().unwrap_or(0.0);
             let millisecond = args.get(6).copied().unwrap_or(0.0);
-            Date::new(year, month, date, hour, minute, second, millisecond).utc()
+            Date::new(year, month, date, hour, minute, second, millisecond)
+                .utc(&activation.context.clock.timezone())
         }
     };
     this.set_native(
@@ -382,7 +404,9 @@ fn function<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(AvmString::new_utf8(activation.gc(), Date::now().local().to_string()).into())
+    let timezone = activation.context.clock.timezone();
+    let date = Date::now(&activation.context.clock);
+    Ok(AvmString::new_utf8(activation.gc(), LocalDate(date, timezone).to_string()).into())
 }
 
 /// ECMA-262 Date.UTC
@@ -451,6 +475,7 @@ fn method<'gc>(
         _ => return Ok(Value::Undefined),
     };
     let date = date_ref.get();
+    let timezone = activation.context.clock.timezone();
 
     match index {
         GET_TIME => return Ok(date.time().into()),
@@ -460,7 +485,7 @@ fn method<'gc>(
             date_ref.set(new_date);
             return Ok(new_date.time().into());
         }
-        GET_TIMEZONE_OFFSET => return Ok(date.timezone_offset().into()),
+        GET_TIMEZONE_OFFSET => return Ok(date.timezone_offset(&timezone).into()),
         _ => {}
     }
 
@@ -492,12 +517,12 @@ fn method<'gc>(
             .or_else(|| (i == index).then_some(f64::NAN))
     };
 
-    let date = if is_utc { date } else { date.local() };
+    let date = if is_utc { date } else { date.local(&timezone) };
 
     let set_date = |day: f64, time: f64| {
         let mut date = Date::make_date(day, time);
         if !is_utc {
-            date = date.utc();
+            date = date.utc(&timezone);
         }
         date = date.clip();
         date_ref.set(date);
@@ -560,7 +585,11 @@ fn method<'gc>(
             )
             .into()
         }
-        TO_STRING => AvmString::new_utf8(activation.gc(), date.to_string()).into(),
+        TO_STRING => AvmString::new_utf8(
+            activation.gc(),
+            LocalDate(date_ref.get(), timezone).to_string(),
+        )
+        .into(),
         GET_TIME..=GET_TIMEZONE_OFFSET | SET_YEAR.. => unreachable!(), // Handled above.
     })
 }
@@ -202,6 +202,53 @@ impl fmt::Display for PlayerType {
     }
 }
 
+/// The conversion mode of the input method editor
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImeConversionMode {
+    AlphanumericFull,
+    AlphanumericHalf,
+    Chinese,
+    JapaneseHiragana,
+    JapaneseKatakanaFull,
+    JapaneseKatakanaHalf,
+    Korean,
+    Unknown,
+}
+
+impl ImeConversionMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "ALPHANUMERIC_FULL" => ImeConversionMode::AlphanumericFull,
+            "ALPHANUMERIC_HALF" => ImeConversionMode::AlphanumericHalf,
+            "CHINESE" => ImeConversionMode::Chinese,
+            "JAPANESE_HIRAGANA" => ImeConversionMode::JapaneseHiragana,
+            // AVM1 exposes this constant with a typo, accept both spellings.
+            "JAPANESE_KATAKANA_FULL" | "JAPENESE_KATAKANA_FULL" => {
+                ImeConversionMode::JapaneseKatakanaFull
+            }
+            "JAPANESE_KATAKANA_HALF" => ImeConversionMode::JapaneseKatakanaHalf,
+            "KOREAN" => ImeConversionMode::Korean,
+            "UNKNOWN" => ImeConversionMode::Unknown,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for ImeConversionMode {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match self {
+            ImeConversionMode::AlphanumericFull => "ALPHANUMERIC_FULL",
+            ImeConversionMode::AlphanumericHalf => "ALPHANUMERIC_HALF",
+            ImeConversionMode::Chinese => "CHINESE",
+            ImeConversionMode::JapaneseHiragana => "JAPANESE_HIRAGANA",
+            ImeConversionMode::JapaneseKatakanaFull => "JAPANESE_KATAKANA_FULL",
+            ImeConversionMode::JapaneseKatakanaHalf => "JAPANESE_KATAKANA_HALF",
+            ImeConversionMode::Korean => "KOREAN",
+            ImeConversionMode::Unknown => "UNKNOWN",
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, FromPrimitive)]
 enum SettingsPanel {
     Privacy = 0,
@@ -269,6 +316,10 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// Whether the movie allows the input method editor to be used
+    pub ime_enabled: bool,
+    /// The conversion mode requested from the input method editor
+    pub ime_conversion_mode: ImeConversionMode,
 }
 
 impl Default for SystemProperties {
@@ -297,6 +348,8 @@ impl SystemProperties {
             os: OperatingSystem::Linux,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            ime_enabled: true,
+            ime_conversion_mode: ImeConversionMode::AlphanumericHalf,
         }
     }
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
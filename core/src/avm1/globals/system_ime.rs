@@ -1,10 +1,11 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::globals::system::ImeConversionMode;
 use crate::avm1::object::Object;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{ScriptObject, Value};
-use crate::string::StringContext;
+use crate::string::{AvmString, StringContext};
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "ALPHANUMERIC_FULL" => string("ALPHANUMERIC_FULL"; DONT_ENUM | DONT_DELETE | READ_ONLY);
@@ -40,19 +41,20 @@ fn do_conversion<'gc>(
 }
 
 fn get_conversion_mode<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok("KOREAN".into())
+    let mode = activation.context.system.ime_conversion_mode.to_string();
+    Ok(AvmString::new_utf8(activation.gc(), mode).into())
 }
 
 fn get_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    Ok(activation.context.system.ime_enabled.into())
 }
 
 fn set_composition_string<'gc>(
@@ -64,19 +66,34 @@ fn set_composition_string<'gc>(
 }
 
 fn set_conversion_mode<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    if let Some(mode) = ImeConversionMode::from_name(&mode.to_utf8_lossy()) {
+        activation.context.system.ime_conversion_mode = mode;
+        return Ok(true.into());
+    }
     Ok(false.into())
 }
 
 fn set_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    let enabled = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    activation.context.system.ime_enabled = enabled;
+    let focus_tracker = activation.context.focus_tracker;
+    focus_tracker.update_ime(activation.context);
+    Ok(true.into())
 }
 
 pub fn create<'gc>(
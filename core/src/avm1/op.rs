@@ -0,0 +1,162 @@
+//! Decoded AVM1 actions.
+
+use crate::avm1::Value;
+use gc_arena::{Collect, Gc};
+use swf::avm1::types::{GetUrl2, GotoFrame, GotoFrame2, StoreRegister};
+
+/// A single action, decoded ahead of time from the SWF.
+///
+/// Operands are converted to the form they're used in at runtime: jump
+/// offsets become indices into the decoded actions, and strings become
+/// `AvmString`s. Actions whose operands borrow large parts of the SWF are kept
+/// as `Undecoded`, and read again whenever they run.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum Op<'gc> {
+    Add,
+    Add2,
+    And,
+    AsciiToChar,
+    BitAnd,
+    BitLShift,
+    BitOr,
+    BitRShift,
+    BitURShift,
+    BitXor,
+    Call,
+    CallFunction,
+    CallMethod,
+    CastOp,
+    CharToAscii,
+    CloneSprite,
+    ConstantPool(Gc<'gc, Vec<Value<'gc>>>),
+    Decrement,
+    DefineLocal,
+    DefineLocal2,
+    Delete,
+    Delete2,
+    Divide,
+    End,
+    EndDrag,
+    Enumerate,
+    Enumerate2,
+    Equals,
+    Equals2,
+    Extends,
+    GetMember,
+    GetProperty,
+    GetTime,
+    GetUrl2(#[collect(require_static)] GetUrl2),
+    GetVariable,
+    GotoFrame(#[collect(require_static)] GotoFrame),
+    GotoFrame2(#[collect(require_static)] GotoFrame2),
+    Greater,
+    If {
+        target: JumpTarget,
+    },
+    ImplementsOp,
+    Increment,
+    InitArray,
+    InitObject,
+    InstanceOf,
+    Jump {
+        target: JumpTarget,
+    },
+    Less,
+    Less2,
+    MBAsciiToChar,
+    MBCharToAscii,
+    MBStringExtract,
+    MBStringLength,
+    Modulo,
+    Multiply,
+    NewMethod,
+    NewObject,
+    NextFrame,
+    Not,
+    Or,
+    Play,
+    Pop,
+    PreviousFrame,
+    Push(Box<[PushValue<'gc>]>),
+    PushDuplicate,
+    RandomNumber,
+    RemoveSprite,
+    Return,
+    SetMember,
+    SetProperty,
+    SetTarget2,
+    SetVariable,
+    StackSwap,
+    StartDrag,
+    Stop,
+    StopSounds,
+    StoreRegister(#[collect(require_static)] StoreRegister),
+    StrictEquals,
+    StringAdd,
+    StringEquals,
+    StringExtract,
+    StringGreater,
+    StringLength,
+    StringLess,
+    Subtract,
+    TargetPath,
+    Throw,
+    ToInteger,
+    ToNumber,
+    ToString,
+    ToggleQuality,
+    Trace,
+    TypeOf,
+    WaitForFrame {
+        frame: u16,
+
+        /// The index of the action to continue at if the frame isn't loaded.
+        skip_to: u32,
+    },
+    WaitForFrame2 {
+        /// The index of the action to continue at if the frame isn't loaded.
+        skip_to: u32,
+    },
+
+    /// An action that is read from the SWF again every time it runs, at the
+    /// given position in the movie data.
+    ///
+    /// This is used for actions which define nested blocks of code or carry
+    /// strings that are only used once (`DefineFunction`, `Try`, `With`,
+    /// `GetUrl`, `GotoLabel`, `SetTarget`), unknown actions, and actions that
+    /// failed to parse, so that reading them again reports the error at the
+    /// point where it would have happened.
+    Undecoded {
+        position: u32,
+    },
+}
+
+/// A value pushed by `Op::Push`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum PushValue<'gc> {
+    /// A value that's known when decoding.
+    Value(Value<'gc>),
+
+    /// The value of a register.
+    Register(u8),
+
+    /// An entry of the constant pool that's current when the action runs.
+    Constant(u16),
+}
+
+/// Where execution continues after a jump.
+#[derive(Clone, Collect, Copy, Debug, Eq, PartialEq)]
+#[collect(require_static)]
+pub enum JumpTarget {
+    /// The index of an action in the same decoded actions.
+    ///
+    /// This may be one past the last action, which returns from the code.
+    Index(u32),
+
+    /// A position in the movie data which isn't the start of a decoded action,
+    /// such as the middle of an action. Execution continues by decoding the
+    /// movie data from there.
+    Position(u32),
+}
@@ -1,3 +1,4 @@
+use crate::avm1::decode::{ActionCache, DecodedActions};
 use crate::avm1::function::{ExecutionReason, FunctionObject};
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::{as_broadcaster, create_globals};
@@ -14,7 +15,6 @@ use crate::tag_utils::SwfSlice;
 use crate::{avm1, avm_debug};
 use gc_arena::{Collect, Gc, Mutation};
 use std::borrow::Cow;
-use swf::avm1::read::Reader;
 use tracing::instrument;
 
 #[derive(Collect)]
@@ -27,6 +27,9 @@ pub struct Avm1<'gc> {
     /// don't close over the constant pool they were defined with.
     constant_pool: Gc<'gc, Vec<Value<'gc>>>,
 
+    /// The actions run so far, decoded from the SWF.
+    action_cache: ActionCache<'gc>,
+
     /// The global scope (pre-allocated so that it can be reused by fresh `Activation`s).
     global_scope: Gc<'gc, Scope<'gc>>,
 
@@ -97,6 +100,7 @@ impl<'gc> Avm1<'gc> {
         Self {
             player_version,
             constant_pool: Gc::new(gc_context, vec![]),
+            action_cache: ActionCache::default(),
             global_scope: Gc::new(gc_context, Scope::from_global_object(globals)),
             prototypes,
             broadcaster_functions,
@@ -381,6 +385,17 @@ impl<'gc> Avm1<'gc> {
         self.constant_pool = constant_pool;
     }
 
+    /// Returns the decoded actions of `code`, decoding them the first time
+    /// they're run with this SWF version.
+    pub fn decoded_actions(
+        &mut self,
+        context: &mut StringContext<'gc>,
+        code: &SwfSlice,
+        swf_version: u8,
+    ) -> Gc<'gc, DecodedActions<'gc>> {
+        self.action_cache.get(context, code, swf_version)
+    }
+
     /// DisplayObject property map.
     pub fn display_properties(&self) -> &stage_object::DisplayPropertyMap<'gc> {
         &self.display_properties
@@ -572,16 +587,6 @@ impl<'gc> Avm1<'gc> {
     pub const fn set_show_debug_output(&self, _visible: bool) {}
 }
 
-/// Utility function used by `Avm1::action_wait_for_frame` and
-/// `Avm1::action_wait_for_frame_2`.
-pub fn skip_actions(reader: &mut Reader<'_>, num_actions_to_skip: u8) {
-    for _ in 0..num_actions_to_skip {
-        if let Err(e) = reader.read_action() {
-            tracing::warn!("Couldn't skip action: {}", e);
-        }
-    }
-}
-
 pub fn root_error_handler<'gc>(activation: &mut Activation<'_, 'gc>, error: Error<'gc>) {
     match &error {
         Error::ThrownValue(value) => {
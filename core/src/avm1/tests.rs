@@ -1,6 +1,10 @@
+use crate::avm1::activation::ReturnType;
 use crate::avm1::error::Error;
 use crate::avm1::test_utils::with_avm;
-use crate::avm1::TObject;
+use crate::avm1::{Activation, TObject};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::Gc;
+use std::sync::Arc;
 
 #[test]
 fn locals_into_form_values() {
@@ -23,3 +27,64 @@ fn locals_into_form_values() {
         Ok(())
     });
 }
+
+fn run_bytecode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytecode: &[u8],
+) -> Result<ReturnType<'gc>, Error<'gc>> {
+    let movie = SwfMovie::fake_with_compressed_data(activation.swf_version(), bytecode.to_vec());
+    activation.run_actions(SwfSlice::from(Arc::new(movie)))
+}
+
+#[test]
+fn jump_into_middle_of_action() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        let bytecode = [
+            0x96, 0x05, 0x00, 0x07, 0x2A, 0x00, 0x00, 0x00, // Push 42
+            0x99, 0x02, 0x00, 0x04, 0x00, // Jump to the value of the next Push
+            0x96, 0x05, 0x00, 0x07, 0x3E, 0x00, 0x00, 0x00, // Push 62, or Return
+            0x3E, // Return
+        ];
+        let result = run_bytecode(activation, &bytecode)?;
+        assert_eq!(result.value(), 42.into());
+        Ok(())
+    });
+}
+
+#[test]
+fn jump_past_end_returns() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        let bytecode = [
+            0x99, 0x02, 0x00, 0x10, 0x00, // Jump past the end
+            0x96, 0x02, 0x00, 0x05, 0x01, // Push true
+            0x3E, // Return
+        ];
+        let result = run_bytecode(activation, &bytecode)?;
+        assert!(matches!(result, ReturnType::Implicit));
+        Ok(())
+    });
+}
+
+#[test]
+fn constant_pool_is_reused() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        let bytecode = [
+            0x88, 0x04, 0x00, 0x01, 0x00, b'a', 0x00, // ConstantPool ["a"]
+            0x96, 0x02, 0x00, 0x08, 0x00, // Push constant 0
+            0x3E, // Return
+        ];
+        let movie = Arc::new(SwfMovie::fake_with_compressed_data(
+            activation.swf_version(),
+            bytecode.to_vec(),
+        ));
+
+        let result = activation.run_actions(SwfSlice::from(movie.clone()))?;
+        assert_eq!(result.value(), "a".into());
+        let constant_pool = activation.constant_pool();
+
+        let result = activation.run_actions(SwfSlice::from(movie))?;
+        assert_eq!(result.value(), "a".into());
+        assert!(Gc::ptr_eq(constant_pool, activation.constant_pool()));
+        Ok(())
+    });
+}
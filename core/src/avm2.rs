@@ -49,6 +49,9 @@ mod filters;
 mod flv;
 mod function;
 pub mod globals;
+mod inline_cache;
+#[cfg(feature = "jit")]
+mod jit;
 mod metadata;
 mod method;
 mod multiname;
@@ -82,6 +85,8 @@ pub use crate::avm2::domain::{Domain, DomainPtr};
 pub use crate::avm2::error::Error;
 pub use crate::avm2::flv::FlvValueAvm2Ext;
 pub use crate::avm2::globals::flash::ui::context_menu::make_context_menu_state;
+#[cfg(feature = "jit")]
+pub use crate::avm2::jit::JitMode;
 pub use crate::avm2::multiname::{CommonMultinames, Multiname};
 pub use crate::avm2::namespace::{CommonNamespaces, Namespace};
 pub use crate::avm2::object::{
@@ -95,7 +100,13 @@ use self::api_version::ApiVersion;
 use self::object::WeakObject;
 use self::scope::Scope;
 
-const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
+const BROADCAST_WHITELIST: [&str; 5] = [
+    "enterFrame",
+    "exitFrame",
+    "frameConstructed",
+    "render",
+    "imeComposition",
+];
 
 const PREALLOCATED_STACK_SIZE: usize = 120000;
 
@@ -187,6 +198,15 @@ pub struct Avm2<'gc> {
     pub debug_output: bool,
 
     pub optimizer_enabled: bool,
+
+    /// Whether the optimizer attaches inline caches to property accesses
+    /// that it couldn't resolve statically.
+    pub inline_caches_enabled: bool,
+
+    /// The baseline JIT compiler, which runs hot methods as native code.
+    #[cfg(feature = "jit")]
+    #[collect(require_static)]
+    jit: jit::Jit,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -237,6 +257,10 @@ impl<'gc> Avm2<'gc> {
             debug_output: false,
 
             optimizer_enabled: true,
+            inline_caches_enabled: true,
+
+            #[cfg(feature = "jit")]
+            jit: Default::default(),
         }
     }
 
@@ -663,9 +687,11 @@ impl<'gc> Avm2<'gc> {
 
         let num_scripts = abc.scripts.len();
         let tunit = TranslationUnit::from_abc(abc, domain, name, movie, activation.gc());
-        tunit.load_classes(&mut activation)?;
+        // Classes and script traits are only loaded once they're referenced,
+        // which keeps ABC files with thousands of classes quick to load.
+        tunit.register_classes(&mut activation)?;
         for i in 0..num_scripts {
-            tunit.load_script(i as u32, &mut activation)?;
+            tunit.register_script(i as u32, &mut activation)?;
         }
 
         if !flags.contains(DoAbc2Flag::LAZY_INITIALIZE) {
@@ -850,6 +876,24 @@ impl<'gc> Avm2<'gc> {
     pub fn set_optimizer_enabled(&mut self, value: bool) {
         self.optimizer_enabled = value;
     }
+
+    pub fn inline_caches_enabled(&self) -> bool {
+        self.inline_caches_enabled
+    }
+
+    pub fn set_inline_caches_enabled(&mut self, value: bool) {
+        self.inline_caches_enabled = value;
+    }
+
+    #[cfg(feature = "jit")]
+    pub fn jit_mode(&self) -> JitMode {
+        self.jit.mode()
+    }
+
+    #[cfg(feature = "jit")]
+    pub fn set_jit_mode(&mut self, mode: JitMode) {
+        self.jit.set_mode(mode);
+    }
 }
 
 /// If the provided `DisplayObjectWeak` should have frames run, returns
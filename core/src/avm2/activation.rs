@@ -7,6 +7,7 @@ use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
     make_error_1065, make_error_1127, make_error_1506, make_null_or_undefined_error, type_error,
 };
+use crate::avm2::inline_cache::PropertyCache;
 use crate::avm2::method::{BytecodeMethod, Method, ResolvedParamConfig};
 use crate::avm2::object::{
     ArrayObject, ByteArrayObject, ClassObject, FunctionObject, NamespaceObject, ScriptObject,
@@ -719,6 +720,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
     ) -> Result<Value<'gc>, Error<'gc>> {
+        #[cfg(feature = "jit")]
+        if let Some(result) = crate::avm2::jit::try_run(self, method) {
+            return result;
+        }
+
         // The method must be verified at this point
 
         let verified_info = method.verified_info.borrow();
@@ -835,6 +841,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     multiname,
                     num_args,
                 } => self.op_call_property(*multiname, *num_args),
+                Op::CallPropertyCached { cache, num_args } => {
+                    self.op_call_property_cached(*cache, *num_args)
+                }
                 Op::CallPropLex {
                     multiname,
                     num_args,
@@ -843,6 +852,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     multiname,
                     num_args,
                 } => self.op_call_prop_void(*multiname, *num_args),
+                Op::CallPropVoidCached { cache, num_args } => {
+                    self.op_call_prop_void_cached(*cache, *num_args)
+                }
                 Op::CallStatic { index, num_args } => {
                     self.op_call_static(method, *index, *num_args)
                 }
@@ -858,7 +870,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 Op::ReturnValueNoCoerce => self.op_return_value_no_coerce(),
                 Op::ReturnVoid => self.op_return_void(),
                 Op::GetProperty { multiname } => self.op_get_property(*multiname),
+                Op::GetPropertyCached { cache } => self.op_get_property_cached(*cache),
                 Op::SetProperty { multiname } => self.op_set_property(*multiname),
+                Op::SetPropertyCached { cache } => self.op_set_property_cached(*cache),
+                Op::GetVectorElement { multiname } => self.op_get_vector_element(*multiname),
+                Op::SetVectorElement { multiname } => self.op_set_vector_element(*multiname),
                 Op::InitProperty { multiname } => self.op_init_property(*multiname),
                 Op::DeleteProperty { multiname } => self.op_delete_property(*multiname),
                 Op::GetSuper { multiname } => self.op_get_super(*multiname),
@@ -1167,6 +1183,25 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_call_property_cached(
+        &mut self,
+        cache: Gc<'gc, PropertyCache<'gc>>,
+        arg_count: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let args = self.pop_stack_args(arg_count);
+        let multiname = cache.multiname();
+        let receiver = self
+            .pop_stack()
+            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertyCache::get_trait(cache, self.gc(), receiver.vtable());
+
+        let value = receiver.call_resolved_property(&multiname, property, &args, self)?;
+
+        self.push_stack(value);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_call_prop_lex(
         &mut self,
         multiname: Gc<'gc, Multiname<'gc>>,
@@ -1201,6 +1236,23 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_call_prop_void_cached(
+        &mut self,
+        cache: Gc<'gc, PropertyCache<'gc>>,
+        arg_count: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let args = self.pop_stack_args(arg_count);
+        let multiname = cache.multiname();
+        let receiver = self
+            .pop_stack()
+            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertyCache::get_trait(cache, self.gc(), receiver.vtable());
+
+        receiver.call_resolved_property(&multiname, property, &args, self)?;
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_call_static(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1344,6 +1396,23 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_get_property_cached(
+        &mut self,
+        cache: Gc<'gc, PropertyCache<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let multiname = cache.multiname();
+        let object = self
+            .pop_stack()
+            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertyCache::get_trait(cache, self.gc(), object.vtable());
+
+        let value = object.get_resolved_property(&multiname, property, self)?;
+
+        self.push_stack(value);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_set_property(
         &mut self,
         multiname: Gc<'gc, Multiname<'gc>>,
@@ -1379,6 +1448,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
                             return Ok(FrameControl::Continue);
                         }
+
+                        if let Some(mut vector) = object.as_vector_storage_mut(self.gc()) {
+                            if vector.set_exact(name_int as usize, &value) {
+                                let _ = self.pop_stack();
+                                let _ = self.pop_stack();
+
+                                return Ok(FrameControl::Continue);
+                            }
+                        }
                     }
                     Value::Object(name_object) => {
                         if let Some(dictionary) = object.as_dictionary_object() {
@@ -1403,6 +1481,73 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_set_property_cached(
+        &mut self,
+        cache: Gc<'gc, PropertyCache<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.pop_stack();
+        let multiname = cache.multiname();
+        let object = self
+            .pop_stack()
+            .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+        let property = PropertyCache::get_trait(cache, self.gc(), object.vtable());
+
+        object.set_resolved_property(&multiname, property, value, self)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_get_vector_element(
+        &mut self,
+        multiname: Gc<'gc, Multiname<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let index_value = self.context.avm2.peek(0);
+        let object_value = self.context.avm2.peek(1);
+
+        if let (Value::Object(object), Value::Integer(index)) = (object_value, index_value) {
+            let value = object
+                .as_vector_storage()
+                .and_then(|vector| vector.get_optional(index.try_into().ok()?));
+
+            if let Some(value) = value {
+                let _ = self.pop_stack();
+                let _ = self.pop_stack();
+                self.push_stack(value);
+
+                return Ok(FrameControl::Continue);
+            }
+        }
+
+        // Not a vector, an index out of range or a non-integer index; these all
+        // take the regular path, which handles them or throws the right error.
+        self.op_get_property(multiname)
+    }
+
+    fn op_set_vector_element(
+        &mut self,
+        multiname: Gc<'gc, Multiname<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.peek(0);
+        let index_value = self.context.avm2.peek(1);
+        let object_value = self.context.avm2.peek(2);
+
+        if let (Value::Object(object), Value::Integer(index)) = (object_value, index_value) {
+            if let Ok(index) = index.try_into() {
+                if let Some(mut vector) = object.as_vector_storage_mut(self.gc()) {
+                    if vector.set_exact(index, &value) {
+                        let _ = self.pop_stack();
+                        let _ = self.pop_stack();
+                        let _ = self.pop_stack();
+
+                        return Ok(FrameControl::Continue);
+                    }
+                }
+            }
+        }
+
+        self.op_set_property(multiname)
+    }
+
     fn op_init_property(
         &mut self,
         multiname: Gc<'gc, Multiname<'gc>>,
@@ -392,6 +392,7 @@ pub fn deserialize_value_impl<'gc>(
                 vec.iter().map(|v| (*v).into()).collect(),
                 *is_fixed,
                 Some(activation.avm2().class_defs().number),
+                activation,
             );
             VectorObject::from_vector(storage, activation)?.into()
         }
@@ -400,6 +401,7 @@ pub fn deserialize_value_impl<'gc>(
                 vec.iter().map(|v| (*v).into()).collect(),
                 *is_fixed,
                 Some(activation.avm2().class_defs().uint),
+                activation,
             );
             VectorObject::from_vector(storage, activation)?.into()
         }
@@ -408,6 +410,7 @@ pub fn deserialize_value_impl<'gc>(
                 vec.iter().map(|v| (*v).into()).collect(),
                 *is_fixed,
                 Some(activation.avm2().class_defs().int),
+                activation,
             );
             VectorObject::from_vector(storage, activation)?.into()
         }
@@ -1,10 +1,11 @@
 use crate::avm2::error::{eof_error, make_error_2006};
 use crate::avm2::Activation;
 use crate::avm2::Error;
+use crate::memory::ExternalMemory;
 use crate::string::{FromWStr, WStr};
 use flate2::read::*;
 use flate2::Compression;
-use gc_arena::Collect;
+use gc_arena::{Collect, Mutation};
 use std::cell::Cell;
 use std::cmp;
 use std::fmt::{self, Display, Formatter};
@@ -96,6 +97,9 @@ pub struct ByteArrayStorage {
 
     /// The encoding used when serializing/deserializing using readObject/writeObject
     object_encoding: ObjectEncoding,
+
+    /// The memory used by `bytes`, as reported to the GC arena.
+    memory: ExternalMemory,
 }
 
 impl ByteArrayStorage {
@@ -106,19 +110,35 @@ impl ByteArrayStorage {
             position: Cell::new(0),
             endian: Endian::Big,
             object_encoding: ObjectEncoding::Amf3,
+            memory: ExternalMemory::default(),
         }
     }
 
     /// Create a new ByteArrayStorage using an already existing vector
     pub fn from_vec(bytes: Vec<u8>) -> ByteArrayStorage {
+        let memory = ExternalMemory::default();
+        memory.set(bytes.capacity());
         ByteArrayStorage {
             bytes,
             position: Cell::new(0),
             endian: Endian::Big,
             object_encoding: ObjectEncoding::Amf3,
+            memory,
         }
     }
 
+    /// Reports the memory used by this ByteArray to the GC arena of `mc`, so
+    /// that it's taken into account when pacing collection.
+    pub fn attach_memory(&mut self, mc: &Mutation<'_>) {
+        self.memory.attach(mc);
+    }
+
+    /// Updates the reported memory after `bytes` may have been reallocated.
+    #[inline]
+    fn update_memory(&self) {
+        self.memory.set(self.bytes.capacity());
+    }
+
     /// Write bytes at the next position in the ByteArray, growing if needed.
     #[inline]
     pub fn write_bytes(&mut self, buf: &[u8]) -> Result<(), ByteArrayError> {
@@ -312,12 +332,14 @@ impl ByteArrayStorage {
 
     #[inline]
     pub fn shrink_to_fit(&mut self) {
-        self.bytes.shrink_to_fit()
+        self.bytes.shrink_to_fit();
+        self.update_memory();
     }
 
     #[inline]
     pub fn set_length(&mut self, new_len: usize) {
         self.bytes.resize(new_len, 0);
+        self.update_memory();
         self.position.set(self.position().min(new_len));
     }
 
@@ -327,7 +349,8 @@ impl ByteArrayStorage {
 
     pub fn set(&mut self, item: usize, value: u8) {
         if self.len() < (item + 1) {
-            self.bytes.resize(item + 1, 0)
+            self.bytes.resize(item + 1, 0);
+            self.update_memory();
         }
 
         *self.bytes.get_mut(item).unwrap() = value;
@@ -5,7 +5,8 @@ use std::cell::Ref;
 use crate::avm2::activation::Activation;
 use crate::avm2::object::{ByteArrayObject, TObject};
 use crate::avm2::property_map::PropertyMap;
-use crate::avm2::script::Script;
+use crate::avm2::scope::ScopeChain;
+use crate::avm2::script::{Script, TranslationUnit};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
@@ -29,6 +30,14 @@ pub struct Domain<'gc>(GcCell<'gc, DomainData<'gc>>);
 #[collect(no_drop)]
 pub struct DomainWeak<'gc>(GcWeakCell<'gc, DomainData<'gc>>);
 
+/// A class that can be loaded from the ABC file of a `TranslationUnit`.
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+struct RegisteredClass<'gc> {
+    unit: TranslationUnit<'gc>,
+    class_index: u32,
+}
+
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 struct DomainData<'gc> {
@@ -39,6 +48,12 @@ struct DomainData<'gc> {
     /// to perform early interface resolution.
     classes: PropertyMap<'gc, Class<'gc>>,
 
+    /// Classes that were registered in this domain by name, but not loaded
+    /// yet.
+    ///
+    /// These are loaded (and moved to `classes`) when first looked up.
+    registered_classes: PropertyMap<'gc, RegisteredClass<'gc>>,
+
     /// The parent domain.
     parent: Option<Domain<'gc>>,
 
@@ -74,6 +89,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: PropertyMap::new(),
                 classes: PropertyMap::new(),
+                registered_classes: PropertyMap::new(),
                 parent,
                 domain_memory: None,
                 default_domain_memory: None,
@@ -122,6 +138,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: PropertyMap::new(),
                 classes: PropertyMap::new(),
+                registered_classes: PropertyMap::new(),
                 parent: Some(parent),
                 domain_memory: None,
                 default_domain_memory: None,
@@ -161,10 +178,12 @@ impl<'gc> Domain<'gc> {
     }
 
     /// Determine if a class has been defined within the current domain (including parents)
+    ///
+    /// This includes classes that were registered but not loaded yet.
     pub fn has_class(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
 
-        if read.classes.contains_key(name) {
+        if read.classes.contains_key(name) || read.registered_classes.contains_key(name) {
             return true;
         }
 
@@ -199,25 +218,81 @@ impl<'gc> Domain<'gc> {
         Ok(None)
     }
 
-    fn get_class_inner(self, multiname: &Multiname<'gc>) -> Option<Class<'gc>> {
+    fn get_class_inner(
+        self,
+        context: &mut UpdateContext<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Class<'gc>> {
         let read = self.0.read();
         if let Some(class) = read.classes.get_for_multiname(multiname).copied() {
             return Some(class);
         }
 
-        if let Some(parent) = read.parent {
-            return parent.get_class_inner(multiname);
+        let registered_name = read
+            .registered_classes
+            .get_with_ns_for_multiname(multiname)
+            .zip(multiname.local_name())
+            .map(|((ns, _), local_name)| QName::new(ns, local_name));
+        let parent = read.parent;
+        drop(read);
+
+        if let Some(name) = registered_name {
+            if let Some(class) = self.load_registered_class(context, name) {
+                return Some(class);
+            }
+        }
+
+        if let Some(parent) = parent {
+            return parent.get_class_inner(context, multiname);
         }
 
         None
     }
 
+    /// Load a class that was registered in this domain, and move it to the
+    /// loaded classes.
+    ///
+    /// If the class fails to load, the error is logged and the class is
+    /// treated as if it was never defined, like when a `DoAbc` tag fails to
+    /// load.
+    fn load_registered_class(
+        self,
+        context: &mut UpdateContext<'gc>,
+        name: QName<'gc>,
+    ) -> Option<Class<'gc>> {
+        // Unregister the class before loading it, so that a class which
+        // extends itself fails to resolve its superclass instead of recursing.
+        let registered = self.0.write(context.gc()).registered_classes.remove(name)?;
+
+        let mut activation = Activation::from_domain(context, self);
+        // Make sure we have the correct domain for code that tries to access it
+        // using `activation.domain()`
+        activation.set_outer(ScopeChain::new(self));
+
+        match registered
+            .unit
+            .load_class(registered.class_index, &mut activation)
+        {
+            Ok(class) => {
+                self.0.write(activation.gc()).classes.insert(name, class);
+                Some(class)
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Error loading class {}: {e:?}",
+                    name.to_qualified_name(activation.gc())
+                );
+                None
+            }
+        }
+    }
+
     pub fn get_class(
         self,
         context: &mut UpdateContext<'gc>,
         multiname: &Multiname<'gc>,
     ) -> Option<Class<'gc>> {
-        let class = self.get_class_inner(multiname);
+        let class = self.get_class_inner(context, multiname);
 
         if let Some(class) = class {
             if let Some(param) = multiname.param() {
@@ -339,6 +414,26 @@ impl<'gc> Domain<'gc> {
         self.0.write(mc).classes.insert(export_name, class);
     }
 
+    /// Register a class from a translation unit in the current application
+    /// domain, to be loaded when it's first looked up.
+    ///
+    /// This does nothing if the class already exists in this domain or a parent.
+    pub fn register_class(
+        &self,
+        export_name: QName<'gc>,
+        unit: TranslationUnit<'gc>,
+        class_index: u32,
+        mc: &Mutation<'gc>,
+    ) {
+        if self.has_class(export_name) {
+            return;
+        }
+        self.0
+            .write(mc)
+            .registered_classes
+            .insert(export_name, RegisteredClass { unit, class_index });
+    }
+
     pub fn defs(&self) -> Ref<PropertyMap<'gc, Script<'gc>>> {
         Ref::map(self.0.read(), |this| &this.defs)
     }
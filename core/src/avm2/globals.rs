@@ -75,6 +75,7 @@ pub struct SystemClasses<'gc> {
     pub framelabel: ClassObject<'gc>,
     pub scene: ClassObject<'gc>,
     pub application_domain: ClassObject<'gc>,
+    pub ime: ClassObject<'gc>,
     pub event: ClassObject<'gc>,
     pub fullscreenevent: ClassObject<'gc>,
     pub video: ClassObject<'gc>,
@@ -106,6 +107,10 @@ pub struct SystemClasses<'gc> {
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub imeevent: ClassObject<'gc>,
+    pub touchevent: ClassObject<'gc>,
+    pub gestureevent: ClassObject<'gc>,
+    pub transformgestureevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
@@ -239,6 +244,7 @@ impl<'gc> SystemClasses<'gc> {
             framelabel: object,
             scene: object,
             application_domain: object,
+            ime: object,
             event: object,
             fullscreenevent: object,
             video: object,
@@ -268,6 +274,10 @@ impl<'gc> SystemClasses<'gc> {
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            imeevent: object,
+            touchevent: object,
+            gestureevent: object,
+            transformgestureevent: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
@@ -799,7 +809,8 @@ pub fn load_player_globals<'gc>(
 
 /// This file is built by 'core/build_playerglobal/'
 /// See that tool, and 'core/src/avm2/globals/README.md', for more details
-const PLAYERGLOBAL: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/playerglobal.swf"));
+pub(crate) const PLAYERGLOBAL: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/playerglobal.swf"));
 
 mod native {
     include!(concat!(env!("OUT_DIR"), "/native_table.rs"));
@@ -919,6 +930,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "IMEEvent", imeevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
@@ -933,6 +945,13 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.events", "FocusEvent", focusevent),
+            ("flash.events", "TouchEvent", touchevent),
+            ("flash.events", "GestureEvent", gestureevent),
+            (
+                "flash.events",
+                "TransformGestureEvent",
+                transformgestureevent
+            ),
             ("flash.geom", "Matrix", matrix),
             ("flash.geom", "Matrix3D", matrix3d),
             ("flash.geom", "PerspectiveProjection", perspectiveprojection),
@@ -951,6 +970,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.utils", "ByteArray", bytearray),
             ("flash.utils", "Dictionary", dictionary),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "IME", ime),
             ("flash.text", "Font", font),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
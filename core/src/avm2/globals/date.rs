@@ -5,9 +5,11 @@ pub use crate::avm2::object::date_allocator;
 use crate::avm2::object::{DateObject, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::locale::{get_current_date_time, get_timezone};
+use crate::locale::{self, Timezone};
 use crate::string::{utils as string_utils, AvmString, WStr};
-use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, TimeZone, Timelike, Utc,
+};
 use num_traits::ToPrimitive;
 
 struct DateAdjustment<'builder, 'activation_a: 'builder, 'gc: 'activation_a, T: TimeZone + 'builder>
@@ -152,15 +154,12 @@ impl<'builder, 'activation_a, 'gc, T: TimeZone> DateAdjustment<'builder, 'activa
             + Duration::try_seconds(second)?
             + Duration::try_milliseconds(millisecond)?;
 
-        if let LocalResult::Single(Some(result)) = current
-            .timezone()
-            .with_ymd_and_hms(year, (month + 1) as u32, 1, 0, 0, 0)
-            .map(|date| date.checked_add_signed(duration))
-        {
-            Some(result.with_timezone(&Utc))
-        } else {
-            None
-        }
+        // Add the duration in local time, so that the time of day is kept across DST transitions.
+        let local = NaiveDate::from_ymd_opt(year, (month + 1) as u32, 1)?
+            .and_hms_opt(0, 0, 0)?
+            .checked_add_signed(duration)?;
+        locale::from_local_datetime(&current.timezone(), &local)
+            .map(|result| result.with_timezone(&Utc))
     }
 
     fn apply(&mut self, object: DateObject<'gc>) -> f64 {
@@ -202,7 +201,7 @@ pub fn init<'gc>(
     let timestamp = arguments.get(0).unwrap_or(&Value::Undefined);
     if timestamp != &Value::Undefined {
         if arguments.len() > 1 {
-            let timezone = get_timezone();
+            let timezone = activation.context.clock.timezone();
 
             // We need a starting value to adjust from.
             this.set_date_time(Some(
@@ -236,7 +235,7 @@ pub fn init<'gc>(
             }
         }
     } else {
-        this.set_date_time(Some(get_current_date_time()))
+        this.set_date_time(Some(activation.context.clock.date_time()))
     }
 
     Ok(Value::Undefined)
@@ -296,7 +295,7 @@ pub fn set_time<'gc>(
 
 /// Implements the `getMilliseconds` method.
 pub fn get_milliseconds<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -306,7 +305,7 @@ pub fn get_milliseconds<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.timestamp_subsec_millis() as f64).into())
     } else {
@@ -325,7 +324,8 @@ pub fn _set_milliseconds<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .millisecond(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -333,7 +333,7 @@ pub fn _set_milliseconds<'gc>(
 
 /// Implements the `getSeconds` method.
 pub fn get_seconds<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -343,7 +343,7 @@ pub fn get_seconds<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.second() as f64).into())
     } else {
@@ -362,7 +362,8 @@ pub fn _set_seconds<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .second(args.get(0))?
         .millisecond(args.get(1))?
         .apply(this);
@@ -371,7 +372,7 @@ pub fn _set_seconds<'gc>(
 
 /// Implements `getMinutes` method.
 pub fn get_minutes<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -381,7 +382,7 @@ pub fn get_minutes<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.minute() as f64).into())
     } else {
@@ -400,7 +401,8 @@ pub fn _set_minutes<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .minute(args.get(0))?
         .second(args.get(1))?
         .millisecond(args.get(2))?
@@ -410,7 +412,7 @@ pub fn _set_minutes<'gc>(
 
 /// Implements the `getHours` method.
 pub fn get_hours<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -420,7 +422,7 @@ pub fn get_hours<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.hour() as f64).into())
     } else {
@@ -439,7 +441,8 @@ pub fn _set_hours<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .hour(args.get(0))?
         .minute(args.get(1))?
         .second(args.get(2))?
@@ -450,7 +453,7 @@ pub fn _set_hours<'gc>(
 
 /// Implements `getDate` method.
 pub fn get_date<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -460,7 +463,7 @@ pub fn get_date<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.day() as f64).into())
     } else {
@@ -479,7 +482,8 @@ pub fn _set_date<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .day(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -487,7 +491,7 @@ pub fn _set_date<'gc>(
 
 /// Implements the `getMonth` method.
 pub fn get_month<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -497,7 +501,7 @@ pub fn get_month<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.month0() as f64).into())
     } else {
@@ -516,7 +520,8 @@ pub fn _set_month<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timestamp = DateAdjustment::new(activation, &get_timezone())
+    let timezone = activation.context.clock.timezone();
+    let timestamp = DateAdjustment::new(activation, &timezone)
         .month(args.get(0))?
         .day(args.get(1))?
         .apply(this);
@@ -525,7 +530,7 @@ pub fn _set_month<'gc>(
 
 /// Implements the `getFullYear` method.
 pub fn get_full_year<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -535,7 +540,7 @@ pub fn get_full_year<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.year() as f64).into())
     } else {
@@ -554,7 +559,7 @@ pub fn _set_full_year<'gc>(
     let this = this.as_date_object().unwrap();
     let args = get_arguments_array(args);
 
-    let timezone = get_timezone();
+    let timezone = activation.context.clock.timezone();
     if this.date_time().is_none() {
         this.set_date_time(Some(
             timezone
@@ -574,7 +579,7 @@ pub fn _set_full_year<'gc>(
 
 /// Implements the `getDay` method.
 pub fn get_day<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -584,7 +589,7 @@ pub fn get_day<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok((date.weekday().num_days_from_sunday() as f64).into())
     } else {
@@ -865,7 +870,7 @@ pub fn get_utc_day<'gc>(
 
 /// Implements the `getTimezoneOffset` method.
 pub fn get_timezone_offset<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -875,7 +880,7 @@ pub fn get_timezone_offset<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         let offset = date.offset().utc_minus_local() as f64;
         Ok((offset / 60.0).into())
@@ -925,7 +930,7 @@ pub fn to_string<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok(AvmString::new_utf8(
             activation.gc(),
@@ -970,7 +975,7 @@ pub fn to_locale_string<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok(AvmString::new_utf8(
             activation.gc(),
@@ -994,7 +999,7 @@ pub fn to_time_string<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok(AvmString::new_utf8(activation.gc(), date.format("%T GMT%z").to_string()).into())
     } else {
@@ -1014,7 +1019,7 @@ pub fn to_locale_time_string<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok(AvmString::new_utf8(activation.gc(), date.format("%T %p").to_string()).into())
     } else {
@@ -1034,7 +1039,7 @@ pub fn to_date_string<'gc>(
 
     if let Some(date) = this
         .date_time()
-        .map(|date| date.with_timezone(&get_timezone()))
+        .map(|date| date.with_timezone(&activation.context.clock.timezone()))
     {
         Ok(AvmString::new_utf8(activation.gc(), date.format("%a %b %-d %-Y").to_string()).into())
     } else {
@@ -1146,7 +1151,7 @@ pub fn parse_full_date<'gc>(
 ) -> Option<f64> {
     const DAYS: [&[u8]; 7] = [b"Sun", b"Mon", b"Tue", b"Wed", b"Thu", b"Fri", b"Sat"];
 
-    let timezone = get_timezone();
+    let timezone = activation.context.clock.timezone();
     let mut final_time = DateAdjustment::new(activation, &timezone);
     let mut new_timezone = None;
     // The Date parser is flash is super flexible, so we need to go through each item individually and parse it to match Flash.
@@ -1239,6 +1244,7 @@ pub fn parse_full_date<'gc>(
     }
     if let Some(timestamp) = final_time.calculate(
         new_timezone
+            .map(Timezone::Fixed)
             .unwrap_or(timezone)
             .with_ymd_and_hms(0, 1, 1, 0, 0, 0)
             .single()
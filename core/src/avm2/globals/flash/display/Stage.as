@@ -13,9 +13,7 @@ package flash.display {
     [Ruffle(Abstract)]
     public class Stage extends DisplayObjectContainer {
         private var _colorCorrection:String = ColorCorrection.DEFAULT;
-        private var _mouseLock:Boolean = false;
         private var _nativeWindow:NativeWindow;
-        private var _fullScreenSourceRect:Rectangle;
 
         override public function set accessibilityProperties(value:AccessibilityProperties):void {
             throw new IllegalOperationError("Error #2071: The Stage class does not implement this property or method.", 2071);
@@ -212,15 +210,8 @@ package flash.display {
 
         public native function get fullScreenHeight():uint;
 
-        public function get fullScreenSourceRect():Rectangle {
-            stub_getter("flash.display.Stage", "fullScreenSourceRect");
-            return this._fullScreenSourceRect;
-        }
-
-        public function set fullScreenSourceRect(rect: Rectangle):void {
-            stub_setter("flash.display.Stage", "fullScreenSourceRect");
-            this._fullScreenSourceRect = rect;
-        }
+        public native function get fullScreenSourceRect():Rectangle;
+        public native function set fullScreenSourceRect(rect: Rectangle):void;
 
         public native function get fullScreenWidth():uint;
 
@@ -276,16 +267,10 @@ package flash.display {
         }
 
         [API("678")]
-        public function get mouseLock():Boolean {
-            stub_getter("flash.display.Stage", "mouseLock");
-            return this._mouseLock;
-        }
+        public native function get mouseLock():Boolean;
 
         [API("678")]
-        public function set mouseLock(value:Boolean):void {
-            stub_setter("flash.display.Stage", "mouseLock");
-            this._mouseLock = value;
-        }
+        public native function set mouseLock(value:Boolean):void;
 
         [API("668")]
         public static function get supportsOrientationChange():Boolean {
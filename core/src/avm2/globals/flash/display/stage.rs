@@ -1,7 +1,8 @@
 //! `flash.display.Stage` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{illegal_operation_error, make_error_2008};
+use crate::avm2::globals::flash::display::display_object::{new_rectangle, object_to_rectangle};
 use crate::avm2::object::{TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -172,6 +173,37 @@ pub fn set_display_state<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `mouseLock`'s getter
+pub fn get_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.mouse_lock().into())
+}
+
+/// Implement `mouseLock`'s setter
+pub fn set_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mouse_lock = args.get_bool(0);
+    if mouse_lock && !activation.context.stage.is_fullscreen() {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Mouse lock is only available in full screen mode.",
+            0,
+        )?));
+    }
+
+    activation
+        .context
+        .stage
+        .set_mouse_lock(activation.context, mouse_lock);
+    Ok(Value::Undefined)
+}
+
 /// Implement `focus`'s getter
 pub fn get_focus<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -436,6 +468,7 @@ pub fn get_stage3ds<'gc>(
                 .collect(),
             false,
             Some(activation.avm2().classes().stage3d.inner_class_definition()),
+            activation,
         );
         let stage3ds = VectorObject::from_vector(storage, activation)?;
         return Ok(stage3ds.into());
@@ -457,14 +490,30 @@ pub fn invalidate<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Returns the size of the screen, falling back to the viewport size if it's unknown.
+fn full_screen_size(activation: &mut Activation<'_, '_>) -> (u32, u32) {
+    let viewport_dimensions = activation.context.renderer.viewport_dimensions();
+    let (width, height) = activation
+        .context
+        .ui
+        .screen_size()
+        .unwrap_or((viewport_dimensions.width, viewport_dimensions.height));
+    // Screen size is adjusted for HiDPI, like the viewport size.
+    let adjusted_width = f64::from(width) / viewport_dimensions.scale_factor;
+    let adjusted_height = f64::from(height) / viewport_dimensions.scale_factor;
+    (
+        adjusted_width.round() as u32,
+        adjusted_height.round() as u32,
+    )
+}
+
 /// Stage.fullScreenHeight's getter
 pub fn get_full_screen_height<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenHeight");
-    Ok(768.into())
+    Ok(full_screen_size(activation).1.into())
 }
 
 /// Stage.fullScreenWidth's getter
@@ -473,8 +522,36 @@ pub fn get_full_screen_width<'gc>(
     _this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenWidth");
-    Ok(1024.into())
+    Ok(full_screen_size(activation).0.into())
+}
+
+/// Stage.fullScreenSourceRect's getter
+pub fn get_full_screen_source_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match activation.context.stage.full_screen_source_rect() {
+        Some(rect) => Ok(new_rectangle(activation, rect)?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Stage.fullScreenSourceRect's setter
+pub fn set_full_screen_source_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = match args.try_get_object(activation, 0) {
+        Some(rect) => Some(object_to_rectangle(activation, rect)?),
+        None => None,
+    };
+    activation
+        .context
+        .stage
+        .set_full_screen_source_rect(activation.context, rect);
+    Ok(Value::Undefined)
 }
 
 pub fn set_tab_children<'gc>(
@@ -0,0 +1,21 @@
+package flash.events {
+    public class IMEEvent extends TextEvent {
+
+        public static const IME_COMPOSITION:String = "imeComposition";
+
+        public function IMEEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, text:String = "")
+        {
+            super(type,bubbles,cancelable,text);
+        }
+
+        override public function clone() : Event
+        {
+            return new IMEEvent(this.type,this.bubbles,this.cancelable,this.text);
+        }
+
+        override public function toString() : String
+        {
+            return this.formatToString("IMEEvent","type","bubbles","cancelable","eventPhase","text");
+        }
+    }
+}
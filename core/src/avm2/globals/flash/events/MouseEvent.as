@@ -47,8 +47,10 @@ package flash.events
         private var _isRelatedObjectInaccessible: Boolean;
 
         [API("678")]
+        [Ruffle(InternalSlot)]
         public var movementX: Number;
         [API("678")]
+        [Ruffle(InternalSlot)]
         public var movementY: Number;
 
         public function MouseEvent(type:String, 
@@ -73,8 +75,9 @@ package flash.events
             this.buttonDown = buttonDown;
             this.delta = delta;
 
-            this.movementX = 0.0; // unimplemented
-            this.movementY = 0.0; // unimplemented
+            // Only set by the player for `mouseMove` events.
+            this.movementX = 0.0;
+            this.movementY = 0.0;
         }
 
         override public function clone() : Event
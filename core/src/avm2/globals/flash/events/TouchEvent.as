@@ -27,7 +27,9 @@ public class TouchEvent extends Event {
 
     public var touchPointID: int; // A unique identification number (as an int) assigned to the touch point.
     public var isPrimaryTouchPoint: Boolean; // Indicates whether the first point of contact is mapped to mouse events.
+    [Ruffle(InternalSlot)]
     public var localX: Number; // The horizontal coordinate at which the event occurred relative to the containing sprite.
+    [Ruffle(InternalSlot)]
     public var localY: Number; // The vertical coordinate at which the event occurred relative to the containing sprite.
     public var sizeX: Number; // Width of the contact area.
     public var sizeY: Number; // Height of the contact area.
@@ -37,8 +39,6 @@ public class TouchEvent extends Event {
     public var altKey: Boolean; // Indicates whether the Alt key is active (true) or inactive (false).
     public var shiftKey: Boolean; // Indicates whether the Shift key is active (true) or inactive (false).
     public var isRelatedObjectInaccessible: Boolean; // If true, the relatedObject property is set to null for reasons related to security sandboxes.
-    private var _stageX: Number; // [read-only] The horizontal coordinate at which the event occurred in global Stage coordinates.
-    private var _stageY: Number; // [read-only] The vertical coordinate at which the event occurred in global Stage coordinates.
 
     public function TouchEvent(type: String, bubbles: Boolean = true, cancelable: Boolean = false, touchPointID: int = 0,
                                isPrimaryTouchPoint: Boolean = false, localX: Number = NaN, localY: Number = NaN,
@@ -91,12 +91,10 @@ public class TouchEvent extends Event {
     // Instructs Flash Player or Adobe AIR to render after processing of this event completes, if the display list has been modified.
     public native function updateAfterEvent(): void;
 
-    public function get stageX(): Number {
-        return this._stageX;
-    }
+    // [read-only] The horizontal coordinate at which the event occurred in global Stage coordinates.
+    public native function get stageX(): Number;
 
-    public function get stageY(): Number {
-        return this._stageY;
-    }
+    // [read-only] The vertical coordinate at which the event occurred in global Stage coordinates.
+    public native function get stageY(): Number;
 }
 }
@@ -2,13 +2,26 @@ package flash.system {
     public final class System {
         import __ruffle__.stub_method;
 
-        public static function gc(): void {
+        private static var _ime: IME = null;
 
+        public static function get ime(): IME {
+            if (_ime == null) {
+                _ime = new IME();
+            }
+            return _ime;
         }
 
-        public static function pauseForGCIfCollectionImminent(imminence:Number = 0.75): void {
-            stub_method("flash.system.System", "pauseForGCIfCollectionImminent");
-        }
+        public static native function get totalMemory(): uint;
+
+        public static native function get totalMemoryNumber(): Number;
+
+        public static native function get privateMemory(): Number;
+
+        public static native function get freeMemory(): Number;
+
+        public static native function gc(): void;
+
+        public static native function pauseForGCIfCollectionImminent(imminence:Number = 0.75): void;
 
         public static native function setClipboard(string:String): void;
 
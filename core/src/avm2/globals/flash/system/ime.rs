@@ -0,0 +1,54 @@
+//! `flash.system.IME` native methods
+
+use crate::avm1::globals::system::ImeConversionMode;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, AvmString, Error, Value};
+
+/// Implements `flash.system.IME.enabled`'s getter
+pub fn get_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.system.ime_enabled.into())
+}
+
+/// Implements `flash.system.IME.enabled`'s setter
+pub fn set_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.system.ime_enabled = args.get_bool(0);
+
+    let focus_tracker = activation.context.focus_tracker;
+    focus_tracker.update_ime(activation.context);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.conversionMode`'s getter
+pub fn get_conversion_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = activation.context.system.ime_conversion_mode.to_string();
+    Ok(AvmString::new_utf8(activation.gc(), mode).into())
+}
+
+/// Implements `flash.system.IME.conversionMode`'s setter
+pub fn set_conversion_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = args.get_string(activation, 0)?;
+    let Some(mode) = ImeConversionMode::from_name(&mode.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "conversionMode"));
+    };
+    activation.context.system.ime_conversion_mode = mode;
+
+    Ok(Value::Undefined)
+}
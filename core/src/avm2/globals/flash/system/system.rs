@@ -1,6 +1,7 @@
 //! `flash.system.System` native methods
 
 use crate::avm2::activation::Activation;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 
@@ -31,3 +32,82 @@ pub fn set_clipboard<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `flash.system.System.totalMemory` getter
+pub fn get_total_memory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let total = activation.context.gc().metrics().total_allocation();
+    Ok(u32::try_from(total).unwrap_or(u32::MAX).into())
+}
+
+/// Implements `flash.system.System.totalMemoryNumber` getter
+pub fn get_total_memory_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let total = activation.context.gc().metrics().total_allocation();
+    Ok((total as f64).into())
+}
+
+/// Implements `flash.system.System.privateMemory` getter
+pub fn get_private_memory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // All of the memory we know about is used by this player.
+    get_total_memory_number(activation, this, args)
+}
+
+/// Implements `flash.system.System.freeMemory` getter
+///
+/// This is the memory that can still be allocated before the next collection
+/// cycle starts.
+pub fn get_free_memory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let free = activation
+        .context
+        .gc_scheduler
+        .free_memory(activation.context.gc().metrics());
+    Ok((free as f64).into())
+}
+
+/// Implements `flash.system.System.gc` method
+///
+/// The collection happens once the current script has finished running.
+pub fn gc<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.gc_scheduler.request_collection(0.0);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.System.pauseForGCIfCollectionImminent` method
+///
+/// Like `gc`, the collection happens once the current script has finished
+/// running.
+pub fn pause_for_gc_if_collection_imminent<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let imminence = args.get_f64(activation, 0)?;
+    // NaN is treated like the default imminence.
+    let imminence = if imminence.is_nan() { 0.75 } else { imminence };
+    activation
+        .context
+        .gc_scheduler
+        .request_collection(imminence);
+
+    Ok(Value::Undefined)
+}
@@ -1,6 +1,24 @@
 package flash.ui {
     public final class Multitouch {
-        // FIXME - implement a setter and gett
-        public static var inputMode:String = MultitouchInputMode.GESTURE;
+        public static native function get inputMode():String;
+        public static native function set inputMode(value:String):void;
+
+        public static function get supportsTouchEvents():Boolean {
+            return true;
+        }
+
+        public static function get supportsGestureEvents():Boolean {
+            return true;
+        }
+
+        public static function get supportedGestures():Vector.<String> {
+            return Vector.<String>([
+                "gesturePan",
+                "gestureRotate",
+                "gestureSwipe",
+                "gestureZoom",
+                "gestureTwoFingerTap"
+            ]);
+        }
     }
-}
\ No newline at end of file
+}
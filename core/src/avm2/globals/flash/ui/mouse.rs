@@ -1,8 +1,18 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::slots::flash_ui_mouse_cursor_data as cursor_data_slots;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::avm2::TObject;
+use crate::backend::ui::CursorImage;
+use crate::player::RegisteredCursor;
+use crate::string::AvmString;
+
+/// Names of the native cursors, see `flash.ui.MouseCursor`.
+const NATIVE_CURSORS: [&str; 4] = ["arrow", "button", "hand", "ibeam"];
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -21,3 +31,132 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+/// Implements `flash.ui.Mouse.cursor`'s getter
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = activation
+        .context
+        .mouse_data
+        .cursor_name
+        .as_deref()
+        .unwrap_or("auto");
+    Ok(AvmString::new_utf8(activation.gc(), name).into())
+}
+
+/// Implements `flash.ui.Mouse.cursor`'s setter
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let mouse_data = &mut *activation.context.mouse_data;
+    if name == "auto" {
+        mouse_data.cursor_name = None;
+    } else if NATIVE_CURSORS.contains(&name.as_str())
+        || mouse_data.registered_cursors.contains_key(&name)
+    {
+        mouse_data.cursor_name = Some(name);
+    } else {
+        return Err(make_error_2008(activation, "cursor"));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Mouse.registerCursor`
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let cursor = args.get_object(activation, 1, "cursor")?;
+
+    let mut frames = Vec::new();
+    if let Some(data) = cursor.get_slot(cursor_data_slots::_DATA).as_object() {
+        if let Some(data) = data.as_vector_storage() {
+            for bitmap_data in data.iter() {
+                let Some(bitmap_data) = bitmap_data.as_object().and_then(|o| o.as_bitmap_data())
+                else {
+                    continue;
+                };
+                if bitmap_data.disposed() {
+                    continue;
+                }
+
+                let bitmap_data = bitmap_data.sync(activation.context.renderer);
+                let bitmap_data = bitmap_data.read();
+                let rgba = bitmap_data
+                    .pixels()
+                    .iter()
+                    .flat_map(|p| {
+                        let p = p.to_un_multiplied_alpha();
+                        [p.red(), p.green(), p.blue(), p.alpha()]
+                    })
+                    .collect();
+                frames.push(CursorImage {
+                    width: bitmap_data.width(),
+                    height: bitmap_data.height(),
+                    rgba,
+                });
+            }
+        }
+    }
+
+    if frames.is_empty() {
+        // There is nothing to display.
+        return Ok(Value::Undefined);
+    }
+
+    let hotspot = match cursor.get_slot(cursor_data_slots::_HOT_SPOT).as_object() {
+        Some(hotspot) => {
+            let x = hotspot
+                .get_public_property("x", activation)?
+                .coerce_to_number(activation)?;
+            let y = hotspot
+                .get_public_property("y", activation)?
+                .coerce_to_number(activation)?;
+            (x.max(0.0) as u32, y.max(0.0) as u32)
+        }
+        None => (0, 0),
+    };
+    let frame_rate = cursor
+        .get_slot(cursor_data_slots::_FRAME_RATE)
+        .coerce_to_number(activation)?;
+
+    activation.context.mouse_data.registered_cursors.insert(
+        name,
+        RegisteredCursor {
+            frames: frames.into(),
+            hotspot,
+            frame_rate,
+        },
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Mouse.unregisterCursor`
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let mouse_data = &mut *activation.context.mouse_data;
+    mouse_data.registered_cursors.remove(&name);
+
+    // A cursor which is no longer registered can't stay in use.
+    if mouse_data.cursor_name.as_deref() == Some(name.as_str())
+        && !NATIVE_CURSORS.contains(&name.as_str())
+    {
+        mouse_data.cursor_name = None;
+    }
+
+    Ok(Value::Undefined)
+}
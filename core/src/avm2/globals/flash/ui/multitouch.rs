@@ -0,0 +1,32 @@
+//! `flash.ui.Multitouch` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::touch::MultitouchInputMode;
+
+/// Implements `flash.ui.Multitouch.inputMode`'s getter
+pub fn get_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.touch_data.input_mode().name().into())
+}
+
+/// Implements `flash.ui.Multitouch.inputMode`'s setter
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = args.get_string(activation, 0)?;
+    let Some(mode) = MultitouchInputMode::from_name(&mode.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "inputMode"));
+    };
+    activation.context.touch_data.set_input_mode(mode);
+
+    Ok(Value::Undefined)
+}
@@ -150,6 +150,7 @@ include "flash/errors/StackOverflowError.as"
 // Event needs to come before its subclasses
 include "flash/events/Event.as"
 include "flash/events/TextEvent.as"
+include "flash/events/IMEEvent.as"
 include "flash/events/ActivityEvent.as"
 include "flash/events/ErrorEvent.as"
 include "flash/events/GestureEvent.as"
@@ -426,8 +427,6 @@ include "flash/ui/KeyLocation.as"
 include "flash/ui/Mouse.as"
 include "flash/ui/MouseCursor.as"
 include "flash/ui/MouseCursorData.as"
-// `MultitouchInputMode` needs to come before `Multitouch`, since `Multitouch` uses constants from
-// `MultitouchInputMode`.
 include "flash/ui/MultitouchInputMode.as"
 include "flash/ui/Multitouch.as"
 
@@ -903,8 +903,12 @@ pub fn splice<'gc>(
             to_coerce.push(value.coerce_to_type(activation, value_type_for_coercion)?);
         }
 
-        let new_vs =
-            VectorStorage::from_values(vs.splice(start..end, to_coerce)?, false, value_type);
+        let new_vs = VectorStorage::from_values(
+            vs.splice(start..end, to_coerce)?,
+            false,
+            value_type,
+            activation,
+        );
         let new_vector = VectorObject::from_vector(new_vs, activation)?;
 
         return Ok(new_vector.into());
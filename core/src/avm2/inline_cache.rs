@@ -0,0 +1,129 @@
+//! Inline caches for property accesses
+
+use crate::avm2::property::Property;
+use crate::avm2::vtable::VTable;
+use crate::avm2::Multiname;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::Lock;
+use gc_arena::{Collect, Gc, Mutation};
+use std::fmt::{self, Debug};
+
+/// How many different vtables a single cache remembers before it gives up.
+const POLYMORPHIC_LIMIT: usize = 4;
+
+/// The cache for a single `getproperty`, `setproperty`, `callproperty` or
+/// `callpropvoid` instruction with a name known at verification time.
+///
+/// When the optimizer can't tell the type of the receiver, the name has to be
+/// looked up in the receiver's `VTable` on every execution. This remembers the
+/// result of that lookup for the last few vtables seen by the instruction, so
+/// that call sites which always see objects of the same few classes skip the
+/// `PropertyMap` lookup entirely.
+///
+/// Only the trait lookup is cached. When the name isn't a trait, that fact is
+/// cached, but dynamic properties and the prototype chain are still searched on
+/// every execution, so adding or deleting dynamic properties and editing
+/// prototypes is always observed. The traits of a vtable are only modified
+/// while its class is being linked, before bytecode can see any objects using
+/// it, so entries never go stale.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct PropertyCache<'gc> {
+    multiname: Gc<'gc, Multiname<'gc>>,
+
+    state: Lock<CacheState<'gc>>,
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+enum CacheState<'gc> {
+    /// The receivers seen so far had at most `POLYMORPHIC_LIMIT` different
+    /// vtables. Entries are filled in order.
+    Polymorphic([Option<CacheEntry<'gc>>; POLYMORPHIC_LIMIT]),
+
+    /// The receivers seen so far had too many different vtables, so every
+    /// lookup goes to the vtable.
+    Megamorphic,
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+struct CacheEntry<'gc> {
+    vtable: VTable<'gc>,
+
+    /// The trait `multiname` resolved to in `vtable`, if any.
+    property: Option<Property>,
+}
+
+impl<'gc> PropertyCache<'gc> {
+    pub fn new(mc: &Mutation<'gc>, multiname: Gc<'gc, Multiname<'gc>>) -> Gc<'gc, Self> {
+        Gc::new(
+            mc,
+            Self {
+                multiname,
+                state: Lock::new(CacheState::Polymorphic([None; POLYMORPHIC_LIMIT])),
+            },
+        )
+    }
+
+    pub fn multiname(&self) -> Gc<'gc, Multiname<'gc>> {
+        self.multiname
+    }
+
+    /// Looks up the cached name in `vtable`, with the same result as
+    /// `VTable::get_trait`.
+    pub fn get_trait(
+        this: Gc<'gc, Self>,
+        mc: &Mutation<'gc>,
+        vtable: VTable<'gc>,
+    ) -> Option<Property> {
+        let mut entries = match this.state.get() {
+            CacheState::Polymorphic(entries) => entries,
+            CacheState::Megamorphic => return vtable.get_trait(&this.multiname),
+        };
+
+        let mut free_index = None;
+        for (index, entry) in entries.iter().enumerate() {
+            match entry {
+                Some(entry) if entry.vtable == vtable => return entry.property,
+                Some(_) => {}
+                None => {
+                    free_index = Some(index);
+                    break;
+                }
+            }
+        }
+
+        let property = vtable.get_trait(&this.multiname);
+
+        let state = if let Some(index) = free_index {
+            entries[index] = Some(CacheEntry { vtable, property });
+            CacheState::Polymorphic(entries)
+        } else {
+            CacheState::Megamorphic
+        };
+        unlock!(Gc::write(mc, this), PropertyCache, state).set(state);
+
+        property
+    }
+}
+
+impl Debug for PropertyCache<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state.get() {
+            CacheState::Polymorphic(entries) => {
+                match entries.iter().filter(|entry| entry.is_some()).count() {
+                    0 => "uninitialized",
+                    1 => "monomorphic",
+                    _ => "polymorphic",
+                }
+            }
+            CacheState::Megamorphic => "megamorphic",
+        };
+
+        f.debug_struct("PropertyCache")
+            .field("multiname", &self.multiname)
+            .field("state", &state)
+            .finish()
+    }
+}
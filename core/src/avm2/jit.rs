@@ -0,0 +1,1664 @@
+//! Baseline JIT compiler for AVM2 methods
+//!
+//! Methods that only work with `int`, `uint`, `Number` and `Boolean` values
+//! (arithmetic, comparisons, branches, locals and domain memory) are compiled
+//! to native code with Cranelift. Every stack slot and local register has to
+//! have a single known type at each instruction, which is checked by running
+//! the verified code through an abstract interpreter before compiling it.
+//! Anything else is left to the interpreter.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::error::make_error_1506;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::op::Op;
+use crate::avm2::{Error, Value};
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::{
+    types, AbiParam, Block, Endianness, InstBuilder, MemFlags, SigRef, Signature, Type,
+    Value as ClifValue,
+};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Module};
+use gc_arena::Gc;
+use std::ffi::c_void;
+use std::rc::Rc;
+use std::time::Duration;
+use swf::avm2::types::MethodFlags as AbcMethodFlags;
+use web_time::Instant;
+
+/// How many times a method has to be called before it's compiled in
+/// `JitMode::Hot`.
+const HOT_THRESHOLD: u32 = 16;
+
+/// How many backward branches compiled code takes between checks of the
+/// script timeout. This matches how many ops the interpreter runs between
+/// checks.
+const TIMEOUT_CHECK_INTERVAL: i64 = 64000;
+
+/// The compiled code returned normally.
+const STATUS_RETURN: u32 = 0;
+
+/// The script timeout expired.
+const STATUS_TIMEOUT: u32 = 1;
+
+/// Domain memory was accessed out of bounds.
+const STATUS_RANGE_ERROR: u32 = 2;
+
+/// Which AVM2 methods are compiled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum JitMode {
+    /// Every method is interpreted.
+    Off,
+
+    /// Methods are compiled once they have been called a few times.
+    #[default]
+    Hot,
+
+    /// Methods are compiled the first time they're called. This is meant for
+    /// testing the compiler.
+    Always,
+}
+
+/// The state of the JIT compiler for a player.
+pub struct Jit {
+    mode: JitMode,
+
+    /// The module holding all compiled code, created on first use.
+    module: Option<JITModule>,
+
+    /// Whether creating the module failed, for example because the host
+    /// architecture isn't supported.
+    unavailable: bool,
+}
+
+impl Jit {
+    pub fn new() -> Self {
+        Self {
+            mode: JitMode::default(),
+            module: None,
+            unavailable: false,
+        }
+    }
+
+    pub fn mode(&self) -> JitMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: JitMode) {
+        self.mode = mode;
+    }
+
+    fn module(&mut self) -> Option<&mut JITModule> {
+        if self.module.is_none() && !self.unavailable {
+            match create_module() {
+                Ok(module) => self.module = Some(module),
+                Err(e) => {
+                    tracing::warn!("AVM2 JIT is unavailable: {e}");
+                    self.unavailable = true;
+                }
+            }
+        }
+
+        self.module.as_mut()
+    }
+}
+
+impl Default for Jit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn create_module() -> Result<JITModule, String> {
+    let mut flags = settings::builder();
+    flags.set("opt_level", "speed").map_err(|e| e.to_string())?;
+    let isa = cranelift_native::builder()?
+        .finish(settings::Flags::new(flags))
+        .map_err(|e| e.to_string())?;
+
+    Ok(JITModule::new(JITBuilder::with_isa(
+        isa,
+        default_libcall_names(),
+    )))
+}
+
+/// The JIT state of a single method.
+pub enum MethodState {
+    /// The method hasn't been compiled yet.
+    Cold { calls: u32 },
+
+    /// The method was compiled.
+    Compiled(Rc<CompiledMethod>),
+
+    /// The method uses something the compiler doesn't support, and is always
+    /// interpreted.
+    Unsupported,
+}
+
+impl Default for MethodState {
+    fn default() -> Self {
+        Self::Cold { calls: 0 }
+    }
+}
+
+/// The type of a value in compiled code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    /// An `int`, stored as an `i32`.
+    Int,
+
+    /// A `uint`, stored as an `i32`.
+    Uint,
+
+    /// A `Number` that is known to be an integer, stored as an `i64`.
+    ///
+    /// This is the exact sum or difference of two 32-bit integers, which
+    /// would be a `Number` in the interpreter. Keeping it as an integer lets
+    /// `i + j` followed by a conversion to `int` compile to integer
+    /// arithmetic.
+    Long,
+
+    /// A `Number`, stored as an `f64`.
+    Number,
+
+    /// A `Boolean`, stored as an `i32` that is either 0 or 1.
+    Boolean,
+
+    /// The `this` object of the method, which may only be pushed as a scope.
+    This,
+}
+
+impl Kind {
+    /// Every kind, in the order of their tags in `JitReturn`.
+    const ALL: [Kind; 6] = [
+        Kind::Int,
+        Kind::Uint,
+        Kind::Long,
+        Kind::Number,
+        Kind::Boolean,
+        Kind::This,
+    ];
+
+    fn tag(self) -> u32 {
+        self as u32
+    }
+
+    /// Whether values of this kind are 32-bit integers.
+    fn is_int32(self) -> bool {
+        matches!(self, Kind::Int | Kind::Uint | Kind::Boolean)
+    }
+
+    /// Which of the variables of a stack slot or local register hold values
+    /// of this kind.
+    fn class(self) -> usize {
+        match self {
+            Kind::Int | Kind::Uint | Kind::Boolean | Kind::This => 0,
+            Kind::Long => 1,
+            Kind::Number => 2,
+        }
+    }
+
+    fn ty(self) -> Type {
+        match self.class() {
+            0 => types::I32,
+            1 => types::I64,
+            _ => types::F64,
+        }
+    }
+}
+
+/// How many variables each stack slot and local register has, one per
+/// `Kind::class`.
+const CLASSES: usize = 3;
+
+/// The system classes that correspond to a `Kind`.
+struct Classes<'gc> {
+    int: Class<'gc>,
+    uint: Class<'gc>,
+    number: Class<'gc>,
+    boolean: Class<'gc>,
+}
+
+impl<'gc> Classes<'gc> {
+    fn kind_of(&self, class: Class<'gc>) -> Option<Kind> {
+        if class == self.int {
+            Some(Kind::Int)
+        } else if class == self.uint {
+            Some(Kind::Uint)
+        } else if class == self.number {
+            Some(Kind::Number)
+        } else if class == self.boolean {
+            Some(Kind::Boolean)
+        } else {
+            None
+        }
+    }
+}
+
+/// The kinds of the stack and local registers before an instruction.
+#[derive(Clone, Debug, PartialEq)]
+struct State {
+    stack: Vec<Kind>,
+
+    /// The kind of each local register, or `None` if it may hold anything
+    /// else (such as `undefined`) or different kinds depending on the path
+    /// taken to get here.
+    locals: Vec<Option<Kind>>,
+}
+
+impl State {
+    fn pop(&mut self) -> Option<Kind> {
+        self.stack.pop()
+    }
+
+    /// Pops a value that can be converted to a number.
+    fn pop_numeric(&mut self) -> Option<Kind> {
+        self.stack.pop().filter(|kind| *kind != Kind::This)
+    }
+
+    fn push(&mut self, kind: Kind) {
+        self.stack.push(kind);
+    }
+
+    fn local(&self, index: u32) -> Option<Kind> {
+        self.locals.get(index as usize).copied().flatten()
+    }
+
+    fn local_numeric(&self, index: u32) -> Option<Kind> {
+        self.local(index).filter(|kind| *kind != Kind::This)
+    }
+
+    fn set_local(&mut self, index: u32, kind: Option<Kind>) -> Option<()> {
+        *self.locals.get_mut(index as usize)? = kind;
+        Some(())
+    }
+
+    /// Merges the state of another path into this one, returning whether
+    /// anything changed.
+    fn merge(&mut self, other: &State) -> Option<bool> {
+        if self.stack != other.stack {
+            return None;
+        }
+
+        let mut changed = false;
+        for (local, other) in self.locals.iter_mut().zip(&other.locals) {
+            if local.is_some() && local != other {
+                *local = None;
+                changed = true;
+            }
+        }
+
+        Some(changed)
+    }
+}
+
+/// Where execution continues after an instruction.
+enum Flow {
+    Next,
+    Branch(i32),
+    Jump(i32),
+    Return,
+}
+
+/// The kind of the result of adding or subtracting two numbers.
+fn sum_kind(a: Kind, b: Kind) -> Kind {
+    if a.is_int32() && b.is_int32() {
+        Kind::Long
+    } else {
+        Kind::Number
+    }
+}
+
+/// The kind of the result of incrementing or decrementing a number.
+fn step_kind(a: Kind) -> Kind {
+    if a.is_int32() {
+        Kind::Long
+    } else {
+        Kind::Number
+    }
+}
+
+/// Applies an instruction to the kinds in `state`, or returns `None` if the
+/// instruction isn't supported with these kinds.
+fn step(op: &Op<'_>, state: &mut State, classes: &Classes<'_>) -> Option<Flow> {
+    match op {
+        Op::PushByte { .. } | Op::PushShort { .. } | Op::PushInt { .. } => state.push(Kind::Int),
+        Op::PushUint { .. } => state.push(Kind::Uint),
+        Op::PushDouble { .. } | Op::PushNaN => state.push(Kind::Number),
+        Op::PushTrue | Op::PushFalse => state.push(Kind::Boolean),
+        Op::Pop => {
+            state.pop()?;
+        }
+        Op::Dup => {
+            let kind = state.pop()?;
+            state.push(kind);
+            state.push(kind);
+        }
+        Op::Swap => {
+            let b = state.pop()?;
+            let a = state.pop()?;
+            state.push(b);
+            state.push(a);
+        }
+        Op::GetLocal { index } => {
+            let kind = state.local(*index)?;
+            state.push(kind);
+        }
+        Op::SetLocal { index } => {
+            let kind = state.pop()?;
+            state.set_local(*index, Some(kind))?;
+        }
+        Op::Kill { index } => state.set_local(*index, None)?,
+        Op::IncLocal { index } | Op::DecLocal { index } => {
+            let kind = state.local_numeric(*index)?;
+            state.set_local(*index, Some(step_kind(kind)))?;
+        }
+        Op::IncLocalI { index } | Op::DecLocalI { index } => {
+            state.local_numeric(*index)?;
+            state.set_local(*index, Some(Kind::Int))?;
+        }
+        Op::Increment | Op::Decrement => {
+            let kind = state.pop_numeric()?;
+            state.push(step_kind(kind));
+        }
+        Op::IncrementI
+        | Op::DecrementI
+        | Op::NegateI
+        | Op::BitNot
+        | Op::Sxi1
+        | Op::Sxi8
+        | Op::Sxi16 => {
+            state.pop_numeric()?;
+            state.push(Kind::Int);
+        }
+        Op::Negate => {
+            state.pop_numeric()?;
+            state.push(Kind::Number);
+        }
+        Op::Add | Op::Subtract => {
+            let b = state.pop_numeric()?;
+            let a = state.pop_numeric()?;
+            state.push(sum_kind(a, b));
+        }
+        Op::Multiply | Op::Divide | Op::Modulo => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+            state.push(Kind::Number);
+        }
+        Op::AddI
+        | Op::SubtractI
+        | Op::MultiplyI
+        | Op::BitAnd
+        | Op::BitOr
+        | Op::BitXor
+        | Op::LShift
+        | Op::RShift => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+            state.push(Kind::Int);
+        }
+        Op::URShift => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+            state.push(Kind::Uint);
+        }
+        Op::Not => {
+            state.pop_numeric()?;
+            state.push(Kind::Boolean);
+        }
+        Op::Equals
+        | Op::StrictEquals
+        | Op::LessThan
+        | Op::LessEquals
+        | Op::GreaterThan
+        | Op::GreaterEquals => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+            state.push(Kind::Boolean);
+        }
+        Op::CoerceA => {
+            state.stack.last()?;
+        }
+        Op::CoerceD | Op::CoerceI | Op::CoerceU | Op::CoerceB | Op::Coerce { .. } => {
+            let target = coerce_kind(op, classes)?;
+            state.pop_numeric()?;
+            state.push(target);
+        }
+        Op::CoerceDSwapPop | Op::CoerceISwapPop | Op::CoerceUSwapPop | Op::CoerceSwapPop { .. } => {
+            let target = coerce_kind(op, classes)?;
+            state.pop_numeric()?;
+            state.pop()?;
+            state.push(target);
+        }
+        Op::IfTrue { offset } | Op::IfFalse { offset } => {
+            state.pop_numeric()?;
+            return Some(Flow::Branch(*offset));
+        }
+        Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfLt { offset }
+        | Op::IfLe { offset }
+        | Op::IfGt { offset }
+        | Op::IfGe { offset }
+        | Op::IfNlt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNge { offset } => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+            return Some(Flow::Branch(*offset));
+        }
+        Op::Jump { offset } => return Some(Flow::Jump(*offset)),
+        Op::ReturnValue | Op::ReturnValueNoCoerce => {
+            state.pop_numeric()?;
+            return Some(Flow::Return);
+        }
+        Op::ReturnVoid => return Some(Flow::Return),
+        Op::Li8 | Op::Li16 | Op::Li32 => {
+            state.pop_numeric()?;
+            state.push(Kind::Int);
+        }
+        Op::Lf32 | Op::Lf64 => {
+            state.pop_numeric()?;
+            state.push(Kind::Number);
+        }
+        Op::Si8 | Op::Si16 | Op::Si32 | Op::Sf32 | Op::Sf64 => {
+            state.pop_numeric()?;
+            state.pop_numeric()?;
+        }
+        Op::PushScope => {
+            if state.pop()? != Kind::This {
+                return None;
+            }
+        }
+        Op::PopScope
+        | Op::Nop
+        | Op::Bkpt
+        | Op::BkptLine { .. }
+        | Op::Debug { .. }
+        | Op::DebugFile { .. }
+        | Op::DebugLine { .. } => {}
+        _ => return None,
+    }
+
+    Some(Flow::Next)
+}
+
+/// The kind a coercion instruction converts its operand to.
+fn coerce_kind(op: &Op<'_>, classes: &Classes<'_>) -> Option<Kind> {
+    match op {
+        Op::CoerceD | Op::CoerceDSwapPop => Some(Kind::Number),
+        Op::CoerceI | Op::CoerceISwapPop => Some(Kind::Int),
+        Op::CoerceU | Op::CoerceUSwapPop => Some(Kind::Uint),
+        Op::CoerceB => Some(Kind::Boolean),
+        Op::Coerce { class } | Op::CoerceSwapPop { class } => classes.kind_of(*class),
+        _ => None,
+    }
+}
+
+/// Returns the instruction a branch at `ip` by `offset` lands on.
+fn branch_target(ip: usize, offset: i32, len: usize) -> Option<usize> {
+    let target = usize::try_from(ip as i64 + 1 + i64::from(offset)).ok()?;
+    (target < len).then_some(target)
+}
+
+/// Finds the kinds of the stack and locals before each instruction, or
+/// returns `None` if the method can't be compiled.
+///
+/// Instructions that can't be reached have no state.
+fn analyze(
+    code: &[Op<'_>],
+    entry: State,
+    max_stack: usize,
+    classes: &Classes<'_>,
+) -> Option<Vec<Option<State>>> {
+    let mut states: Vec<Option<State>> = vec![None; code.len()];
+    states[0] = Some(entry);
+    let mut worklist = vec![0];
+
+    while let Some(ip) = worklist.pop() {
+        let mut state = states[ip]
+            .clone()
+            .expect("Queued instructions have a state");
+        let flow = step(&code[ip], &mut state, classes)?;
+        if state.stack.len() > max_stack {
+            return None;
+        }
+
+        let (next, target) = match flow {
+            Flow::Next => (Some(ip + 1), None),
+            Flow::Branch(offset) => (Some(ip + 1), Some(branch_target(ip, offset, code.len())?)),
+            Flow::Jump(offset) => (None, Some(branch_target(ip, offset, code.len())?)),
+            Flow::Return => (None, None),
+        };
+
+        for successor in next.into_iter().chain(target) {
+            if successor >= code.len() {
+                return None;
+            }
+
+            match &mut states[successor] {
+                Some(existing) => {
+                    if existing.merge(&state)? {
+                        worklist.push(successor);
+                    }
+                }
+                empty @ None => {
+                    *empty = Some(state.clone());
+                    worklist.push(successor);
+                }
+            }
+        }
+    }
+
+    Some(states)
+}
+
+/// A method compiled to native code.
+pub struct CompiledMethod {
+    entry: JitFn,
+
+    /// The kinds of the parameters, which are passed in this order.
+    params: Box<[Kind]>,
+
+    /// Whether the method accesses domain memory.
+    uses_memory: bool,
+}
+
+/// The signature of compiled methods.
+///
+/// Compiled code is called with its parameters, the domain memory, a
+/// `JitContext`, and where to write the returned value, and returns one of
+/// the `STATUS_*` constants.
+type JitFn = unsafe extern "C" fn(
+    params: *const u64,
+    memory: *mut u8,
+    memory_len: usize,
+    context: *const c_void,
+    ret: *mut JitReturn,
+) -> u32;
+
+/// The parts of the player that compiled code needs.
+struct JitContext {
+    update_start: Instant,
+    max_execution_duration: Duration,
+}
+
+/// The value returned by compiled code.
+#[derive(Default)]
+#[repr(C)]
+struct JitReturn {
+    /// The bits of the value.
+    value: u64,
+
+    /// The tag of the `Kind` of the value, or anything else if the method
+    /// returned `undefined`.
+    kind: u32,
+
+    /// Whether the value has to be coerced to the return type of the method.
+    coerce: u32,
+}
+
+const RETURN_VALUE_OFFSET: i32 = 0;
+const RETURN_KIND_OFFSET: i32 = 8;
+const RETURN_COERCE_OFFSET: i32 = 12;
+
+extern "C" fn check_timeout(context: *const c_void) -> u32 {
+    // SAFETY: Compiled code is only ever called with a valid `JitContext`.
+    let context = unsafe { &*(context as *const JitContext) };
+    (context.update_start.elapsed() >= context.max_execution_duration) as u32
+}
+
+extern "C" fn to_int32(value: f64) -> i32 {
+    f64_to_wrapping_i32(value)
+}
+
+extern "C" fn modulo(a: f64, b: f64) -> f64 {
+    a % b
+}
+
+/// Runs `method` as native code if it is, or should now be, compiled.
+///
+/// `activation` must have been initialized for the method. Returns `None` if
+/// the method has to be interpreted instead.
+pub fn try_run<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+) -> Option<Result<Value<'gc>, Error<'gc>>> {
+    let threshold = match activation.avm2().jit.mode() {
+        JitMode::Off => return None,
+        JitMode::Hot => HOT_THRESHOLD,
+        JitMode::Always => 1,
+    };
+
+    let compiled = match &mut *method.jit.borrow_mut() {
+        MethodState::Unsupported => return None,
+        MethodState::Compiled(compiled) => Some(compiled.clone()),
+        MethodState::Cold { calls } => {
+            *calls += 1;
+            if *calls < threshold {
+                return None;
+            }
+            None
+        }
+    };
+
+    let compiled = match compiled {
+        Some(compiled) => compiled,
+        None => {
+            let compiled = compile(activation, method).map(Rc::new);
+            *method.jit.borrow_mut() = match &compiled {
+                Some(compiled) => MethodState::Compiled(compiled.clone()),
+                None => MethodState::Unsupported,
+            };
+            compiled?
+        }
+    };
+
+    let params = compiled
+        .params
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| unbox(activation.local_register(1 + i as u32), *kind))
+        .collect::<Option<Vec<u64>>>()?;
+
+    let context = JitContext {
+        update_start: activation.context.update_start,
+        max_execution_duration: activation.context.max_execution_duration,
+    };
+    let mut ret = JitReturn::default();
+
+    let status = if compiled.uses_memory {
+        let memory = activation.outer().domain().domain_memory();
+        let mut memory = memory
+            .as_bytearray_mut()
+            .expect("Bytearray storage should exist");
+        let memory = memory.bytes_mut();
+
+        // SAFETY: The compiled code only accesses memory within the bounds it
+        // is given, and only reads as many parameters as it was compiled for.
+        unsafe {
+            (compiled.entry)(
+                params.as_ptr(),
+                memory.as_mut_ptr(),
+                memory.len(),
+                &context as *const JitContext as *const c_void,
+                &mut ret,
+            )
+        }
+    } else {
+        // SAFETY: As above, and code that doesn't use domain memory never
+        // accesses it.
+        unsafe {
+            (compiled.entry)(
+                params.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                &context as *const JitContext as *const c_void,
+                &mut ret,
+            )
+        }
+    };
+
+    Some(match status {
+        STATUS_RETURN => {
+            let value = box_value(&ret);
+            match method.resolved_return_type() {
+                Some(return_type) if ret.coerce != 0 => {
+                    value.coerce_to_type(activation, return_type)
+                }
+                _ => Ok(value),
+            }
+        }
+        STATUS_TIMEOUT => Err(
+            "A script in this movie has taken too long to execute and has been terminated.".into(),
+        ),
+        STATUS_RANGE_ERROR => Err(make_error_1506(activation)),
+        _ => unreachable!("Compiled code returned unknown status {status}"),
+    })
+}
+
+/// Converts a parameter to the bits compiled code expects for its kind.
+fn unbox(value: Value<'_>, kind: Kind) -> Option<u64> {
+    Some(match (kind, value) {
+        (Kind::Int, Value::Integer(i)) => u64::from(i as u32),
+        (Kind::Int, Value::Number(n)) => u64::from(f64_to_wrapping_i32(n) as u32),
+        (Kind::Uint, Value::Integer(i)) => u64::from(i as u32),
+        (Kind::Uint, Value::Number(n)) => u64::from(f64_to_wrapping_u32(n)),
+        (Kind::Number, Value::Integer(i)) => f64::from(i).to_bits(),
+        (Kind::Number, Value::Number(n)) => n.to_bits(),
+        (Kind::Boolean, Value::Bool(b)) => u64::from(b),
+        _ => return None,
+    })
+}
+
+/// Converts the value returned by compiled code back to a `Value`.
+fn box_value<'gc>(ret: &JitReturn) -> Value<'gc> {
+    match Kind::ALL.get(ret.kind as usize) {
+        Some(Kind::Int) => (ret.value as u32 as i32).into(),
+        Some(Kind::Uint) => (ret.value as u32).into(),
+        Some(Kind::Long) => {
+            let value = ret.value as i64;
+            i32::try_from(value).map_or_else(|_| (value as f64).into(), Value::from)
+        }
+        Some(Kind::Number) => f64::from_bits(ret.value).into(),
+        Some(Kind::Boolean) => (ret.value != 0).into(),
+        Some(Kind::This) | None => Value::Undefined,
+    }
+}
+
+/// Checks whether `method` can be compiled, and compiles it if so.
+fn compile<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+) -> Option<CompiledMethod> {
+    if method.method().flags.intersects(
+        AbcMethodFlags::NEED_ACTIVATION
+            | AbcMethodFlags::NEED_ARGUMENTS
+            | AbcMethodFlags::NEED_REST
+            | AbcMethodFlags::SET_DXNS,
+    ) {
+        return None;
+    }
+
+    let body = method.body()?;
+    let class_defs = activation.avm2().class_defs();
+    let classes = Classes {
+        int: class_defs.int,
+        uint: class_defs.uint,
+        number: class_defs.number,
+        boolean: class_defs.boolean,
+    };
+
+    let verified_info = method.verified_info.borrow();
+    let verified_info = verified_info.as_ref()?;
+    if !verified_info.exceptions.is_empty() || verified_info.parsed_code.is_empty() {
+        return None;
+    }
+
+    let params = verified_info
+        .param_config
+        .iter()
+        .map(|param| classes.kind_of(param.param_type?))
+        .collect::<Option<Box<[Kind]>>>()?;
+
+    let num_locals = (body.num_locals as usize).max(params.len() + 1);
+    let mut locals = vec![None; num_locals];
+    locals[0] = Some(Kind::This);
+    for (i, kind) in params.iter().enumerate() {
+        locals[i + 1] = Some(*kind);
+    }
+    let entry = State {
+        stack: Vec::new(),
+        locals,
+    };
+
+    let code = &verified_info.parsed_code;
+    let max_stack = body.max_stack as usize;
+    let states = analyze(code, entry, max_stack, &classes)?;
+    let uses_memory = code.iter().enumerate().any(|(ip, op)| {
+        states[ip].is_some()
+            && matches!(
+                op,
+                Op::Li8
+                    | Op::Li16
+                    | Op::Li32
+                    | Op::Lf32
+                    | Op::Lf64
+                    | Op::Si8
+                    | Op::Si16
+                    | Op::Si32
+                    | Op::Sf32
+                    | Op::Sf64
+            )
+    });
+
+    let module = activation.avm2().jit.module()?;
+    match lower(
+        module, code, &states, &params, max_stack, num_locals, &classes,
+    ) {
+        Ok(entry) => Some(CompiledMethod {
+            entry,
+            params,
+            uses_memory,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to compile {}: {e}", method.method_name());
+            None
+        }
+    }
+}
+
+/// A comparison between two values.
+#[derive(Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    StrictEq,
+    StrictNe,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Not less than, which is also true if either value is `NaN`.
+    Nlt,
+    Nle,
+    Ngt,
+    Nge,
+}
+
+impl Comparison {
+    fn int_cc(self) -> IntCC {
+        match self {
+            Comparison::Eq | Comparison::StrictEq => IntCC::Equal,
+            Comparison::Ne | Comparison::StrictNe => IntCC::NotEqual,
+            Comparison::Lt | Comparison::Nge => IntCC::SignedLessThan,
+            Comparison::Le | Comparison::Ngt => IntCC::SignedLessThanOrEqual,
+            Comparison::Gt | Comparison::Nle => IntCC::SignedGreaterThan,
+            Comparison::Ge | Comparison::Nlt => IntCC::SignedGreaterThanOrEqual,
+        }
+    }
+
+    fn float_cc(self) -> FloatCC {
+        match self {
+            Comparison::Eq | Comparison::StrictEq => FloatCC::Equal,
+            Comparison::Ne | Comparison::StrictNe => FloatCC::NotEqual,
+            Comparison::Lt => FloatCC::LessThan,
+            Comparison::Le => FloatCC::LessThanOrEqual,
+            Comparison::Gt => FloatCC::GreaterThan,
+            Comparison::Ge => FloatCC::GreaterThanOrEqual,
+            Comparison::Nlt => FloatCC::UnorderedOrGreaterThanOrEqual,
+            Comparison::Nle => FloatCC::UnorderedOrGreaterThan,
+            Comparison::Ngt => FloatCC::UnorderedOrLessThanOrEqual,
+            Comparison::Nge => FloatCC::UnorderedOrLessThan,
+        }
+    }
+}
+
+fn branch_comparison(op: &Op<'_>) -> Option<Comparison> {
+    Some(match op {
+        Op::IfEq { .. } => Comparison::Eq,
+        Op::IfNe { .. } => Comparison::Ne,
+        Op::IfStrictEq { .. } => Comparison::StrictEq,
+        Op::IfStrictNe { .. } => Comparison::StrictNe,
+        Op::IfLt { .. } => Comparison::Lt,
+        Op::IfLe { .. } => Comparison::Le,
+        Op::IfGt { .. } => Comparison::Gt,
+        Op::IfGe { .. } => Comparison::Ge,
+        Op::IfNlt { .. } => Comparison::Nlt,
+        Op::IfNle { .. } => Comparison::Nle,
+        Op::IfNgt { .. } => Comparison::Ngt,
+        Op::IfNge { .. } => Comparison::Nge,
+        _ => return None,
+    })
+}
+
+/// Generates native code for the method, given the states found by
+/// `analyze`.
+fn lower(
+    module: &mut JITModule,
+    code: &[Op<'_>],
+    states: &[Option<State>],
+    params: &[Kind],
+    max_stack: usize,
+    num_locals: usize,
+    classes: &Classes<'_>,
+) -> Result<JitFn, String> {
+    let pointer = module.target_config().pointer_type();
+    let call_conv = module.isa().default_call_conv();
+
+    let mut ctx = module.make_context();
+    let signature = &mut ctx.func.signature;
+    signature.call_conv = call_conv;
+    for _ in 0..5 {
+        signature.params.push(AbiParam::new(pointer));
+    }
+    signature.returns.push(AbiParam::new(types::I32));
+
+    let mut builder_context = FunctionBuilderContext::new();
+    let mut builder = FunctionBuilder::new(&mut ctx.func, &mut builder_context);
+
+    let helper_signature = |params: &[Type], returns: &[Type]| {
+        let mut signature = Signature::new(call_conv);
+        signature
+            .params
+            .extend(params.iter().map(|ty| AbiParam::new(*ty)));
+        signature
+            .returns
+            .extend(returns.iter().map(|ty| AbiParam::new(*ty)));
+        signature
+    };
+    let check_timeout_sig = builder.import_signature(helper_signature(&[pointer], &[types::I32]));
+    let to_int32_sig = builder.import_signature(helper_signature(&[types::F64], &[types::I32]));
+    let modulo_sig =
+        builder.import_signature(helper_signature(&[types::F64, types::F64], &[types::F64]));
+
+    // Every jump target and every instruction after a conditional branch
+    // starts a block.
+    let mut blocks: Vec<Option<Block>> = vec![None; code.len()];
+    blocks[0] = Some(builder.create_block());
+    for (ip, op) in code.iter().enumerate() {
+        if states[ip].is_none() {
+            continue;
+        }
+        let mut state = states[ip].clone().expect("Reachable instruction");
+        match step(op, &mut state, classes) {
+            Some(Flow::Branch(offset)) => {
+                for target in [ip + 1, branch_target(ip, offset, code.len()).unwrap()] {
+                    blocks[target].get_or_insert_with(|| builder.create_block());
+                }
+            }
+            Some(Flow::Jump(offset)) => {
+                let target = branch_target(ip, offset, code.len()).unwrap();
+                blocks[target].get_or_insert_with(|| builder.create_block());
+            }
+            _ => {}
+        }
+    }
+
+    let entry = builder.create_block();
+    builder.append_block_params_for_function_params(entry);
+    builder.switch_to_block(entry);
+    let [params_ptr, memory, memory_len, context, ret] = *builder.block_params(entry) else {
+        unreachable!("Compiled methods have five parameters");
+    };
+
+    let timeout = builder.create_block();
+    let range_error = builder.create_block();
+
+    let mut lowering = Lowering {
+        builder,
+        pointer,
+        max_stack,
+        memory,
+        memory_len,
+        context,
+        ret,
+        fuel: Variable::from_u32(((max_stack + num_locals) * CLASSES) as u32),
+        check_timeout_sig,
+        to_int32_sig,
+        modulo_sig,
+        timeout,
+        range_error,
+    };
+
+    for slot in 0..max_stack + num_locals {
+        for kind in [Kind::Int, Kind::Long, Kind::Number] {
+            let var = Variable::from_u32((slot * CLASSES + kind.class()) as u32);
+            lowering.builder.declare_var(var, kind.ty());
+        }
+    }
+    lowering.builder.declare_var(lowering.fuel, types::I32);
+
+    let fuel = lowering
+        .builder
+        .ins()
+        .iconst(types::I32, TIMEOUT_CHECK_INTERVAL);
+    lowering.builder.def_var(lowering.fuel, fuel);
+    for (i, kind) in params.iter().enumerate() {
+        let offset = (i * 8) as i32;
+        let value = match kind.class() {
+            2 => lowering
+                .builder
+                .ins()
+                .load(types::F64, MemFlags::trusted(), params_ptr, offset),
+            _ => {
+                let bits = lowering.builder.ins().load(
+                    types::I64,
+                    MemFlags::trusted(),
+                    params_ptr,
+                    offset,
+                );
+                lowering.builder.ins().ireduce(types::I32, bits)
+            }
+        };
+        let var = lowering.local_var(1 + i as u32, *kind);
+        lowering.builder.def_var(var, value);
+    }
+    lowering
+        .builder
+        .ins()
+        .jump(blocks[0].expect("First instruction starts a block"), &[]);
+
+    let mut terminated = true;
+    for (ip, op) in code.iter().enumerate() {
+        let Some(before) = &states[ip] else {
+            continue;
+        };
+
+        if let Some(block) = blocks[ip] {
+            if !terminated {
+                lowering.builder.ins().jump(block, &[]);
+            }
+            lowering.builder.switch_to_block(block);
+            terminated = false;
+        }
+        debug_assert!(!terminated, "Reachable instructions follow a block start");
+
+        let mut after = before.clone();
+        let flow = step(op, &mut after, classes).expect("Instruction was analyzed");
+        lowering.lower_op(op, before, &after);
+
+        match flow {
+            Flow::Next => {}
+            Flow::Branch(offset) => {
+                let condition = lowering.branch_condition(op, before);
+                let target = branch_target(ip, offset, code.len()).unwrap();
+                let next = blocks[ip + 1].expect("Instructions after branches start a block");
+                let target_block = blocks[target].expect("Branch targets start a block");
+
+                if target <= ip {
+                    let edge = lowering.builder.create_block();
+                    lowering.builder.ins().brif(condition, edge, &[], next, &[]);
+                    lowering.builder.switch_to_block(edge);
+                    lowering.backward_jump(target_block);
+                } else {
+                    lowering
+                        .builder
+                        .ins()
+                        .brif(condition, target_block, &[], next, &[]);
+                }
+                terminated = true;
+            }
+            Flow::Jump(offset) => {
+                let target = branch_target(ip, offset, code.len()).unwrap();
+                let target_block = blocks[target].expect("Jump targets start a block");
+                if target <= ip {
+                    lowering.backward_jump(target_block);
+                } else {
+                    lowering.builder.ins().jump(target_block, &[]);
+                }
+                terminated = true;
+            }
+            Flow::Return => terminated = true,
+        }
+    }
+
+    lowering.builder.switch_to_block(timeout);
+    let status = lowering
+        .builder
+        .ins()
+        .iconst(types::I32, i64::from(STATUS_TIMEOUT));
+    lowering.builder.ins().return_(&[status]);
+
+    lowering.builder.switch_to_block(range_error);
+    let status = lowering
+        .builder
+        .ins()
+        .iconst(types::I32, i64::from(STATUS_RANGE_ERROR));
+    lowering.builder.ins().return_(&[status]);
+
+    lowering.builder.seal_all_blocks();
+    lowering.builder.finalize();
+
+    let id = module
+        .declare_anonymous_function(&ctx.func.signature)
+        .map_err(|e| e.to_string())?;
+    module
+        .define_function(id, &mut ctx)
+        .map_err(|e| e.to_string())?;
+    module.clear_context(&mut ctx);
+    module.finalize_definitions().map_err(|e| e.to_string())?;
+
+    let code = module.get_finalized_function(id);
+    // SAFETY: The function was compiled with the signature of `JitFn`, using
+    // the default calling convention of the host, which `extern "C"` uses.
+    Ok(unsafe { std::mem::transmute::<*const u8, JitFn>(code) })
+}
+
+/// Generates the code for single instructions.
+struct Lowering<'a> {
+    builder: FunctionBuilder<'a>,
+    pointer: Type,
+    max_stack: usize,
+
+    memory: ClifValue,
+    memory_len: ClifValue,
+    context: ClifValue,
+    ret: ClifValue,
+
+    /// How many more backward branches can be taken before the script
+    /// timeout is checked.
+    fuel: Variable,
+
+    check_timeout_sig: SigRef,
+    to_int32_sig: SigRef,
+    modulo_sig: SigRef,
+
+    /// Returns `STATUS_TIMEOUT`.
+    timeout: Block,
+
+    /// Returns `STATUS_RANGE_ERROR`.
+    range_error: Block,
+}
+
+impl Lowering<'_> {
+    fn stack_var(&self, depth: usize, kind: Kind) -> Variable {
+        Variable::from_u32((depth * CLASSES + kind.class()) as u32)
+    }
+
+    fn local_var(&self, index: u32, kind: Kind) -> Variable {
+        Variable::from_u32(((self.max_stack + index as usize) * CLASSES + kind.class()) as u32)
+    }
+
+    /// Reads the value at `depth` of the stack.
+    fn get(&mut self, state: &State, depth: usize) -> (ClifValue, Kind) {
+        let kind = state.stack[depth];
+        (self.builder.use_var(self.stack_var(depth, kind)), kind)
+    }
+
+    /// Reads the value `n` entries below the top of the stack.
+    fn peek(&mut self, state: &State, n: usize) -> (ClifValue, Kind) {
+        self.get(state, state.stack.len() - 1 - n)
+    }
+
+    /// Writes the top of the stack after an instruction.
+    fn set_top(&mut self, after: &State, value: ClifValue) {
+        let depth = after.stack.len() - 1;
+        let var = self.stack_var(depth, after.stack[depth]);
+        self.builder.def_var(var, value);
+    }
+
+    fn iconst32(&mut self, value: i32) -> ClifValue {
+        self.builder
+            .ins()
+            .iconst(types::I32, i64::from(value as u32))
+    }
+
+    fn call(&mut self, sig: SigRef, function: usize, args: &[ClifValue]) -> ClifValue {
+        let callee = self.builder.ins().iconst(self.pointer, function as i64);
+        let call = self.builder.ins().call_indirect(sig, callee, args);
+        self.builder.inst_results(call)[0]
+    }
+
+    fn to_f64(&mut self, (value, kind): (ClifValue, Kind)) -> ClifValue {
+        match kind {
+            Kind::Int | Kind::Long => self.builder.ins().fcvt_from_sint(types::F64, value),
+            Kind::Uint | Kind::Boolean | Kind::This => {
+                self.builder.ins().fcvt_from_uint(types::F64, value)
+            }
+            Kind::Number => value,
+        }
+    }
+
+    fn to_i32(&mut self, (value, kind): (ClifValue, Kind)) -> ClifValue {
+        match kind {
+            Kind::Int | Kind::Uint | Kind::Boolean | Kind::This => value,
+            Kind::Long => self.builder.ins().ireduce(types::I32, value),
+            Kind::Number => self.call(self.to_int32_sig, to_int32 as usize, &[value]),
+        }
+    }
+
+    /// Extends a value of an integer kind to an `i64`.
+    fn to_i64(&mut self, (value, kind): (ClifValue, Kind)) -> ClifValue {
+        match kind {
+            Kind::Int => self.builder.ins().sextend(types::I64, value),
+            Kind::Uint | Kind::Boolean | Kind::This => {
+                self.builder.ins().uextend(types::I64, value)
+            }
+            Kind::Long => value,
+            Kind::Number => unreachable!("Numbers aren't integers"),
+        }
+    }
+
+    /// Returns an `i8` that is 1 if the value is truthy.
+    fn truthy(&mut self, (value, kind): (ClifValue, Kind)) -> ClifValue {
+        match kind {
+            Kind::Number => {
+                let zero = self.builder.ins().f64const(0.0);
+                self.builder
+                    .ins()
+                    .fcmp(FloatCC::OrderedNotEqual, value, zero)
+            }
+            _ => self.builder.ins().icmp_imm(IntCC::NotEqual, value, 0),
+        }
+    }
+
+    /// Returns an `i8` that is 1 if the comparison holds.
+    fn compare(
+        &mut self,
+        comparison: Comparison,
+        a: (ClifValue, Kind),
+        b: (ClifValue, Kind),
+    ) -> ClifValue {
+        if matches!(comparison, Comparison::StrictEq | Comparison::StrictNe)
+            && (a.1 == Kind::Boolean) != (b.1 == Kind::Boolean)
+        {
+            // Booleans are never strictly equal to numbers.
+            let result = matches!(comparison, Comparison::StrictNe);
+            return self.builder.ins().iconst(types::I8, i64::from(result));
+        }
+
+        if a.1 != Kind::Number && b.1 != Kind::Number {
+            let a = self.to_i64(a);
+            let b = self.to_i64(b);
+            self.builder.ins().icmp(comparison.int_cc(), a, b)
+        } else {
+            let a = self.to_f64(a);
+            let b = self.to_f64(b);
+            self.builder.ins().fcmp(comparison.float_cc(), a, b)
+        }
+    }
+
+    /// Returns the `i8` condition a conditional branch jumps on.
+    fn branch_condition(&mut self, op: &Op<'_>, before: &State) -> ClifValue {
+        match op {
+            Op::IfTrue { .. } => {
+                let value = self.peek(before, 0);
+                self.truthy(value)
+            }
+            Op::IfFalse { .. } => {
+                let value = self.peek(before, 0);
+                let truthy = self.truthy(value);
+                self.builder.ins().bxor_imm(truthy, 1)
+            }
+            _ => {
+                let comparison = branch_comparison(op).expect("Branch is a comparison");
+                let b = self.peek(before, 0);
+                let a = self.peek(before, 1);
+                self.compare(comparison, a, b)
+            }
+        }
+    }
+
+    /// Jumps back to `target`, checking the script timeout every so often.
+    fn backward_jump(&mut self, target: Block) {
+        let fuel = self.builder.use_var(self.fuel);
+        let fuel = self.builder.ins().iadd_imm(fuel, -1);
+        self.builder.def_var(self.fuel, fuel);
+
+        let check = self.builder.create_block();
+        let resume = self.builder.create_block();
+        let empty = self
+            .builder
+            .ins()
+            .icmp_imm(IntCC::SignedLessThanOrEqual, fuel, 0);
+        self.builder.ins().brif(empty, check, &[], resume, &[]);
+
+        self.builder.switch_to_block(check);
+        let full = self
+            .builder
+            .ins()
+            .iconst(types::I32, TIMEOUT_CHECK_INTERVAL);
+        self.builder.def_var(self.fuel, full);
+        let expired = self.call(
+            self.check_timeout_sig,
+            check_timeout as usize,
+            &[self.context],
+        );
+        self.builder
+            .ins()
+            .brif(expired, self.timeout, &[], resume, &[]);
+
+        self.builder.switch_to_block(resume);
+        self.builder.ins().jump(target, &[]);
+    }
+
+    /// Returns the address of `size` bytes of domain memory at `address`,
+    /// jumping to the range error block if they're out of bounds.
+    fn memory_address(&mut self, address: (ClifValue, Kind), size: i64) -> ClifValue {
+        let address = self.to_i32(address);
+        let address = self.builder.ins().uextend(types::I64, address);
+        let len = if self.pointer == types::I64 {
+            self.memory_len
+        } else {
+            self.builder.ins().uextend(types::I64, self.memory_len)
+        };
+
+        let end = self.builder.ins().iadd_imm(address, size);
+        let out_of_bounds = self
+            .builder
+            .ins()
+            .icmp(IntCC::UnsignedGreaterThan, end, len);
+        let in_bounds = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(out_of_bounds, self.range_error, &[], in_bounds, &[]);
+        self.builder.switch_to_block(in_bounds);
+
+        let offset = if self.pointer == types::I64 {
+            address
+        } else {
+            self.builder.ins().ireduce(self.pointer, address)
+        };
+        self.builder.ins().iadd(self.memory, offset)
+    }
+
+    fn lower_op(&mut self, op: &Op<'_>, before: &State, after: &State) {
+        // Domain memory is little endian, and may be accessed at any address.
+        let memory_flags = MemFlags::new()
+            .with_notrap()
+            .with_endianness(Endianness::Little);
+
+        match op {
+            Op::PushByte { value } => {
+                let value = self.iconst32(i32::from(*value));
+                self.set_top(after, value);
+            }
+            Op::PushShort { value } => {
+                let value = self.iconst32(i32::from(*value));
+                self.set_top(after, value);
+            }
+            Op::PushInt { value } => {
+                let value = self.iconst32(*value);
+                self.set_top(after, value);
+            }
+            Op::PushUint { value } => {
+                let value = self.iconst32(*value as i32);
+                self.set_top(after, value);
+            }
+            Op::PushDouble { value } => {
+                let value = self.builder.ins().f64const(*value);
+                self.set_top(after, value);
+            }
+            Op::PushNaN => {
+                let value = self.builder.ins().f64const(f64::NAN);
+                self.set_top(after, value);
+            }
+            Op::PushTrue | Op::PushFalse => {
+                let value = self.iconst32(matches!(op, Op::PushTrue) as i32);
+                self.set_top(after, value);
+            }
+            Op::Dup => {
+                let (value, _) = self.peek(before, 0);
+                self.set_top(after, value);
+            }
+            Op::Swap => {
+                let depth = before.stack.len();
+                let (a, kind_a) = self.get(before, depth - 2);
+                let (b, kind_b) = self.get(before, depth - 1);
+                let var = self.stack_var(depth - 2, kind_b);
+                self.builder.def_var(var, b);
+                let var = self.stack_var(depth - 1, kind_a);
+                self.builder.def_var(var, a);
+            }
+            Op::GetLocal { index } => {
+                let kind = before.local(*index).expect("Local was analyzed");
+                let value = self.builder.use_var(self.local_var(*index, kind));
+                self.set_top(after, value);
+            }
+            Op::SetLocal { index } => {
+                let (value, kind) = self.peek(before, 0);
+                let var = self.local_var(*index, kind);
+                self.builder.def_var(var, value);
+            }
+            Op::IncLocal { index }
+            | Op::DecLocal { index }
+            | Op::IncLocalI { index }
+            | Op::DecLocalI { index } => {
+                let kind = before.local(*index).expect("Local was analyzed");
+                let value = self.builder.use_var(self.local_var(*index, kind));
+                let result_kind = after.local(*index).expect("Local was analyzed");
+                let delta = if matches!(op, Op::IncLocal { .. } | Op::IncLocalI { .. }) {
+                    1
+                } else {
+                    -1
+                };
+                let result = self.step_value((value, kind), result_kind, delta);
+                let var = self.local_var(*index, result_kind);
+                self.builder.def_var(var, result);
+            }
+            Op::Increment | Op::Decrement | Op::IncrementI | Op::DecrementI => {
+                let value = self.peek(before, 0);
+                let delta = if matches!(op, Op::Increment | Op::IncrementI) {
+                    1
+                } else {
+                    -1
+                };
+                let result_kind = after.stack[after.stack.len() - 1];
+                let result = self.step_value(value, result_kind, delta);
+                self.set_top(after, result);
+            }
+            Op::Negate => {
+                let value = self.peek(before, 0);
+                let value = self.to_f64(value);
+                let result = self.builder.ins().fneg(value);
+                self.set_top(after, result);
+            }
+            Op::NegateI | Op::BitNot | Op::Sxi1 | Op::Sxi8 | Op::Sxi16 => {
+                let value = self.peek(before, 0);
+                let value = self.to_i32(value);
+                let ins = self.builder.ins();
+                let result = match op {
+                    Op::NegateI => ins.ineg(value),
+                    Op::BitNot => ins.bnot(value),
+                    Op::Sxi1 => {
+                        let shifted = ins.ishl_imm(value, 31);
+                        self.builder.ins().sshr_imm(shifted, 31)
+                    }
+                    Op::Sxi8 => {
+                        let narrow = ins.ireduce(types::I8, value);
+                        self.builder.ins().sextend(types::I32, narrow)
+                    }
+                    _ => {
+                        let narrow = ins.ireduce(types::I16, value);
+                        self.builder.ins().sextend(types::I32, narrow)
+                    }
+                };
+                self.set_top(after, result);
+            }
+            Op::Add | Op::Subtract => {
+                let b = self.peek(before, 0);
+                let a = self.peek(before, 1);
+                let result = if after.stack[after.stack.len() - 1] == Kind::Long {
+                    let a = self.to_i64(a);
+                    let b = self.to_i64(b);
+                    if matches!(op, Op::Add) {
+                        self.builder.ins().iadd(a, b)
+                    } else {
+                        self.builder.ins().isub(a, b)
+                    }
+                } else {
+                    let a = self.to_f64(a);
+                    let b = self.to_f64(b);
+                    if matches!(op, Op::Add) {
+                        self.builder.ins().fadd(a, b)
+                    } else {
+                        self.builder.ins().fsub(a, b)
+                    }
+                };
+                self.set_top(after, result);
+            }
+            Op::Multiply | Op::Divide | Op::Modulo => {
+                let b = self.peek(before, 0);
+                let a = self.peek(before, 1);
+                let a = self.to_f64(a);
+                let b = self.to_f64(b);
+                let result = match op {
+                    Op::Multiply => self.builder.ins().fmul(a, b),
+                    Op::Divide => self.builder.ins().fdiv(a, b),
+                    _ => self.call(self.modulo_sig, modulo as usize, &[a, b]),
+                };
+                self.set_top(after, result);
+            }
+            Op::AddI
+            | Op::SubtractI
+            | Op::MultiplyI
+            | Op::BitAnd
+            | Op::BitOr
+            | Op::BitXor
+            | Op::LShift
+            | Op::RShift
+            | Op::URShift => {
+                let b = self.peek(before, 0);
+                let a = self.peek(before, 1);
+                let a = self.to_i32(a);
+                let b = self.to_i32(b);
+                // Shifts only use the low 5 bits of the shift count, like
+                // Cranelift's shift instructions.
+                let ins = self.builder.ins();
+                let result = match op {
+                    Op::AddI => ins.iadd(a, b),
+                    Op::SubtractI => ins.isub(a, b),
+                    Op::MultiplyI => ins.imul(a, b),
+                    Op::BitAnd => ins.band(a, b),
+                    Op::BitOr => ins.bor(a, b),
+                    Op::BitXor => ins.bxor(a, b),
+                    Op::LShift => ins.ishl(a, b),
+                    Op::RShift => ins.sshr(a, b),
+                    _ => ins.ushr(a, b),
+                };
+                self.set_top(after, result);
+            }
+            Op::Not => {
+                let value = self.peek(before, 0);
+                let truthy = self.truthy(value);
+                let result = self.builder.ins().bxor_imm(truthy, 1);
+                let result = self.builder.ins().uextend(types::I32, result);
+                self.set_top(after, result);
+            }
+            Op::Equals
+            | Op::StrictEquals
+            | Op::LessThan
+            | Op::LessEquals
+            | Op::GreaterThan
+            | Op::GreaterEquals => {
+                let comparison = match op {
+                    Op::Equals => Comparison::Eq,
+                    Op::StrictEquals => Comparison::StrictEq,
+                    Op::LessThan => Comparison::Lt,
+                    Op::LessEquals => Comparison::Le,
+                    Op::GreaterThan => Comparison::Gt,
+                    _ => Comparison::Ge,
+                };
+                let b = self.peek(before, 0);
+                let a = self.peek(before, 1);
+                let result = self.compare(comparison, a, b);
+                let result = self.builder.ins().uextend(types::I32, result);
+                self.set_top(after, result);
+            }
+            Op::CoerceD
+            | Op::CoerceI
+            | Op::CoerceU
+            | Op::CoerceB
+            | Op::Coerce { .. }
+            | Op::CoerceDSwapPop
+            | Op::CoerceISwapPop
+            | Op::CoerceUSwapPop
+            | Op::CoerceSwapPop { .. } => {
+                let value = self.peek(before, 0);
+                let result = match after.stack[after.stack.len() - 1] {
+                    Kind::Number => self.to_f64(value),
+                    Kind::Int | Kind::Uint => self.to_i32(value),
+                    _ => {
+                        let truthy = self.truthy(value);
+                        self.builder.ins().uextend(types::I32, truthy)
+                    }
+                };
+                self.set_top(after, result);
+            }
+            Op::ReturnValue | Op::ReturnValueNoCoerce => {
+                let (value, kind) = self.peek(before, 0);
+                let bits = match kind {
+                    Kind::Long | Kind::Number => value,
+                    _ => self.builder.ins().uextend(types::I64, value),
+                };
+                let coerce = matches!(op, Op::ReturnValue) as i32;
+                self.finish(bits, kind.tag() as i32, coerce);
+            }
+            Op::ReturnVoid => {
+                let bits = self.builder.ins().iconst(types::I64, 0);
+                self.finish(bits, -1, 0);
+            }
+            Op::Li8 | Op::Li16 | Op::Li32 | Op::Lf32 | Op::Lf64 => {
+                let address = self.peek(before, 0);
+                let size = match op {
+                    Op::Li8 => 1,
+                    Op::Li16 => 2,
+                    Op::Li32 | Op::Lf32 => 4,
+                    _ => 8,
+                };
+                let address = self.memory_address(address, size);
+                let ins = self.builder.ins();
+                let result = match op {
+                    Op::Li8 => ins.uload8(types::I32, memory_flags, address, 0),
+                    Op::Li16 => ins.uload16(types::I32, memory_flags, address, 0),
+                    Op::Li32 => ins.load(types::I32, memory_flags, address, 0),
+                    Op::Lf32 => {
+                        let value = ins.load(types::F32, memory_flags, address, 0);
+                        self.builder.ins().fpromote(types::F64, value)
+                    }
+                    _ => ins.load(types::F64, memory_flags, address, 0),
+                };
+                self.set_top(after, result);
+            }
+            Op::Si8 | Op::Si16 | Op::Si32 | Op::Sf32 | Op::Sf64 => {
+                let address = self.peek(before, 0);
+                let value = self.peek(before, 1);
+                let size = match op {
+                    Op::Si8 => 1,
+                    Op::Si16 => 2,
+                    Op::Si32 | Op::Sf32 => 4,
+                    _ => 8,
+                };
+                let address = self.memory_address(address, size);
+                match op {
+                    Op::Si8 | Op::Si16 | Op::Si32 => {
+                        let value = self.to_i32(value);
+                        let ins = self.builder.ins();
+                        match op {
+                            Op::Si8 => ins.istore8(memory_flags, value, address, 0),
+                            Op::Si16 => ins.istore16(memory_flags, value, address, 0),
+                            _ => ins.store(memory_flags, value, address, 0),
+                        };
+                    }
+                    _ => {
+                        let value = self.to_f64(value);
+                        let value = if matches!(op, Op::Sf32) {
+                            self.builder.ins().fdemote(types::F32, value)
+                        } else {
+                            value
+                        };
+                        self.builder.ins().store(memory_flags, value, address, 0);
+                    }
+                }
+            }
+            // These only change kinds, or only branch, which is handled
+            // separately.
+            _ => {}
+        }
+    }
+
+    /// Adds `delta` to a value, giving a result of `result_kind`.
+    fn step_value(&mut self, value: (ClifValue, Kind), result_kind: Kind, delta: i64) -> ClifValue {
+        match result_kind {
+            Kind::Int => {
+                let value = self.to_i32(value);
+                self.builder.ins().iadd_imm(value, delta)
+            }
+            Kind::Long => {
+                let value = self.to_i64(value);
+                self.builder.ins().iadd_imm(value, delta)
+            }
+            _ => {
+                let value = self.to_f64(value);
+                let delta = self.builder.ins().f64const(delta as f64);
+                self.builder.ins().fadd(value, delta)
+            }
+        }
+    }
+
+    /// Writes the returned value and returns from the compiled code.
+    fn finish(&mut self, bits: ClifValue, kind: i32, coerce: i32) {
+        let flags = MemFlags::trusted();
+        self.builder
+            .ins()
+            .store(flags, bits, self.ret, RETURN_VALUE_OFFSET);
+        let kind = self.iconst32(kind);
+        self.builder
+            .ins()
+            .store(flags, kind, self.ret, RETURN_KIND_OFFSET);
+        let coerce = self.iconst32(coerce);
+        self.builder
+            .ins()
+            .store(flags, coerce, self.ret, RETURN_COERCE_OFFSET);
+        let status = self.iconst32(STATUS_RETURN as i32);
+        self.builder.ins().return_(&[status]);
+    }
+}
@@ -151,6 +151,11 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// Whether this method was, or should be, compiled by the JIT.
+    #[cfg(feature = "jit")]
+    #[collect(require_static)]
+    pub jit: std::cell::RefCell<crate::avm2::jit::MethodState>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -189,6 +194,8 @@ impl<'gc> BytecodeMethod<'gc> {
             return_type,
             is_function,
             activation_class: Lock::new(None),
+            #[cfg(feature = "jit")]
+            jit: Default::default(),
         })
     }
 
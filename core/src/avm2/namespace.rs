@@ -34,7 +34,10 @@ enum NamespaceData<'gc> {
     Private(AvmAtom<'gc>),
 }
 
-fn strip_version_mark(url: &WStr, is_playerglobals: bool) -> Option<(&WStr, ApiVersion)> {
+pub(crate) fn strip_version_mark(
+    url: &WStr,
+    is_playerglobals: bool,
+) -> Option<(&WStr, ApiVersion)> {
     // See https://github.com/adobe/avmplus/blob/858d034a3bd3a54d9b70909386435cf4aec81d21/core/AvmCore.h#L485
     const MIN_API_MARK: usize = 0xE000;
     const MAX_API_MARK: usize = 0xF8FF;
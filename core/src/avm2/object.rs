@@ -252,14 +252,27 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This corresponds directly to the AVM2 operation `getproperty`, with the
     /// exception that it does not special-case object lookups on dictionary
     /// structured objects.
-    #[allow(unused_mut)] //Not unused.
     #[no_dynamic]
     fn get_property(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().get_trait(multiname);
+        self.get_resolved_property(multiname, property, activation)
+    }
+
+    /// Same as get_property, but with `multiname` already looked up in this
+    /// object's vtable.
+    #[allow(unused_mut)] //Not unused.
+    #[no_dynamic]
+    fn get_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 Ok(self.base().get_slot(slot_id))
             }
@@ -361,7 +374,21 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        let property = self.vtable().get_trait(multiname);
+        self.set_resolved_property(multiname, property, value, activation)
+    }
+
+    /// Same as set_property, but with `multiname` already looked up in this
+    /// object's vtable.
+    #[no_dynamic]
+    fn set_resolved_property(
+        &self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -498,15 +525,29 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This method should not be overridden.
     ///
     /// This corresponds directly to the `callproperty` operation in AVM2.
-    #[allow(unused_mut)]
     #[no_dynamic]
     fn call_property(
+        self,
+        multiname: &Multiname<'gc>,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().get_trait(multiname);
+        self.call_resolved_property(multiname, property, arguments, activation)
+    }
+
+    /// Same as call_property, but with `multiname` already looked up in this
+    /// object's vtable.
+    #[allow(unused_mut)]
+    #[no_dynamic]
+    fn call_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().get_trait(multiname) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 let obj = self.base().get_slot(slot_id);
 
@@ -34,9 +34,10 @@ pub fn byte_array_allocator<'gc>(
         Some(ByteArrayStorage::new())
     };
 
-    let storage = storage.unwrap_or_else(|| {
+    let mut storage = storage.unwrap_or_else(|| {
         unreachable!("A ByteArray subclass should have ByteArray in superclass chain")
     });
+    storage.attach_memory(activation.gc());
 
     let base = ScriptObjectData::new(class);
 
@@ -84,8 +85,9 @@ const _: () = assert!(
 impl<'gc> ByteArrayObject<'gc> {
     pub fn from_storage(
         activation: &mut Activation<'_, 'gc>,
-        bytes: ByteArrayStorage,
+        mut bytes: ByteArrayStorage,
     ) -> Result<Object<'gc>, Error<'gc>> {
+        bytes.attach_memory(activation.gc());
         let class = activation.avm2().classes().bytearray;
         let base = ScriptObjectData::new(class);
 
@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::events::Event;
+use crate::avm2::globals::slots::flash_events_mouse_event as mouse_event_slots;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
@@ -15,6 +16,7 @@ use gc_arena::barrier::unlock;
 use gc_arena::{lock::RefLock, Collect, Gc, GcWeak, Mutation};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use swf::{Point, Twips};
 
 /// A class instance allocator that allocates Event objects.
 pub fn event_allocator<'gc>(
@@ -115,7 +117,7 @@ impl<'gc> EventObject<'gc> {
         let event_type: AvmString<'gc> = event_type.into();
 
         let mouse_event_cls = activation.avm2().classes().mouseevent;
-        mouse_event_cls
+        let event = mouse_event_cls
             .construct(
                 activation,
                 &[
@@ -148,7 +150,23 @@ impl<'gc> EventObject<'gc> {
                     delta.into(),
                 ],
             )
-            .unwrap() // we don't expect to break here
+            .unwrap(); // we don't expect to break here
+
+        if &*event_type == b"mouseMove" {
+            let (movement_x, movement_y) = activation.context.mouse_data.movement;
+            event.set_slot_no_coerce(
+                mouse_event_slots::MOVEMENT_X,
+                movement_x.into(),
+                activation.gc(),
+            );
+            event.set_slot_no_coerce(
+                mouse_event_slots::MOVEMENT_Y,
+                movement_y.into(),
+                activation.gc(),
+            );
+        }
+
+        event
     }
 
     pub fn mouse_event_down(
@@ -214,6 +232,172 @@ impl<'gc> EventObject<'gc> {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn touch_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        touch_point_id: i32,
+        is_primary_touch_point: bool,
+        position: Point<Twips>,
+        pressure: Option<f64>,
+        related_object: Option<InteractiveObject<'gc>>,
+        bubbles: bool,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.global_to_local(position).unwrap_or(Point::ZERO);
+
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let touch_event_cls = activation.avm2().classes().touchevent;
+        touch_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    bubbles.into(),
+                    // cancelable
+                    false.into(),
+                    // touchPointID
+                    touch_point_id.into(),
+                    // isPrimaryTouchPoint
+                    is_primary_touch_point.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // sizeX
+                    f64::NAN.into(),
+                    // sizeY
+                    f64::NAN.into(),
+                    // pressure
+                    pressure.unwrap_or(f64::NAN).into(),
+                    // relatedObject
+                    related_object
+                        .map(|o| o.as_displayobject().object2())
+                        .unwrap_or(Value::Null),
+                    // ctrlKey
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::CONTROL)
+                        .into(),
+                    // altKey
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    // shiftKey
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn gesture_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        phase: &'static str,
+        position: Point<Twips>,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.global_to_local(position).unwrap_or(Point::ZERO);
+
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let gesture_event_cls = activation.avm2().classes().gestureevent;
+        gesture_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    // phase
+                    phase.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // ctrlKey
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::CONTROL)
+                        .into(),
+                    // altKey
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    // shiftKey
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn transform_gesture_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        phase: &'static str,
+        position: Point<Twips>,
+        scale: f64,
+        rotation: f64,
+        offset: (f64, f64),
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.global_to_local(position).unwrap_or(Point::ZERO);
+
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let transform_gesture_event_cls = activation.avm2().classes().transformgestureevent;
+        transform_gesture_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    // phase
+                    phase.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // scaleX
+                    scale.into(),
+                    // scaleY
+                    scale.into(),
+                    // rotation
+                    rotation.into(),
+                    // offsetX
+                    offset.0.into(),
+                    // offsetY
+                    offset.1.into(),
+                    // ctrlKey
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::CONTROL)
+                        .into(),
+                    // altKey
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    // shiftKey
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
     pub fn text_event<S>(
         activation: &mut Activation<'_, 'gc>,
         event_type: S,
@@ -243,6 +427,33 @@ impl<'gc> EventObject<'gc> {
             .unwrap() // we don't expect to break here
     }
 
+    pub fn ime_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        text: AvmString<'gc>,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let ime_event_cls = activation.avm2().classes().imeevent;
+        ime_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    false.into(),
+                    // cancelable
+                    false.into(),
+                    // text
+                    text.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
     pub fn net_status_event<S>(
         activation: &mut Activation<'_, 'gc>,
         event_type: S,
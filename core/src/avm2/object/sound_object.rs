@@ -10,6 +10,7 @@ use crate::avm2::EventObject;
 use crate::backend::audio::{AudioManager, SoundHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
+use crate::memory::ExternalMemory;
 use crate::string::AvmString;
 use core::fmt;
 use gc_arena::barrier::unlock;
@@ -38,6 +39,7 @@ pub fn sound_allocator<'gc>(
                 queued_plays: Vec::new(),
             }),
             id3: Lock::new(None),
+            memory: ExternalMemory::new(activation.gc(), 0),
         },
     ))
     .into())
@@ -71,6 +73,10 @@ pub struct SoundObjectData<'gc> {
 
     /// ID3Info Object
     id3: Lock<Option<Object<'gc>>>,
+
+    /// The memory used by the audio data of the sound, as reported to the GC arena.
+    #[collect(require_static)]
+    memory: ExternalMemory,
 }
 
 const _: () = assert!(std::mem::offset_of!(SoundObjectData, base) == 0);
@@ -151,6 +157,8 @@ impl<'gc> SoundObject<'gc> {
                     play_queued(queued, sound, &mut activation)?;
                 }
                 *sound_data = SoundData::Loaded { sound };
+                let size = activation.context.audio.get_sound_size(sound);
+                self.0.memory.set(size.unwrap_or(0) as usize);
             }
             SoundData::Loaded { sound: old_sound } => {
                 panic!("Tried to replace sound {old_sound:?} with {sound:?}")
@@ -107,7 +107,11 @@ impl<'gc> SoundChannelObject<'gc> {
         // the sound has played, the position will be 0 (#9952).
         let sound_channel_data = self.0.sound_channel_data.borrow();
         if let SoundChannelData::Loaded { sound_instance } = &*sound_channel_data {
-            if let Some(pos) = context.audio.get_sound_position(*sound_instance) {
+            if let Some(pos) =
+                context
+                    .audio_manager
+                    .sound_position(context.audio, &context.clock, *sound_instance)
+            {
                 self.0.position.set(pos);
             }
         }
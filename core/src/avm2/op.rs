@@ -1,4 +1,5 @@
 use crate::avm2::class::Class;
+use crate::avm2::inline_cache::PropertyCache;
 use crate::avm2::multiname::Multiname;
 use crate::avm2::script::Script;
 use crate::string::AvmAtom;
@@ -39,6 +40,11 @@ pub enum Op<'gc> {
 
         num_args: u32,
     },
+    CallPropertyCached {
+        cache: Gc<'gc, PropertyCache<'gc>>,
+
+        num_args: u32,
+    },
     CallPropLex {
         multiname: Gc<'gc, Multiname<'gc>>,
 
@@ -49,6 +55,11 @@ pub enum Op<'gc> {
 
         num_args: u32,
     },
+    CallPropVoidCached {
+        cache: Gc<'gc, PropertyCache<'gc>>,
+
+        num_args: u32,
+    },
     CallStatic {
         #[collect(require_static)]
         index: Index<Method>,
@@ -151,6 +162,9 @@ pub enum Op<'gc> {
     GetProperty {
         multiname: Gc<'gc, Multiname<'gc>>,
     },
+    GetPropertyCached {
+        cache: Gc<'gc, PropertyCache<'gc>>,
+    },
     GetScopeObject {
         index: u8,
     },
@@ -164,6 +178,10 @@ pub enum Op<'gc> {
     GetSuper {
         multiname: Gc<'gc, Multiname<'gc>>,
     },
+    GetVectorElement {
+        // note: `multiname` is only used when falling back to `GetProperty`.
+        multiname: Gc<'gc, Multiname<'gc>>,
+    },
     GreaterEquals,
     GreaterThan,
     HasNext,
@@ -317,6 +335,9 @@ pub enum Op<'gc> {
     SetProperty {
         multiname: Gc<'gc, Multiname<'gc>>,
     },
+    SetPropertyCached {
+        cache: Gc<'gc, PropertyCache<'gc>>,
+    },
     SetSlot {
         // note: 0-indexed, as opposed to FP.
         index: u32,
@@ -328,6 +349,10 @@ pub enum Op<'gc> {
     SetSuper {
         multiname: Gc<'gc, Multiname<'gc>>,
     },
+    SetVectorElement {
+        // note: `multiname` is only used when falling back to `SetProperty`.
+        multiname: Gc<'gc, Multiname<'gc>>,
+    },
     Sf32,
     Sf64,
     Si16,
@@ -1,4 +1,5 @@
 use crate::avm2::error::verify_error;
+use crate::avm2::inline_cache::PropertyCache;
 use crate::avm2::method::{BytecodeMethod, ResolvedParamConfig};
 use crate::avm2::multiname::Multiname;
 use crate::avm2::object::TObject;
@@ -8,7 +9,7 @@ use crate::avm2::verify::{Exception, JumpSource};
 use crate::avm2::vtable::VTable;
 use crate::avm2::{Activation, Class, Error};
 
-use gc_arena::Gc;
+use gc_arena::{Gc, Mutation};
 use std::collections::{HashMap, HashSet};
 
 #[allow(clippy::enum_variant_names)]
@@ -499,6 +500,16 @@ pub fn optimize<'gc>(
         namespace: activation.avm2().class_defs().namespace,
     };
 
+    // The element class of vectors that `value` is known to be, if they store
+    // their elements unboxed.
+    let unboxed_vector_element = |value: OptValue<'gc>| {
+        let element_class = value.class?.param()??;
+
+        [types.int, types.uint, types.number, types.boolean]
+            .contains(&element_class)
+            .then_some(element_class)
+    };
+
     let method_body = method
         .body()
         .expect("Cannot verify non-native method without body!");
@@ -1215,7 +1226,9 @@ pub fn optimize<'gc>(
                                 // is unlikely to cause any real problems with SWFs.
                                 *op = Op::GetScriptGlobals { script };
 
-                                if script.traits_loaded() {
+                                // This only loads the traits of the script, its
+                                // initializer still runs when the op is executed.
+                                if script.ensure_traits_loaded(activation.context).is_ok() {
                                     stack_push_done = true;
                                     stack.push_class_not_null(activation, script.global_class())?;
                                 }
@@ -1295,10 +1308,30 @@ pub fn optimize<'gc>(
                 }
                 Op::GetProperty { multiname } => {
                     let mut stack_push_done = false;
-                    stack.pop_for_multiname(activation, *multiname)?;
+                    let index_value = if is_index_access(*multiname) {
+                        Some(stack.pop(activation)?)
+                    } else {
+                        stack.pop_for_multiname(activation, *multiname)?;
+                        None
+                    };
                     let stack_value = stack.pop(activation)?;
 
-                    if !multiname.has_lazy_component() {
+                    if let Some(index_value) = index_value {
+                        if let Some(element_class) = unboxed_vector_element(stack_value) {
+                            *op = Op::GetVectorElement {
+                                multiname: *multiname,
+                            };
+
+                            // Holes in `Boolean` vectors are `null`.
+                            if element_class != types.boolean
+                                && (index_value.class == Some(types.int)
+                                    || index_value.class == Some(types.uint))
+                            {
+                                stack_push_done = true;
+                                stack.push_class(activation, element_class)?;
+                            }
+                        }
+                    } else if !multiname.has_lazy_component() {
                         if let Some(vtable) = stack_value.vtable() {
                             match vtable.get_trait(multiname) {
                                 Some(Property::Slot { slot_id })
@@ -1392,7 +1425,17 @@ pub fn optimize<'gc>(
 
                     stack.pop_for_multiname(activation, *multiname)?;
                     let stack_value = stack.pop(activation)?;
-                    if !multiname.has_lazy_component() {
+                    if is_index_access(*multiname) {
+                        // Values of the vector's element type are stored
+                        // without being coerced.
+                        if let Some(element_class) = unboxed_vector_element(stack_value) {
+                            if set_value.class == Some(element_class) {
+                                *op = Op::SetVectorElement {
+                                    multiname: *multiname,
+                                };
+                            }
+                        }
+                    } else if !multiname.has_lazy_component() {
                         if let Some(vtable) = stack_value.vtable() {
                             match vtable.get_trait(multiname) {
                                 Some(Property::Slot { slot_id }) => {
@@ -1953,7 +1996,11 @@ pub fn optimize<'gc>(
                     stack.push_class(activation, types.uint)?;
                 }
                 Op::GetScriptGlobals { script } => {
-                    stack.push_class_not_null(activation, script.global_class())?;
+                    if script.traits_loaded() {
+                        stack.push_class_not_null(activation, script.global_class())?;
+                    } else {
+                        stack.push_any(activation)?;
+                    }
                 }
                 Op::ReturnValueNoCoerce => {
                     last_block_op_was_block_terminating = true;
@@ -1963,15 +2010,93 @@ pub fn optimize<'gc>(
                     stack.pop(activation)?;
                     stack.pop(activation)?;
                 }
+                Op::GetPropertyCached { .. } => {
+                    stack.pop(activation)?;
+                    stack.push_any(activation)?;
+                }
+                Op::SetPropertyCached { .. } => {
+                    stack.pop(activation)?;
+                    stack.pop(activation)?;
+                }
+                Op::CallPropertyCached { num_args, .. } => {
+                    stack.popn(activation, *num_args)?;
+                    stack.pop(activation)?;
+                    stack.push_any(activation)?;
+                }
+                Op::CallPropVoidCached { num_args, .. } => {
+                    stack.popn(activation, *num_args)?;
+                    stack.pop(activation)?;
+                }
+                Op::GetVectorElement { .. } => {
+                    stack.pop(activation)?;
+                    stack.pop(activation)?;
+                    stack.push_any(activation)?;
+                }
+                Op::SetVectorElement { .. } => {
+                    stack.pop(activation)?;
+                    stack.pop(activation)?;
+                    stack.pop(activation)?;
+                }
             }
 
             i += 1;
         }
     }
 
+    if activation.avm2().inline_caches_enabled() {
+        install_inline_caches(activation.gc(), code);
+    }
+
     Ok(())
 }
 
+/// Whether a property access with this multiname looks up the local name
+/// value on the stack, which is how indexed accesses are compiled.
+fn is_index_access(multiname: Gc<'_, Multiname<'_>>) -> bool {
+    multiname.has_lazy_name() && !multiname.has_lazy_ns()
+}
+
+/// Attach an inline cache to every remaining property access with a static name.
+///
+/// Accesses on receivers of a known type were already turned into slot or
+/// method accesses above, so these are the ones whose receiver type is only
+/// known at runtime.
+fn install_inline_caches<'gc>(mc: &Mutation<'gc>, code: &mut [Op<'gc>]) {
+    for op in code {
+        match *op {
+            Op::GetProperty { multiname } if !multiname.has_lazy_component() => {
+                *op = Op::GetPropertyCached {
+                    cache: PropertyCache::new(mc, multiname),
+                };
+            }
+            Op::SetProperty { multiname } if !multiname.has_lazy_component() => {
+                *op = Op::SetPropertyCached {
+                    cache: PropertyCache::new(mc, multiname),
+                };
+            }
+            Op::CallProperty {
+                multiname,
+                num_args,
+            } if !multiname.has_lazy_component() => {
+                *op = Op::CallPropertyCached {
+                    cache: PropertyCache::new(mc, multiname),
+                    num_args,
+                };
+            }
+            Op::CallPropVoid {
+                multiname,
+                num_args,
+            } if !multiname.has_lazy_component() => {
+                *op = Op::CallPropVoidCached {
+                    cache: PropertyCache::new(mc, multiname),
+                    num_args,
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn check_target<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -13,6 +13,7 @@ use crate::avm2::value::Value;
 use crate::avm2::vtable::VTable;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
+use crate::avm2::QName;
 use crate::avm2::{Avm2, Error};
 use crate::context::UpdateContext;
 use crate::string::{AvmAtom, AvmString, StringContext};
@@ -133,6 +134,25 @@ impl<'gc> TranslationUnit<'gc> {
         Ok(())
     }
 
+    /// Register all classes from the ABC's class list in the domain by name,
+    /// without loading them.
+    ///
+    /// Each class is loaded when it's first looked up in the domain, or when a
+    /// trait referring to it is loaded, whichever comes first. Classes are
+    /// registered in the order they appear in the constant pool, so that the
+    /// first of several classes with the same name is the one that's exported.
+    pub fn register_classes(self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        let abc = self.abc();
+        let domain = self.domain();
+
+        for (class_index, abc_instance) in abc.instances.iter().enumerate() {
+            let name = QName::from_abc_multiname(activation, self, abc_instance.name)?;
+            domain.register_class(name, self, class_index as u32, activation.gc());
+        }
+
+        Ok(())
+    }
+
     pub fn domain(self) -> Domain<'gc> {
         self.0.read().domain
     }
@@ -244,6 +264,22 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         script_index: u32,
         activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Script<'gc>, Error<'gc>> {
+        let script = self.register_script(script_index, activation)?;
+
+        script.load_traits(self, script_index, activation)?;
+
+        Ok(script)
+    }
+
+    /// Load a script from the ABC file without loading its traits, and export
+    /// its definitions to the domain.
+    ///
+    /// The traits of the script are loaded when its globals are first needed.
+    pub fn register_script(
+        self,
+        script_index: u32,
+        activation: &mut Activation<'_, 'gc>,
     ) -> Result<Script<'gc>, Error<'gc>> {
         let read = self.0.read();
         if let Some(Some(scripts)) = read.scripts.get(script_index as usize) {
@@ -257,7 +293,7 @@ impl<'gc> TranslationUnit<'gc> {
         let script = Script::from_abc_index(self, script_index, domain, activation)?;
         self.0.write(activation.gc()).scripts[script_index as usize] = Some(script);
 
-        script.load_traits(self, script_index, activation)?;
+        script.export_definitions(self, script_index, activation)?;
 
         Ok(script)
     }
@@ -495,6 +531,30 @@ impl<'gc> Script<'gc> {
         )))
     }
 
+    /// Export the names of all of this script's traits to its domain, without
+    /// loading the traits themselves.
+    pub fn export_definitions(
+        self,
+        unit: TranslationUnit<'gc>,
+        script_index: u32,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let abc = unit.abc();
+        let script: Result<_, Error<'gc>> = abc
+            .scripts
+            .get(script_index as usize)
+            .ok_or_else(|| "LoadError: Script index not valid".into());
+        let script = script?;
+        let mut domain = self.domain();
+
+        for abc_trait in script.traits.iter() {
+            let name = QName::from_abc_multiname(activation, unit, abc_trait.name)?;
+            domain.export_definition(name, self, activation.gc());
+        }
+
+        Ok(())
+    }
+
     /// Finish the class-loading process by loading traits.
     ///
     /// This process must be done after the `Script` has been stored in the
@@ -596,11 +656,45 @@ impl<'gc> Script<'gc> {
             .instance_class()
     }
 
+    /// Load the traits of a script that was only registered, if they weren't
+    /// loaded yet.
+    ///
+    /// This doesn't run the script initializer.
+    pub fn ensure_traits_loaded(self, context: &mut UpdateContext<'gc>) -> Result<(), Error<'gc>> {
+        let read = self.0.read();
+        let unloaded_traits = if read.traits_loaded {
+            None
+        } else {
+            read.translation_unit.zip(read.abc_index)
+        };
+        let domain = read.domain;
+        drop(read);
+
+        if let Some((unit, script_index)) = unloaded_traits {
+            let mut activation = Activation::from_domain(context, domain);
+            // Make sure we have the correct domain for code that tries to access it
+            // using `activation.domain()`
+            activation.set_outer(ScopeChain::new(domain));
+
+            if let Err(e) = self.load_traits(unit, script_index, &mut activation) {
+                // Try again (and fail the same way) on the next reference,
+                // rather than leaving a script without globals behind.
+                self.0.write(activation.gc()).traits_loaded = false;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Return the global scope for the script.
     ///
-    /// If the script has not yet been initialized, this will initialize it on
-    /// the same stack.
+    /// If the script's traits were not loaded yet, this loads them. If the
+    /// script has not yet been initialized, this will initialize it on the
+    /// same stack.
     pub fn globals(self, context: &mut UpdateContext<'gc>) -> Result<Object<'gc>, Error<'gc>> {
+        self.ensure_traits_loaded(context)?;
+
         let mut write = self.0.write(context.gc());
 
         let globals = write.globals.expect("Global object should be initialized");
@@ -8,12 +8,14 @@ use crate::context::UpdateContext;
 use crate::string::AvmString;
 use crate::stub::Stub;
 use fnv::{FnvHashMap, FnvHashSet};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fs::File;
 use std::path::Path;
 use std::process::exit;
 
+mod coverage;
+
 fn is_false(b: &bool) -> bool {
     !(*b)
 }
@@ -83,7 +85,8 @@ fn format_signature(params: &[ParamConfig], is_variadic: bool) -> Vec<ParamInfo>
     result
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct ClassInfo {
     #[serde(skip_serializing_if = "is_false")]
     dynamic: bool,
@@ -99,7 +102,8 @@ struct ClassInfo {
     is_final: bool,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct VariableInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "type")]
@@ -132,7 +136,8 @@ impl VariableInfo {
     }
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct ParamInfo {
     #[serde(rename = "type")]
     type_info: String,
@@ -145,11 +150,11 @@ struct ParamInfo {
     variadic: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 struct FunctionInfo {
     args: Vec<ParamInfo>,
     returns: String,
-    #[serde(skip_serializing_if = "is_false")]
+    #[serde(default, skip_serializing_if = "is_false")]
     stubbed: bool,
 }
 
@@ -179,7 +184,8 @@ impl FunctionInfo {
     }
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct TraitList {
     #[serde(rename = "const")]
     #[serde(skip_serializing_if = "FnvHashMap::is_empty")]
@@ -199,7 +205,8 @@ struct TraitList {
     setter: FnvHashMap<String, VariableInfo>,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct Definition {
     #[serde(skip_serializing_if = "Option::is_none")]
     classinfo: Option<ClassInfo>,
@@ -217,6 +224,29 @@ struct Definition {
     prototype: Option<TraitList>,
 }
 
+impl TraitList {
+    /// Every trait in this list, as `(kind, name, stubbed)`.
+    fn members(&self) -> impl Iterator<Item = (&'static str, &str, bool)> {
+        fn variables<'a>(
+            kind: &'static str,
+            map: &'a FnvHashMap<String, VariableInfo>,
+        ) -> impl Iterator<Item = (&'static str, &'a str, bool)> {
+            map.iter()
+                .map(move |(name, info)| (kind, name.as_str(), info.stubbed))
+        }
+
+        variables("const", &self.constants)
+            .chain(variables("var", &self.variables))
+            .chain(
+                self.function
+                    .iter()
+                    .map(|(name, info)| ("function", name.as_str(), info.stubbed)),
+            )
+            .chain(variables("getter", &self.getter))
+            .chain(variables("setter", &self.setter))
+    }
+}
+
 #[derive(Default)]
 struct ClassStubs {
     methods: FnvHashSet<Cow<'static, str>>,
@@ -466,10 +496,16 @@ impl Definition {
 }
 
 #[allow(unreachable_code, unused_variables, clippy::diverging_sub_expression)]
-pub fn capture_specification(context: &mut UpdateContext, output: &Path) {
+pub fn capture_specification(
+    context: &mut UpdateContext,
+    output: Option<&Path>,
+    coverage_output: Option<&Path>,
+    coverage_reference: Option<&Path>,
+) {
     let stubs = crate::stub::get_known_stubs();
 
     let mut definitions = FnvHashMap::<String, Definition>::default();
+    let mut class_names = FnvHashSet::default();
 
     let defs = context.avm2.playerglobals_domain.defs().clone();
     let mut activation = Activation::from_nothing(context);
@@ -493,6 +529,7 @@ pub fn capture_specification(context: &mut UpdateContext, output: &Path) {
                     .to_qualified_name_err_message(activation.gc())
                     .to_string();
                 let class_stubs = ClassStubs::for_class(&class_name, &stubs);
+                class_names.insert(class_name.clone());
                 definitions.insert(
                     class_name,
                     Definition::from_class(class, &mut activation, &class_stubs),
@@ -518,7 +555,20 @@ pub fn capture_specification(context: &mut UpdateContext, output: &Path) {
             );
         }
     }
-    serde_json::to_writer_pretty(&File::create(output).unwrap(), &definitions).unwrap();
-    tracing::info!("Wrote stub report to {output:?}");
+    if let Some(output) = output {
+        serde_json::to_writer_pretty(&File::create(output).unwrap(), &definitions).unwrap();
+        tracing::info!("Wrote stub report to {output:?}");
+    }
+    if let Some(coverage_output) = coverage_output {
+        let native_method_table = activation.avm2().native_method_table;
+        coverage::capture_coverage(
+            &definitions,
+            &class_names,
+            coverage_reference,
+            native_method_table,
+            coverage_output,
+        );
+        tracing::info!("Wrote API coverage report to {coverage_output:?}");
+    }
     exit(0);
 }
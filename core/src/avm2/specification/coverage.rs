@@ -0,0 +1,594 @@
+//! A machine-readable matrix of which Flash Player APIs are implemented,
+//! stubbed or missing, to track our API coverage over time.
+
+use super::{Definition, TraitList};
+use crate::avm2::api_version::ApiVersion;
+use crate::avm2::globals::PLAYERGLOBAL;
+use crate::avm2::namespace::strip_version_mark;
+use crate::stub::{get_known_stub_locations, KnownStub, Stub};
+use fnv::{FnvHashMap, FnvHashSet};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    AbcFile, Index, MethodFlags, Multiname, Namespace as AbcNamespace, Trait, TraitKind,
+};
+use swf::Tag;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ApiStatus {
+    Implemented,
+    Stubbed,
+    Missing,
+}
+
+#[derive(Serialize, Default)]
+struct StatusCounts {
+    implemented: usize,
+    stubbed: usize,
+    missing: usize,
+}
+
+impl StatusCounts {
+    fn add(&mut self, status: ApiStatus) {
+        match status {
+            ApiStatus::Implemented => self.implemented += 1,
+            ApiStatus::Stubbed => self.stubbed += 1,
+            ApiStatus::Missing => self.missing += 1,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StubSite {
+    stub: String,
+    location: String,
+}
+
+impl StubSite {
+    fn new(known_stub: &KnownStub) -> Self {
+        Self {
+            stub: known_stub.stub.to_string(),
+            location: known_stub.location(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApiCoverage {
+    definition: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    member: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<&'static str>,
+
+    kind: &'static str,
+
+    version: String,
+
+    status: ApiStatus,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stubs: Vec<StubSite>,
+}
+
+#[derive(Serialize, Default)]
+struct CoverageReport {
+    total: StatusCounts,
+
+    /// The number of APIs in each package, grouped by API version.
+    packages: BTreeMap<String, BTreeMap<String, StatusCounts>>,
+
+    /// Every API, grouped by package.
+    apis: BTreeMap<String, Vec<ApiCoverage>>,
+
+    /// Stubs that don't belong to any AVM2 API, such as AVM1 stubs.
+    other_stubs: Vec<StubSite>,
+}
+
+impl CoverageReport {
+    fn add(&mut self, package: &str, api: ApiCoverage) {
+        self.total.add(api.status);
+        self.packages
+            .entry(package.to_string())
+            .or_default()
+            .entry(api.version.clone())
+            .or_default()
+            .add(api.status);
+        self.apis.entry(package.to_string()).or_default().push(api);
+    }
+}
+
+/// What we can learn about our `playerglobal` from its ABC, as opposed to
+/// the loaded classes: the API versions of definitions and their members, and
+/// native methods that the native method table has no Rust implementation for.
+#[derive(Default)]
+struct PlayerglobalInfo {
+    /// Keyed by definition name and member name (`None` for the definition itself).
+    versions: FnvHashMap<(String, Option<String>), ApiVersion>,
+    missing_natives: FnvHashSet<(String, String)>,
+}
+
+impl PlayerglobalInfo {
+    fn parse<T>(native_method_table: &[Option<T>]) -> Self {
+        let mut info = Self::default();
+        let swf_buf = swf::decompress_swf(PLAYERGLOBAL).expect("playerglobal.swf should be valid");
+        let swf = swf::parse_swf(&swf_buf).expect("playerglobal.swf should be valid");
+        for tag in &swf.tags {
+            if let Tag::DoAbc2(do_abc) = tag {
+                let abc = Reader::new(do_abc.data)
+                    .read()
+                    .expect("playerglobal.swf should be valid");
+                info.add_abc(&abc, native_method_table);
+            }
+        }
+        info
+    }
+
+    fn add_abc<T>(&mut self, abc: &AbcFile, native_method_table: &[Option<T>]) {
+        for script in &abc.scripts {
+            for script_trait in &script.traits {
+                let Some((uri, name, version)) = trait_name(abc, &script_trait.name) else {
+                    continue;
+                };
+                if let TraitKind::Class { class, .. } = &script_trait.kind {
+                    // Named the same way as `QName::to_qualified_name_err_message`
+                    let definition = if uri.is_empty() {
+                        name
+                    } else {
+                        format!("{uri}.{name}")
+                    };
+                    self.versions.insert((definition.clone(), None), version);
+
+                    let class = class.0 as usize;
+                    let instance_traits = abc.instances.get(class).map(|i| &i.traits[..]);
+                    let class_traits = abc.classes.get(class).map(|c| &c.traits[..]);
+                    for member in instance_traits
+                        .unwrap_or_default()
+                        .iter()
+                        .chain(class_traits.unwrap_or_default())
+                    {
+                        self.add_member(abc, &definition, member, native_method_table);
+                    }
+                } else {
+                    // Package-level definitions are grouped under their package
+                    self.add_member(abc, &uri, script_trait, native_method_table);
+                }
+            }
+        }
+    }
+
+    fn add_member<T>(
+        &mut self,
+        abc: &AbcFile,
+        definition: &str,
+        member: &Trait,
+        native_method_table: &[Option<T>],
+    ) {
+        let Some((_, name, version)) = trait_name(abc, &member.name) else {
+            return;
+        };
+        if let TraitKind::Method { method, .. }
+        | TraitKind::Getter { method, .. }
+        | TraitKind::Setter { method, .. } = &member.kind
+        {
+            let method_id = method.0 as usize;
+            let is_native = abc
+                .methods
+                .get(method_id)
+                .is_some_and(|m| m.flags.contains(MethodFlags::NATIVE));
+            if is_native
+                && !native_method_table
+                    .get(method_id)
+                    .is_some_and(Option::is_some)
+            {
+                self.missing_natives
+                    .insert((definition.to_string(), name.clone()));
+            }
+        }
+        self.versions
+            .entry((definition.to_string(), Some(name)))
+            .or_insert(version);
+    }
+
+    fn version(&self, definition: &str, member: Option<&str>) -> Option<ApiVersion> {
+        self.versions
+            .get(&(definition.to_string(), member.map(str::to_string)))
+            .copied()
+    }
+}
+
+fn abc_string<'a>(abc: &'a AbcFile, index: &Index<String>) -> &'a [u8] {
+    // String 0 is the empty string
+    match index.0.checked_sub(1) {
+        Some(i) => &abc.constant_pool.strings[i as usize],
+        None => b"",
+    }
+}
+
+/// Resolves the namespace URI, local name and API version of a public or
+/// namespaced trait. Other traits aren't part of the API.
+fn trait_name(abc: &AbcFile, name: &Index<Multiname>) -> Option<(String, String, ApiVersion)> {
+    let multiname = abc
+        .constant_pool
+        .multinames
+        .get(name.0.checked_sub(1)? as usize)?;
+    // The compiler emits the names of our traits as multinames with a single namespace
+    let (namespace, name) = match multiname {
+        Multiname::QName { namespace, name } => (namespace, name),
+        Multiname::Multiname {
+            namespace_set,
+            name,
+        } => {
+            let namespace_set = abc
+                .constant_pool
+                .namespace_sets
+                .get(namespace_set.0.checked_sub(1)? as usize)?;
+            match &namespace_set[..] {
+                [namespace] => (namespace, name),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let namespace = abc
+        .constant_pool
+        .namespaces
+        .get(namespace.0.checked_sub(1)? as usize)?;
+    let (AbcNamespace::Package(uri) | AbcNamespace::Namespace(uri)) = namespace else {
+        return None;
+    };
+
+    let uri = ruffle_wstr::from_utf8_bytes(abc_string(abc, uri));
+    let (uri, version) = strip_version_mark(&uri, true).unwrap_or((&uri, ApiVersion::AllVersions));
+    Some((
+        uri.to_utf8_lossy().into_owned(),
+        String::from_utf8_lossy(abc_string(abc, name)).into_owned(),
+        version,
+    ))
+}
+
+/// Where stubs apply, as `(definition, member, kind)`, where the kind of a
+/// constructor stub is `"class"`.
+type StubKey<'a> = (&'a str, Option<&'a str>, &'static str);
+
+fn stub_key(stub: &Stub) -> Option<StubKey<'_>> {
+    match stub {
+        Stub::Avm2Method { class, method, .. } => {
+            Some((class.as_ref(), Some(method.as_ref()), "function"))
+        }
+        Stub::Avm2Getter { class, property } => {
+            Some((class.as_ref(), Some(property.as_ref()), "getter"))
+        }
+        Stub::Avm2Setter { class, property } => {
+            Some((class.as_ref(), Some(property.as_ref()), "setter"))
+        }
+        Stub::Avm2Constructor { class, .. } => Some((class.as_ref(), None, "class")),
+        _ => None,
+    }
+}
+
+/// Guesses whether a definition that only exists in the reference is a class,
+/// rather than a package holding package-level functions and constants.
+fn looks_like_class(definition: &str) -> bool {
+    let name = definition.rsplit('.').next().unwrap_or(definition);
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// The static, instance and prototype traits of a definition.
+fn trait_lists(definition: Option<&Definition>) -> [Option<&TraitList>; 3] {
+    definition.map_or([None, None, None], |d| {
+        [
+            d.static_traits.as_ref(),
+            d.instance_traits.as_ref(),
+            d.prototype.as_ref(),
+        ]
+    })
+}
+
+fn read_reference(path: &Path) -> FnvHashMap<String, Definition> {
+    let file = File::open(path)
+        .unwrap_or_else(|e| panic!("Couldn't open reference specification {path:?}: {e}"));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("Couldn't parse reference specification {path:?}: {e}"))
+}
+
+pub(super) fn capture_coverage<T>(
+    definitions: &FnvHashMap<String, Definition>,
+    class_names: &FnvHashSet<String>,
+    reference: Option<&Path>,
+    native_method_table: &[Option<T>],
+    output: &Path,
+) {
+    let reference = reference.map(read_reference).unwrap_or_default();
+    let info = PlayerglobalInfo::parse(native_method_table);
+
+    let mut known_stubs: Vec<&KnownStub> = get_known_stub_locations().collect();
+    known_stubs.sort_by_key(|known_stub| (known_stub.file, known_stub.line));
+
+    let report = build_report(definitions, class_names, &reference, &info, &known_stubs);
+    serde_json::to_writer_pretty(&File::create(output).unwrap(), &report).unwrap();
+}
+
+/// Compares our definitions to the reference ones, and attributes stubs to
+/// the APIs they belong to.
+fn build_report(
+    definitions: &FnvHashMap<String, Definition>,
+    class_names: &FnvHashSet<String>,
+    reference: &FnvHashMap<String, Definition>,
+    info: &PlayerglobalInfo,
+    known_stubs: &[&KnownStub],
+) -> CoverageReport {
+    let mut stubs_by_key = FnvHashMap::<StubKey, Vec<usize>>::default();
+    for (i, known_stub) in known_stubs.iter().enumerate() {
+        if let Some(key) = stub_key(known_stub.stub) {
+            stubs_by_key.entry(key).or_default().push(i);
+        }
+    }
+    let mut reported_stubs = FnvHashSet::default();
+    let mut stub_sites = |key: StubKey| {
+        let indices = stubs_by_key.get(&key).map_or(&[][..], |i| &i[..]);
+        reported_stubs.extend(indices.iter().copied());
+        indices
+            .iter()
+            .map(|&i| StubSite::new(known_stubs[i]))
+            .collect::<Vec<_>>()
+    };
+
+    let mut report = CoverageReport::default();
+    let mut names: Vec<&String> = definitions.keys().chain(reference.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    for definition_name in names {
+        let ours = definitions.get(definition_name);
+        let theirs = reference.get(definition_name);
+        let is_class = class_names.contains(definition_name)
+            || (ours.is_none() && looks_like_class(definition_name));
+        let package = if is_class {
+            definition_name.rsplit_once('.').map_or("", |(p, _)| p)
+        } else {
+            definition_name.as_str()
+        };
+        let definition_version = info.version(definition_name, None);
+        // Members of a versioned class usually aren't versioned themselves
+        let version_name = |version: Option<ApiVersion>| {
+            version
+                .filter(|version| *version != ApiVersion::AllVersions)
+                .or(definition_version)
+                .or(version)
+                .map_or_else(|| "unknown".to_string(), |v| format!("{v:?}"))
+        };
+
+        if is_class {
+            let stubs = stub_sites((definition_name, None, "class"));
+            let status = match ours {
+                None => ApiStatus::Missing,
+                Some(_) if !stubs.is_empty() => ApiStatus::Stubbed,
+                Some(_) => ApiStatus::Implemented,
+            };
+            report.add(
+                package,
+                ApiCoverage {
+                    definition: definition_name.clone(),
+                    member: None,
+                    scope: None,
+                    kind: "class",
+                    version: version_name(None),
+                    status,
+                    stubs,
+                },
+            );
+        }
+
+        let ours_lists = trait_lists(ours);
+        let theirs_lists = trait_lists(theirs);
+        for (i, scope) in ["static", "instance", "prototype"].into_iter().enumerate() {
+            // `None` if only the reference has this member, otherwise whether it's stubbed
+            let mut members = BTreeMap::<(&str, &str), Option<bool>>::new();
+            for (kind, name, stubbed) in ours_lists[i].into_iter().flat_map(TraitList::members) {
+                members.insert((name, kind), Some(stubbed));
+            }
+            for (kind, name, _) in theirs_lists[i].into_iter().flat_map(TraitList::members) {
+                members.entry((name, kind)).or_insert(None);
+            }
+
+            for ((name, kind), stubbed) in members {
+                let stubs = stub_sites((definition_name, Some(name), kind));
+                let missing_native = info
+                    .missing_natives
+                    .contains(&(definition_name.to_string(), name.to_string()));
+                let status = match stubbed {
+                    None => ApiStatus::Missing,
+                    Some(_) if missing_native => ApiStatus::Missing,
+                    Some(stubbed) if stubbed || !stubs.is_empty() => ApiStatus::Stubbed,
+                    Some(_) => ApiStatus::Implemented,
+                };
+                report.add(
+                    package,
+                    ApiCoverage {
+                        definition: definition_name.clone(),
+                        member: Some(name.to_string()),
+                        // Package-level definitions are reported as instance traits
+                        scope: is_class.then_some(scope),
+                        kind,
+                        version: version_name(info.version(definition_name, Some(name))),
+                        status,
+                        stubs,
+                    },
+                );
+            }
+        }
+    }
+
+    report.other_stubs = known_stubs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !reported_stubs.contains(i))
+        .map(|(_, known_stub)| StubSite::new(known_stub))
+        .collect();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    static SPRITE_GRAPHICS: Stub = Stub::Avm2Getter {
+        class: Cow::Borrowed("flash.display.Sprite"),
+        property: Cow::Borrowed("graphics"),
+    };
+    static TEXT_FIELD: Stub = Stub::Avm1Constructor { class: "TextField" };
+
+    fn definitions(value: serde_json::Value) -> FnvHashMap<String, Definition> {
+        serde_json::from_value(value).expect("Definitions should be valid")
+    }
+
+    #[test]
+    fn report() {
+        let ours = definitions(json!({
+            "flash.display.Sprite": {
+                "instance": {
+                    "getter": {
+                        "graphics": { "type": "Graphics" },
+                        "soundTransform": { "type": "SoundTransform" },
+                    },
+                    "function": {
+                        "startTouchDrag": { "args": [], "returns": "void", "stubbed": true },
+                    },
+                },
+            },
+            "flash.utils": {
+                "instance": {
+                    "function": { "getTimer": { "args": [], "returns": "int" } },
+                },
+            },
+        }));
+        let reference = definitions(json!({
+            "flash.display.Sprite": {
+                "instance": {
+                    "getter": {
+                        "graphics": { "type": "Graphics" },
+                        "hitArea": { "type": "Sprite" },
+                    },
+                },
+            },
+            "flash.display.Stage3D": {
+                "instance": { "getter": { "context3D": { "type": "Context3D" } } },
+            },
+        }));
+        let class_names = FnvHashSet::from_iter(["flash.display.Sprite".to_string()]);
+
+        let mut info = PlayerglobalInfo::default();
+        info.versions.insert(
+            ("flash.display.Stage3D".to_string(), None),
+            ApiVersion::SWF_13,
+        );
+        info.missing_natives.insert((
+            "flash.display.Sprite".to_string(),
+            "soundTransform".to_string(),
+        ));
+
+        let known_stubs = [
+            &KnownStub {
+                stub: &SPRITE_GRAPHICS,
+                file: "Sprite.as",
+                line: 10,
+            },
+            &KnownStub {
+                stub: &TEXT_FIELD,
+                file: "text_field.rs",
+                line: 20,
+            },
+        ];
+
+        let report = build_report(&ours, &class_names, &reference, &info, &known_stubs);
+        let sprite = |kind, member: Option<&str>, status| {
+            let mut api = json!({
+                "definition": "flash.display.Sprite",
+                "kind": kind,
+                "version": "unknown",
+                "status": status,
+            });
+            if let Some(member) = member {
+                api["member"] = json!(member);
+                api["scope"] = json!("instance");
+            }
+            api
+        };
+        let mut sprite_graphics = sprite("getter", Some("graphics"), "stubbed");
+        sprite_graphics["stubs"] = json!([{
+            "stub": "AVM2 flash.display.Sprite.graphics getter",
+            "location": "Sprite.as:10",
+        }]);
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "total": { "implemented": 2, "stubbed": 2, "missing": 4 },
+                "packages": {
+                    "flash.display": {
+                        "SWF_13": { "implemented": 0, "stubbed": 0, "missing": 2 },
+                        "unknown": { "implemented": 1, "stubbed": 2, "missing": 2 },
+                    },
+                    "flash.utils": {
+                        "unknown": { "implemented": 1, "stubbed": 0, "missing": 0 },
+                    },
+                },
+                "apis": {
+                    "flash.display": [
+                        sprite("class", None, "implemented"),
+                        sprite_graphics,
+                        // Only in the reference
+                        sprite("getter", Some("hitArea"), "missing"),
+                        // Native, without a Rust implementation
+                        sprite("getter", Some("soundTransform"), "missing"),
+                        sprite("function", Some("startTouchDrag"), "stubbed"),
+                        {
+                            "definition": "flash.display.Stage3D",
+                            "kind": "class",
+                            "version": "SWF_13",
+                            "status": "missing",
+                        },
+                        {
+                            "definition": "flash.display.Stage3D",
+                            "member": "context3D",
+                            "scope": "instance",
+                            "kind": "getter",
+                            "version": "SWF_13",
+                            "status": "missing",
+                        },
+                    ],
+                    "flash.utils": [{
+                        "definition": "flash.utils",
+                        "member": "getTimer",
+                        "kind": "function",
+                        "version": "unknown",
+                        "status": "implemented",
+                    }],
+                },
+                "other_stubs": [{
+                    "stub": "AVM1 TextField() constructor",
+                    "location": "text_field.rs:20",
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn guesses_classes() {
+        assert!(looks_like_class("flash.display.Stage3D"));
+        assert!(looks_like_class("Math"));
+        assert!(!looks_like_class("flash.utils"));
+        assert!(!looks_like_class("flash.net.navigateToURL"));
+    }
+}
@@ -1,27 +1,21 @@
 #[macro_export]
 macro_rules! avm2_stub_method_context {
     ($context: expr, $class: literal, $method: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Method {
             class: std::borrow::Cow::Borrowed($class),
             method: std::borrow::Cow::Borrowed($method),
             specifics: None,
         };
+        $crate::register_known_stub!(STUB);
         $context.stub_tracker.encounter(&STUB);
     };
     ($context: expr, $class: literal, $method: literal, $specifics: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Method {
             class: std::borrow::Cow::Borrowed($class),
             method: std::borrow::Cow::Borrowed($method),
             specifics: Some(std::borrow::Cow::Borrowed($specifics)),
         };
+        $crate::register_known_stub!(STUB);
         $context.stub_tracker.encounter(&STUB);
     };
 }
@@ -39,25 +33,19 @@ macro_rules! avm2_stub_method {
 #[macro_export]
 macro_rules! avm2_stub_constructor {
     ($activation: ident, $class: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Constructor {
             class: std::borrow::Cow::Borrowed($class),
             specifics: None,
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
     ($activation: ident, $class: literal, $specifics: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Constructor {
             class: std::borrow::Cow::Borrowed($class),
             specifics: Some(std::borrow::Cow::Borrowed($specifics)),
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
 }
@@ -65,14 +53,11 @@ macro_rules! avm2_stub_constructor {
 #[macro_export]
 macro_rules! avm2_stub_getter {
     ($activation: ident, $class: literal, $property: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Getter {
             class: std::borrow::Cow::Borrowed($class),
             property: std::borrow::Cow::Borrowed($property),
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
 }
@@ -80,14 +65,11 @@ macro_rules! avm2_stub_getter {
 #[macro_export]
 macro_rules! avm2_stub_setter {
     ($activation: ident, $class: literal, $property: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub = $crate::stub::Stub::Avm2Setter {
             class: std::borrow::Cow::Borrowed($class),
             property: std::borrow::Cow::Borrowed($property),
         };
+        $crate::register_known_stub!(STUB);
         $activation.context.stub_tracker.encounter(&STUB);
     };
 }
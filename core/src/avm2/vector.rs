@@ -5,10 +5,264 @@ use crate::avm2::class::Class;
 use crate::avm2::error::{make_error_1125, range_error};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use gc_arena::Collect;
 use std::cmp::{max, min};
-use std::ops::RangeBounds;
-use std::slice::SliceIndex;
+use std::ops::Range;
+
+/// A type that vector elements are stored as.
+trait Element<'gc>: Clone {
+    /// The value of vector holes.
+    fn hole() -> Self;
+
+    /// Convert a value that was already coerced to the vector's value type.
+    fn unbox(value: Value<'gc>) -> Self;
+
+    /// Convert a value that needs no coercion to be stored, or `None` if the
+    /// value has to be coerced first.
+    fn unbox_exact(value: &Value<'gc>) -> Option<Self>;
+
+    fn boxed(self) -> Value<'gc>;
+}
+
+impl<'gc> Element<'gc> for i32 {
+    fn hole() -> Self {
+        0
+    }
+
+    fn unbox(value: Value<'gc>) -> Self {
+        match value {
+            Value::Integer(i) => i,
+            Value::Number(n) => f64_to_wrapping_i32(n),
+            _ => Self::hole(),
+        }
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn unbox_exact(value: &Value<'gc>) -> Option<Self> {
+        match *value {
+            Value::Integer(i) => Some(i),
+            Value::Number(n) if n == n as i32 as f64 => Some(n as i32),
+            _ => None,
+        }
+    }
+
+    fn boxed(self) -> Value<'gc> {
+        self.into()
+    }
+}
+
+impl<'gc> Element<'gc> for u32 {
+    fn hole() -> Self {
+        0
+    }
+
+    fn unbox(value: Value<'gc>) -> Self {
+        match value {
+            Value::Integer(i) => i as u32,
+            Value::Number(n) => f64_to_wrapping_u32(n),
+            _ => Self::hole(),
+        }
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn unbox_exact(value: &Value<'gc>) -> Option<Self> {
+        match *value {
+            Value::Integer(i) if i >= 0 => Some(i as u32),
+            Value::Number(n) if n == n as u32 as f64 => Some(n as u32),
+            _ => None,
+        }
+    }
+
+    fn boxed(self) -> Value<'gc> {
+        self.into()
+    }
+}
+
+impl<'gc> Element<'gc> for f64 {
+    fn hole() -> Self {
+        0.0
+    }
+
+    fn unbox(value: Value<'gc>) -> Self {
+        match value {
+            Value::Integer(i) => i as f64,
+            Value::Number(n) => n,
+            _ => Self::hole(),
+        }
+    }
+
+    fn unbox_exact(value: &Value<'gc>) -> Option<Self> {
+        match *value {
+            Value::Integer(i) => Some(i as f64),
+            Value::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn boxed(self) -> Value<'gc> {
+        Value::Number(self)
+    }
+}
+
+/// `Boolean` vector holes are `null` rather than `false`, so booleans are
+/// stored as `Option<bool>`, with `None` standing for `null`.
+impl<'gc> Element<'gc> for Option<bool> {
+    fn hole() -> Self {
+        None
+    }
+
+    fn unbox(value: Value<'gc>) -> Self {
+        match value {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_boolean()),
+        }
+    }
+
+    fn unbox_exact(value: &Value<'gc>) -> Option<Self> {
+        match *value {
+            Value::Bool(b) => Some(Some(b)),
+            _ => None,
+        }
+    }
+
+    fn boxed(self) -> Value<'gc> {
+        self.map_or(Value::Null, Value::Bool)
+    }
+}
+
+impl<'gc> Element<'gc> for Value<'gc> {
+    fn hole() -> Self {
+        Value::Null
+    }
+
+    fn unbox(value: Value<'gc>) -> Self {
+        value
+    }
+
+    /// Values stored in vectors of other types are always coerced.
+    fn unbox_exact(_value: &Value<'gc>) -> Option<Self> {
+        None
+    }
+
+    fn boxed(self) -> Value<'gc> {
+        self
+    }
+}
+
+/// Evaluate an expression with the `Vec` backing any kind of vector values.
+macro_rules! with_elements {
+    ($values:expr, $elements:ident => $body:expr) => {
+        match $values {
+            VectorValues::Int($elements) => $body,
+            VectorValues::Uint($elements) => $body,
+            VectorValues::Number($elements) => $body,
+            VectorValues::Boolean($elements) => $body,
+            VectorValues::Any($elements) => $body,
+        }
+    };
+}
+
+/// The values of a vector.
+///
+/// Vectors of `int`, `uint`, `Number` and `Boolean` store their values
+/// unboxed, while all other vectors store them as `Value`s.
+#[derive(Collect, Clone, Debug)]
+#[collect(no_drop)]
+enum VectorValues<'gc> {
+    Int(Vec<i32>),
+    Uint(Vec<u32>),
+    Number(Vec<f64>),
+    Boolean(Vec<Option<bool>>),
+    Any(Vec<Value<'gc>>),
+}
+
+impl<'gc> VectorValues<'gc> {
+    fn new(value_type: Option<Class<'gc>>, activation: &mut Activation<'_, 'gc>) -> Self {
+        let class_defs = activation.avm2().class_defs();
+
+        match value_type {
+            Some(value_type) if value_type == class_defs.int => Self::Int(Vec::new()),
+            Some(value_type) if value_type == class_defs.uint => Self::Uint(Vec::new()),
+            Some(value_type) if value_type == class_defs.number => Self::Number(Vec::new()),
+            Some(value_type) if value_type == class_defs.boolean => Self::Boolean(Vec::new()),
+            _ => Self::Any(Vec::new()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        with_elements!(self, elements => elements.len())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, index: usize) -> Option<Value<'gc>> {
+        with_elements!(self, elements => elements.get(index).cloned().map(Element::boxed))
+    }
+
+    fn set(&mut self, index: usize, value: Value<'gc>) {
+        with_elements!(self, elements => elements[index] = Element::unbox(value))
+    }
+
+    /// Store a value at an index up to the length of the values, if it needs
+    /// no coercion.
+    fn set_exact(&mut self, index: usize, value: &Value<'gc>) -> bool {
+        with_elements!(self, elements => match Element::unbox_exact(value) {
+            Some(element) if index < elements.len() => {
+                elements[index] = element;
+                true
+            }
+            Some(element) => {
+                elements.push(element);
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn push(&mut self, value: Value<'gc>) {
+        with_elements!(self, elements => elements.push(Element::unbox(value)))
+    }
+
+    fn insert(&mut self, index: usize, value: Value<'gc>) {
+        with_elements!(self, elements => elements.insert(index, Element::unbox(value)))
+    }
+
+    fn remove(&mut self, index: usize) -> Value<'gc> {
+        with_elements!(self, elements => elements.remove(index).boxed())
+    }
+
+    fn pop(&mut self) -> Option<Value<'gc>> {
+        with_elements!(self, elements => elements.pop().map(Element::boxed))
+    }
+
+    fn resize(&mut self, new_len: usize) {
+        with_elements!(self, elements => elements.resize_with(new_len, Element::hole))
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        with_elements!(self, elements => elements.reserve_exact(additional))
+    }
+
+    fn reverse(&mut self) {
+        with_elements!(self, elements => elements.reverse())
+    }
+
+    fn replace(&mut self, values: Vec<Value<'gc>>) {
+        with_elements!(self, elements => {
+            *elements = values.into_iter().map(Element::unbox).collect()
+        })
+    }
+
+    fn splice(&mut self, range: Range<usize>, replace_with: Vec<Value<'gc>>) -> Vec<Value<'gc>> {
+        with_elements!(self, elements => elements
+            .splice(range, replace_with.into_iter().map(Element::unbox))
+            .map(Element::boxed)
+            .collect())
+    }
+}
 
 /// The vector storage portion of a vector object.
 ///
@@ -23,7 +277,7 @@ use std::slice::SliceIndex;
 #[collect(no_drop)]
 pub struct VectorStorage<'gc> {
     /// The storage for vector values.
-    storage: Vec<Value<'gc>>,
+    storage: VectorValues<'gc>,
 
     /// Whether or not the array length is fixed.
     is_fixed: bool,
@@ -46,19 +300,14 @@ impl<'gc> VectorStorage<'gc> {
         value_type: Option<Class<'gc>>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Self {
-        let storage = Vec::new();
+        let mut storage = VectorValues::new(value_type, activation);
+        storage.resize(length);
 
-        let mut self_vec = VectorStorage {
+        VectorStorage {
             storage,
             is_fixed,
             value_type,
-        };
-
-        self_vec
-            .storage
-            .resize(length, self_vec.default(activation));
-
-        self_vec
+        }
     }
 
     pub fn check_fixed(&self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
@@ -77,10 +326,14 @@ impl<'gc> VectorStorage<'gc> {
     /// The values are assumed to already have been coerced to the value type
     /// given.
     pub fn from_values(
-        storage: Vec<Value<'gc>>,
+        values: Vec<Value<'gc>>,
         is_fixed: bool,
         value_type: Option<Class<'gc>>,
+        activation: &mut Activation<'_, 'gc>,
     ) -> Self {
+        let mut storage = VectorValues::new(value_type, activation);
+        storage.replace(values);
+
         VectorStorage {
             storage,
             is_fixed,
@@ -110,7 +363,7 @@ impl<'gc> VectorStorage<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
         self.check_fixed(activation)?;
-        self.storage.resize(new_length, self.default(activation));
+        self.storage.resize(new_length);
 
         Ok(())
     }
@@ -176,7 +429,7 @@ impl<'gc> VectorStorage<'gc> {
 
     /// Retrieve a value from the vector or `None` for out-of-bounds.
     pub fn get_optional(&self, index: usize) -> Option<Value<'gc>> {
-        self.storage.get(index).cloned()
+        self.storage.get(index)
     }
 
     /// Store a value into the vector.
@@ -195,17 +448,33 @@ impl<'gc> VectorStorage<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
         if !self.is_fixed && pos == self.length() {
-            self.storage.resize(pos + 1, self.default(activation));
+            self.storage.resize(pos + 1);
         }
 
-        if let Some(v) = self.storage.get_mut(pos) {
-            *v = value;
+        if pos < self.length() {
+            self.storage.set(pos, value);
             Ok(())
         } else {
             Err(make_error_1125(activation, pos, self.length()))
         }
     }
 
+    /// Store a value into the vector if it's already of the vector's value
+    /// type, without reentering the AVM2 runtime to coerce it.
+    ///
+    /// Values of object vectors are never stored this way. This returns
+    /// `false` without storing anything when the value needs coercion or the
+    /// position is outside the length of the vector, in which case the value
+    /// must be stored through the regular, coercing path instead.
+    pub fn set_exact(&mut self, pos: usize, value: &Value<'gc>) -> bool {
+        let length = self.length();
+        if pos > length || (pos == length && self.is_fixed) {
+            return false;
+        }
+
+        self.storage.set_exact(pos, value)
+    }
+
     /// Push a value to the end of the vector.
     ///
     /// This function returns an error if the vector is fixed.
@@ -361,23 +630,27 @@ impl<'gc> VectorStorage<'gc> {
         &'a self,
     ) -> impl DoubleEndedIterator<Item = Value<'gc>> + ExactSizeIterator<Item = Value<'gc>> + 'a
     {
-        self.storage.iter().cloned()
+        (0..self.length()).map(move |index| {
+            self.storage
+                .get(index)
+                .expect("Vector index should be in range")
+        })
     }
 
     /// Replace this vector's storage with new values.
+    ///
+    /// The values are assumed to already have been coerced to the vector's
+    /// value type.
     pub fn replace_storage(&mut self, new_storage: Vec<Value<'gc>>) {
-        self.storage = new_storage;
+        self.storage.replace(new_storage);
     }
 
-    pub fn splice<R>(
+    pub fn splice(
         &mut self,
-        range: R,
+        range: Range<usize>,
         replace_with: Vec<Value<'gc>>,
-    ) -> Result<Vec<Value<'gc>>, Error<'gc>>
-    where
-        R: Clone + SliceIndex<[Value<'gc>], Output = [Value<'gc>]> + RangeBounds<usize>,
-    {
+    ) -> Result<Vec<Value<'gc>>, Error<'gc>> {
         // NOTE: no fixed check here for bug compatibility
-        Ok(self.storage.splice(range, replace_with).collect())
+        Ok(self.storage.splice(range, replace_with))
     }
 }
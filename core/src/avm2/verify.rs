@@ -8,13 +8,17 @@ use crate::avm2::multiname::Multiname;
 use crate::avm2::op::Op;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::{Activation, Error, QName};
+use crate::persistent_cache::{ExceptionOffsets, VerifiedBytecode};
 use crate::string::AvmAtom;
 
+use fnv::FnvHasher;
 use gc_arena::{Collect, Gc};
 use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, MethodFlags as AbcMethodFlags, Multiname as AbcMultiname, Op as AbcOp,
+    Class as AbcClass, Exception as AbcException, Index, MethodBody as AbcMethodBody,
+    MethodFlags as AbcMethodFlags, Multiname as AbcMultiname, Op as AbcOp,
 };
 use swf::error::Error as AbcReadError;
 
@@ -87,8 +91,6 @@ pub fn verify_method<'gc>(
         return Err(make_error_1025(activation, 1 + param_count as u32));
     }
 
-    use swf::extensions::ReadSwfExt;
-
     if body.code.is_empty() {
         return Err(Error::AvmError(verify_error(
             activation,
@@ -100,6 +102,94 @@ pub fn verify_method<'gc>(
     let resolved_param_config = resolve_param_config(activation, method.signature())?;
     let resolved_return_type = resolve_return_type(activation, method.return_type)?;
 
+    let persistent_cache = activation
+        .context
+        .library
+        .library_for_movie(translation_unit.movie())
+        .and_then(|library| library.persistent_cache().cloned());
+    let cache_key = method_cache_key(&method, body);
+    let cached = persistent_cache
+        .as_ref()
+        .and_then(|cache| cache.method(cache_key))
+        .filter(|cached| cached.exceptions.len() == body.exceptions.len());
+
+    let (bytecode, new_exceptions) = if let Some(cached) = cached {
+        // The bytecode itself was verified before it was cached, but what it
+        // refers to may have changed.
+        for op in &cached.code {
+            verify_op_references(activation, method, op)?;
+        }
+
+        let mut new_exceptions = Vec::with_capacity(body.exceptions.len());
+        for (exception, offsets) in body.exceptions.iter().zip(&cached.exceptions) {
+            let (variable_name, target_class) =
+                resolve_exception_names(activation, method, exception)?;
+
+            new_exceptions.push(Exception {
+                from_offset: offsets.map_or(0, |offsets| offsets.from_offset),
+                to_offset: offsets.map_or(0, |offsets| offsets.to_offset),
+                target_offset: offsets
+                    .and_then(|offsets| offsets.target_offset)
+                    .unwrap_or(0),
+                variable_name,
+                target_class,
+            });
+        }
+
+        (cached, new_exceptions)
+    } else {
+        let (bytecode, new_exceptions) = verify_bytecode(activation, method)?;
+        if let Some(cache) = &persistent_cache {
+            cache.insert_method(cache_key, &bytecode);
+        }
+
+        (bytecode, new_exceptions)
+    };
+
+    // Record a target->sources mapping of all jump
+    // targets- this will be used in the optimizer.
+    let potential_jump_targets = potential_jump_targets(&bytecode);
+
+    let mut verified_code = Vec::new();
+    for abc_op in bytecode.code {
+        let resolved_op = resolve_op(activation, translation_unit, abc_op)?;
+
+        verified_code.push(resolved_op);
+    }
+
+    if activation.avm2().optimizer_enabled() {
+        crate::avm2::optimize::optimize(
+            activation,
+            method,
+            &mut verified_code,
+            &resolved_param_config,
+            resolved_return_type,
+            &new_exceptions,
+            potential_jump_targets,
+        )?;
+    }
+
+    Ok(VerifiedMethodInfo {
+        parsed_code: verified_code,
+        exceptions: new_exceptions,
+        param_config: resolved_param_config,
+        return_type: resolved_return_type,
+    })
+}
+
+/// Verify the control flow of a method's bytecode, and convert its jump and
+/// exception offsets from bytes to ops.
+fn verify_bytecode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+) -> Result<(VerifiedBytecode, Vec<Exception<'gc>>), Error<'gc>> {
+    use swf::extensions::ReadSwfExt;
+
+    let body = method
+        .body()
+        .expect("Cannot verify non-native method without body!");
+    let max_locals = body.num_locals;
+
     let mut seen_exception_indices = HashSet::new();
 
     let mut worklist = vec![0];
@@ -312,88 +402,30 @@ pub fn verify_method<'gc>(
                     }
                 }
 
-                AbcOp::FindDef { index } => {
-                    let multiname = method
-                        .translation_unit()
-                        .pool_maybe_uninitialized_multiname(activation, index)?;
-
-                    if multiname.has_lazy_component() {
-                        return Err(Error::AvmError(verify_error(
-                            activation,
-                            "Error #1078: Illegal opcode/multiname combination.",
-                            1078,
-                        )?));
-                    }
+                AbcOp::FindDef { .. }
+                | AbcOp::GetOuterScope { .. }
+                | AbcOp::AsType { .. }
+                | AbcOp::IsType { .. }
+                | AbcOp::Coerce { .. } => {
+                    verify_op_references(activation, method, &op)?;
                 }
 
                 AbcOp::GetLex { index } => {
-                    let multiname = method
-                        .translation_unit()
-                        .pool_maybe_uninitialized_multiname(activation, index)?;
-
-                    if multiname.has_lazy_component() {
-                        return Err(Error::AvmError(verify_error(
-                            activation,
-                            "Error #1078: Illegal opcode/multiname combination.",
-                            1078,
-                        )?));
-                    }
+                    verify_op_references(activation, method, &op)?;
 
                     // Split this `GetLex` into a `FindPropStrict` and a `GetProperty`.
                     // A `GetLex` is guaranteed to take up at least 2 bytes. We need
                     // one byte for the opcode and at least one byte for the multiname
-                    // index. Overwrite the op registered at the opcode byte with a
-                    // `FindPropStrict` op, and register a non-jumpable `GetProperty`
-                    // op at the next byte. This isn't the best way to do it, but it's
-                    // simpler than actually emitting ops and rewriting the jump offsets
-                    // to match.
+                    // index. Keep the `GetLex` op registered at the opcode byte (it's
+                    // resolved to a `FindPropStrict` op later), and register a
+                    // non-jumpable `GetProperty` op at the next byte. This isn't the
+                    // best way to do it, but it's simpler than actually emitting ops
+                    // and rewriting the jump offsets to match.
                     assert!(bytes_read > 1);
-                    byte_info[previous_position as usize] =
-                        ByteInfo::OpStart(AbcOp::FindPropStrict { index });
                     byte_info[(previous_position + 1) as usize] =
                         ByteInfo::OpStartNonJumpable(AbcOp::GetProperty { index });
                 }
 
-                AbcOp::GetOuterScope { index } => {
-                    if activation.outer().get(index as usize).is_none() {
-                        return Err(Error::AvmError(verify_error(
-                            activation,
-                            "Error #1019: Getscopeobject  is out of bounds.",
-                            1019,
-                        )?));
-                    }
-                }
-
-                AbcOp::AsType {
-                    type_name: name_index,
-                }
-                | AbcOp::IsType { index: name_index }
-                | AbcOp::Coerce { index: name_index } => {
-                    let multiname = method
-                        .translation_unit()
-                        .pool_maybe_uninitialized_multiname(activation, name_index)?;
-
-                    if multiname.has_lazy_component() {
-                        // This matches FP's error message
-                        return Err(make_error_1014(
-                            activation,
-                            Error1014Type::VerifyError,
-                            "[]".into(),
-                        ));
-                    }
-
-                    activation
-                        .domain()
-                        .get_class(activation.context, &multiname)
-                        .ok_or_else(|| {
-                            make_error_1014(
-                                activation,
-                                Error1014Type::VerifyError,
-                                multiname.to_qualified_name(activation.gc()),
-                            )
-                        })?;
-                }
-
                 _ => {}
             }
         }
@@ -411,73 +443,12 @@ pub fn verify_method<'gc>(
         }
     }
 
-    // Record a target->sources mapping of all jump
-    // targets- this will be used in the optimizer.
-    let mut potential_jump_targets: HashMap<i32, Vec<JumpSource>> = HashMap::new();
-
     // Handle exceptions
     let mut new_exceptions = Vec::new();
+    let mut exception_offsets = Vec::new();
     for (exception_index, exception) in body.exceptions.iter().enumerate() {
         // Resolve the variable name and target class.
-
-        let target_class = if exception.type_name.0 == 0 {
-            None
-        } else {
-            let pooled_type_name = method
-                .translation_unit()
-                .pool_maybe_uninitialized_multiname(activation, exception.type_name)?;
-
-            if pooled_type_name.has_lazy_component() {
-                // This matches FP's error message
-                return Err(make_error_1014(
-                    activation,
-                    Error1014Type::VerifyError,
-                    "[]".into(),
-                ));
-            }
-
-            let resolved_type = activation
-                .domain()
-                .get_class(activation.context, &pooled_type_name)
-                .ok_or_else(|| {
-                    make_error_1014(
-                        activation,
-                        Error1014Type::VerifyError,
-                        pooled_type_name.to_qualified_name(activation.gc()),
-                    )
-                })?;
-
-            Some(resolved_type)
-        };
-
-        let variable_name = if exception.variable_name.0 == 0 {
-            None
-        } else {
-            let pooled_variable_name = method
-                .translation_unit()
-                .pool_maybe_uninitialized_multiname(activation, exception.variable_name)?;
-
-            // FIXME: avmplus also seems to check the namespace(s)?
-            if pooled_variable_name.has_lazy_component()
-                || pooled_variable_name.is_attribute()
-                || pooled_variable_name.is_any_name()
-            {
-                // This matches FP's error message
-                return Err(make_error_1107(activation));
-            }
-
-            let namespaces = pooled_variable_name.namespace_set();
-
-            if namespaces.is_empty() {
-                // NOTE: avmplus segfaults here
-                panic!("Should have at least one namespace for QName in exception variable name");
-            }
-
-            let name = pooled_variable_name.local_name().expect("Just checked");
-
-            // avmplus uses the first namespace, regardless of how many namespaces there are.
-            Some(QName::new(namespaces[0], name))
-        };
+        let (variable_name, target_class) = resolve_exception_names(activation, method, exception)?;
 
         if !seen_exception_indices.contains(&exception_index) {
             // We need to push an exception because otherwise `newcatch` ops can try to
@@ -490,6 +461,7 @@ pub fn verify_method<'gc>(
                 variable_name,
                 target_class,
             });
+            exception_offsets.push(None);
             continue;
         }
 
@@ -542,15 +514,6 @@ pub fn verify_method<'gc>(
             .copied();
 
         // The large "NOTE" comment below is also relevant here
-        if let Some(new_target_offset) = maybe_new_target_offset {
-            // If this is a reachable target offset, insert it into the list
-            // of potential jump targets.
-            potential_jump_targets
-                .entry(new_target_offset)
-                .or_default()
-                .push(JumpSource::ExceptionTarget);
-        }
-
         let new_target_offset = maybe_new_target_offset.unwrap_or(0);
 
         // NOTE: That `unwrap_or` is definitely reachable, e.g. in a case where
@@ -575,9 +538,14 @@ pub fn verify_method<'gc>(
             variable_name,
             target_class,
         });
+        exception_offsets.push(Some(ExceptionOffsets {
+            from_offset: new_from_offset,
+            to_offset: new_to_offset,
+            target_offset: maybe_new_target_offset.map(|offset| offset as u32),
+        }));
     }
 
-    let mut adjust_jump_to_idx = |i, offset, is_jump| -> Result<(i32, i32), Error<'gc>> {
+    let mut adjust_jump_to_idx = |i, offset, is_jump| -> Result<i32, Error<'gc>> {
         const JUMP_INSTRUCTION_LENGTH: usize = 4;
 
         let mut byte_offset = idx_to_byte_offset
@@ -595,7 +563,7 @@ pub fn verify_method<'gc>(
             .copied()
             .ok_or_else(|| make_error_1021(activation))?; // See above comment
 
-        Ok((new_idx, new_idx - i - 1))
+        Ok(new_idx - i - 1)
     };
 
     // Adjust jump offsets from byte-based to idx-based
@@ -618,29 +586,231 @@ pub fn verify_method<'gc>(
             | AbcOp::IfStrictNe { offset }
             | AbcOp::IfTrue { offset }
             | AbcOp::Jump { offset } => {
-                let adjusted_result = adjust_jump_to_idx(i, *offset, true)?;
-                *offset = adjusted_result.1;
+                *offset = adjust_jump_to_idx(i, *offset, true)?;
+            }
+            AbcOp::LookupSwitch(ref mut lookup_switch) => {
+                lookup_switch.default_offset =
+                    adjust_jump_to_idx(i, lookup_switch.default_offset, false)?;
 
+                for case in lookup_switch.case_offsets.iter_mut() {
+                    *case = adjust_jump_to_idx(i, *case, false)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let bytecode = VerifiedBytecode {
+        code: new_code,
+        exceptions: exception_offsets,
+    };
+
+    Ok((bytecode, new_exceptions))
+}
+
+/// Compute the key of a method body in the persistent cache.
+///
+/// This covers everything `verify_bytecode` looks at, other than the
+/// constant pool (which `verify_op_references` checks on every load).
+fn method_cache_key(method: &BytecodeMethod<'_>, body: &AbcMethodBody) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write_usize(method.method().params.len());
+    hasher.write_u8(method.method().flags.bits());
+    hasher.write_u32(body.num_locals);
+    hasher.write_usize(body.exceptions.len());
+    for exception in &body.exceptions {
+        hasher.write_u32(exception.from_offset);
+        hasher.write_u32(exception.to_offset);
+        hasher.write_u32(exception.target_offset);
+    }
+    hasher.write(&body.code);
+    hasher.finish()
+}
+
+/// Verify the parts of an op which depend on the constant pool or the
+/// scope, rather than on the bytecode alone.
+fn verify_op_references<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    op: &AbcOp,
+) -> Result<(), Error<'gc>> {
+    match op {
+        AbcOp::FindDef { index } | AbcOp::GetLex { index } => {
+            let multiname = method
+                .translation_unit()
+                .pool_maybe_uninitialized_multiname(activation, *index)?;
+
+            if multiname.has_lazy_component() {
+                return Err(Error::AvmError(verify_error(
+                    activation,
+                    "Error #1078: Illegal opcode/multiname combination.",
+                    1078,
+                )?));
+            }
+        }
+
+        AbcOp::GetOuterScope { index } => {
+            if activation.outer().get(*index as usize).is_none() {
+                return Err(Error::AvmError(verify_error(
+                    activation,
+                    "Error #1019: Getscopeobject  is out of bounds.",
+                    1019,
+                )?));
+            }
+        }
+
+        AbcOp::AsType {
+            type_name: name_index,
+        }
+        | AbcOp::IsType { index: name_index }
+        | AbcOp::Coerce { index: name_index } => {
+            let multiname = method
+                .translation_unit()
+                .pool_maybe_uninitialized_multiname(activation, *name_index)?;
+
+            if multiname.has_lazy_component() {
+                // This matches FP's error message
+                return Err(make_error_1014(
+                    activation,
+                    Error1014Type::VerifyError,
+                    "[]".into(),
+                ));
+            }
+
+            activation
+                .domain()
+                .get_class(activation.context, &multiname)
+                .ok_or_else(|| {
+                    make_error_1014(
+                        activation,
+                        Error1014Type::VerifyError,
+                        multiname.to_qualified_name(activation.gc()),
+                    )
+                })?;
+        }
+
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Resolve the variable name and target class of an exception handler.
+fn resolve_exception_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    exception: &AbcException,
+) -> Result<(Option<QName<'gc>>, Option<Class<'gc>>), Error<'gc>> {
+    let target_class = if exception.type_name.0 == 0 {
+        None
+    } else {
+        let pooled_type_name = method
+            .translation_unit()
+            .pool_maybe_uninitialized_multiname(activation, exception.type_name)?;
+
+        if pooled_type_name.has_lazy_component() {
+            // This matches FP's error message
+            return Err(make_error_1014(
+                activation,
+                Error1014Type::VerifyError,
+                "[]".into(),
+            ));
+        }
+
+        let resolved_type = activation
+            .domain()
+            .get_class(activation.context, &pooled_type_name)
+            .ok_or_else(|| {
+                make_error_1014(
+                    activation,
+                    Error1014Type::VerifyError,
+                    pooled_type_name.to_qualified_name(activation.gc()),
+                )
+            })?;
+
+        Some(resolved_type)
+    };
+
+    let variable_name = if exception.variable_name.0 == 0 {
+        None
+    } else {
+        let pooled_variable_name = method
+            .translation_unit()
+            .pool_maybe_uninitialized_multiname(activation, exception.variable_name)?;
+
+        // FIXME: avmplus also seems to check the namespace(s)?
+        if pooled_variable_name.has_lazy_component()
+            || pooled_variable_name.is_attribute()
+            || pooled_variable_name.is_any_name()
+        {
+            // This matches FP's error message
+            return Err(make_error_1107(activation));
+        }
+
+        let namespaces = pooled_variable_name.namespace_set();
+
+        if namespaces.is_empty() {
+            // NOTE: avmplus segfaults here
+            panic!("Should have at least one namespace for QName in exception variable name");
+        }
+
+        let name = pooled_variable_name.local_name().expect("Just checked");
+
+        // avmplus uses the first namespace, regardless of how many namespaces there are.
+        Some(QName::new(namespaces[0], name))
+    };
+
+    Ok((variable_name, target_class))
+}
+
+/// Record a target->sources mapping of all jump targets in verified bytecode.
+fn potential_jump_targets(bytecode: &VerifiedBytecode) -> HashMap<i32, Vec<JumpSource>> {
+    let mut potential_jump_targets: HashMap<i32, Vec<JumpSource>> = HashMap::new();
+
+    for offsets in bytecode.exceptions.iter().flatten() {
+        if let Some(target_offset) = offsets.target_offset {
+            // If this is a reachable target offset, insert it into the list
+            // of potential jump targets.
+            potential_jump_targets
+                .entry(target_offset as i32)
+                .or_default()
+                .push(JumpSource::ExceptionTarget);
+        }
+    }
+
+    for (i, op) in bytecode.code.iter().enumerate() {
+        let i = i as i32;
+
+        match op {
+            AbcOp::IfEq { offset }
+            | AbcOp::IfFalse { offset }
+            | AbcOp::IfGe { offset }
+            | AbcOp::IfGt { offset }
+            | AbcOp::IfLe { offset }
+            | AbcOp::IfLt { offset }
+            | AbcOp::IfNe { offset }
+            | AbcOp::IfNge { offset }
+            | AbcOp::IfNgt { offset }
+            | AbcOp::IfNle { offset }
+            | AbcOp::IfNlt { offset }
+            | AbcOp::IfStrictEq { offset }
+            | AbcOp::IfStrictNe { offset }
+            | AbcOp::IfTrue { offset }
+            | AbcOp::Jump { offset } => {
                 potential_jump_targets
-                    .entry(adjusted_result.0)
+                    .entry(i + 1 + offset)
                     .or_default()
                     .push(JumpSource::JumpFrom(i));
             }
-            AbcOp::LookupSwitch(ref mut lookup_switch) => {
-                let adjusted_default = adjust_jump_to_idx(i, lookup_switch.default_offset, false)?;
-                lookup_switch.default_offset = adjusted_default.1;
-
+            AbcOp::LookupSwitch(lookup_switch) => {
                 potential_jump_targets
-                    .entry(adjusted_default.0)
+                    .entry(i + 1 + lookup_switch.default_offset)
                     .or_default()
                     .push(JumpSource::JumpFrom(i));
 
-                for case in lookup_switch.case_offsets.iter_mut() {
-                    let adjusted_case = adjust_jump_to_idx(i, *case, false)?;
-                    *case = adjusted_case.1;
-
+                for case in lookup_switch.case_offsets.iter() {
                     potential_jump_targets
-                        .entry(adjusted_case.0)
+                        .entry(i + 1 + case)
                         .or_default()
                         .push(JumpSource::JumpFrom(i));
                 }
@@ -649,31 +819,7 @@ pub fn verify_method<'gc>(
         }
     }
 
-    let mut verified_code = Vec::new();
-    for abc_op in new_code {
-        let resolved_op = resolve_op(activation, translation_unit, abc_op.clone())?;
-
-        verified_code.push(resolved_op);
-    }
-
-    if activation.avm2().optimizer_enabled() {
-        crate::avm2::optimize::optimize(
-            activation,
-            method,
-            &mut verified_code,
-            &resolved_param_config,
-            resolved_return_type,
-            &new_exceptions,
-            potential_jump_targets,
-        )?;
-    }
-
-    Ok(VerifiedMethodInfo {
-        parsed_code: verified_code,
-        exceptions: new_exceptions,
-        param_config: resolved_param_config,
-        return_type: resolved_return_type,
-    })
+    potential_jump_targets
 }
 
 pub fn resolve_param_config<'gc>(
@@ -1018,8 +1164,12 @@ fn resolve_op<'gc>(
 
             Op::FindPropStrict { multiname }
         }
-        AbcOp::GetLex { .. } => {
-            unreachable!("Verifier emits FindPropStrict and GetProperty instead of GetLex")
+        AbcOp::GetLex { index } => {
+            // The verifier registers a `GetProperty` op right after every
+            // `GetLex` op, so only the lookup is left to do here.
+            let multiname = pool_multiname(activation, translation_unit, index)?;
+
+            Op::FindPropStrict { multiname }
         }
         AbcOp::GetDescendants { index } => {
             let multiname = pool_multiname(activation, translation_unit, index)?;
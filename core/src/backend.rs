@@ -1,5 +1,7 @@
 pub mod audio;
+pub mod cache;
 pub mod log;
 pub mod navigator;
 pub mod storage;
 pub mod ui;
+pub mod worker;
@@ -3,6 +3,7 @@ use crate::{
     avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
     buffer::Substream,
     context::UpdateContext,
+    deterministic::Clock,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
 };
 use downcast_rs::Downcast;
@@ -386,7 +387,18 @@ impl<'gc> AudioManager<'gc> {
 
         // Update the position of sounds, and remove any completed sounds.
         context.audio_manager.sounds.retain(|sound| {
-            if let Some(pos) = context.audio.get_sound_position(sound.instance) {
+            let pos = match &sound.frame_locked {
+                Some(frame_locked) => {
+                    let pos = frame_locked.position(&context.clock);
+                    if pos.is_none() {
+                        // The sound has ended in virtual time, regardless of how far the backend got.
+                        context.audio.stop_sound(sound.instance);
+                    }
+                    pos
+                }
+                None => context.audio.get_sound_position(sound.instance),
+            };
+            if let Some(pos) = pos {
                 // Sounds still playing; update position for AVM1 sounds.
                 // AVM2 sounds do not update position and instead grab the position on demand.
 
@@ -443,6 +455,7 @@ impl<'gc> AudioManager<'gc> {
     pub fn start_sound(
         &mut self,
         audio: &mut dyn AudioBackend,
+        clock: &Clock,
         sound: SoundHandle,
         settings: &swf::SoundInfo,
         display_object: Option<DisplayObject<'gc>>,
@@ -450,6 +463,13 @@ impl<'gc> AudioManager<'gc> {
     ) -> Option<SoundInstanceHandle> {
         if self.sounds.len() < Self::MAX_SOUNDS {
             let handle = audio.start_sound(sound, settings).ok()?;
+            let frame_locked = if clock.is_virtual() {
+                audio
+                    .get_sound_duration(sound)
+                    .map(|duration| FrameLockedPosition::new(clock, settings, duration))
+            } else {
+                None
+            };
             let instance = SoundInstance {
                 sound: Some(sound),
                 instance: handle,
@@ -458,6 +478,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                frame_locked,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -557,6 +578,23 @@ impl<'gc> AudioManager<'gc> {
         audio.stop_all_sounds();
     }
 
+    /// Get the position of a sound instance in milliseconds.
+    ///
+    /// When the player runs deterministically, the position of event sounds is
+    /// derived from the virtual clock, so that it only changes as frames run.
+    pub fn sound_position(
+        &self,
+        audio: &dyn AudioBackend,
+        clock: &Clock,
+        instance: SoundInstanceHandle,
+    ) -> Option<f64> {
+        let sound = self.sounds.iter().find(|other| other.instance == instance);
+        match sound.and_then(|sound| sound.frame_locked.as_ref()) {
+            Some(frame_locked) => frame_locked.position(clock),
+            None => audio.get_sound_position(instance),
+        }
+    }
+
     pub fn is_sound_playing(&self, sound: SoundInstanceHandle) -> bool {
         self.sounds.iter().any(|other| other.instance == sound)
     }
@@ -583,6 +621,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                frame_locked: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -609,6 +648,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                frame_locked: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -824,6 +864,61 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The playback range of this sound in virtual time, when running deterministically.
+    #[collect(require_static)]
+    frame_locked: Option<FrameLockedPosition>,
+}
+
+/// The playback range of an event sound, used to report its position from the
+/// virtual clock instead of the audio backend.
+#[derive(Clone, Debug)]
+struct FrameLockedPosition {
+    /// The time since the SWF was launched at which the sound was started.
+    start_time: Duration,
+
+    /// The position in milliseconds within the sound at which each loop starts.
+    loop_start: f64,
+
+    /// The length in milliseconds of a single loop.
+    loop_length: f64,
+
+    num_loops: u16,
+}
+
+impl FrameLockedPosition {
+    /// The sample rate of `in_sample` and `out_sample` in sound info, regardless of the sound's own rate.
+    const SAMPLE_RATE: f64 = 44.1;
+
+    fn new(clock: &Clock, settings: &swf::SoundInfo, duration: f64) -> Self {
+        let loop_start = settings
+            .in_sample
+            .map_or(0.0, |sample| f64::from(sample) / Self::SAMPLE_RATE)
+            .min(duration);
+        let loop_end = settings
+            .out_sample
+            .map_or(duration, |sample| f64::from(sample) / Self::SAMPLE_RATE)
+            .clamp(loop_start, duration);
+        Self {
+            start_time: clock.elapsed(),
+            loop_start,
+            loop_length: loop_end - loop_start,
+            num_loops: settings.num_loops.max(1),
+        }
+    }
+
+    /// The position of the sound in milliseconds, or `None` if it has finished playing.
+    fn position(&self, clock: &Clock) -> Option<f64> {
+        let elapsed = clock
+            .elapsed()
+            .saturating_sub(self.start_time)
+            .as_secs_f64()
+            * 1000.0;
+        if elapsed >= self.loop_length * f64::from(self.num_loops) {
+            return None;
+        }
+        Some(self.loop_start + elapsed % self.loop_length)
+    }
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+/// Stores the persistent cache of movies between runs.
+///
+/// Each movie's cache is a single blob, stored under a key which is safe to
+/// use as a file name. Backends may drop blobs at any time, for example to
+/// stay within a size limit.
+pub trait CacheBackend {
+    fn get(&mut self, key: &str) -> Option<Vec<u8>>;
+
+    fn put(&mut self, key: &str, value: &[u8]);
+}
+
+/// A cache which only lasts as long as the player.
+#[derive(Default)]
+pub struct MemoryCacheBackend {
+    map: HashMap<String, Vec<u8>>,
+}
+
+impl MemoryCacheBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheBackend for MemoryCacheBackend {
+    fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        self.map.get(key).cloned()
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) {
+        self.map.insert(key.into(), value.to_vec());
+    }
+}
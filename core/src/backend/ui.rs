@@ -5,6 +5,8 @@ use downcast_rs::Downcast;
 use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
 use std::borrow::Cow;
+use std::sync::Arc;
+use swf::{Rectangle, Twips};
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
@@ -68,6 +70,13 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Grabs or releases the mouse pointer.
+    ///
+    /// While grabbed, the cursor is hidden and the frontend should report
+    /// the mouse movement with `PlayerEvent::MouseMotion`.
+    /// Returns `false` if the pointer couldn't be grabbed.
+    fn set_mouse_lock(&mut self, locked: bool) -> bool;
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
@@ -81,6 +90,11 @@ pub trait UiBackend: Downcast {
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
+    /// Returns the size of the screen showing the player, in device pixels like the viewport.
+    ///
+    /// Returns `None` if it's unknown, in which case the viewport size is used instead.
+    fn screen_size(&self) -> Option<(u32, u32)>;
+
     /// Displays a message about an error during root movie download.
     /// In particular, on web this can be a CORS error, which we can sidestep
     /// by providing a direct .swf link instead.
@@ -93,6 +107,18 @@ pub trait UiBackend: Downcast {
 
     fn close_virtual_keyboard(&self);
 
+    /// Enables or disables the input method editor (IME).
+    ///
+    /// The IME is enabled while an editable text field has focus,
+    /// unless the movie has disabled it using `IME.enabled`.
+    fn set_ime_enabled(&mut self, enabled: bool);
+
+    /// Informs the input method editor (IME) about the area occupied by the caret,
+    /// so that the candidate window can be positioned next to it.
+    ///
+    /// The area is given in viewport coordinates, the same as mouse positions in `PlayerEvent`.
+    fn set_ime_cursor_area(&mut self, area: Rectangle<Twips>);
+
     fn language(&self) -> LanguageIdentifier;
 
     fn display_unsupported_video(&self, url: Url);
@@ -133,7 +159,7 @@ impl_downcast!(UiBackend);
 
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MouseCursor {
     /// The default arrow icon.
     /// Equivalent to AS3 `MouseCursor.ARROW`.
@@ -150,6 +176,52 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A bitmap cursor registered by the movie with AS3 `Mouse.registerCursor`.
+    Custom(CustomCursor),
+}
+
+/// A mouse cursor made of one or more bitmaps.
+///
+/// Animated cursors are advanced by the player, which sends the cursor
+/// again with a different `frame` whenever the displayed image changes.
+#[derive(Clone, Debug)]
+pub struct CustomCursor {
+    /// The images of the cursor, shared between all frames of the animation.
+    pub frames: Arc<[CursorImage]>,
+
+    /// The index of the image to display.
+    pub frame: usize,
+
+    /// The position of the image (in pixels) which points at the mouse location.
+    pub hotspot: (u32, u32),
+}
+
+impl CustomCursor {
+    /// The image that should be currently displayed.
+    pub fn image(&self) -> &CursorImage {
+        &self.frames[self.frame]
+    }
+}
+
+impl PartialEq for CustomCursor {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.frames, &other.frames)
+            && self.frame == other.frame
+            && self.hotspot == other.hotspot
+    }
+}
+
+impl Eq for CustomCursor {}
+
+/// A single image of a custom mouse cursor.
+#[derive(Debug)]
+pub struct CursorImage {
+    pub width: u32,
+    pub height: u32,
+
+    /// Non-premultiplied RGBA pixels, row by row.
+    pub rgba: Vec<u8>,
 }
 
 /// UiBackend that does nothing.
@@ -170,6 +242,10 @@ impl UiBackend for NullUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
+    fn set_mouse_lock(&mut self, _locked: bool) -> bool {
+        false
+    }
+
     fn clipboard_content(&mut self) -> String {
         "".into()
     }
@@ -180,6 +256,10 @@ impl UiBackend for NullUiBackend {
         Ok(())
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     fn display_root_movie_download_failed_message(&self, _invalid_swf: bool) {}
 
     fn message(&self, _message: &str) {}
@@ -199,6 +279,10 @@ impl UiBackend for NullUiBackend {
 
     fn close_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, _enabled: bool) {}
+
+    fn set_ime_cursor_area(&mut self, _area: Rectangle<Twips>) {}
+
     fn language(&self) -> LanguageIdentifier {
         US_ENGLISH.clone()
     }
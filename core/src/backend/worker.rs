@@ -0,0 +1,311 @@
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// A job to be run by a `WorkerBackend`.
+pub type Job = Box<dyn FnOnce() + Send>;
+
+/// Runs CPU-heavy preloading work, such as shape tessellation and image
+/// decoding, away from the main thread.
+///
+/// Without a worker backend, this work is done on the main thread as it's
+/// needed.
+pub trait WorkerBackend {
+    /// Queues a job to be run on a worker thread.
+    fn spawn(&self, job: Job);
+
+    /// Whether the player should wait for results that aren't ready yet,
+    /// rather than carrying on without them until a worker finishes.
+    ///
+    /// This makes the player's output independent of how fast workers are,
+    /// at the cost of blocking the main thread.
+    fn waits_for_results(&self) -> bool {
+        false
+    }
+}
+
+impl dyn WorkerBackend + '_ {
+    /// Queues a function to be run on a worker thread, returning a task to
+    /// get its result from.
+    pub fn start<T: Send + 'static>(
+        &self,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> WorkerTask<T> {
+        let task = WorkerTask::new(Box::new(f), self.waits_for_results());
+        let shared = task.shared.clone();
+        self.spawn(Box::new(move || shared.run()));
+        task
+    }
+}
+
+/// The result of a function queued on a `WorkerBackend`.
+///
+/// If the result is needed before any worker started the function, it's run
+/// on the calling thread instead, so it's never run twice. Dropping the task
+/// before a worker started the function means it's never run at all.
+pub struct WorkerTask<T> {
+    shared: Arc<Shared<T>>,
+
+    /// Whether `try_join` waits for the result, see
+    /// `WorkerBackend::waits_for_results`.
+    waits_for_result: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    finished: Condvar,
+}
+
+enum State<T> {
+    /// No thread started the function yet.
+    Queued(Box<dyn FnOnce() -> T + Send>),
+
+    /// A worker is running the function.
+    Running,
+
+    /// The function has finished. The result is `None` if it panicked on a
+    /// worker, or if it was already taken.
+    Finished(Option<T>),
+}
+
+impl<T> WorkerTask<T> {
+    fn new(f: Box<dyn FnOnce() -> T + Send>, waits_for_result: bool) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State::Queued(f)),
+                finished: Condvar::new(),
+            }),
+            waits_for_result,
+        }
+    }
+
+    /// Returns the result of this task if a worker finished it, or gives the
+    /// task back otherwise, without blocking.
+    ///
+    /// If the backend waits for results, this is the same as `join`.
+    ///
+    /// Returns `Ok(None)` if the task panicked on a worker.
+    pub fn try_join(self) -> Result<Option<T>, Self> {
+        if self.waits_for_result {
+            return Ok(self.join());
+        }
+
+        let mut state = self.shared.lock();
+        if let State::Finished(result) = &mut *state {
+            let result = result.take();
+            drop(state);
+            return Ok(result);
+        }
+        drop(state);
+        Err(self)
+    }
+
+    /// Returns the result of this task, running it on this thread if no
+    /// worker started it yet, or waiting for the worker otherwise.
+    ///
+    /// Returns `None` if the task panicked on a worker.
+    pub fn join(self) -> Option<T> {
+        let mut state = self.shared.lock();
+        loop {
+            match mem::replace(&mut *state, State::Finished(None)) {
+                State::Queued(f) => {
+                    drop(state);
+                    return Some(f());
+                }
+                State::Running => {
+                    *state = State::Running;
+                    state = self
+                        .shared
+                        .finished
+                        .wait(state)
+                        .expect("Worker task lock poisoned");
+                }
+                State::Finished(result) => return result,
+            }
+        }
+    }
+}
+
+impl<T> Drop for WorkerTask<T> {
+    fn drop(&mut self) {
+        // Nobody can get the result anymore, so don't bother running it.
+        let mut state = self.shared.lock();
+        if let State::Queued(_) = &*state {
+            *state = State::Finished(None);
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().expect("Worker task lock poisoned")
+    }
+
+    /// Runs the function of this task on a worker, unless it was already run
+    /// on the thread which queued it.
+    fn run(&self) {
+        let f = {
+            let mut state = self.lock();
+            match mem::replace(&mut *state, State::Running) {
+                State::Queued(f) => f,
+                finished => {
+                    *state = finished;
+                    return;
+                }
+            }
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let (result, panic) = match result {
+            Ok(result) => (Some(result), None),
+            Err(panic) => (None, Some(panic)),
+        };
+        *self.lock() = State::Finished(result);
+        self.finished.notify_all();
+
+        // Let the worker backend see the panic, now that nobody waits for it.
+        if let Some(panic) = panic {
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    /// Keeps every job until it's run by hand.
+    #[derive(Default)]
+    struct ManualWorkers(RefCell<Vec<Job>>);
+
+    impl WorkerBackend for ManualWorkers {
+        fn spawn(&self, job: Job) {
+            self.0.borrow_mut().push(job);
+        }
+    }
+
+    impl ManualWorkers {
+        fn run_all(&self) {
+            for job in self.0.take() {
+                job();
+            }
+        }
+    }
+
+    /// Runs every job on a new thread.
+    struct ThreadWorkers;
+
+    impl WorkerBackend for ThreadWorkers {
+        fn spawn(&self, job: Job) {
+            thread::spawn(job);
+        }
+    }
+
+    /// Keeps every job until it's run by hand, but waits for results.
+    #[derive(Default)]
+    struct WaitingWorkers(ManualWorkers);
+
+    impl WorkerBackend for WaitingWorkers {
+        fn spawn(&self, job: Job) {
+            self.0.spawn(job);
+        }
+
+        fn waits_for_results(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn join_after_worker() {
+        let workers = ManualWorkers::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let task = (&workers as &dyn WorkerBackend).start({
+            let runs = runs.clone();
+            move || runs.fetch_add(1, Ordering::SeqCst) + 41
+        });
+
+        workers.run_all();
+        assert_eq!(task.join(), Some(41));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn join_before_worker() {
+        let workers = ManualWorkers::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let task = (&workers as &dyn WorkerBackend).start({
+            let runs = runs.clone();
+            move || runs.fetch_add(1, Ordering::SeqCst) + 41
+        });
+
+        // The function runs on this thread, and the worker skips it.
+        assert_eq!(task.join(), Some(41));
+        workers.run_all();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn join_waits_for_running_worker() {
+        let (started_sender, started) = std::sync::mpsc::channel();
+        let (finish, finish_receiver) = std::sync::mpsc::channel::<()>();
+        let task = (&ThreadWorkers as &dyn WorkerBackend).start(move || {
+            started_sender.send(()).unwrap();
+            finish_receiver.recv().unwrap();
+            "done"
+        });
+
+        // The worker is running the function now, so it's not run again here.
+        started.recv().unwrap();
+        thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(50));
+            finish.send(()).unwrap();
+        });
+        assert_eq!(task.join(), Some("done"));
+    }
+
+    #[test]
+    fn try_join() {
+        let workers = ManualWorkers::default();
+        let task = (&workers as &dyn WorkerBackend).start(|| 41);
+
+        let task = task.try_join().expect_err("task isn't finished yet");
+        workers.run_all();
+        assert_eq!(task.try_join().ok(), Some(Some(41)));
+    }
+
+    #[test]
+    fn try_join_waits_for_result() {
+        let workers = WaitingWorkers::default();
+        let task = (&workers as &dyn WorkerBackend).start(|| 41);
+
+        // The function runs on this thread, like with `join`.
+        assert_eq!(task.try_join().ok(), Some(Some(41)));
+    }
+
+    #[test]
+    fn dropped_task() {
+        let workers = ManualWorkers::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let task = (&workers as &dyn WorkerBackend).start({
+            let runs = runs.clone();
+            move || runs.fetch_add(1, Ordering::SeqCst)
+        });
+
+        drop(task);
+        workers.run_all();
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn panicking_worker() {
+        let workers = ManualWorkers::default();
+        let task = (&workers as &dyn WorkerBackend).start(|| -> u32 { panic!("oops") });
+
+        let job_panicked = panic::catch_unwind(AssertUnwindSafe(|| workers.run_all())).is_err();
+        assert!(job_panicked);
+        assert_eq!(task.join(), None);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
+use crate::memory::ExternalMemory;
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use ruffle_render::backend::RenderBackend;
@@ -199,6 +200,10 @@ pub struct BitmapData<'gc> {
     #[collect(require_static)]
     pixels: Vec<Color>,
 
+    /// The memory used by `pixels`, as reported to the GC arena.
+    #[collect(require_static)]
+    memory: ExternalMemory,
+
     width: u32,
     height: u32,
     transparency: bool,
@@ -250,13 +255,14 @@ mod wrapper {
     use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
     use crate::context::RenderContext;
     use crate::display_object::DisplayObjectWeak;
+    use crate::memory::ExternalMemory;
     use gc_arena::{Collect, GcCell, Mutation};
     use ruffle_render::backend::RenderBackend;
     use ruffle_render::bitmap::{BitmapHandle, PixelRegion, PixelSnapping};
     use ruffle_render::commands::CommandHandler;
     use std::cell::Ref;
 
-    use super::{copy_pixels_to_bitmapdata, BitmapData, DirtyState};
+    use super::{copy_pixels_to_bitmapdata, pixels_memory, BitmapData, DirtyState};
 
     /// A wrapper type that ensures that we always wait for a pending
     /// GPU -> CPU sync to complete (using `sync_handle`) before accessing
@@ -291,8 +297,10 @@ mod wrapper {
     pub struct BitmapDataWrapper<'gc>(GcCell<'gc, BitmapData<'gc>>);
 
     impl<'gc> BitmapDataWrapper<'gc> {
-        pub fn new(data: GcCell<'gc, BitmapData<'gc>>) -> Self {
-            BitmapDataWrapper(data)
+        /// Wraps `data`, reporting the memory used by its pixels to the GC arena.
+        pub fn new(mc: &Mutation<'gc>, mut data: BitmapData<'gc>) -> Self {
+            data.memory.attach(mc);
+            BitmapDataWrapper(GcCell::new(mc, data))
         }
 
         // Creates a dummy BitmapData with no pixels or handle, marked as disposed.
@@ -304,6 +312,7 @@ mod wrapper {
                 mc,
                 BitmapData {
                     pixels: Vec::new(),
+                    memory: ExternalMemory::default(),
                     width: 0,
                     height: 0,
                     transparency: false,
@@ -327,6 +336,7 @@ mod wrapper {
             let data = data.read();
             BitmapData {
                 pixels: data.pixels.clone(),
+                memory: pixels_memory(&data.pixels),
                 width: data.width,
                 height: data.height,
                 transparency: data.transparency,
@@ -560,13 +570,22 @@ impl std::fmt::Debug for BitmapData<'_> {
     }
 }
 
+/// Accounts for the memory used by `pixels`, to be reported to the GC arena.
+fn pixels_memory(pixels: &Vec<Color>) -> ExternalMemory {
+    let memory = ExternalMemory::default();
+    memory.set(pixels.capacity() * std::mem::size_of::<Color>());
+    memory
+}
+
 impl<'gc> BitmapData<'gc> {
     pub fn new(width: u32, height: u32, transparency: bool, fill_color: u32) -> Self {
+        let pixels = vec![
+            Color(fill_color).to_premultiplied_alpha(transparency);
+            width as usize * height as usize
+        ];
         Self {
-            pixels: vec![
-                Color(fill_color).to_premultiplied_alpha(transparency);
-                width as usize * height as usize
-            ],
+            memory: pixels_memory(&pixels),
+            pixels,
             width,
             height,
             transparency,
@@ -587,6 +606,7 @@ impl<'gc> BitmapData<'gc> {
         pixels: Vec<Color>,
     ) -> Self {
         Self {
+            memory: pixels_memory(&pixels),
             pixels,
             width,
             height,
@@ -608,7 +628,8 @@ impl<'gc> BitmapData<'gc> {
     pub fn dispose(&mut self) {
         self.width = 0;
         self.height = 0;
-        self.pixels.clear();
+        self.pixels = Vec::new();
+        self.memory.set(0);
         self.bitmap_handle = None;
         // There's no longer a handle to update
         self.dirty_state = DirtyState::Clean;
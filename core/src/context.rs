@@ -19,8 +19,10 @@ use crate::backend::{
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::UiBackend,
+    worker::WorkerBackend,
 };
 use crate::context_menu::ContextMenuState;
+use crate::deterministic::Clock;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -29,6 +31,7 @@ use crate::input::InputManager;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
+use crate::memory::GcScheduler;
 use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
@@ -39,6 +42,7 @@ use crate::string::{AvmString, StringContext};
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
+use crate::touch::TouchData;
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
@@ -115,6 +119,10 @@ pub struct UpdateContext<'gc> {
     /// The video backend, used for video decoding
     pub video: &'gc mut dyn VideoBackend,
 
+    /// The worker backend, used to preload shapes and bitmaps off the main
+    /// thread. Without one, this work is done on the main thread.
+    pub workers: Option<&'gc dyn WorkerBackend>,
+
     /// The RNG, used by the AVM `RandomNumber` opcode, `Math.random(),` and `random()`.
     pub rng: &'gc mut SmallRng,
 
@@ -123,6 +131,9 @@ pub struct UpdateContext<'gc> {
 
     pub mouse_data: &'gc mut MouseData<'gc>,
 
+    /// Touch points and gestures in progress.
+    pub touch_data: &'gc mut TouchData<'gc>,
+
     /// The input manager, tracking keys state.
     pub input: &'gc InputManager,
 
@@ -175,8 +186,11 @@ pub struct UpdateContext<'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'gc mut ExternalInterface<'gc>,
 
-    /// The instant at which the SWF was launched.
-    pub start_time: Instant,
+    /// The clock that scripts see, which is virtual when running deterministically.
+    pub clock: Clock,
+
+    /// Schedules garbage collection, and tracks collection requests from scripts.
+    pub gc_scheduler: &'gc mut GcScheduler,
 
     /// The instant at which the current update started.
     pub update_start: Instant,
@@ -261,7 +275,7 @@ impl<'gc> UpdateContext<'gc> {
         avm1_object: Option<Avm1Object<'gc>>,
     ) -> Option<SoundInstanceHandle> {
         self.audio_manager
-            .start_sound(self.audio, sound, settings, owner, avm1_object)
+            .start_sound(self.audio, &self.clock, sound, settings, owner, avm1_object)
     }
 
     pub fn attach_avm2_sound_channel(
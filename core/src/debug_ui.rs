@@ -3,6 +3,7 @@ mod avm2;
 mod display_object;
 mod domain;
 mod handle;
+mod memory;
 mod movie;
 
 use crate::context::{RenderContext, UpdateContext};
@@ -13,6 +14,7 @@ use crate::debug_ui::domain::DomainListWindow;
 use crate::debug_ui::handle::{
     AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle, DomainHandle,
 };
+use crate::debug_ui::memory::MemoryWindow;
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
 use crate::display_object::TDisplayObject;
 use crate::tag_utils::SwfMovie;
@@ -34,6 +36,7 @@ pub struct DebugUi {
     items_to_save: Vec<ItemToSave>,
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
+    memory: Option<MemoryWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
 }
 
@@ -48,6 +51,7 @@ pub enum Message {
     TrackTopLevelMovie,
     ShowKnownMovies,
     ShowDomains,
+    ShowMemory,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
 }
@@ -91,6 +95,12 @@ impl DebugUi {
             }
         }
 
+        if let Some(mut memory) = self.memory.take() {
+            if memory.show(egui_ctx, context) {
+                self.memory = Some(memory);
+            }
+        }
+
         if let Some(mut search) = self.display_object_search.take() {
             if search.show(egui_ctx, context, &mut messages, movie_offset) {
                 self.display_object_search = Some(search);
@@ -129,6 +139,9 @@ impl DebugUi {
                 Message::ShowDomains => {
                     self.domain_list = Some(Default::default());
                 }
+                Message::ShowMemory => {
+                    self.memory = Some(Default::default());
+                }
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
@@ -0,0 +1,92 @@
+use crate::context::UpdateContext;
+use egui::{Grid, Window};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct MemoryWindow {}
+
+impl MemoryWindow {
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+
+        Window::new("Memory")
+            .open(&mut keep_open)
+            .show(egui_ctx, |ui| {
+                let metrics = context.gc().metrics();
+                let scheduler = &*context.gc_scheduler;
+                let stats = scheduler.stats();
+
+                Grid::new("memory_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Total Memory");
+                    ui.label(format_bytes(metrics.total_allocation()));
+                    ui.end_row();
+
+                    ui.label("GC Heap");
+                    ui.label(format_bytes(metrics.total_gc_allocation()));
+                    ui.end_row();
+
+                    ui.label("External Memory");
+                    ui.label(format_bytes(metrics.total_external_allocation()))
+                        .on_hover_text("Bitmap pixels, audio and ByteArray contents");
+                    ui.end_row();
+
+                    ui.label("Collector");
+                    if scheduler.collecting() {
+                        ui.label("Collecting");
+                    } else {
+                        ui.label(format!(
+                            "Sleeping ({:.0}% to next cycle)",
+                            scheduler.imminence(metrics) * 100.0
+                        ));
+                    }
+                    ui.end_row();
+
+                    ui.label("Allocation Debt");
+                    ui.label(format_bytes(metrics.allocation_debt() as usize));
+                    ui.end_row();
+
+                    ui.label("Timing Factor");
+                    ui.label(format!("{:.3}", stats.timing_factor));
+                    ui.end_row();
+
+                    ui.label("Cycles");
+                    ui.label(stats.cycles.to_string());
+                    ui.end_row();
+
+                    ui.label("Last Pause");
+                    ui.label(format_duration(stats.last_pause));
+                    ui.end_row();
+
+                    ui.label("Longest Pause");
+                    ui.label(format_duration(stats.longest_pause));
+                    ui.end_row();
+                });
+
+                if ui.button("Collect Garbage").clicked() {
+                    context.gc_scheduler.request_collection(0.0);
+                }
+            });
+        keep_open
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
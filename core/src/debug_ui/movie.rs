@@ -2,9 +2,10 @@ use crate::character::Character;
 use crate::context::UpdateContext;
 use crate::debug_ui::{ItemToSave, Message};
 use crate::tag_utils::SwfMovie;
-use egui::{CollapsingHeader, Grid, Id, TextEdit, Ui, Window};
+use egui::{CollapsingHeader, Grid, Id, ScrollArea, TextEdit, Ui, Window};
 use std::sync::Arc;
-use swf::CharacterId;
+use swf::avm1::decompile::find_scripts;
+use swf::{CharacterId, Tag};
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone)]
@@ -12,6 +13,13 @@ enum Panel {
     #[default]
     Information,
     Characters,
+    Scripts,
+}
+
+#[derive(Debug)]
+struct DecompiledScript {
+    name: String,
+    source: String,
 }
 
 #[derive(Debug, Default)]
@@ -66,6 +74,11 @@ impl MovieListWindow {
 pub struct MovieWindow {
     open_panel: Panel,
     character_search: String,
+    script_search: String,
+    selected_script: Option<usize>,
+
+    /// All AVM1 scripts in the movie, decompiled when first viewed.
+    scripts: Option<Vec<DecompiledScript>>,
 }
 
 impl MovieWindow {
@@ -95,12 +108,17 @@ impl MovieWindow {
                             );
                         }
                     }
+
+                    if !movie.is_action_script_3() && !movie.data().is_empty() {
+                        ui.selectable_value(&mut self.open_panel, Panel::Scripts, "Scripts");
+                    }
                 });
                 ui.separator();
 
                 match self.open_panel {
                     Panel::Information => self.show_information(ui, &movie, messages),
                     Panel::Characters => self.show_characters(ui, context, &movie),
+                    Panel::Scripts => self.show_scripts(ui, &movie),
                 }
             });
         keep_open
@@ -151,6 +169,54 @@ impl MovieWindow {
             });
     }
 
+    fn show_scripts(&mut self, ui: &mut Ui, movie: &Arc<SwfMovie>) {
+        let scripts = self.scripts.get_or_insert_with(|| decompile_scripts(movie));
+
+        if scripts.is_empty() {
+            ui.weak("(No scripts)");
+            return;
+        }
+
+        TextEdit::singleline(&mut self.script_search)
+            .hint_text("Search for location or event")
+            .show(ui);
+
+        ui.horizontal_top(|ui| {
+            ScrollArea::vertical()
+                .id_salt(ui.id().with("script_list"))
+                .show(ui, |ui| {
+                    ui.vertical(|ui| {
+                        let search = self.script_search.to_ascii_lowercase();
+                        for (index, script) in scripts.iter().enumerate() {
+                            if !script.name.to_ascii_lowercase().contains(&search) {
+                                continue;
+                            }
+
+                            if ui
+                                .selectable_label(self.selected_script == Some(index), &script.name)
+                                .clicked()
+                            {
+                                self.selected_script = Some(index);
+                            }
+                        }
+                    });
+                });
+
+            ui.separator();
+
+            if let Some(script) = self.selected_script.and_then(|index| scripts.get(index)) {
+                ScrollArea::both()
+                    .id_salt(ui.id().with("script_source"))
+                    .show(ui, |ui| {
+                        TextEdit::multiline(&mut script.source.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY)
+                            .show(ui);
+                    });
+            }
+        });
+    }
+
     fn show_information(
         &mut self,
         ui: &mut Ui,
@@ -246,6 +312,31 @@ impl MovieWindow {
     }
 }
 
+/// Find and decompile every AVM1 script in a movie.
+///
+/// Tags that fail to parse are skipped, along with any scripts inside them.
+fn decompile_scripts(movie: &SwfMovie) -> Vec<DecompiledScript> {
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    let mut tags = vec![];
+    while !reader.get_ref().is_empty() {
+        let remaining = reader.get_ref().len();
+        match reader.read_tag() {
+            Ok(Tag::End) => break,
+            Ok(tag) => tags.push(tag),
+            Err(_) if reader.get_ref().len() < remaining => {}
+            Err(_) => break,
+        }
+    }
+
+    find_scripts(&tags, movie.version())
+        .iter()
+        .map(|script| DecompiledScript {
+            name: script.to_string(),
+            source: script.decompile(movie.version()),
+        })
+        .collect()
+}
+
 pub fn movie_name(movie: &Arc<SwfMovie>) -> String {
     format!("SWF {:p}", Arc::as_ptr(movie))
 }
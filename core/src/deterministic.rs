@@ -0,0 +1,113 @@
+//! Deterministic playback, used by tool-assisted speedruns and replay-based testing.
+//!
+//! In deterministic mode, the random number generator is seeded with a known value
+//! and scripts see time from a virtual clock, which only advances as frames run.
+//! Combined with a recorded input log (see `input_log`, available with the `input_log` feature), this allows a
+//! session to be reproduced exactly.
+
+use crate::locale::{self, Timezone};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Options for running a player deterministically.
+#[derive(Clone, Debug)]
+pub struct DeterministicOptions {
+    /// The seed of the random number generator used by `Math.random()`.
+    pub seed: u64,
+
+    /// The date and time that the virtual clock starts at.
+    pub start_date: DateTime<Utc>,
+
+    /// The timezone reported to scripts.
+    pub timezone: Timezone,
+}
+
+impl Default for DeterministicOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            start_date: locale::mock_date_time(),
+            timezone: locale::mock_timezone(),
+        }
+    }
+}
+
+/// The source of the time seen by scripts, through `getTimer()`, `Date` and the like.
+#[derive(Clone, Copy, Debug)]
+pub enum Clock {
+    /// The time of the host system.
+    Real {
+        /// The instant at which the SWF was launched.
+        start_time: Instant,
+
+        timezone: Timezone,
+    },
+
+    /// A virtual clock which only advances as the player runs frames.
+    Virtual {
+        /// The virtual time since the SWF was launched.
+        elapsed: Duration,
+
+        /// The date and time at which the SWF was launched.
+        start_date: DateTime<Utc>,
+
+        timezone: Timezone,
+    },
+}
+
+impl Clock {
+    pub fn real(timezone: Timezone) -> Self {
+        Self::Real {
+            start_time: Instant::now(),
+            timezone,
+        }
+    }
+
+    pub fn new_virtual(options: &DeterministicOptions) -> Self {
+        Self::Virtual {
+            elapsed: Duration::ZERO,
+            start_date: options.start_date,
+            timezone: options.timezone,
+        }
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, Self::Virtual { .. })
+    }
+
+    /// The time since the SWF was launched.
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            Self::Real { start_time, .. } => start_time.elapsed(),
+            Self::Virtual { elapsed, .. } => *elapsed,
+        }
+    }
+
+    /// The current date and time.
+    pub fn date_time(&self) -> DateTime<Utc> {
+        match self {
+            Self::Real { .. } => locale::get_current_date_time(),
+            Self::Virtual {
+                elapsed,
+                start_date,
+                ..
+            } => *start_date + *elapsed,
+        }
+    }
+
+    /// The local timezone.
+    pub fn timezone(&self) -> Timezone {
+        match self {
+            Self::Real { timezone, .. } | Self::Virtual { timezone, .. } => *timezone,
+        }
+    }
+
+    /// Advances a virtual clock by the given number of milliseconds.
+    /// The real clock advances by itself, so this does nothing to it.
+    pub fn advance(&mut self, dt: f64) {
+        if let Self::Virtual { elapsed, .. } = self {
+            *elapsed += Duration::from_secs_f64(dt.max(0.0) / 1000.0);
+        }
+    }
+}
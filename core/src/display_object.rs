@@ -52,9 +52,11 @@ pub use bitmap::{Bitmap, BitmapClass};
 pub use edit_text::LayoutDebugBoxesFlag;
 pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
-pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
+pub use interactive::{
+    lowest_common_ancestor, Avm2MousePick, InteractiveObject, TInteractiveObject,
+};
 pub use loader_display::LoaderDisplay;
-pub use morph_shape::MorphShape;
+pub use morph_shape::{interpolate_morph_shape, MorphShape};
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
 use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
@@ -18,7 +18,7 @@ use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
-use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
+use crate::events::{ClipEvent, ClipEventResult, ImeEvent, TextControlCode};
 use crate::font::{FontType, Glyph, TextRenderSettings};
 use crate::html;
 use crate::html::{
@@ -184,6 +184,13 @@ pub struct EditTextData<'gc> {
     /// Information related to the last click event inside this text field.
     #[collect(require_static)]
     last_click: Option<ClickEventData>,
+
+    /// The ongoing IME composition, if any.
+    ///
+    /// The composition is not a part of the text until it's committed,
+    /// it's only rendered over the text at the caret.
+    #[collect(require_static)]
+    ime_composition: Option<ImeComposition>,
 }
 
 impl EditTextData<'_> {
@@ -336,6 +343,7 @@ impl<'gc> EditText<'gc> {
                 is_fte: false,
                 restrict: EditTextRestrict::allow_all(),
                 last_click: None,
+                ime_composition: None,
                 layout_debug_boxes_flags: LayoutDebugBoxesFlag::empty(),
             },
         ));
@@ -405,6 +413,7 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc());
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.ime_composition = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -430,6 +439,7 @@ impl<'gc> EditText<'gc> {
                 write.flags.contains(EditTextFlag::CONDENSE_WHITE),
                 write.static_data.swf.version(),
             );
+            write.ime_composition = None;
             drop(write);
 
             self.relayout(context);
@@ -704,10 +714,10 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'gc>,
     ) {
-        self.0
-            .write(context.gc())
-            .text_spans
-            .replace_text(from, to, text);
+        let mut edit_text = self.0.write(context.gc());
+        edit_text.text_spans.replace_text(from, to, text);
+        edit_text.ime_composition = None;
+        drop(edit_text);
         self.relayout(context);
     }
 
@@ -1003,6 +1013,114 @@ impl<'gc> EditText<'gc> {
         self.render_lines(context, &edit_text.layout, |context, line| {
             self.render_layout_line(context, line);
         });
+        self.render_ime_composition(context, edit_text);
+    }
+
+    /// Render the ongoing IME composition over the text at the caret.
+    ///
+    /// The composition is drawn on the background of the text field and underlined,
+    /// using the format of the text it's going to be inserted into.
+    fn render_ime_composition(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        edit_text: &EditTextData<'gc>,
+    ) {
+        let (Some(composition), Some(selection)) =
+            (&edit_text.ime_composition, edit_text.selection)
+        else {
+            return;
+        };
+
+        let position = selection.start();
+        let layout = &edit_text.layout;
+        let Some(line) = layout
+            .find_line_index_by_position(position)
+            .or_else(|| layout.lines().len().checked_sub(1))
+            .and_then(|index| layout.lines().get(index))
+        else {
+            return;
+        };
+        let Some(lbox) = line
+            .boxes_iter()
+            .filter(|lbox| lbox.is_text_box())
+            .find(|lbox| position < lbox.end())
+            .or_else(|| line.boxes_iter().filter(|lbox| lbox.is_text_box()).last())
+        else {
+            return;
+        };
+        let Some((_, _, font, params, color)) =
+            lbox.as_renderable_text(edit_text.text_spans.displayed_text())
+        else {
+            return;
+        };
+
+        let x = if position < lbox.end() {
+            line.char_x_bounds(position).map(|(start, _)| start)
+        } else {
+            position
+                .checked_sub(1)
+                .and_then(|position| line.char_x_bounds(position))
+                .map(|(_, end)| end)
+        }
+        .unwrap_or_else(|| lbox.bounds().origin().x());
+
+        let baseline = font.get_baseline_for_height(params.height());
+        let descent = font.get_descent_for_height(params.height());
+        let height = baseline + descent;
+        let width = font.measure(&composition.text, params);
+
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::translate(x, lbox.bounds().origin().y()),
+            ..Default::default()
+        });
+
+        let background = context.transform_stack.transform().matrix
+            * Matrix::create_box(
+                width.to_pixels() as f32,
+                height.to_pixels() as f32,
+                Twips::ZERO,
+                Twips::ZERO,
+            );
+        let background_color = if edit_text.flags.contains(EditTextFlag::HAS_BACKGROUND) {
+            edit_text.background_color
+        } else {
+            Color::WHITE
+        };
+        context.commands.draw_rect(background_color, background);
+
+        let mut caret_x = width;
+        font.evaluate(
+            &composition.text,
+            self.text_transform(color, baseline - params.height()),
+            params,
+            |pos, transform, glyph: &Glyph, _advance, x| {
+                if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
+                    context.transform_stack.push(transform);
+                    context
+                        .commands
+                        .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                    context.transform_stack.pop();
+                }
+                if pos == composition.cursor {
+                    caret_x = x;
+                }
+            },
+        );
+
+        // The underline is drawn just below the baseline.
+        let mut underline = context.transform_stack.transform().matrix
+            * Matrix::create_box(
+                width.to_pixels() as f32,
+                1.0,
+                Twips::ZERO,
+                baseline + Twips::ONE,
+            );
+        let pixel_snapping = EditTextPixelSnapping::new(context.stage.quality());
+        pixel_snapping.apply(&mut underline);
+        context.commands.draw_line(color, underline);
+
+        self.render_caret(context, caret_x, height, color);
+        context.transform_stack.pop();
     }
 
     /// Render the black selection background.
@@ -1829,14 +1947,15 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn text_input(self, character: char, context: &mut UpdateContext<'gc>) {
-        if self.0.read().flags.contains(EditTextFlag::READ_ONLY)
-            || (character.is_control() && character != Self::INPUT_NEWLINE)
-            || self.available_chars() == 0
-        {
-            return;
-        }
+        self.input_text(&character.to_string(), context);
+    }
 
-        if !self.is_multiline() && character == Self::INPUT_NEWLINE {
+    /// Insert text entered by the user in place of the selection, as a single edit.
+    ///
+    /// Characters which can't be entered into this field are dropped, and the text
+    /// is cut short once the field reaches its maximum number of characters.
+    fn input_text(self, text: &str, context: &mut UpdateContext<'gc>) {
+        if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
             return;
         }
 
@@ -1844,21 +1963,26 @@ impl<'gc> EditText<'gc> {
             return;
         };
 
-        let Some(character) = self.0.read().restrict.to_allowed(character) else {
+        let is_multiline = self.is_multiline();
+        let available_chars = self.available_chars();
+        let edit_text = self.0.read();
+        let text: String = text
+            .chars()
+            .filter(|&c| !c.is_control() || (c == Self::INPUT_NEWLINE && is_multiline))
+            .filter_map(|c| edit_text.restrict.to_allowed(c))
+            .take(available_chars)
+            .collect();
+        drop(edit_text);
+        if text.is_empty() {
             return;
-        };
+        }
 
         if let Avm2Value::Object(target) = self.object2() {
-            let character_string = AvmString::new_utf8(context.gc(), character.to_string());
+            let text_string = AvmString::new_utf8(context.gc(), &text);
 
             let mut activation = Avm2Activation::from_nothing(context);
-            let text_evt = Avm2EventObject::text_event(
-                &mut activation,
-                "textInput",
-                character_string,
-                true,
-                true,
-            );
+            let text_evt =
+                Avm2EventObject::text_event(&mut activation, "textInput", text_string, true, true);
             Avm2::dispatch_event(activation.context, text_evt, target);
 
             if text_evt.as_event().unwrap().is_cancelled() {
@@ -1866,13 +1990,9 @@ impl<'gc> EditText<'gc> {
             }
         }
 
-        self.replace_text(
-            selection.start(),
-            selection.end(),
-            &WString::from_char(character),
-            context,
-        );
-        let new_pos = selection.start() + character.len_utf8();
+        let text = WString::from_utf8(&text);
+        self.replace_text(selection.start(), selection.end(), &text, context);
+        let new_pos = selection.start() + text.len();
         self.set_selection(Some(TextSelection::for_position(new_pos)), context.gc());
 
         let mut activation = Avm1Activation::from_nothing(
@@ -1884,6 +2004,73 @@ impl<'gc> EditText<'gc> {
         self.on_changed(&mut activation);
     }
 
+    /// Handle an event coming from the input method editor.
+    ///
+    /// The composition is displayed over the text until it's committed,
+    /// committed text is inserted as if it was typed by the user.
+    pub fn ime(self, event: ImeEvent, context: &mut UpdateContext<'gc>) {
+        if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
+            return;
+        }
+
+        match event {
+            ImeEvent::Preedit(text, cursor) => {
+                let composition = (!text.is_empty()).then(|| {
+                    // The IME reports the cursor as byte offsets into the UTF-8 composition.
+                    let cursor = cursor.map_or(text.len(), |(_, to)| to);
+                    ImeComposition {
+                        text: WString::from_utf8(&text),
+                        cursor: text.get(..cursor).map_or(0, |t| t.encode_utf16().count()),
+                    }
+                });
+                self.0.write(context.gc()).ime_composition = composition;
+                self.invalidate_cached_bitmap(context.gc());
+            }
+            ImeEvent::Commit(text) => {
+                self.0.write(context.gc()).ime_composition = None;
+                self.input_text(&text, context);
+
+                if self.movie().is_action_script_3() {
+                    let mut activation = Avm2Activation::from_nothing(context);
+                    let text = AvmString::new_utf8(activation.gc(), text);
+                    let ime_evt =
+                        Avm2EventObject::ime_event(&mut activation, "imeComposition", text);
+                    let ime_class = activation.avm2().classes().ime;
+                    Avm2::broadcast_event(activation.context, ime_evt, ime_class);
+                }
+            }
+        }
+
+        self.update_ime_cursor_area(context);
+    }
+
+    /// Inform the IME about the position of the caret, in viewport coordinates.
+    pub fn update_ime_cursor_area(self, context: &mut UpdateContext<'gc>) {
+        let Some(selection) = self.selection() else {
+            return;
+        };
+        let edit_text = self.0.read();
+        let position = selection.end();
+
+        // When the caret is at the end of the text, there is no character after it.
+        let bounds = edit_text.layout.char_bounds(position).or_else(|| {
+            let bounds = edit_text.layout.char_bounds(position.checked_sub(1)?)?;
+            Some(Rectangle {
+                x_min: bounds.x_max,
+                ..bounds
+            })
+        });
+        let Some(bounds) = bounds else {
+            return;
+        };
+
+        let matrix = context.stage.view_matrix()
+            * self.local_to_global_matrix()
+            * self.layout_to_local_matrix(&edit_text);
+        drop(edit_text);
+        context.ui.set_ime_cursor_area(matrix * bounds);
+    }
+
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc>) {
         if let Avm1Value::Object(object) = self.object() {
             activation.context.avm1.broadcaster_functions().initialize(
@@ -2818,6 +3005,11 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         focused: bool,
         _other: Option<InteractiveObject<'gc>>,
     ) {
+        if !focused {
+            // An unfinished composition is abandoned when the text field loses focus.
+            self.ime(ImeEvent::Preedit(String::new(), None), context);
+        }
+
         let is_avm1 = !self.movie().is_action_script_3();
         if !focused && is_avm1 {
             self.set_selection(None, context.gc());
@@ -2888,6 +3080,15 @@ struct EditTextStatic {
     initial_text: Option<WString>,
 }
 
+/// Text being composed using the input method editor.
+#[derive(Clone, Debug)]
+struct ImeComposition {
+    text: WString,
+
+    /// The position of the caret within the composition.
+    cursor: usize,
+}
+
 #[derive(Clone, Debug)]
 struct ClickEventData {
     /// The position in text resolved from click coordinates.
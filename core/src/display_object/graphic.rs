@@ -3,10 +3,10 @@ use crate::avm2::{
     Activation as Avm2Activation, ClassObject as Avm2ClassObject, Object as Avm2Object,
     StageObject as Avm2StageObject,
 };
+use crate::backend::worker::WorkerTask;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
 use crate::drawing::Drawing;
-use crate::library::MovieLibrarySource;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -14,7 +14,8 @@ use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
-use std::cell::{Ref, RefMut};
+use ruffle_render::tessellator::Mesh;
+use std::cell::{Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -49,14 +50,22 @@ impl<'gc> Graphic<'gc> {
         movie: Arc<SwfMovie>,
     ) -> Self {
         let library = context.library.library_for_movie(movie.clone()).unwrap();
+
+        // With workers, the shape is registered once it's first rendered.
+        let mesh_task = context.workers.and_then(|workers| {
+            library.tessellate_shape_on_worker(workers, context.renderer, &swf_shape)
+        });
+        let render_handle = if mesh_task.is_none() {
+            Some(library.register_shape(context.renderer, &swf_shape))
+        } else {
+            None
+        };
+
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone(),
-            render_handle: Some(
-                context
-                    .renderer
-                    .register_shape((&swf_shape).into(), &MovieLibrarySource { library }),
-            ),
+            render_handle: RefCell::new(render_handle),
+            mesh_task: RefCell::new(mesh_task),
             shape: swf_shape,
             movie,
         };
@@ -78,7 +87,8 @@ impl<'gc> Graphic<'gc> {
         let static_data = GraphicStatic {
             id: 0,
             bounds: Default::default(),
-            render_handle: None,
+            render_handle: RefCell::new(None),
+            mesh_task: RefCell::new(None),
             shape: swf::Shape {
                 version: 32,
                 id: 0,
@@ -115,6 +125,44 @@ impl<'gc> Graphic<'gc> {
     pub fn set_avm2_class(self, mc: &Mutation<'gc>, class: Avm2ClassObject<'gc>) {
         self.0.write(mc).class = Some(class);
     }
+
+    /// Returns the shape of this graphic registered with the renderer.
+    ///
+    /// If the shape was handed to a worker, it's registered once the worker
+    /// finished tessellating it. Until then, nothing is drawn on screen.
+    /// Offscreen drawing, such as for `BitmapData.draw` or a cached bitmap,
+    /// keeps what it draws, so it waits for the worker instead, or
+    /// tessellates the shape here if no worker got to it yet.
+    fn render_handle(self, context: &mut RenderContext) -> Option<ShapeHandle> {
+        let static_data = self.0.read().static_data;
+        let mut render_handle = static_data.render_handle.borrow_mut();
+        if static_data.mesh_task.borrow().is_some() {
+            let library = context
+                .library
+                .library_for_movie(static_data.movie.clone())?;
+            let mesh_task = static_data
+                .mesh_task
+                .take()
+                .expect("Mesh task was just checked");
+            let mesh = if context.is_offscreen {
+                mesh_task.join()
+            } else {
+                match mesh_task.try_join() {
+                    Ok(mesh) => mesh,
+                    Err(mesh_task) => {
+                        *static_data.mesh_task.borrow_mut() = Some(mesh_task);
+                        return None;
+                    }
+                }
+            };
+            *render_handle = Some(library.register_tessellated_shape(
+                context.renderer,
+                &static_data.shape,
+                mesh,
+            ));
+        }
+        render_handle.clone()
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
@@ -198,7 +246,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
-        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
+        } else if let Some(render_handle) = self.render_handle(context) {
             context
                 .commands
                 .render_shape(render_handle, context.transform_stack.transform())
@@ -278,7 +326,13 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 struct GraphicStatic {
     id: CharacterId,
     shape: swf::Shape,
-    render_handle: Option<ShapeHandle>,
+
+    /// The shape registered with the renderer. This is `None` until the
+    /// first render if the shape was handed to a worker.
+    render_handle: RefCell<Option<ShapeHandle>>,
+
+    /// The tessellation of the shape by a worker, until it's registered.
+    mesh_task: RefCell<Option<WorkerTask<Mesh>>>,
     bounds: Rectangle<Twips>,
     movie: Arc<SwfMovie>,
 }
@@ -28,7 +28,7 @@ use swf::{Point, Rectangle, Twips};
 /// `to`.
 ///
 /// If no such common ancestor exists, this returns `None`.
-fn lowest_common_ancestor<'gc>(
+pub fn lowest_common_ancestor<'gc>(
     from: DisplayObject<'gc>,
     to: DisplayObject<'gc>,
 ) -> Option<DisplayObject<'gc>> {
@@ -247,156 +247,164 @@ impl MorphShapeStatic {
     }
 
     fn build_morph_frame(&self, ratio: u16) -> Frame {
-        // Interpolate MorphShapes into a Shape.
-        use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
-        // Start shape is ratio 65535, end shape is ratio 0.
-        let b = f32::from(ratio) / 65535.0;
-        let a = 1.0 - b;
-        let fill_styles: Vec<FillStyle> = self
-            .start
-            .fill_styles
-            .iter()
-            .zip(self.end.fill_styles.iter())
-            .map(|(start, end)| lerp_fill(start, end, a, b))
-            .collect();
-        let line_styles: Vec<LineStyle> = self
-            .start
-            .line_styles
-            .iter()
-            .zip(self.end.line_styles.iter())
-            .map(|(start, end)| {
-                start
-                    .clone()
-                    .with_width(lerp_twips(start.width(), end.width(), a, b))
-                    .with_fill_style(lerp_fill(start.fill_style(), end.fill_style(), a, b))
-            })
-            .collect();
-
-        let mut shape = Vec::with_capacity(self.start.shape.len());
-        let mut start_iter = self.start.shape.iter();
-        let mut end_iter = self.end.shape.iter();
-        let mut start = start_iter.next();
-        let mut end = end_iter.next();
-        let mut start_x = Twips::ZERO;
-        let mut start_y = Twips::ZERO;
-        let mut end_x = Twips::ZERO;
-        let mut end_y = Twips::ZERO;
-        // TODO: Feels like this could be cleaned up a bit.
-        // We step through both the start records and end records, interpolating edges pairwise.
-        // Fill style/line style changes should only appear in the start records.
-        // However, StyleChangeRecord move_to can appear it both start and end records,
-        // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
-        // in case one side is missing a move_to; it will implicitly use the last pen position.
-        while let (Some(s), Some(e)) = (start, end) {
-            match (s, e) {
-                (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = start_change.clone();
-                    if start_change.move_to.is_some() || end_change.move_to.is_some() {
-                        if let Some(move_to) = &start_change.move_to {
-                            start_x = move_to.x;
-                            start_y = move_to.y;
-                        }
-                        if let Some(move_to) = &end_change.move_to {
-                            end_x = move_to.x;
-                            end_y = move_to.y;
-                        }
-                        style_change.move_to = Some(Point::new(
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    start = start_iter.next();
-                    end = end_iter.next();
-                }
-                (ShapeRecord::StyleChange(start_change), _) => {
-                    let mut style_change = start_change.clone();
+        let shape = interpolate_morph_shape(&self.start, &self.end, ratio);
+        let bounds = shape.shape_bounds.clone();
+        Frame {
+            shape_handle: None,
+            shape,
+            bounds,
+        }
+    }
+}
+
+/// Interpolates the start and end shapes of a morph shape into the shape shown at `ratio`.
+///
+/// A ratio of 0 gives the start shape, and a ratio of 65535 the end shape.
+pub fn interpolate_morph_shape(
+    start_shape: &swf::MorphShape,
+    end_shape: &swf::MorphShape,
+    ratio: u16,
+) -> swf::Shape {
+    use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
+    // `a` weighs the start shape and `b` the end shape.
+    let b = f32::from(ratio) / 65535.0;
+    let a = 1.0 - b;
+    let fill_styles: Vec<FillStyle> = start_shape
+        .fill_styles
+        .iter()
+        .zip(end_shape.fill_styles.iter())
+        .map(|(start, end)| lerp_fill(start, end, a, b))
+        .collect();
+    let line_styles: Vec<LineStyle> = start_shape
+        .line_styles
+        .iter()
+        .zip(end_shape.line_styles.iter())
+        .map(|(start, end)| {
+            start
+                .clone()
+                .with_width(lerp_twips(start.width(), end.width(), a, b))
+                .with_fill_style(lerp_fill(start.fill_style(), end.fill_style(), a, b))
+        })
+        .collect();
+
+    let mut shape = Vec::with_capacity(start_shape.shape.len());
+    let mut start_iter = start_shape.shape.iter();
+    let mut end_iter = end_shape.shape.iter();
+    let mut start = start_iter.next();
+    let mut end = end_iter.next();
+    let mut start_x = Twips::ZERO;
+    let mut start_y = Twips::ZERO;
+    let mut end_x = Twips::ZERO;
+    let mut end_y = Twips::ZERO;
+    // TODO: Feels like this could be cleaned up a bit.
+    // We step through both the start records and end records, interpolating edges pairwise.
+    // Fill style/line style changes should only appear in the start records.
+    // However, StyleChangeRecord move_to can appear it both start and end records,
+    // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
+    // in case one side is missing a move_to; it will implicitly use the last pen position.
+    while let (Some(s), Some(e)) = (start, end) {
+        match (s, e) {
+            (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
+                let mut style_change = start_change.clone();
+                if start_change.move_to.is_some() || end_change.move_to.is_some() {
                     if let Some(move_to) = &start_change.move_to {
                         start_x = move_to.x;
                         start_y = move_to.y;
-                        style_change.move_to = Some(Point::new(
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
                     }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    start = start_iter.next();
-                }
-                (_, ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = end_change.clone();
                     if let Some(move_to) = &end_change.move_to {
                         end_x = move_to.x;
                         end_y = move_to.y;
-                        style_change.move_to = Some(Point::new(
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
                     }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut end_x, &mut end_y, s);
-                    end = end_iter.next();
-                    continue;
+                    style_change.move_to = Some(Point::new(
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
                 }
-                _ => {
-                    shape.push(lerp_edges(
-                        Point::new(start_x, start_y),
-                        Point::new(end_x, end_y),
-                        s,
-                        e,
-                        a,
-                        b,
+                shape.push(ShapeRecord::StyleChange(style_change));
+                start = start_iter.next();
+                end = end_iter.next();
+            }
+            (ShapeRecord::StyleChange(start_change), _) => {
+                let mut style_change = start_change.clone();
+                if let Some(move_to) = &start_change.move_to {
+                    start_x = move_to.x;
+                    start_y = move_to.y;
+                    style_change.move_to = Some(Point::new(
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                update_pos(&mut start_x, &mut start_y, s);
+                start = start_iter.next();
+            }
+            (_, ShapeRecord::StyleChange(end_change)) => {
+                let mut style_change = end_change.clone();
+                if let Some(move_to) = &end_change.move_to {
+                    end_x = move_to.x;
+                    end_y = move_to.y;
+                    style_change.move_to = Some(Point::new(
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
                     ));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    Self::update_pos(&mut end_x, &mut end_y, e);
-                    start = start_iter.next();
-                    end = end_iter.next();
                 }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                update_pos(&mut end_x, &mut end_y, s);
+                end = end_iter.next();
+                continue;
+            }
+            _ => {
+                shape.push(lerp_edges(
+                    Point::new(start_x, start_y),
+                    Point::new(end_x, end_y),
+                    s,
+                    e,
+                    a,
+                    b,
+                ));
+                update_pos(&mut start_x, &mut start_y, s);
+                update_pos(&mut end_x, &mut end_y, e);
+                start = start_iter.next();
+                end = end_iter.next();
             }
         }
+    }
 
-        let styles = ShapeStyles {
-            fill_styles,
-            line_styles,
-        };
-
-        let bounds = ruffle_render::shape_utils::calculate_shape_bounds(&shape);
-        let shape = swf::Shape {
-            version: 4,
-            id: 0,
-            shape_bounds: bounds.clone(),
-            edge_bounds: bounds.clone(),
-            flags: swf::ShapeFlag::HAS_SCALING_STROKES,
-            styles,
-            shape,
-        };
-
-        Frame {
-            shape_handle: None,
-            shape,
-            bounds,
-        }
+    let styles = ShapeStyles {
+        fill_styles,
+        line_styles,
+    };
+
+    let bounds = ruffle_render::shape_utils::calculate_shape_bounds(&shape);
+    swf::Shape {
+        version: 4,
+        id: 0,
+        shape_bounds: bounds.clone(),
+        edge_bounds: bounds,
+        flags: swf::ShapeFlag::HAS_SCALING_STROKES,
+        styles,
+        shape,
     }
+}
 
-    fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
-        use swf::ShapeRecord;
-        match record {
-            ShapeRecord::StraightEdge { delta } => {
-                *x += delta.dx;
-                *y += delta.dy;
-            }
-            ShapeRecord::CurvedEdge {
-                control_delta,
-                anchor_delta,
-            } => {
-                *x += control_delta.dx + anchor_delta.dx;
-                *y += control_delta.dy + anchor_delta.dy;
-            }
-            ShapeRecord::StyleChange(ref style_change) => {
-                if let Some(move_to) = &style_change.move_to {
-                    *x = move_to.x;
-                    *y = move_to.y;
-                }
+fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
+    use swf::ShapeRecord;
+    match record {
+        ShapeRecord::StraightEdge { delta } => {
+            *x += delta.dx;
+            *y += delta.dy;
+        }
+        ShapeRecord::CurvedEdge {
+            control_delta,
+            anchor_delta,
+        } => {
+            *x += control_delta.dx + anchor_delta.dx;
+            *y += control_delta.dy + anchor_delta.dy;
+        }
+        ShapeRecord::StyleChange(ref style_change) => {
+            if let Some(move_to) = &style_change.move_to {
+                *x = move_to.x;
+                *y = move_to.y;
             }
         }
     }
@@ -3466,6 +3466,16 @@ impl<'gc> MovieClipData<'gc> {
 
 // Preloading of definition tags
 impl<'gc, 'a> MovieClipData<'gc> {
+    /// Starts decoding a bitmap character on a worker, if the player has any.
+    fn prepare_bitmap(&self, context: &mut UpdateContext<'gc>, id: CharacterId) {
+        if let Some(workers) = context.workers {
+            context
+                .library
+                .library_for_movie_mut(self.movie())
+                .prepare_bitmap(workers, id);
+        }
+    }
+
     #[inline]
     fn define_bits_lossless(
         &mut self,
@@ -3492,6 +3502,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
                     avm2_bitmapdata_class: GcCell::new(context.gc_context, BitmapClass::NoSubclass),
                 },
             );
+        self.prepare_bitmap(context, define_bits_lossless.id);
         Ok(())
     }
 
@@ -3640,6 +3651,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
                     avm2_bitmapdata_class: GcCell::new(context.gc_context, BitmapClass::NoSubclass),
                 },
             );
+        self.prepare_bitmap(context, id);
         Ok(())
     }
 
@@ -3668,6 +3680,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
                     avm2_bitmapdata_class: GcCell::new(context.gc_context, BitmapClass::NoSubclass),
                 },
             );
+        self.prepare_bitmap(context, id);
         Ok(())
     }
 
@@ -3702,6 +3715,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
                     avm2_bitmapdata_class: GcCell::new(context.gc_context, BitmapClass::NoSubclass),
                 },
             );
+        self.prepare_bitmap(context, id);
         Ok(())
     }
 
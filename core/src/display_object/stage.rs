@@ -103,6 +103,13 @@ pub struct StageData<'gc> {
     /// Whether to allow the stage's displayState to be changed.
     allow_fullscreen: bool,
 
+    /// Whether the mouse is locked, hiding the cursor and reporting only relative motion.
+    mouse_lock: bool,
+
+    /// The area of the stage which is scaled to fill the screen in full screen mode.
+    #[collect(require_static)]
+    full_screen_source_rect: Option<Rectangle<Twips>>,
+
     /// Whether or not a RENDER event should be dispatched on the next render
     invalidated: bool,
 
@@ -178,6 +185,8 @@ impl<'gc> Stage<'gc> {
                 align: Default::default(),
                 forced_align: false,
                 allow_fullscreen: true,
+                mouse_lock: false,
+                full_screen_source_rect: None,
                 use_bitmap_downsampling: false,
                 view_bounds: Default::default(),
                 window_mode: Default::default(),
@@ -211,7 +220,6 @@ impl<'gc> Stage<'gc> {
             .unwrap_or(Matrix::ZERO)
     }
 
-    #[allow(dead_code)]
     pub fn view_matrix(self) -> Matrix {
         self.0.read().viewport_matrix
     }
@@ -397,10 +405,63 @@ impl<'gc> Stage<'gc> {
 
         if result.is_ok() {
             self.0.write(context.gc()).display_state = display_state;
+            if !self.is_fullscreen() {
+                // Mouse lock is only available in full screen.
+                self.set_mouse_lock(context, false);
+            }
+            if self.full_screen_source_rect().is_some() {
+                self.build_matrices(context);
+            }
             self.fire_fullscreen_event(context);
         }
     }
 
+    /// Get the area of the stage displayed in full screen mode.
+    pub fn full_screen_source_rect(self) -> Option<Rectangle<Twips>> {
+        self.0.read().full_screen_source_rect
+    }
+
+    /// Get the full screen source rectangle, if the stage is in full screen mode.
+    fn active_full_screen_source_rect(self) -> Option<Rectangle<Twips>> {
+        let stage = self.0.read();
+        stage
+            .full_screen_source_rect
+            .filter(|_| Self::is_fullscreen_state(stage.display_state))
+    }
+
+    /// Set the area of the stage which is scaled to fill the screen in full screen mode.
+    /// Empty rectangles display the whole stage as usual.
+    pub fn set_full_screen_source_rect(
+        self,
+        context: &mut UpdateContext<'gc>,
+        rect: Option<Rectangle<Twips>>,
+    ) {
+        let rect = rect.filter(|rect| rect.width() > Twips::ZERO && rect.height() > Twips::ZERO);
+        self.0.write(context.gc()).full_screen_source_rect = rect;
+        if self.is_fullscreen() {
+            self.build_matrices(context);
+        }
+    }
+
+    /// Get whether the mouse is locked.
+    pub fn mouse_lock(self) -> bool {
+        self.0.read().mouse_lock
+    }
+
+    /// Lock or release the mouse.
+    ///
+    /// While locked, the cursor is hidden and the mouse position doesn't change,
+    /// but its movement is still reported in `MouseEvent.movementX/Y`.
+    pub fn set_mouse_lock(self, context: &mut UpdateContext<'gc>, mouse_lock: bool) {
+        if mouse_lock == self.mouse_lock() {
+            return;
+        }
+
+        if context.ui.set_mouse_lock(mouse_lock) || !mouse_lock {
+            self.0.write(context.gc()).mouse_lock = mouse_lock;
+        }
+    }
+
     /// Get the stage alignment.
     pub fn align(self) -> StageAlign {
         self.0.read().align
@@ -464,6 +525,11 @@ impl<'gc> Stage<'gc> {
 
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self) -> bool {
+        // The area around the full screen source rectangle is always letterboxed.
+        if self.active_full_screen_source_rect().is_some() {
+            return true;
+        }
+
         // Only enable letterbox in the default `ShowAll` scale mode.
         // If content changes the scale mode or alignment, it signals that it is size-aware.
         // For example, `NoScale` is used to make responsive layouts; don't letterbox over it.
@@ -492,6 +558,46 @@ impl<'gc> Stage<'gc> {
         } else {
             stage.movie_size
         };
+
+        let viewport_width = viewport_size.width as f64;
+        let viewport_height = viewport_size.height as f64;
+
+        // In full screen, the source rectangle replaces the stage area scaled into the viewport.
+        let source_rect = stage
+            .full_screen_source_rect
+            .filter(|_| Self::is_fullscreen_state(stage.display_state));
+        if let Some(source_rect) = source_rect {
+            let rect_x = source_rect.x_min.to_pixels();
+            let rect_y = source_rect.y_min.to_pixels();
+            let rect_width = source_rect.width().to_pixels();
+            let rect_height = source_rect.height().to_pixels();
+
+            if scale_mode == StageScaleMode::NoScale {
+                stage.stage_size = (rect_width.round() as u32, rect_height.round() as u32);
+            }
+
+            // The rectangle keeps its aspect ratio and is centered on the screen.
+            let scale = (viewport_width / rect_width).min(viewport_height / rect_height);
+            let tx = (viewport_width - rect_width * scale) / 2.0 - rect_x * scale;
+            let ty = (viewport_height - rect_height * scale) / 2.0 - rect_y * scale;
+            stage.viewport_matrix = Matrix {
+                a: scale as f32,
+                b: 0.0,
+                c: 0.0,
+                d: scale as f32,
+                tx: Twips::from_pixels(tx),
+                ty: Twips::from_pixels(ty),
+            };
+            stage.view_bounds = source_rect;
+
+            let stage_size_changed = prev_stage_size != stage.stage_size;
+            drop(stage);
+            if scale_mode == StageScaleMode::NoScale && stage_size_changed {
+                self.fire_resize_event(context);
+            }
+            return;
+        }
+
         let stage_size_changed = prev_stage_size != stage.stage_size;
 
         // Create view matrix to scale stage into viewport area.
@@ -499,9 +605,6 @@ impl<'gc> Stage<'gc> {
         let movie_width = movie_width as f64;
         let movie_height = movie_height as f64;
 
-        let viewport_width = viewport_size.width as f64;
-        let viewport_height = viewport_size.height as f64;
-
         let movie_aspect = movie_width / movie_height;
         let viewport_aspect = viewport_width / viewport_height;
 
@@ -604,16 +707,24 @@ impl<'gc> Stage<'gc> {
 
         let view_matrix = self.0.read().viewport_matrix;
 
-        let (movie_width, movie_height) = self.0.read().movie_size;
-        let movie_width = movie_width as f32 * view_matrix.a;
-        let movie_height = movie_height as f32 * view_matrix.d;
+        // The shown area is either the full screen source rectangle or the whole movie.
+        let shown_area = self.active_full_screen_source_rect().unwrap_or_else(|| {
+            let (movie_width, movie_height) = self.0.read().movie_size;
+            Rectangle {
+                x_min: Twips::ZERO,
+                y_min: Twips::ZERO,
+                x_max: Twips::from_pixels(movie_width.into()),
+                y_max: Twips::from_pixels(movie_height.into()),
+            }
+        });
+        let shown_area = view_matrix * shown_area;
 
-        let margin_left = view_matrix.tx.to_pixels() as f32;
-        let margin_right = viewport_width - movie_width - margin_left;
-        let margin_top = view_matrix.ty.to_pixels() as f32;
-        let margin_bottom = viewport_height - movie_height - margin_top;
+        let margin_left = shown_area.x_min.to_pixels() as f32;
+        let margin_right = viewport_width - shown_area.x_max.to_pixels() as f32;
+        let margin_top = shown_area.y_min.to_pixels() as f32;
+        let margin_bottom = viewport_height - shown_area.y_max.to_pixels() as f32;
 
-        // Letterboxing only occurs in `StageScaleMode::ShowAll`, and they would only appear on the top+bottom or left+right.
+        // The shown area keeps its aspect ratio, so letterboxes would only appear on the top+bottom or left+right.
         if margin_top + margin_bottom > margin_left + margin_right {
             // Top + bottom
             if margin_top > 0.0 {
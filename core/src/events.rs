@@ -1,7 +1,7 @@
 use crate::display_object::InteractiveObject;
 use swf::ClipEventFlag;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
@@ -27,6 +27,15 @@ pub enum PlayerEvent {
         index: Option<usize>,
     },
     MouseLeave,
+
+    /// Relative motion of the mouse, in pixels.
+    ///
+    /// This is only used while the mouse is locked with `Stage.mouseLock`,
+    /// when the cursor position no longer changes.
+    MouseMotion {
+        dx: f64,
+        dy: f64,
+    },
     MouseWheel {
         delta: MouseWheelDelta,
     },
@@ -42,10 +51,47 @@ pub enum PlayerEvent {
     TextControl {
         code: TextControlCode,
     },
+    Ime(ImeEvent),
+
+    /// A touch point has come into contact with the screen.
+    ///
+    /// Frontends are expected to also send mouse events for the primary touch point,
+    /// as touch input is interpreted as mouse input when multitouch is disabled.
+    TouchBegin {
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: Option<f64>,
+    },
+    TouchMove {
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: Option<f64>,
+    },
+    TouchEnd {
+        id: i32,
+        x: f64,
+        y: f64,
+    },
     FocusGained,
     FocusLost,
 }
 
+/// An event coming from the input method editor (IME).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The composition (preedit) string has changed.
+    ///
+    /// The second parameter is the byte range of the cursor within the
+    /// composition string. When it's `None`, the cursor is placed at its end.
+    /// An empty string means that the composition has been cleared.
+    Preedit(String, Option<(usize, usize)>),
+
+    /// The composition has been finished and the text should be inserted.
+    Commit(String),
+}
+
 /// The distance scrolled by the mouse wheel.
 #[derive(Debug, Clone, Copy)]
 pub enum MouseWheelDelta {
@@ -763,8 +809,8 @@ impl ButtonKeyCode {
         })
     }
 
-    pub fn from_player_event(event: PlayerEvent) -> Option<Self> {
-        match event {
+    pub fn from_player_event(event: &PlayerEvent) -> Option<Self> {
+        match *event {
             // ASCII characters convert directly to keyPress button events.
             PlayerEvent::TextInput { codepoint }
                 if codepoint as u32 >= 32 && codepoint as u32 <= 126 =>
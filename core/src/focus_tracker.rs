@@ -110,6 +110,7 @@ impl<'gc> FocusTracker<'gc> {
         if InteractiveObject::option_ptr_eq(old, new) {
             // Re-open the keyboard when the user clicked an already focused text field.
             self.update_virtual_keyboard(context);
+            self.update_ime(context);
             return;
         }
 
@@ -198,6 +199,20 @@ impl<'gc> FocusTracker<'gc> {
         }
 
         self.update_virtual_keyboard(context);
+        self.update_ime(context);
+    }
+
+    /// Enable the IME when an editable text field is focused, unless it has been disabled by the movie.
+    pub fn update_ime(&self, context: &mut UpdateContext<'gc>) {
+        let text_field = self
+            .get_as_edit_text()
+            .filter(|text_field| text_field.is_editable());
+        if let Some(text_field) = text_field.filter(|_| context.system.ime_enabled) {
+            context.ui.set_ime_enabled(true);
+            text_field.update_ime_cursor_area(context);
+        } else {
+            context.ui.set_ime_enabled(false);
+        }
     }
 
     fn update_virtual_keyboard(&self, context: &mut UpdateContext<'gc>) {
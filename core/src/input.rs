@@ -127,22 +127,30 @@ impl InputManager {
     }
 
     fn update_last_click(&mut self, x: f64, y: f64, index: Option<usize>) {
-        let time = Utc::now();
-        let index = index.unwrap_or_else(|| {
-            let Some(last_click) = self.last_click.as_ref() else {
-                return 0;
-            };
-
-            // TODO Make this configurable as "double click delay" and "double click distance"
-            if (time - last_click.time).abs() < TimeDelta::milliseconds(500)
-                && last_click.distance_squared_to(x, y) < 4.0
-            {
-                last_click.index + 1
-            } else {
-                0
-            }
+        let index = index.unwrap_or_else(|| self.click_index(x, y));
+        self.last_click = Some(ClickEventData {
+            x,
+            y,
+            time: Utc::now(),
+            index,
         });
-        self.last_click = Some(ClickEventData { x, y, time, index });
+    }
+
+    /// The index of a click at the given position if it happened now,
+    /// i.e. 0 for a single click, 1 for a double click and so on.
+    pub fn click_index(&self, x: f64, y: f64) -> usize {
+        let Some(last_click) = self.last_click.as_ref() else {
+            return 0;
+        };
+
+        // TODO Make this configurable as "double click delay" and "double click distance"
+        if (Utc::now() - last_click.time).abs() < TimeDelta::milliseconds(500)
+            && last_click.distance_squared_to(x, y) < 4.0
+        {
+            last_click.index + 1
+        } else {
+            0
+        }
     }
 
     pub fn is_key_down(&self, key: KeyCode) -> bool {
@@ -0,0 +1,431 @@
+//! Recording and replaying of player input.
+//!
+//! Input logs use the same format as the input files of the test suite: a list of
+//! [`AutomatedEvent`]s, where each frame's worth of input is terminated by a
+//! [`AutomatedEvent::Wait`].
+
+use crate::events::{
+    GamepadButton, ImeEvent, KeyCode, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode,
+};
+use ruffle_input_format::{
+    GamepadButton as InputGamepadButton, MouseButton as InputMouseButton, MousePosition,
+    TextControlCode as InputTextControlCode,
+};
+use std::io;
+
+pub use ruffle_input_format::{AutomatedEvent, InputInjector};
+
+/// Converts a logged event into a player event.
+///
+/// Returns `None` for events that are not delivered to the player itself,
+/// such as [`AutomatedEvent::Wait`] and [`AutomatedEvent::SetClipboardText`],
+/// and for malformed events, such as a mouse wheel event without a delta.
+pub fn player_event(event: &AutomatedEvent) -> Option<PlayerEvent> {
+    Some(match event {
+        AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => return None,
+        AutomatedEvent::MouseMove { pos } => PlayerEvent::MouseMove { x: pos.0, y: pos.1 },
+        AutomatedEvent::MouseDown {
+            pos, btn, index, ..
+        } => PlayerEvent::MouseDown {
+            x: pos.0,
+            y: pos.1,
+            button: mouse_button(*btn),
+            index: *index,
+        },
+        AutomatedEvent::MouseUp { pos, btn } => PlayerEvent::MouseUp {
+            x: pos.0,
+            y: pos.1,
+            button: mouse_button(*btn),
+        },
+        AutomatedEvent::MouseLeave => PlayerEvent::MouseLeave,
+        AutomatedEvent::MouseMotion { dx, dy } => PlayerEvent::MouseMotion { dx: *dx, dy: *dy },
+        AutomatedEvent::MouseWheel { lines, pixels } => PlayerEvent::MouseWheel {
+            delta: match (lines, pixels) {
+                (Some(lines), None) => MouseWheelDelta::Lines(*lines),
+                (None, Some(pixels)) => MouseWheelDelta::Pixels(*pixels),
+                // Exactly one of them has to be given.
+                _ => return None,
+            },
+        },
+        AutomatedEvent::KeyDown { key_code, key_char } => PlayerEvent::KeyDown {
+            key_code: KeyCode::from_code(*key_code),
+            key_char: *key_char,
+        },
+        AutomatedEvent::KeyUp { key_code, key_char } => PlayerEvent::KeyUp {
+            key_code: KeyCode::from_code(*key_code),
+            key_char: *key_char,
+        },
+        AutomatedEvent::GamepadButtonDown { button } => PlayerEvent::GamepadButtonDown {
+            button: gamepad_button(*button),
+        },
+        AutomatedEvent::GamepadButtonUp { button } => PlayerEvent::GamepadButtonUp {
+            button: gamepad_button(*button),
+        },
+        AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
+            codepoint: *codepoint,
+        },
+        AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
+            code: text_control_code(*code),
+        },
+        AutomatedEvent::ImePreedit { text, cursor } => {
+            PlayerEvent::Ime(ImeEvent::Preedit(text.clone(), *cursor))
+        }
+        AutomatedEvent::ImeCommit { text } => PlayerEvent::Ime(ImeEvent::Commit(text.clone())),
+        AutomatedEvent::TouchBegin { id, pos, pressure } => PlayerEvent::TouchBegin {
+            id: *id,
+            x: pos.0,
+            y: pos.1,
+            pressure: *pressure,
+        },
+        AutomatedEvent::TouchMove { id, pos, pressure } => PlayerEvent::TouchMove {
+            id: *id,
+            x: pos.0,
+            y: pos.1,
+            pressure: *pressure,
+        },
+        AutomatedEvent::TouchEnd { id, pos } => PlayerEvent::TouchEnd {
+            id: *id,
+            x: pos.0,
+            y: pos.1,
+        },
+        AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
+        AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
+    })
+}
+
+/// Converts a player event into its logged form.
+///
+/// Returns `None` for events that cannot be represented in an input log,
+/// such as presses of an unknown mouse button.
+pub fn automated_event(event: &PlayerEvent) -> Option<AutomatedEvent> {
+    Some(match event {
+        PlayerEvent::KeyDown { key_code, key_char } => AutomatedEvent::KeyDown {
+            key_code: key_code.value(),
+            key_char: *key_char,
+        },
+        PlayerEvent::KeyUp { key_code, key_char } => AutomatedEvent::KeyUp {
+            key_code: key_code.value(),
+            key_char: *key_char,
+        },
+        PlayerEvent::MouseMove { x, y } => AutomatedEvent::MouseMove {
+            pos: MousePosition(*x, *y),
+        },
+        PlayerEvent::MouseUp { x, y, button } => AutomatedEvent::MouseUp {
+            pos: MousePosition(*x, *y),
+            btn: input_mouse_button(*button)?,
+        },
+        PlayerEvent::MouseDown {
+            x,
+            y,
+            button,
+            index,
+        } => AutomatedEvent::MouseDown {
+            pos: MousePosition(*x, *y),
+            btn: input_mouse_button(*button)?,
+            index: *index,
+            assert_handled: None,
+        },
+        PlayerEvent::MouseLeave => AutomatedEvent::MouseLeave,
+        PlayerEvent::MouseMotion { dx, dy } => AutomatedEvent::MouseMotion { dx: *dx, dy: *dy },
+        PlayerEvent::MouseWheel { delta } => match delta {
+            MouseWheelDelta::Lines(lines) => AutomatedEvent::MouseWheel {
+                lines: Some(*lines),
+                pixels: None,
+            },
+            MouseWheelDelta::Pixels(pixels) => AutomatedEvent::MouseWheel {
+                lines: None,
+                pixels: Some(*pixels),
+            },
+        },
+        PlayerEvent::GamepadButtonDown { button } => AutomatedEvent::GamepadButtonDown {
+            button: input_gamepad_button(*button),
+        },
+        PlayerEvent::GamepadButtonUp { button } => AutomatedEvent::GamepadButtonUp {
+            button: input_gamepad_button(*button),
+        },
+        PlayerEvent::TextInput { codepoint } => AutomatedEvent::TextInput {
+            codepoint: *codepoint,
+        },
+        PlayerEvent::TextControl { code } => AutomatedEvent::TextControl {
+            code: input_text_control_code(*code),
+        },
+        PlayerEvent::Ime(ImeEvent::Preedit(text, cursor)) => AutomatedEvent::ImePreedit {
+            text: text.clone(),
+            cursor: *cursor,
+        },
+        PlayerEvent::Ime(ImeEvent::Commit(text)) => {
+            AutomatedEvent::ImeCommit { text: text.clone() }
+        }
+        PlayerEvent::TouchBegin { id, x, y, pressure } => AutomatedEvent::TouchBegin {
+            id: *id,
+            pos: MousePosition(*x, *y),
+            pressure: *pressure,
+        },
+        PlayerEvent::TouchMove { id, x, y, pressure } => AutomatedEvent::TouchMove {
+            id: *id,
+            pos: MousePosition(*x, *y),
+            pressure: *pressure,
+        },
+        PlayerEvent::TouchEnd { id, x, y } => AutomatedEvent::TouchEnd {
+            id: *id,
+            pos: MousePosition(*x, *y),
+        },
+        PlayerEvent::FocusGained => AutomatedEvent::FocusGained,
+        PlayerEvent::FocusLost => AutomatedEvent::FocusLost,
+    })
+}
+
+/// Records the input delivered to a player, frame by frame.
+#[derive(Default)]
+pub struct InputRecorder {
+    events: Vec<AutomatedEvent>,
+}
+
+impl InputRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an event delivered to the player during the current frame.
+    pub fn record(&mut self, event: &PlayerEvent) {
+        if let Some(event) = automated_event(event) {
+            self.events.push(event);
+        }
+    }
+
+    /// Records a clipboard change, which has to be replayed alongside the input.
+    pub fn record_clipboard(&mut self, text: String) {
+        self.events.push(AutomatedEvent::SetClipboardText { text });
+    }
+
+    /// Ends the input of the current frame.
+    pub fn end_frame(&mut self) {
+        self.events.push(AutomatedEvent::Wait);
+    }
+
+    pub fn events(&self) -> &[AutomatedEvent] {
+        &self.events
+    }
+
+    /// Writes the recorded input in the input file format.
+    pub fn write<W: io::Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(writer, &self.events)
+    }
+}
+
+fn mouse_button(button: InputMouseButton) -> MouseButton {
+    match button {
+        InputMouseButton::Left => MouseButton::Left,
+        InputMouseButton::Middle => MouseButton::Middle,
+        InputMouseButton::Right => MouseButton::Right,
+    }
+}
+
+fn input_mouse_button(button: MouseButton) -> Option<InputMouseButton> {
+    match button {
+        MouseButton::Unknown => None,
+        MouseButton::Left => Some(InputMouseButton::Left),
+        MouseButton::Middle => Some(InputMouseButton::Middle),
+        MouseButton::Right => Some(InputMouseButton::Right),
+    }
+}
+
+fn gamepad_button(button: InputGamepadButton) -> GamepadButton {
+    match button {
+        InputGamepadButton::South => GamepadButton::South,
+        InputGamepadButton::East => GamepadButton::East,
+        InputGamepadButton::North => GamepadButton::North,
+        InputGamepadButton::West => GamepadButton::West,
+        InputGamepadButton::LeftTrigger => GamepadButton::LeftTrigger,
+        InputGamepadButton::LeftTrigger2 => GamepadButton::LeftTrigger2,
+        InputGamepadButton::RightTrigger => GamepadButton::RightTrigger,
+        InputGamepadButton::RightTrigger2 => GamepadButton::RightTrigger2,
+        InputGamepadButton::Select => GamepadButton::Select,
+        InputGamepadButton::Start => GamepadButton::Start,
+        InputGamepadButton::DPadUp => GamepadButton::DPadUp,
+        InputGamepadButton::DPadDown => GamepadButton::DPadDown,
+        InputGamepadButton::DPadLeft => GamepadButton::DPadLeft,
+        InputGamepadButton::DPadRight => GamepadButton::DPadRight,
+    }
+}
+
+fn input_gamepad_button(button: GamepadButton) -> InputGamepadButton {
+    match button {
+        GamepadButton::South => InputGamepadButton::South,
+        GamepadButton::East => InputGamepadButton::East,
+        GamepadButton::North => InputGamepadButton::North,
+        GamepadButton::West => InputGamepadButton::West,
+        GamepadButton::LeftTrigger => InputGamepadButton::LeftTrigger,
+        GamepadButton::LeftTrigger2 => InputGamepadButton::LeftTrigger2,
+        GamepadButton::RightTrigger => InputGamepadButton::RightTrigger,
+        GamepadButton::RightTrigger2 => InputGamepadButton::RightTrigger2,
+        GamepadButton::Select => InputGamepadButton::Select,
+        GamepadButton::Start => InputGamepadButton::Start,
+        GamepadButton::DPadUp => InputGamepadButton::DPadUp,
+        GamepadButton::DPadDown => InputGamepadButton::DPadDown,
+        GamepadButton::DPadLeft => InputGamepadButton::DPadLeft,
+        GamepadButton::DPadRight => InputGamepadButton::DPadRight,
+    }
+}
+
+fn text_control_code(code: InputTextControlCode) -> TextControlCode {
+    match code {
+        InputTextControlCode::MoveLeft => TextControlCode::MoveLeft,
+        InputTextControlCode::MoveLeftWord => TextControlCode::MoveLeftWord,
+        InputTextControlCode::MoveLeftLine => TextControlCode::MoveLeftLine,
+        InputTextControlCode::MoveLeftDocument => TextControlCode::MoveLeftDocument,
+        InputTextControlCode::MoveRight => TextControlCode::MoveRight,
+        InputTextControlCode::MoveRightWord => TextControlCode::MoveRightWord,
+        InputTextControlCode::MoveRightLine => TextControlCode::MoveRightLine,
+        InputTextControlCode::MoveRightDocument => TextControlCode::MoveRightDocument,
+        InputTextControlCode::SelectLeft => TextControlCode::SelectLeft,
+        InputTextControlCode::SelectLeftWord => TextControlCode::SelectLeftWord,
+        InputTextControlCode::SelectLeftLine => TextControlCode::SelectLeftLine,
+        InputTextControlCode::SelectLeftDocument => TextControlCode::SelectLeftDocument,
+        InputTextControlCode::SelectRight => TextControlCode::SelectRight,
+        InputTextControlCode::SelectRightWord => TextControlCode::SelectRightWord,
+        InputTextControlCode::SelectRightLine => TextControlCode::SelectRightLine,
+        InputTextControlCode::SelectRightDocument => TextControlCode::SelectRightDocument,
+        InputTextControlCode::SelectAll => TextControlCode::SelectAll,
+        InputTextControlCode::Copy => TextControlCode::Copy,
+        InputTextControlCode::Paste => TextControlCode::Paste,
+        InputTextControlCode::Cut => TextControlCode::Cut,
+        InputTextControlCode::Backspace => TextControlCode::Backspace,
+        InputTextControlCode::BackspaceWord => TextControlCode::BackspaceWord,
+        InputTextControlCode::Enter => TextControlCode::Enter,
+        InputTextControlCode::Delete => TextControlCode::Delete,
+        InputTextControlCode::DeleteWord => TextControlCode::DeleteWord,
+    }
+}
+
+fn input_text_control_code(code: TextControlCode) -> InputTextControlCode {
+    match code {
+        TextControlCode::MoveLeft => InputTextControlCode::MoveLeft,
+        TextControlCode::MoveLeftWord => InputTextControlCode::MoveLeftWord,
+        TextControlCode::MoveLeftLine => InputTextControlCode::MoveLeftLine,
+        TextControlCode::MoveLeftDocument => InputTextControlCode::MoveLeftDocument,
+        TextControlCode::MoveRight => InputTextControlCode::MoveRight,
+        TextControlCode::MoveRightWord => InputTextControlCode::MoveRightWord,
+        TextControlCode::MoveRightLine => InputTextControlCode::MoveRightLine,
+        TextControlCode::MoveRightDocument => InputTextControlCode::MoveRightDocument,
+        TextControlCode::SelectLeft => InputTextControlCode::SelectLeft,
+        TextControlCode::SelectLeftWord => InputTextControlCode::SelectLeftWord,
+        TextControlCode::SelectLeftLine => InputTextControlCode::SelectLeftLine,
+        TextControlCode::SelectLeftDocument => InputTextControlCode::SelectLeftDocument,
+        TextControlCode::SelectRight => InputTextControlCode::SelectRight,
+        TextControlCode::SelectRightWord => InputTextControlCode::SelectRightWord,
+        TextControlCode::SelectRightLine => InputTextControlCode::SelectRightLine,
+        TextControlCode::SelectRightDocument => InputTextControlCode::SelectRightDocument,
+        TextControlCode::SelectAll => InputTextControlCode::SelectAll,
+        TextControlCode::Copy => InputTextControlCode::Copy,
+        TextControlCode::Paste => InputTextControlCode::Paste,
+        TextControlCode::Cut => InputTextControlCode::Cut,
+        TextControlCode::Backspace => InputTextControlCode::Backspace,
+        TextControlCode::BackspaceWord => InputTextControlCode::BackspaceWord,
+        TextControlCode::Enter => InputTextControlCode::Enter,
+        TextControlCode::Delete => InputTextControlCode::Delete,
+        TextControlCode::DeleteWord => InputTextControlCode::DeleteWord,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(log: &[u8]) -> Vec<Vec<String>> {
+        let mut injector = InputInjector::from_reader(log).expect("valid input log");
+        let mut frames = Vec::new();
+        while !injector.is_finished() {
+            let mut frame = Vec::new();
+            injector.next(|event, _buttons| match event {
+                AutomatedEvent::SetClipboardText { text } => {
+                    frame.push(format!("clipboard {text}"))
+                }
+                event => frame.push(format!("{:?}", player_event(event).expect("valid event"))),
+            });
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let frames = vec![
+            vec![
+                PlayerEvent::MouseMove { x: 10.0, y: 20.5 },
+                PlayerEvent::MouseDown {
+                    x: 10.0,
+                    y: 20.5,
+                    button: MouseButton::Left,
+                    index: Some(1),
+                },
+                PlayerEvent::MouseUp {
+                    x: 10.0,
+                    y: 20.5,
+                    button: MouseButton::Left,
+                },
+            ],
+            vec![],
+            vec![
+                PlayerEvent::KeyDown {
+                    key_code: KeyCode::A,
+                    key_char: Some('a'),
+                },
+                PlayerEvent::TextInput { codepoint: 'a' },
+                PlayerEvent::KeyUp {
+                    key_code: KeyCode::A,
+                    key_char: Some('a'),
+                },
+                PlayerEvent::MouseWheel {
+                    delta: MouseWheelDelta::Pixels(-3.0),
+                },
+                PlayerEvent::Ime(ImeEvent::Preedit("k".to_string(), Some((0, 1)))),
+                PlayerEvent::Ime(ImeEvent::Commit("ka".to_string())),
+                PlayerEvent::TouchBegin {
+                    id: 2,
+                    x: 1.0,
+                    y: 2.0,
+                    pressure: Some(0.5),
+                },
+                PlayerEvent::TouchEnd {
+                    id: 2,
+                    x: 3.0,
+                    y: 4.0,
+                },
+            ],
+        ];
+
+        let mut recorder = InputRecorder::new();
+        let mut expected = Vec::new();
+        for frame in &frames {
+            for event in frame {
+                recorder.record(event);
+            }
+            recorder.record_clipboard("copied".to_string());
+            recorder.end_frame();
+
+            let mut expected_frame: Vec<_> = frame.iter().map(|e| format!("{e:?}")).collect();
+            expected_frame.push("clipboard copied".to_string());
+            expected.push(expected_frame);
+        }
+
+        let mut log = Vec::new();
+        recorder.write(&mut log).expect("writing to a vector");
+        assert_eq!(replay(&log), expected);
+    }
+
+    #[test]
+    fn malformed_mouse_wheel() {
+        let event = AutomatedEvent::MouseWheel {
+            lines: Some(1.0),
+            pixels: Some(1.0),
+        };
+        assert!(player_event(&event).is_none());
+
+        let event = AutomatedEvent::MouseWheel {
+            lines: None,
+            pixels: None,
+        };
+        assert!(player_event(&event).is_none());
+    }
+}
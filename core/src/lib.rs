@@ -7,7 +7,7 @@
 
 #[macro_use]
 mod display_object;
-pub use display_object::{StageAlign, StageDisplayState, StageScaleMode};
+pub use display_object::{interpolate_morph_shape, StageAlign, StageDisplayState, StageScaleMode};
 
 #[macro_use]
 extern crate smallvec;
@@ -27,6 +27,7 @@ pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+pub mod deterministic;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
@@ -35,12 +36,16 @@ mod font;
 mod frame_lifecycle;
 mod html;
 mod input;
+#[cfg(feature = "input_log")]
+pub mod input_log;
 mod library;
 pub mod limits;
 pub mod loader;
 mod local_connection;
 mod locale;
+pub mod memory;
 mod net_connection;
+mod persistent_cache;
 pub mod pixel_bender;
 mod player;
 mod prelude;
@@ -50,6 +55,7 @@ mod streams;
 pub mod string;
 pub mod tag_utils;
 pub mod timer;
+mod touch;
 mod types;
 mod vminterface;
 mod xml;
@@ -63,11 +69,14 @@ pub mod external;
 pub mod i18n;
 pub mod stub;
 
+#[cfg(feature = "jit")]
+pub use avm2::JitMode;
 pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
 pub use font::DefaultFont;
 pub use indexmap;
 pub use loader::LoadBehavior;
+pub use locale::Timezone;
 pub use player::{Player, PlayerBuilder, PlayerRuntime, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
 pub use swf;
@@ -1,23 +1,28 @@
 use crate::avm1::{PropertyMap as Avm1PropertyMap, PropertyMap};
 use crate::avm2::{Class as Avm2Class, Domain as Avm2Domain};
 use crate::backend::audio::SoundHandle;
-use crate::character::Character;
+use crate::character::{Character, CompressedBitmap};
 use std::borrow::Cow;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, Text};
 use crate::font::{Font, FontDescriptor, FontType};
+use crate::persistent_cache::{MovieCache, PersistentCache};
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Mutation};
-use ruffle_render::backend::RenderBackend;
-use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::bitmap::{Bitmap as DecodedBitmap, BitmapHandle, BitmapSize, BitmapSource};
+use ruffle_render::tessellator::{Mesh, ShapeTessellator};
 use ruffle_render::utils::remove_invalid_jpeg_data;
 
 use crate::backend::ui::{FontDefinition, UiBackend};
+use crate::backend::worker::{WorkerBackend, WorkerTask};
 use crate::DefaultFont;
 use fnv::{FnvHashMap, FnvHashSet};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::{Arc, Weak};
 use weak_table::{traits::WeakElement, PtrWeakKeyHashMap, WeakValueHashMap};
 
@@ -126,6 +131,39 @@ pub struct MovieLibrary<'gc> {
     jpeg_tables: Option<Vec<u8>>,
     fonts: FontMap<'gc>,
     avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// The persistent cache of this movie, if enabled.
+    #[collect(require_static)]
+    persistent_cache: Option<Rc<MovieCache>>,
+
+    /// Bitmaps decoded ahead of time by a worker, waiting for their first use.
+    #[collect(require_static)]
+    prepared_bitmaps: RefCell<PreparedBitmaps>,
+}
+
+/// The bitmaps of a movie which were handed to a worker for decoding.
+///
+/// Decoding every bitmap of a movie up front can take a lot of memory, so the
+/// total size of these is limited to `MAX_PREPARED_BITMAPS_SIZE`. Anything
+/// past that is decoded when it's first used, like without workers.
+#[derive(Default)]
+struct PreparedBitmaps {
+    /// The decoding of each bitmap, and its decoded size in bytes. A failed
+    /// decode is `None`.
+    bitmaps: HashMap<CharacterId, (usize, WorkerTask<Option<DecodedBitmap>>)>,
+
+    /// The total decoded size of `bitmaps`.
+    size: usize,
+}
+
+const MAX_PREPARED_BITMAPS_SIZE: usize = 64 * 1024 * 1024;
+
+impl PreparedBitmaps {
+    fn take(&mut self, id: CharacterId) -> Option<WorkerTask<Option<DecodedBitmap>>> {
+        let (size, task) = self.bitmaps.remove(&id)?;
+        self.size -= size;
+        Some(task)
+    }
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -138,7 +176,156 @@ impl<'gc> MovieLibrary<'gc> {
             jpeg_tables: None,
             fonts: Default::default(),
             avm2_domain: None,
+            persistent_cache: None,
+            prepared_bitmaps: Default::default(),
+        }
+    }
+
+    pub fn persistent_cache(&self) -> Option<&Rc<MovieCache>> {
+        self.persistent_cache.as_ref()
+    }
+
+    /// Decodes a bitmap character of this movie, reusing the decoded bitmap
+    /// from the persistent cache when possible.
+    pub fn decode_bitmap(
+        &self,
+        id: CharacterId,
+        compressed: &CompressedBitmap,
+    ) -> Result<DecodedBitmap, ruffle_render::error::Error> {
+        // The bitmap is needed right away, so unless a worker finished it
+        // already, it's decoded here rather than waiting for the worker.
+        let prepared = self
+            .prepared_bitmaps
+            .borrow_mut()
+            .take(id)
+            .and_then(|task| task.try_join().ok().flatten().flatten());
+
+        let Some(cache) = &self.persistent_cache else {
+            return prepared.map_or_else(|| compressed.decode(), Ok);
+        };
+
+        if let Some(bitmap) = cache.bitmap(id) {
+            return Ok(bitmap);
+        }
+        let bitmap = prepared.map_or_else(|| compressed.decode(), Ok)?;
+        cache.insert_bitmap(id, &bitmap);
+        Ok(bitmap)
+    }
+
+    /// Starts decoding a bitmap character of this movie on a worker, so
+    /// that it's ready by the time it's first used.
+    ///
+    /// Nothing is started if the bitmap is in the persistent cache, or if
+    /// too many bitmaps are waiting for their first use already.
+    pub fn prepare_bitmap(&self, workers: &dyn WorkerBackend, id: CharacterId) {
+        let Some(Character::Bitmap { compressed, .. }) = self.characters.get(&id) else {
+            return;
+        };
+        if self
+            .persistent_cache
+            .as_ref()
+            .is_some_and(|cache| cache.contains_bitmap(id))
+        {
+            return;
+        }
+
+        let bitmap_size = compressed.size();
+        let size = usize::from(bitmap_size.width) * usize::from(bitmap_size.height) * 4;
+        let mut prepared = self.prepared_bitmaps.borrow_mut();
+        if prepared.size + size > MAX_PREPARED_BITMAPS_SIZE || prepared.bitmaps.contains_key(&id) {
+            return;
+        }
+
+        // Errors are reported when the bitmap is decoded again on first use.
+        let compressed = compressed.clone();
+        let task = workers.start(move || compressed.decode().ok());
+        prepared.bitmaps.insert(id, (size, task));
+        prepared.size += size;
+    }
+
+    /// Registers a shape character of this movie with the renderer, reusing
+    /// its tessellation from the persistent cache when possible.
+    pub fn register_shape(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        shape: &swf::Shape,
+    ) -> ShapeHandle {
+        let mesh = self
+            .persistent_cache
+            .as_ref()
+            .filter(|_| renderer.supports_tessellated_shapes())
+            .map(|cache| {
+                cache.shape(shape.id).unwrap_or_else(|| {
+                    let bitmap_source = MovieLibrarySource { library: self };
+                    let mesh =
+                        ShapeTessellator::new().tessellate_shape(shape.into(), &bitmap_source);
+                    cache.insert_shape(shape.id, &mesh);
+                    mesh
+                })
+            });
+
+        self.register_tessellated_shape(renderer, shape, mesh)
+    }
+
+    /// Starts tessellating a shape character of this movie on a worker.
+    ///
+    /// Returns a task whose mesh must be passed to
+    /// `register_tessellated_shape`. Returns `None` if the shape should be
+    /// registered right away instead, because the renderer can't take a
+    /// mesh or because the mesh is already in the persistent cache.
+    pub fn tessellate_shape_on_worker(
+        &self,
+        workers: &dyn WorkerBackend,
+        renderer: &dyn RenderBackend,
+        shape: &swf::Shape,
+    ) -> Option<WorkerTask<Mesh>> {
+        if !renderer.supports_tessellated_shapes()
+            || self
+                .persistent_cache
+                .as_ref()
+                .is_some_and(|cache| cache.contains_shape(shape.id))
+        {
+            return None;
+        }
+
+        let bitmap_source = MovieLibrarySource { library: self };
+        let bitmap_sizes = BitmapSizes(
+            bitmap_fill_ids(shape)
+                .filter_map(|id| Some((id, bitmap_source.bitmap_size(id)?)))
+                .collect(),
+        );
+        let shape = shape.clone();
+        Some(workers.start(move || {
+            ShapeTessellator::new().tessellate_shape((&shape).into(), &bitmap_sizes)
+        }))
+    }
+
+    /// Registers a shape character of this movie with the renderer, using the
+    /// given mesh if it was already tessellated.
+    pub fn register_tessellated_shape(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        shape: &swf::Shape,
+        mesh: Option<Mesh>,
+    ) -> ShapeHandle {
+        let bitmap_source = MovieLibrarySource { library: self };
+
+        if let Some(mesh) = mesh {
+            if let Some(cache) = self
+                .persistent_cache
+                .as_ref()
+                .filter(|cache| !cache.contains_shape(shape.id))
+            {
+                cache.insert_shape(shape.id, &mesh);
+            }
+            if let Some(handle) =
+                renderer.register_tessellated_shape(shape.id, mesh, &bitmap_source)
+            {
+                return handle;
+            }
         }
+
+        renderer.register_shape(shape.into(), &bitmap_source)
     }
 
     pub fn register_character(&mut self, id: CharacterId, character: Character<'gc>) {
@@ -243,7 +430,7 @@ impl<'gc> MovieLibrary<'gc> {
                 avm2_bitmapdata_class,
                 handle: _,
             } => {
-                let bitmap = compressed.decode().unwrap();
+                let bitmap = self.decode_bitmap(id, compressed).unwrap();
                 let bitmap = Bitmap::new(mc, id, bitmap, self.swf.clone())
                     .map_err(|e| Cow::Owned(format!("Failed to instantiate bitmap: {:?}", e)))?;
                 bitmap.set_avm2_bitmapdata_class(mc, *avm2_bitmapdata_class.read());
@@ -354,6 +541,43 @@ pub struct MovieLibrarySource<'a, 'gc> {
     pub library: &'a MovieLibrary<'gc>,
 }
 
+/// The sizes of the bitmaps a shape is filled with, which is all that's
+/// needed to tessellate it away from its library.
+struct BitmapSizes(HashMap<CharacterId, BitmapSize>);
+
+impl BitmapSource for BitmapSizes {
+    fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
+        self.0.get(&id).copied()
+    }
+
+    fn bitmap_handle(&self, _id: u16, _backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        None
+    }
+}
+
+/// Returns the IDs of all bitmaps used by the fill and line styles of a shape.
+fn bitmap_fill_ids(shape: &swf::Shape) -> impl Iterator<Item = CharacterId> + '_ {
+    let new_styles = shape.shape.iter().filter_map(|record| match record {
+        swf::ShapeRecord::StyleChange(style_change) => style_change.new_styles.as_ref(),
+        _ => None,
+    });
+
+    std::iter::once(&shape.styles)
+        .chain(new_styles)
+        .flat_map(|styles| {
+            styles.fill_styles.iter().chain(
+                styles
+                    .line_styles
+                    .iter()
+                    .map(|line_style| line_style.fill_style()),
+            )
+        })
+        .filter_map(|fill_style| match fill_style {
+            swf::FillStyle::Bitmap { id, .. } => Some(*id),
+            _ => None,
+        })
+}
+
 impl ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'_, '_> {
     fn bitmap_size(&self, id: u16) -> Option<ruffle_render::bitmap::BitmapSize> {
         if let Some(Character::Bitmap { compressed, .. }) = self.library.characters.get(&id) {
@@ -376,7 +600,7 @@ impl ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'_, '_> {
         if let Some(handle) = &*handle {
             return Some(handle.clone());
         }
-        let decoded = match compressed.decode() {
+        let decoded = match self.library.decode_bitmap(id, compressed) {
             Ok(decoded) => decoded,
             Err(e) => {
                 tracing::error!("Failed to decode bitmap character {id:?}: {e:?}");
@@ -422,6 +646,9 @@ pub struct Library<'gc> {
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
+
+    /// The persistent cache shared by all movie libraries, if enabled.
+    persistent_cache: Option<PersistentCache>,
 }
 
 unsafe impl gc_arena::Collect for Library<'_> {
@@ -449,6 +676,7 @@ impl<'gc> Library<'gc> {
             default_font_names: Default::default(),
             default_font_cache: Default::default(),
             avm2_class_registry: Default::default(),
+            persistent_cache: None,
         }
     }
 
@@ -461,7 +689,33 @@ impl<'gc> Library<'gc> {
         #[allow(clippy::unwrap_or_default)]
         self.movie_libraries
             .entry(movie.clone())
-            .or_insert_with(|| MovieLibrary::new(movie))
+            .or_insert_with(|| {
+                let mut library = MovieLibrary::new(movie.clone());
+                if let Some(persistent_cache) = &mut self.persistent_cache {
+                    library.persistent_cache = persistent_cache.open(&movie);
+                }
+                library
+            })
+    }
+
+    /// Enables the persistent cache for movies loaded from now on.
+    pub fn set_persistent_cache(&mut self, persistent_cache: PersistentCache) {
+        self.persistent_cache = Some(persistent_cache);
+    }
+
+    /// Writes the persistent cache to its backend.
+    pub fn flush_persistent_cache(&mut self) {
+        if let Some(persistent_cache) = &mut self.persistent_cache {
+            persistent_cache.flush();
+        }
+    }
+
+    /// Writes the persistent cache to its backend, if it wasn't written for a
+    /// while.
+    pub fn flush_persistent_cache_if_due(&mut self) {
+        if let Some(persistent_cache) = &mut self.persistent_cache {
+            persistent_cache.flush_if_due();
+        }
     }
 
     pub fn known_movies(&self) -> Vec<Arc<SwfMovie>> {
@@ -1,4 +1,10 @@
-use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+use chrono::{
+    DateTime, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeDelta,
+    TimeZone, Utc,
+};
+#[cfg(feature = "timezones")]
+use chrono_tz::Tz;
+use std::str::FromStr;
 
 // For tests, we emulate being in Nepal with a local time of 2001-02-03 at 04:05:06.
 // Nepal has a timezone offset of +5:45, and has never used DST.
@@ -7,20 +13,213 @@ const MOCK_TIME: bool = cfg!(any(test, feature = "deterministic"));
 
 pub fn get_current_date_time() -> DateTime<Utc> {
     if MOCK_TIME {
-        get_timezone()
-            .with_ymd_and_hms(2001, 2, 3, 4, 5, 6)
-            .single()
-            .expect("Unambiguous mock time")
-            .into()
+        mock_date_time()
     } else {
         Utc::now()
     }
 }
 
-pub fn get_timezone() -> FixedOffset {
+pub fn get_timezone() -> Timezone {
     if MOCK_TIME {
-        FixedOffset::east_opt(20700).expect("Unambiguous mock timezone")
+        mock_timezone()
     } else {
-        Local::now().offset().fix()
+        Timezone::Local
+    }
+}
+
+/// The fixed date and time used by tests and deterministic playback.
+pub fn mock_date_time() -> DateTime<Utc> {
+    mock_timezone()
+        .with_ymd_and_hms(2001, 2, 3, 4, 5, 6)
+        .single()
+        .expect("Unambiguous mock time")
+        .with_timezone(&Utc)
+}
+
+/// The fixed timezone used by tests and deterministic playback.
+pub fn mock_timezone() -> Timezone {
+    Timezone::Fixed(FixedOffset::east_opt(20700).expect("Unambiguous mock timezone"))
+}
+
+/// The timezone used to compute the local time of dates.
+///
+/// The offset from UTC is looked up separately for each instant, so that dates
+/// on either side of a daylight saving time transition get the right local time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Timezone {
+    /// The timezone of the host system.
+    Local,
+
+    /// A constant offset from UTC, which never observes daylight saving time.
+    Fixed(FixedOffset),
+
+    /// A timezone from the IANA time zone database, such as `Europe/London`.
+    #[cfg(feature = "timezones")]
+    Named(Tz),
+}
+
+/// Converts a local date and time to an instant in the given timezone.
+///
+/// Like in ECMAScript, times which are repeated by a daylight saving time transition
+/// use the offset from before the transition, and so do times which are skipped by one.
+pub fn from_local_datetime<T: TimeZone>(
+    timezone: &T,
+    local: &NaiveDateTime,
+) -> Option<DateTime<T>> {
+    match timezone.from_local_datetime(local) {
+        LocalResult::Single(date) | LocalResult::Ambiguous(date, _) => Some(date),
+        LocalResult::None => {
+            let before = local.checked_sub_signed(TimeDelta::days(1))?;
+            let offset = timezone.offset_from_utc_datetime(&before).fix();
+            let utc = local.checked_sub_offset(offset)?;
+            Some(timezone.from_utc_datetime(&utc))
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    /// Parses either `local` or the name of a timezone from the IANA time zone database.
+    ///
+    /// Names are only supported with the `timezones` feature.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+
+        #[cfg(feature = "timezones")]
+        {
+            Tz::from_str(s).map(Self::Named).map_err(|e| e.to_string())
+        }
+        #[cfg(not(feature = "timezones"))]
+        {
+            Err(format!(
+                "Unsupported timezone '{s}': named timezones require the `timezones` feature"
+            ))
+        }
+    }
+}
+
+impl TimeZone for Timezone {
+    type Offset = FixedOffset;
+
+    fn from_offset(offset: &FixedOffset) -> Self {
+        Self::Fixed(*offset)
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        match self {
+            Self::Local => Local.offset_from_local_date(local),
+            Self::Fixed(offset) => LocalResult::Single(*offset),
+            #[cfg(feature = "timezones")]
+            Self::Named(tz) => tz.offset_from_local_date(local).map(|offset| offset.fix()),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        match self {
+            Self::Local => Local.offset_from_local_datetime(local),
+            Self::Fixed(offset) => LocalResult::Single(*offset),
+            #[cfg(feature = "timezones")]
+            Self::Named(tz) => tz
+                .offset_from_local_datetime(local)
+                .map(|offset| offset.fix()),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        match self {
+            Self::Local => Local.offset_from_utc_date(utc),
+            Self::Fixed(offset) => *offset,
+            #[cfg(feature = "timezones")]
+            Self::Named(tz) => tz.offset_from_utc_date(utc).fix(),
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            Self::Local => Local.offset_from_utc_datetime(utc),
+            Self::Fixed(offset) => *offset,
+            #[cfg(feature = "timezones")]
+            Self::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "timezones")]
+    fn local(year: i32, month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "timezones")]
+    fn offset_follows_dst() {
+        let tz = Timezone::Named(Tz::Europe__London);
+        let winter = Utc.with_ymd_and_hms(2020, 1, 15, 12, 0, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2020, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(winter.with_timezone(&tz).offset().local_minus_utc(), 0);
+        assert_eq!(summer.with_timezone(&tz).offset().local_minus_utc(), 3600);
+    }
+
+    #[test]
+    #[cfg(feature = "timezones")]
+    fn offset_follows_historical_rules() {
+        // The US moved the start of DST from April to March in 2007.
+        let tz = Timezone::Named(Tz::America__New_York);
+        let offset = |year| {
+            from_local_datetime(&tz, &local(year, 3, 20, 12, 0))
+                .unwrap()
+                .offset()
+                .local_minus_utc()
+        };
+        assert_eq!(offset(2006), -5 * 3600);
+        assert_eq!(offset(2007), -4 * 3600);
+    }
+
+    #[test]
+    #[cfg(feature = "timezones")]
+    fn skipped_and_repeated_times() {
+        let tz = Timezone::Named(Tz::Europe__London);
+        // 01:30 doesn't exist on 2020-03-29, and is interpreted with the offset before the transition.
+        assert_eq!(
+            from_local_datetime(&tz, &local(2020, 3, 29, 1, 30)),
+            Some(
+                Utc.with_ymd_and_hms(2020, 3, 29, 1, 30, 0)
+                    .unwrap()
+                    .with_timezone(&tz)
+            )
+        );
+        // 01:30 happens twice on 2020-10-25, and the first one is used.
+        assert_eq!(
+            from_local_datetime(&tz, &local(2020, 10, 25, 1, 30)),
+            Some(
+                Utc.with_ymd_and_hms(2020, 10, 25, 0, 30, 0)
+                    .unwrap()
+                    .with_timezone(&tz)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_timezone() {
+        assert_eq!("local".parse(), Ok(Timezone::Local));
+        assert!("Nowhere/Special".parse::<Timezone>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "timezones")]
+    fn parse_named_timezone() {
+        assert_eq!(
+            "Asia/Kathmandu".parse(),
+            Ok(Timezone::Named(Tz::Asia__Kathmandu))
+        );
     }
 }
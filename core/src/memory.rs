@@ -0,0 +1,315 @@
+//! Garbage collection pacing and memory accounting.
+//!
+//! The GC arena is collected incrementally at the end of every update, doing an
+//! amount of work proportional to what was allocated since the last update.
+//! Memory owned by GC objects but allocated outside the arena, such as bitmap
+//! pixels and `ByteArray` contents, is reported to the arena through
+//! [`ExternalMemory`], so that large external heaps speed up collection too.
+
+use gc_arena::{Metrics, Mutation, Pacing};
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+/// The lowest fraction of the configured timing factor that the collector will
+/// slow down to in order to keep pauses under `GcPacing::max_pause`.
+const MIN_TIMING_SCALE: f64 = 1.0 / 8.0;
+
+/// How the player paces garbage collection.
+#[derive(Clone, Copy, Debug)]
+pub struct GcPacing {
+    /// How many bytes of collection work are done for every byte allocated
+    /// while a collection cycle is in progress.
+    ///
+    /// Higher values finish cycles sooner and keep the heap smaller, at the
+    /// cost of longer pauses.
+    pub timing_factor: f64,
+
+    /// How long the collector sleeps after finishing a cycle before starting
+    /// the next one, as a fraction of the memory that was still in use.
+    pub sleep_factor: f64,
+
+    /// The minimum amount of memory, in bytes, that must be allocated before the
+    /// collector wakes up again.
+    pub min_sleep: usize,
+
+    /// Collection work owed every frame, in bytes, on top of the work owed for
+    /// allocations.
+    ///
+    /// This lets movies which rarely allocate still finish collection cycles
+    /// and free memory.
+    pub debt_per_frame: usize,
+
+    /// The longest pause that collection should take in a single update.
+    ///
+    /// When a pause takes longer, the collector does less work per allocated
+    /// byte in the following updates, and goes back to the configured timing
+    /// factor once pauses are short again. Cycles take longer while slowed
+    /// down, so the heap grows larger before it's collected.
+    pub max_pause: Option<Duration>,
+}
+
+impl Default for GcPacing {
+    fn default() -> Self {
+        Self {
+            timing_factor: Pacing::DEFAULT.timing_factor,
+            sleep_factor: Pacing::DEFAULT.sleep_factor,
+            min_sleep: Pacing::DEFAULT.min_sleep,
+            debt_per_frame: 0,
+            max_pause: None,
+        }
+    }
+}
+
+/// Statistics about garbage collection, as shown in the debug UI.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GcStats {
+    /// The number of collection cycles that were finished.
+    pub cycles: u64,
+
+    /// How long collection took in the last update.
+    pub last_pause: Duration,
+
+    /// The longest time collection took in a single update.
+    pub longest_pause: Duration,
+
+    /// The timing factor currently in use, which may be lower than the
+    /// configured one to keep pauses short.
+    pub timing_factor: f64,
+}
+
+/// Schedules garbage collection work according to a `GcPacing`.
+///
+/// This lives outside of the GC arena, and is driven by the player around
+/// every collection of the arena.
+pub struct GcScheduler {
+    pacing: GcPacing,
+
+    /// The fraction of the configured timing factor currently in use.
+    timing_scale: f64,
+
+    /// Whether the collection work owed for the current frame is still due.
+    frame_debt_due: bool,
+
+    /// The lowest imminence at which a requested full collection should
+    /// happen, if one was requested by `System.gc()` or
+    /// `System.pauseForGCIfCollectionImminent()`.
+    requested_imminence: Option<f64>,
+
+    /// Whether a collection cycle was in progress after the last collection.
+    collecting: bool,
+
+    /// The total allocation when the collector last went to sleep.
+    sleep_start: usize,
+
+    stats: GcStats,
+}
+
+impl GcScheduler {
+    pub fn new(pacing: GcPacing) -> Self {
+        Self {
+            pacing,
+            timing_scale: 1.0,
+            frame_debt_due: false,
+            requested_imminence: None,
+            collecting: false,
+            sleep_start: 0,
+            stats: GcStats {
+                timing_factor: pacing.timing_factor,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn pacing(&self) -> GcPacing {
+        self.pacing
+    }
+
+    pub fn stats(&self) -> GcStats {
+        self.stats
+    }
+
+    /// Whether a collection cycle is in progress.
+    pub fn collecting(&self) -> bool {
+        self.collecting
+    }
+
+    /// The pacing to give to the GC arena for the next collection.
+    pub fn arena_pacing(&self) -> Pacing {
+        Pacing {
+            sleep_factor: self.pacing.sleep_factor,
+            min_sleep: self.pacing.min_sleep,
+            timing_factor: self.pacing.timing_factor * self.timing_scale,
+        }
+    }
+
+    /// Marks the start of a new frame, so that `debt_per_frame` is owed again.
+    pub fn start_frame(&mut self) {
+        self.frame_debt_due = true;
+    }
+
+    /// Takes the collection work owed for the current frame, if it wasn't
+    /// done yet.
+    pub fn take_frame_debt(&mut self) -> usize {
+        if std::mem::take(&mut self.frame_debt_due) {
+            self.pacing.debt_per_frame
+        } else {
+            0
+        }
+    }
+
+    /// Asks for a full collection at the end of the current update, if the
+    /// collector's imminence is at least `imminence` by then.
+    ///
+    /// An imminence of 0 always collects.
+    pub fn request_collection(&mut self, imminence: f64) {
+        let imminence = imminence.clamp(0.0, 1.0);
+        self.requested_imminence = Some(
+            self.requested_imminence
+                .map_or(imminence, |requested| requested.min(imminence)),
+        );
+    }
+
+    /// Takes the pending collection request, returning whether it should
+    /// result in a full collection.
+    pub fn take_requested_collection(&mut self, metrics: &Metrics) -> bool {
+        self.requested_imminence
+            .take()
+            .is_some_and(|requested| self.imminence(metrics) >= requested)
+    }
+
+    /// How close the collector is to starting a collection cycle, between 0
+    /// (just finished one) and 1 (a cycle is in progress).
+    pub fn imminence(&self, metrics: &Metrics) -> f64 {
+        if self.collecting {
+            return 1.0;
+        }
+
+        let allocated = metrics.total_allocation().saturating_sub(self.sleep_start);
+        (allocated as f64 / self.sleep_length() as f64).min(1.0)
+    }
+
+    /// How much memory, in bytes, can be allocated before the collector starts
+    /// the next cycle.
+    pub fn free_memory(&self, metrics: &Metrics) -> usize {
+        if self.collecting {
+            return 0;
+        }
+
+        let allocated = metrics.total_allocation().saturating_sub(self.sleep_start);
+        self.sleep_length().saturating_sub(allocated)
+    }
+
+    /// How much memory the collector lets be allocated after finishing a cycle
+    /// before waking up again.
+    fn sleep_length(&self) -> usize {
+        let sleep = self.sleep_start as f64 * self.pacing.sleep_factor;
+        (sleep as usize).max(self.pacing.min_sleep).max(1)
+    }
+
+    /// Records a collection of the arena which took `pause`.
+    ///
+    /// `collecting` is whether a collection cycle is still in progress after it.
+    pub fn record_collection(&mut self, metrics: &Metrics, pause: Duration, collecting: bool) {
+        if self.collecting && !collecting {
+            self.finish_cycle(metrics);
+        }
+        self.collecting = collecting;
+
+        self.stats.last_pause = pause;
+        self.stats.longest_pause = self.stats.longest_pause.max(pause);
+
+        if let Some(max_pause) = self.pacing.max_pause {
+            if pause > max_pause {
+                self.timing_scale = (self.timing_scale / 2.0).max(MIN_TIMING_SCALE);
+            } else if pause < max_pause / 2 {
+                self.timing_scale = (self.timing_scale * 1.25).min(1.0);
+            }
+        }
+        self.stats.timing_factor = self.pacing.timing_factor * self.timing_scale;
+    }
+
+    /// Records that a collection cycle just finished, leaving the collector
+    /// asleep.
+    pub fn finish_cycle(&mut self, metrics: &Metrics) {
+        self.collecting = false;
+        self.sleep_start = metrics.total_allocation();
+        self.stats.cycles += 1;
+    }
+}
+
+/// Memory owned by a GC object but allocated outside of the GC arena, which is
+/// reported to the arena so that it's taken into account when pacing
+/// collection.
+///
+/// This starts out detached, reporting nothing, until attached to an arena
+/// with `attach`. Reported memory is released when this is dropped, along with
+/// the object owning it.
+#[derive(Default)]
+pub struct ExternalMemory {
+    metrics: Option<Metrics>,
+    bytes: Cell<usize>,
+}
+
+impl ExternalMemory {
+    /// Creates an accounting of `bytes` bytes, reported to the arena of `mc`.
+    pub fn new(mc: &Mutation<'_>, bytes: usize) -> Self {
+        let memory = Self::default();
+        memory.set(bytes);
+        memory.attach(mc);
+        memory
+    }
+
+    /// Starts reporting this memory to the arena of `mc`, if it isn't reported
+    /// to an arena already.
+    pub fn attach(&mut self, mc: &Mutation<'_>) {
+        if self.metrics.is_none() {
+            let metrics = mc.metrics().clone();
+            metrics.mark_external_allocation(self.bytes.get());
+            self.metrics = Some(metrics);
+        }
+    }
+
+    pub fn get(&self) -> usize {
+        self.bytes.get()
+    }
+
+    /// Updates the amount of memory, reporting the difference to the arena.
+    pub fn set(&self, bytes: usize) {
+        let old_bytes = self.bytes.replace(bytes);
+        if let Some(metrics) = &self.metrics {
+            if bytes > old_bytes {
+                metrics.mark_external_allocation(bytes - old_bytes);
+            } else {
+                metrics.mark_external_deallocation(old_bytes - bytes);
+            }
+        }
+    }
+}
+
+impl Clone for ExternalMemory {
+    fn clone(&self) -> Self {
+        if let Some(metrics) = &self.metrics {
+            metrics.mark_external_allocation(self.bytes.get());
+        }
+        Self {
+            metrics: self.metrics.clone(),
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl Drop for ExternalMemory {
+    fn drop(&mut self) {
+        self.set(0);
+    }
+}
+
+impl Debug for ExternalMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalMemory")
+            .field("bytes", &self.bytes.get())
+            .field("attached", &self.metrics.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,715 @@
+//! An opt-in cache of work derived from movies, persisted between runs.
+//!
+//! Verifying AVM2 methods, tessellating shapes and decoding bitmaps is
+//! repeated every time a movie is loaded. With a [`CacheBackend`], the results
+//! are stored per movie, keyed by the SHA-256 hash of the movie's uncompressed
+//! data, and reused the next time the same movie is loaded.
+//!
+//! A cache blob is only used if it was written by the same version of Ruffle
+//! for the same movie, and if its checksum matches. Anything else is discarded
+//! and replaced on the next flush.
+
+use crate::backend::cache::CacheBackend;
+use crate::tag_utils::SwfMovie;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use fnv::{FnvHashMap, FnvHasher};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{Bitmap as MeshBitmap, Draw, DrawType, Gradient, Mesh, Vertex};
+use sha2::{Digest, Sha256};
+use std::cell::{Cell, RefCell};
+use std::hash::Hasher;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use swf::avm2::read::Reader as AbcReader;
+use swf::avm2::types::Op as AbcOp;
+use swf::avm2::write::Writer as AbcWriter;
+use swf::{CharacterId, Color, Fixed8, GradientInterpolation, GradientRecord, GradientSpread};
+use web_time::{Duration, Instant};
+
+const MAGIC: &[u8; 8] = b"RUFCACHE";
+
+/// Bump this whenever the encoding of any entry changes.
+const FORMAT_VERSION: u32 = 2;
+
+/// How often modified caches are written back to the backend.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The largest amount of entry data kept for a single movie. Entries are not
+/// added past this size, so that a movie with many large bitmaps can't grow
+/// the cache without bound.
+const MAX_MOVIE_CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum EntryKind {
+    Method = 0,
+    Shape = 1,
+    Bitmap = 2,
+}
+
+impl EntryKind {
+    fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(Self::Method),
+            1 => Some(Self::Shape),
+            2 => Some(Self::Bitmap),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of an entry, and its key within that kind.
+type EntryKey = (EntryKind, u64);
+
+/// The persistent cache of all movies loaded by a player.
+pub struct PersistentCache {
+    backend: Box<dyn CacheBackend>,
+
+    /// The caches handed out to movie libraries.
+    ///
+    /// These are held on to after their movie is unloaded, until they're
+    /// flushed.
+    movies: Vec<Rc<MovieCache>>,
+
+    last_flush: Instant,
+}
+
+impl PersistentCache {
+    pub fn new(backend: Box<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            movies: Vec::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Opens the cache of a movie, loading it from the backend if it isn't
+    /// open yet.
+    ///
+    /// Returns `None` for anything that isn't an actual movie.
+    pub fn open(&mut self, movie: &SwfMovie) -> Option<Rc<MovieCache>> {
+        if !movie.is_movie() || movie.data().is_empty() {
+            return None;
+        }
+
+        let hash = movie_hash(movie.data());
+        if let Some(cache) = self.movies.iter().find(|cache| cache.hash == hash) {
+            return Some(cache.clone());
+        }
+
+        let cache = Rc::new(MovieCache::new(hash));
+        if let Some(data) = self.backend.get(&cache.key()) {
+            if let Err(e) = cache.load(&data) {
+                tracing::warn!("Discarding persistent cache of {}: {e}", movie.url());
+            }
+        }
+        self.movies.push(cache.clone());
+        Some(cache)
+    }
+
+    /// Writes all modified caches to the backend, and forgets the caches of
+    /// movies that were unloaded.
+    pub fn flush(&mut self) {
+        for cache in &self.movies {
+            if cache.dirty.replace(false) {
+                self.backend.put(&cache.key(), &cache.save());
+            }
+        }
+        self.movies.retain(|cache| Rc::strong_count(cache) > 1);
+        self.last_flush = Instant::now();
+    }
+
+    /// Flushes the cache if it wasn't flushed for a while.
+    pub fn flush_if_due(&mut self) {
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush();
+        }
+    }
+}
+
+/// The cached entries of a single movie.
+///
+/// Entries are kept encoded, and only decoded when they're used.
+pub struct MovieCache {
+    hash: MovieHash,
+    entries: RefCell<FnvHashMap<EntryKey, Box<[u8]>>>,
+    size: Cell<usize>,
+    dirty: Cell<bool>,
+}
+
+impl MovieCache {
+    fn new(hash: MovieHash) -> Self {
+        Self {
+            hash,
+            entries: Default::default(),
+            size: Cell::new(0),
+            dirty: Cell::new(false),
+        }
+    }
+
+    /// The backend key of this cache.
+    fn key(&self) -> String {
+        self.hash.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn contains(&self, kind: EntryKind, key: u64) -> bool {
+        self.entries.borrow().contains_key(&(kind, key))
+    }
+
+    fn get(&self, kind: EntryKind, key: u64) -> Option<Box<[u8]>> {
+        self.entries.borrow().get(&(kind, key)).cloned()
+    }
+
+    fn insert(&self, kind: EntryKind, key: u64, data: Vec<u8>) {
+        if self.size.get() + data.len() > MAX_MOVIE_CACHE_SIZE {
+            return;
+        }
+
+        self.size.set(self.size.get() + data.len());
+        let old = self
+            .entries
+            .borrow_mut()
+            .insert((kind, key), data.into_boxed_slice());
+        if let Some(old) = old {
+            self.size.set(self.size.get() - old.len());
+        }
+        self.dirty.set(true);
+    }
+
+    /// Decodes an entry, dropping it if it turns out to be invalid.
+    fn decode<T>(
+        &self,
+        kind: EntryKind,
+        key: u64,
+        decode: impl FnOnce(&mut &[u8]) -> io::Result<T>,
+    ) -> Option<T> {
+        let data = self.get(kind, key)?;
+        match decode(&mut &data[..]) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!("Dropping invalid persistent cache entry {kind:?} {key}: {e}");
+                self.entries.borrow_mut().remove(&(kind, key));
+                self.size.set(self.size.get() - data.len());
+                self.dirty.set(true);
+                None
+            }
+        }
+    }
+
+    pub fn method(&self, key: u64) -> Option<VerifiedBytecode> {
+        self.decode(EntryKind::Method, key, VerifiedBytecode::read)
+    }
+
+    pub fn insert_method(&self, key: u64, method: &VerifiedBytecode) {
+        let mut data = Vec::new();
+        match method.write(&mut data) {
+            Ok(()) => self.insert(EntryKind::Method, key, data),
+            Err(e) => tracing::warn!("Couldn't cache method: {e}"),
+        }
+    }
+
+    pub fn contains_shape(&self, id: CharacterId) -> bool {
+        self.contains(EntryKind::Shape, id.into())
+    }
+
+    pub fn shape(&self, id: CharacterId) -> Option<Mesh> {
+        self.decode(EntryKind::Shape, id.into(), read_mesh)
+    }
+
+    pub fn insert_shape(&self, id: CharacterId, mesh: &Mesh) {
+        let mut data = Vec::new();
+        match write_mesh(&mut data, mesh) {
+            Ok(()) => self.insert(EntryKind::Shape, id.into(), data),
+            Err(e) => tracing::warn!("Couldn't cache shape {id}: {e}"),
+        }
+    }
+
+    pub fn contains_bitmap(&self, id: CharacterId) -> bool {
+        self.contains(EntryKind::Bitmap, id.into())
+    }
+
+    pub fn bitmap(&self, id: CharacterId) -> Option<Bitmap> {
+        self.decode(EntryKind::Bitmap, id.into(), read_bitmap)
+    }
+
+    pub fn insert_bitmap(&self, id: CharacterId, bitmap: &Bitmap) {
+        let mut data = Vec::new();
+        match write_bitmap(&mut data, bitmap) {
+            Ok(()) => self.insert(EntryKind::Bitmap, id.into(), data),
+            Err(e) => tracing::warn!("Couldn't cache bitmap {id}: {e}"),
+        }
+    }
+
+    /// Loads the entries of a blob written by `save`, after validating it.
+    fn load(&self, data: &[u8]) -> io::Result<()> {
+        let Some((body, checksum)) = data.split_last_chunk::<8>() else {
+            return Err(invalid_data("truncated"));
+        };
+        let mut hasher = FnvHasher::default();
+        hasher.write(body);
+        if hasher.finish() != u64::from_le_bytes(*checksum) {
+            return Err(invalid_data("checksum mismatch"));
+        }
+
+        let mut reader = body;
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC || reader.read_u32::<LittleEndian>()? != FORMAT_VERSION {
+            return Err(invalid_data("unknown format"));
+        }
+        if read_bytes(&mut reader)? != env!("CARGO_PKG_VERSION").as_bytes() {
+            return Err(invalid_data("written by another version of Ruffle"));
+        }
+        let mut hash = MovieHash::default();
+        reader.read_exact(&mut hash)?;
+        if hash != self.hash {
+            return Err(invalid_data("written for another movie"));
+        }
+
+        let num_entries = reader.read_u32::<LittleEndian>()?;
+        let mut entries = FnvHashMap::default();
+        let mut size = 0;
+        for _ in 0..num_entries {
+            let kind = EntryKind::from_u8(reader.read_u8()?)
+                .ok_or_else(|| invalid_data("unknown entry kind"))?;
+            let key = reader.read_u64::<LittleEndian>()?;
+            let data = read_bytes(&mut reader)?;
+            size += data.len();
+            entries.insert((kind, key), data.into());
+        }
+
+        *self.entries.borrow_mut() = entries;
+        self.size.set(size);
+        Ok(())
+    }
+
+    /// Encodes all entries into a blob, to be stored by the backend.
+    fn save(&self) -> Vec<u8> {
+        let entries = self.entries.borrow();
+        let mut data = Vec::with_capacity(self.size.get() + entries.len() * 16 + 64);
+
+        // Writing to a `Vec` can't fail.
+        let mut write = || -> io::Result<()> {
+            data.write_all(MAGIC)?;
+            data.write_u32::<LittleEndian>(FORMAT_VERSION)?;
+            write_bytes(&mut data, env!("CARGO_PKG_VERSION").as_bytes())?;
+            data.write_all(&self.hash)?;
+            data.write_u32::<LittleEndian>(entries.len() as u32)?;
+            for ((kind, key), entry) in entries.iter() {
+                data.write_u8(*kind as u8)?;
+                data.write_u64::<LittleEndian>(*key)?;
+                write_bytes(&mut data, entry)?;
+            }
+            Ok(())
+        };
+        write().expect("Writing to a Vec can't fail");
+
+        let mut hasher = FnvHasher::default();
+        hasher.write(&data);
+        data.extend_from_slice(&hasher.finish().to_le_bytes());
+        data
+    }
+}
+
+/// The SHA-256 hash of the uncompressed data of a movie, which identifies its cache.
+type MovieHash = [u8; 32];
+
+fn movie_hash(data: &[u8]) -> MovieHash {
+    Sha256::digest(data).into()
+}
+
+/// The result of verifying the control flow of an AVM2 method body.
+///
+/// This is everything `verify_method` computes from the bytecode alone,
+/// before anything is resolved against the domain.
+pub struct VerifiedBytecode {
+    /// The reachable ops of the method, with jump offsets counted in ops
+    /// instead of bytes.
+    pub code: Vec<AbcOp>,
+
+    /// The offsets of each exception handler, also counted in ops. This is
+    /// `None` for handlers that can't be reached.
+    pub exceptions: Vec<Option<ExceptionOffsets>>,
+}
+
+#[derive(Clone, Copy)]
+pub struct ExceptionOffsets {
+    pub from_offset: u32,
+    pub to_offset: u32,
+
+    /// The target of the handler, or `None` if nothing jumps to it.
+    pub target_offset: Option<u32>,
+}
+
+impl VerifiedBytecode {
+    fn write(&self, output: &mut Vec<u8>) -> io::Result<()> {
+        let mut code = Vec::new();
+        let mut writer = AbcWriter::new(&mut code);
+        for op in &self.code {
+            writer
+                .write_op(op)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        }
+
+        output.write_u32::<LittleEndian>(self.code.len() as u32)?;
+        write_bytes(output, &code)?;
+
+        output.write_u32::<LittleEndian>(self.exceptions.len() as u32)?;
+        for exception in &self.exceptions {
+            match exception {
+                Some(offsets) => {
+                    output.write_u8(1)?;
+                    output.write_u32::<LittleEndian>(offsets.from_offset)?;
+                    output.write_u32::<LittleEndian>(offsets.to_offset)?;
+                    output
+                        .write_i64::<LittleEndian>(offsets.target_offset.map_or(-1, i64::from))?;
+                }
+                None => output.write_u8(0)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read(input: &mut &[u8]) -> io::Result<Self> {
+        let num_ops = input.read_u32::<LittleEndian>()?;
+        let code_bytes = read_bytes(input)?;
+        let mut reader = AbcReader::new(&code_bytes);
+        let code = (0..num_ops)
+            .map(|_| reader.read_op().map_err(|e| invalid_data(&e.to_string())))
+            .collect::<io::Result<_>>()?;
+
+        let num_exceptions = input.read_u32::<LittleEndian>()?;
+        let exceptions = (0..num_exceptions)
+            .map(|_| {
+                Ok(match input.read_u8()? {
+                    0 => None,
+                    _ => Some(ExceptionOffsets {
+                        from_offset: input.read_u32::<LittleEndian>()?,
+                        to_offset: input.read_u32::<LittleEndian>()?,
+                        target_offset: u32::try_from(input.read_i64::<LittleEndian>()?).ok(),
+                    }),
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { code, exceptions })
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) -> io::Result<()> {
+    output.write_u32::<LittleEndian>(bytes.len() as u32)?;
+    output.write_all(bytes)
+}
+
+fn read_bytes(input: &mut &[u8]) -> io::Result<Vec<u8>> {
+    let len = input.read_u32::<LittleEndian>()? as usize;
+    if len > input.len() {
+        return Err(invalid_data("truncated"));
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes.to_vec())
+}
+
+fn write_color(output: &mut Vec<u8>, color: &Color) -> io::Result<()> {
+    output.write_all(&[color.r, color.g, color.b, color.a])
+}
+
+fn read_color(input: &mut &[u8]) -> io::Result<Color> {
+    let mut rgba = [0; 4];
+    input.read_exact(&mut rgba)?;
+    let [r, g, b, a] = rgba;
+    Ok(Color { r, g, b, a })
+}
+
+fn write_matrix(output: &mut Vec<u8>, matrix: &[[f32; 3]; 3]) -> io::Result<()> {
+    for value in matrix.iter().flatten() {
+        output.write_f32::<LittleEndian>(*value)?;
+    }
+    Ok(())
+}
+
+fn read_matrix(input: &mut &[u8]) -> io::Result<[[f32; 3]; 3]> {
+    let mut matrix = [[0.0; 3]; 3];
+    for value in matrix.iter_mut().flatten() {
+        *value = input.read_f32::<LittleEndian>()?;
+    }
+    Ok(matrix)
+}
+
+fn write_mesh(output: &mut Vec<u8>, mesh: &Mesh) -> io::Result<()> {
+    output.write_u32::<LittleEndian>(mesh.gradients.len() as u32)?;
+    for gradient in &mesh.gradients {
+        output.write_u8(match gradient.gradient_type {
+            GradientType::Linear => 0,
+            GradientType::Radial => 1,
+            GradientType::Focal => 2,
+        })?;
+        output.write_u8(gradient.repeat_mode as u8)?;
+        output.write_i16::<LittleEndian>(gradient.focal_point.get())?;
+        output.write_u8(gradient.interpolation as u8)?;
+        output.write_u32::<LittleEndian>(gradient.records.len() as u32)?;
+        for record in &gradient.records {
+            output.write_u8(record.ratio)?;
+            write_color(output, &record.color)?;
+        }
+    }
+
+    output.write_u32::<LittleEndian>(mesh.draws.len() as u32)?;
+    for draw in &mesh.draws {
+        match &draw.draw_type {
+            DrawType::Color => output.write_u8(0)?,
+            DrawType::Gradient { matrix, gradient } => {
+                output.write_u8(1)?;
+                write_matrix(output, matrix)?;
+                output.write_u32::<LittleEndian>(*gradient as u32)?;
+            }
+            DrawType::Bitmap(bitmap) => {
+                output.write_u8(2)?;
+                write_matrix(output, &bitmap.matrix)?;
+                output.write_u16::<LittleEndian>(bitmap.bitmap_id)?;
+                output.write_u8(bitmap.is_smoothed.into())?;
+                output.write_u8(bitmap.is_repeating.into())?;
+            }
+        }
+
+        output.write_u32::<LittleEndian>(draw.vertices.len() as u32)?;
+        for vertex in &draw.vertices {
+            output.write_f32::<LittleEndian>(vertex.x)?;
+            output.write_f32::<LittleEndian>(vertex.y)?;
+            write_color(output, &vertex.color)?;
+        }
+        output.write_u32::<LittleEndian>(draw.indices.len() as u32)?;
+        for index in &draw.indices {
+            output.write_u32::<LittleEndian>(*index)?;
+        }
+        output.write_u32::<LittleEndian>(draw.mask_index_count)?;
+    }
+
+    Ok(())
+}
+
+fn read_mesh(input: &mut &[u8]) -> io::Result<Mesh> {
+    let num_gradients = input.read_u32::<LittleEndian>()?;
+    let mut gradients = Vec::new();
+    for _ in 0..num_gradients {
+        let gradient_type = match input.read_u8()? {
+            0 => GradientType::Linear,
+            1 => GradientType::Radial,
+            2 => GradientType::Focal,
+            _ => return Err(invalid_data("unknown gradient type")),
+        };
+        let repeat_mode = GradientSpread::from_u8(input.read_u8()?)
+            .ok_or_else(|| invalid_data("unknown gradient spread"))?;
+        let focal_point = Fixed8::from_bits(input.read_i16::<LittleEndian>()?);
+        let interpolation = GradientInterpolation::from_u8(input.read_u8()?)
+            .ok_or_else(|| invalid_data("unknown gradient interpolation"))?;
+        let num_records = input.read_u32::<LittleEndian>()?;
+        let records = (0..num_records)
+            .map(|_| {
+                Ok(GradientRecord {
+                    ratio: input.read_u8()?,
+                    color: read_color(input)?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        gradients.push(Gradient {
+            gradient_type,
+            repeat_mode,
+            focal_point,
+            interpolation,
+            records,
+        });
+    }
+
+    let num_draws = input.read_u32::<LittleEndian>()?;
+    let mut draws = Vec::new();
+    for _ in 0..num_draws {
+        let draw_type = match input.read_u8()? {
+            0 => DrawType::Color,
+            1 => DrawType::Gradient {
+                matrix: read_matrix(input)?,
+                gradient: input.read_u32::<LittleEndian>()? as usize,
+            },
+            2 => DrawType::Bitmap(MeshBitmap {
+                matrix: read_matrix(input)?,
+                bitmap_id: input.read_u16::<LittleEndian>()?,
+                is_smoothed: input.read_u8()? != 0,
+                is_repeating: input.read_u8()? != 0,
+            }),
+            _ => return Err(invalid_data("unknown draw type")),
+        };
+        if let DrawType::Gradient { gradient, .. } = draw_type {
+            if gradient >= gradients.len() {
+                return Err(invalid_data("gradient out of range"));
+            }
+        }
+
+        let num_vertices = input.read_u32::<LittleEndian>()?;
+        let vertices: Vec<_> = (0..num_vertices)
+            .map(|_| {
+                Ok(Vertex {
+                    x: input.read_f32::<LittleEndian>()?,
+                    y: input.read_f32::<LittleEndian>()?,
+                    color: read_color(input)?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        let num_indices = input.read_u32::<LittleEndian>()?;
+        let indices: Vec<_> = (0..num_indices)
+            .map(|_| input.read_u32::<LittleEndian>())
+            .collect::<io::Result<_>>()?;
+        if indices
+            .iter()
+            .any(|index| *index as usize >= vertices.len())
+        {
+            return Err(invalid_data("vertex index out of range"));
+        }
+        let mask_index_count = input.read_u32::<LittleEndian>()?;
+
+        draws.push(Draw {
+            draw_type,
+            vertices,
+            indices,
+            mask_index_count,
+        });
+    }
+
+    Ok(Mesh { draws, gradients })
+}
+
+fn write_bitmap(output: &mut Vec<u8>, bitmap: &Bitmap) -> io::Result<()> {
+    output.write_u32::<LittleEndian>(bitmap.width())?;
+    output.write_u32::<LittleEndian>(bitmap.height())?;
+    output.write_u8(match bitmap.format() {
+        BitmapFormat::Rgb => 0,
+        BitmapFormat::Rgba => 1,
+        BitmapFormat::Yuv420p => 2,
+        BitmapFormat::Yuva420p => 3,
+    })?;
+
+    // Decoded pixels are much larger than the movie's compressed ones, but
+    // inflating them is still far cheaper than decoding a JPEG.
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(bitmap.data())?;
+    write_bytes(output, &encoder.finish()?)
+}
+
+fn read_bitmap(input: &mut &[u8]) -> io::Result<Bitmap> {
+    let width = input.read_u32::<LittleEndian>()?;
+    let height = input.read_u32::<LittleEndian>()?;
+    let format = match input.read_u8()? {
+        0 => BitmapFormat::Rgb,
+        1 => BitmapFormat::Rgba,
+        2 => BitmapFormat::Yuv420p,
+        3 => BitmapFormat::Yuva420p,
+        _ => return Err(invalid_data("unknown bitmap format")),
+    };
+
+    let compressed = read_bytes(input)?;
+    let expected_len = format.length_for_size(width as usize, height as usize);
+    let mut data = Vec::with_capacity(expected_len);
+    ZlibDecoder::new(&compressed[..]).read_to_end(&mut data)?;
+    if data.len() != expected_len {
+        return Err(invalid_data("wrong bitmap size"));
+    }
+
+    Ok(Bitmap::new(width, height, format, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::{Index, LookupSwitch};
+
+    fn verified_bytecode() -> VerifiedBytecode {
+        VerifiedBytecode {
+            code: vec![
+                AbcOp::GetLocal { index: 0 },
+                AbcOp::PushScope,
+                AbcOp::Jump { offset: -3 },
+                AbcOp::LookupSwitch(Box::new(LookupSwitch {
+                    default_offset: 1,
+                    case_offsets: vec![-2, 0].into(),
+                })),
+                AbcOp::GetLex {
+                    index: Index::new(5),
+                },
+                AbcOp::ReturnVoid,
+            ],
+            exceptions: vec![
+                None,
+                Some(ExceptionOffsets {
+                    from_offset: 1,
+                    to_offset: 3,
+                    target_offset: Some(4),
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn method_round_trip() {
+        let cache = MovieCache::new([1; 32]);
+        cache.insert_method(42, &verified_bytecode());
+
+        let loaded = MovieCache::new([1; 32]);
+        loaded.load(&cache.save()).expect("Cache should be valid");
+        let method = loaded.method(42).expect("Method should be cached");
+        assert_eq!(method.code, verified_bytecode().code);
+        assert!(method.exceptions[0].is_none());
+        let offsets = method.exceptions[1].expect("Exception should be reachable");
+        assert_eq!(
+            (
+                offsets.from_offset,
+                offsets.to_offset,
+                offsets.target_offset
+            ),
+            (1, 3, Some(4))
+        );
+        assert!(loaded.method(43).is_none());
+    }
+
+    #[test]
+    fn bitmap_round_trip() {
+        let bitmap = Bitmap::new(2, 2, BitmapFormat::Rgba, (0..16).collect());
+        let cache = MovieCache::new([1; 32]);
+        cache.insert_bitmap(7, &bitmap);
+
+        let loaded = MovieCache::new([1; 32]);
+        loaded.load(&cache.save()).expect("Cache should be valid");
+        let decoded = loaded.bitmap(7).expect("Bitmap should be cached");
+        assert_eq!(decoded.data(), bitmap.data());
+        assert_eq!((decoded.width(), decoded.height()), (2, 2));
+    }
+
+    #[test]
+    fn key_is_movie_hash() {
+        let cache = MovieCache::new(movie_hash(b"abc"));
+        assert_eq!(
+            cache.key(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_blobs() {
+        let cache = MovieCache::new([1; 32]);
+        cache.insert_method(42, &verified_bytecode());
+        let data = cache.save();
+
+        let mut corrupted = data.clone();
+        corrupted[20] ^= 1;
+        assert!(MovieCache::new([1; 32]).load(&corrupted).is_err());
+        assert!(MovieCache::new([1; 32]).load(&data[..4]).is_err());
+        assert!(MovieCache::new([3; 32]).load(&data).is_err());
+    }
+}
@@ -9,10 +9,12 @@ use crate::avm2::{Activation as Avm2Activation, Avm2, CallStack, Object as Avm2O
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    cache::CacheBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
+    ui::{CursorImage, CustomCursor, MouseCursor, UiBackend},
+    worker::WorkerBackend,
 };
 use crate::compatibility_rules::CompatibilityRules;
 use crate::config::Letterbox;
@@ -20,6 +22,7 @@ use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::deterministic::{Clock, DeterministicOptions};
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
@@ -36,8 +39,10 @@ use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
-use crate::locale::get_current_date_time;
+use crate::locale::{get_current_date_time, get_timezone, Timezone};
+use crate::memory::{GcPacing, GcScheduler, GcStats};
 use crate::net_connection::NetConnections;
+use crate::persistent_cache::PersistentCache;
 use crate::prelude::*;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
@@ -46,12 +51,19 @@ use crate::string::{AvmString, AvmStringInterner};
 use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
+use crate::touch::{self, TouchData, TouchPhase};
 use crate::vminterface::Instantiator;
 use crate::DefaultFont;
+#[cfg(feature = "input_log")]
+use crate::{
+    events::TextControlCode,
+    input_log::{self, AutomatedEvent, InputInjector, InputRecorder},
+};
 use gc_arena::lock::GcRefLock;
-use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
+use gc_arena::{Collect, CollectionPhase, DynamicRootSet, Mutation, Rootable};
 use rand::{rngs::SmallRng, SeedableRng};
 use ruffle_render::backend::{null::NullRenderer, RenderBackend, ViewportDimensions};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, PixelRegion};
 use ruffle_render::commands::CommandList;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::TransformStack;
@@ -110,6 +122,18 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The cursor set with AS3 `Mouse.cursor`, or `None` for `MouseCursor.AUTO`.
+    #[collect(require_static)]
+    pub cursor_name: Option<String>,
+
+    /// Cursors registered with AS3 `Mouse.registerCursor`, by name.
+    #[collect(require_static)]
+    pub registered_cursors: HashMap<String, RegisteredCursor>,
+
+    /// The movement of the mouse (in stage pixels) reported by the last mouse move event.
+    #[collect(require_static)]
+    pub movement: (f64, f64),
 }
 
 impl<'gc> MouseData<'gc> {
@@ -130,6 +154,76 @@ impl<'gc> MouseData<'gc> {
             MouseButton::Middle => self.middle_pressed = value,
         }
     }
+
+    /// Returns the cursor which should be displayed, given the cursor chosen
+    /// by the hovered object and the time (in milliseconds) used to animate
+    /// registered cursors.
+    ///
+    /// If the cursor is animated, this also returns the time (in milliseconds)
+    /// until its next frame is displayed.
+    pub fn displayed_cursor(
+        &self,
+        auto_cursor: &MouseCursor,
+        time: f64,
+    ) -> (MouseCursor, Option<f64>) {
+        let name = match (&self.cursor_name, auto_cursor) {
+            (Some(name), _) => name.as_str(),
+            (None, MouseCursor::Arrow) => "arrow",
+            (None, MouseCursor::Hand) => "button",
+            (None, MouseCursor::IBeam) => "ibeam",
+            (None, MouseCursor::Grab) => "hand",
+            (None, MouseCursor::Custom(_)) => return (auto_cursor.clone(), None),
+        };
+
+        // Registered cursors replace native cursors of the same name.
+        if let Some(registered) = self.registered_cursors.get(name) {
+            return registered.cursor_at(time);
+        }
+
+        let cursor = match name {
+            "arrow" => MouseCursor::Arrow,
+            "button" => MouseCursor::Hand,
+            "ibeam" => MouseCursor::IBeam,
+            "hand" => MouseCursor::Grab,
+            _ => auto_cursor.clone(),
+        };
+        (cursor, None)
+    }
+}
+
+/// A bitmap cursor registered with AS3 `Mouse.registerCursor`.
+pub struct RegisteredCursor {
+    pub frames: Arc<[CursorImage]>,
+    pub hotspot: (u32, u32),
+
+    /// The speed of the animation in frames per second.
+    /// Cursors without a valid frame rate only display their first frame.
+    pub frame_rate: f64,
+}
+
+impl RegisteredCursor {
+    /// The duration (in milliseconds) of each frame, if the cursor is animated.
+    fn frame_duration(&self) -> Option<f64> {
+        let is_animated =
+            self.frames.len() > 1 && self.frame_rate > 0.0 && self.frame_rate.is_finite();
+        is_animated.then(|| 1000.0 / self.frame_rate)
+    }
+
+    fn cursor_at(&self, time: f64) -> (MouseCursor, Option<f64>) {
+        let (frame, time_til_next_frame) = match self.frame_duration() {
+            Some(frame_duration) => (
+                (time / frame_duration) as usize % self.frames.len(),
+                Some(frame_duration - time % frame_duration),
+            ),
+            None => (0, None),
+        };
+        let cursor = MouseCursor::Custom(CustomCursor {
+            frames: self.frames.clone(),
+            frame,
+            hotspot: self.hotspot,
+        });
+        (cursor, time_til_next_frame)
+    }
 }
 
 #[derive(Collect)]
@@ -145,6 +239,8 @@ struct GcRootData<'gc> {
 
     mouse_data: MouseData<'gc>,
 
+    touch_data: TouchData<'gc>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -232,6 +328,7 @@ impl<'gc> GcRootData<'gc> {
         &mut LocalConnections<'gc>,
         &mut Vec<PostFrameCallback<'gc>>,
         &mut MouseData<'gc>,
+        &mut TouchData<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -256,6 +353,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.local_connections,
             &mut self.post_frame_callbacks,
             &mut self.mouse_data,
+            &mut self.touch_data,
             self.dynamic_root,
         )
     }
@@ -270,6 +368,7 @@ type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
+type Workers = Box<dyn WorkerBackend>;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum RunState {
@@ -308,6 +407,7 @@ pub struct Player {
     log: Log,
     ui: Ui,
     video: Video,
+    workers: Option<Workers>,
 
     transform_stack: TransformStack,
 
@@ -315,6 +415,9 @@ pub struct Player {
 
     gc_arena: Rc<RefCell<GcArena>>,
 
+    /// Schedules the collection of `gc_arena`.
+    gc_scheduler: GcScheduler,
+
     frame_rate: f64,
     forced_frame_rate: bool,
     actions_since_timeout_check: u16,
@@ -339,9 +442,20 @@ pub struct Player {
     mouse_position: Point<Twips>,
 
     /// The current mouse cursor icon.
+    /// The cursor chosen by the hovered object.
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// The cursor last sent to the UI backend.
+    displayed_cursor: MouseCursor,
+
+    /// Time (in milliseconds) used to animate registered cursors.
+    cursor_time: f64,
+
+    /// Time (in milliseconds) until the displayed cursor shows its next frame,
+    /// if it's animated.
+    time_til_next_cursor_frame: Option<f64>,
+
     system: SystemProperties,
 
     page_url: Option<String>,
@@ -352,8 +466,16 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
-    /// The instant at which the SWF was launched.
-    start_time: Instant,
+    /// The clock that scripts see, which is virtual when running deterministically.
+    clock: Clock,
+
+    /// Recorded input to inject into the player, one frame at a time.
+    #[cfg(feature = "input_log")]
+    input_replay: Option<InputInjector>,
+
+    /// The input delivered to the player so far, if it is being recorded.
+    #[cfg(feature = "input_log")]
+    input_recorder: Option<InputRecorder>,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
@@ -512,12 +634,28 @@ impl Player {
             let mut frame = 0;
 
             while frame < max_frames_per_tick && self.frame_accumulator >= frame_time {
+                #[cfg(feature = "input_log")]
+                self.replay_input();
+
                 let timer = Instant::now();
                 self.run_frame();
                 let elapsed = timer.elapsed().as_millis() as f64;
 
                 self.add_frame_timing(elapsed);
 
+                if self.clock.is_virtual() {
+                    // Scripts only see time pass as frames run, regardless of how long they took.
+                    self.clock.advance(frame_time);
+                    self.update_timers(frame_time);
+                    self.update(|context| {
+                        StreamManager::tick(context, frame_time);
+                    });
+                }
+                #[cfg(feature = "input_log")]
+                if let Some(recorder) = &mut self.input_recorder {
+                    recorder.end_frame();
+                }
+
                 self.frame_accumulator -= frame_time;
                 frame += 1;
                 // The script probably tried implementing an FPS limiter with a busy loop.
@@ -550,22 +688,46 @@ impl Player {
                 self.frame_accumulator = 0.0;
             }
 
-            // Adjust playback speed for next frame to stay in sync with timeline audio tracks ("stream" sounds).
-            let cur_frame_offset = self.frame_accumulator;
-            self.frame_accumulator += self.mutate_with_update_context(|context| {
-                context
-                    .audio_manager
-                    .audio_skew_time(context.audio, cur_frame_offset)
-                    * 1000.0
-            });
+            if !self.clock.is_virtual() {
+                // Adjust playback speed for next frame to stay in sync with timeline audio tracks ("stream" sounds).
+                let cur_frame_offset = self.frame_accumulator;
+                self.frame_accumulator += self.mutate_with_update_context(|context| {
+                    context
+                        .audio_manager
+                        .audio_skew_time(context.audio, cur_frame_offset)
+                        * 1000.0
+                });
+            }
 
             self.update_sockets();
             self.update_net_connections();
-            self.update_timers(dt);
-            self.update(|context| {
-                StreamManager::tick(context, dt);
-            });
+            if !self.clock.is_virtual() {
+                self.update_timers(dt);
+                self.update(|context| {
+                    StreamManager::tick(context, dt);
+                });
+            }
             self.audio.tick();
+
+            self.cursor_time += dt;
+            self.update_mouse_cursor();
+        }
+    }
+
+    /// Update the cursor displayed by the UI backend if it has changed.
+    ///
+    /// This is either the cursor chosen by the hovered object, or the one set
+    /// with `Mouse.cursor`, possibly replaced by a registered cursor.
+    fn update_mouse_cursor(&mut self) {
+        let mouse_cursor = self.mouse_cursor.clone();
+        let time = self.cursor_time;
+        let (cursor, time_til_next_cursor_frame) = self.mutate_with_update_context(|context| {
+            context.mouse_data.displayed_cursor(&mouse_cursor, time)
+        });
+        self.time_til_next_cursor_frame = time_til_next_cursor_frame;
+        if cursor != self.displayed_cursor {
+            self.displayed_cursor = cursor.clone();
+            self.ui.set_mouse_cursor(cursor);
         }
     }
     pub fn time_til_next_timer(&self) -> Option<f64> {
@@ -588,6 +750,11 @@ impl Player {
             dt = dt.min(time_til_next_timer)
         }
 
+        // Animated cursors need a tick to show their next frame.
+        if let Some(time_til_next_cursor_frame) = self.time_til_next_cursor_frame {
+            dt = dt.min(time_til_next_cursor_frame)
+        }
+
         dt = dt.max(0.0);
 
         std::time::Duration::from_micros(dt as u64 * 1000)
@@ -995,6 +1162,80 @@ impl Player {
     /// Handle an event sent into the player from the external windowing system
     /// or an HTML element.
     pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        #[cfg(feature = "input_log")]
+        if self
+            .input_replay
+            .as_ref()
+            .is_some_and(|replay| !replay.is_finished())
+        {
+            // Input from the host would make the session diverge from the replayed one.
+            return false;
+        }
+
+        self.dispatch_event(event)
+    }
+
+    /// The input delivered to the player so far, if recording was enabled with
+    /// [`PlayerBuilder::with_input_recording`].
+    #[cfg(feature = "input_log")]
+    pub fn input_recorder(&self) -> Option<&InputRecorder> {
+        self.input_recorder.as_ref()
+    }
+
+    /// Inject the next frame's worth of events from the input replay, if any.
+    #[cfg(feature = "input_log")]
+    fn replay_input(&mut self) {
+        let Some(mut replay) = self.input_replay.take() else {
+            return;
+        };
+
+        replay.next(|event, _buttons| {
+            if let AutomatedEvent::SetClipboardText { text } = event {
+                self.ui.set_clipboard_content(text.to_owned());
+                if let Some(recorder) = &mut self.input_recorder {
+                    recorder.record_clipboard(text.to_owned());
+                }
+            } else if let Some(event) = input_log::player_event(event) {
+                self.dispatch_event(event);
+            }
+        });
+
+        self.input_replay = Some(replay);
+    }
+
+    /// Record an event about to be dispatched, if input is being recorded.
+    #[cfg(feature = "input_log")]
+    fn record_event(&mut self, mut event: PlayerEvent) -> PlayerEvent {
+        let Some(recorder) = &mut self.input_recorder else {
+            return event;
+        };
+
+        if let PlayerEvent::MouseDown {
+            x,
+            y,
+            ref mut index,
+            ..
+        } = event
+        {
+            // The click index depends on the wall clock, so it's recorded
+            // as computed to make double clicks replay the same way.
+            index.get_or_insert_with(|| self.input.click_index(x, y));
+        }
+        if let PlayerEvent::TextControl {
+            code: TextControlCode::Paste,
+        } = event
+        {
+            // The clipboard belongs to the host, so its content has to be recorded too.
+            recorder.record_clipboard(self.ui.clipboard_content());
+        }
+        recorder.record(&event);
+        event
+    }
+
+    fn dispatch_event(&mut self, event: PlayerEvent) -> bool {
+        #[cfg(feature = "input_log")]
+        let event = self.record_event(event);
+
         match event {
             PlayerEvent::FocusGained | PlayerEvent::FocusLost => self.handle_focus_event(event),
             PlayerEvent::KeyDown { .. }
@@ -1003,11 +1244,16 @@ impl Player {
             | PlayerEvent::MouseUp { .. }
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
+            | PlayerEvent::MouseMotion { .. }
             | PlayerEvent::MouseWheel { .. }
             | PlayerEvent::GamepadButtonDown { .. }
             | PlayerEvent::GamepadButtonUp { .. }
             | PlayerEvent::TextInput { .. }
-            | PlayerEvent::TextControl { .. } => self.handle_input_event(event),
+            | PlayerEvent::TextControl { .. }
+            | PlayerEvent::Ime(_) => self.handle_input_event(event),
+            PlayerEvent::TouchBegin { .. }
+            | PlayerEvent::TouchMove { .. }
+            | PlayerEvent::TouchEnd { .. } => self.handle_touch_event(event),
         }
     }
 
@@ -1022,6 +1268,29 @@ impl Player {
         true
     }
 
+    /// Touch points are dispatched as touch or gesture events,
+    /// depending on `Multitouch.inputMode`.
+    ///
+    /// Frontends also report the primary touch point as mouse input,
+    /// which is handled separately as a regular input event.
+    fn handle_touch_event(&mut self, event: PlayerEvent) -> bool {
+        let (phase, id, x, y, pressure) = match event {
+            PlayerEvent::TouchBegin { id, x, y, pressure } => {
+                (TouchPhase::Begin, id, x, y, pressure)
+            }
+            PlayerEvent::TouchMove { id, x, y, pressure } => (TouchPhase::Move, id, x, y, pressure),
+            PlayerEvent::TouchEnd { id, x, y } => (TouchPhase::End, id, x, y, None),
+            _ => return false,
+        };
+
+        self.mutate_with_update_context(|context| {
+            let position = context.stage.inverse_view_matrix() * Point::from_pixels(x, y);
+            let handled = touch::handle_touch(context, phase, id, position, pressure);
+            Self::run_actions(context);
+            handled
+        })
+    }
+
     /// Input event handling is a complicated affair, involving several different
     /// concerns that need to resolve with specific priority.
     ///
@@ -1050,6 +1319,15 @@ impl Player {
             return false;
         };
 
+        if let PlayerEvent::MouseMove { .. } | PlayerEvent::MouseMotion { .. } = event {
+            // While the mouse is locked, its position is frozen and only relative motion is reported.
+            let mouse_locked =
+                self.mutate_with_update_context(|context| context.stage.mouse_lock());
+            if mouse_locked != matches!(event, PlayerEvent::MouseMotion { .. }) {
+                return false;
+            }
+        }
+
         let prev_mouse_buttons = self.input.get_mouse_down_buttons();
         self.input.handle_event(&event);
         let changed_mouse_buttons = self
@@ -1132,7 +1410,28 @@ impl Player {
         }
 
         self.mutate_with_update_context(|context| {
-            let button_event = ButtonKeyCode::from_player_event(event)
+            // Remember the mouse movement (in stage pixels) for `MouseEvent.movementX/Y`.
+            let inverse_view_matrix = context.stage.inverse_view_matrix();
+            match event {
+                PlayerEvent::MouseMove { x, y } => {
+                    let position = inverse_view_matrix * Point::from_pixels(x, y);
+                    context.mouse_data.movement = (
+                        (position.x - context.mouse_position.x).to_pixels(),
+                        (position.y - context.mouse_position.y).to_pixels(),
+                    );
+                }
+                PlayerEvent::MouseMotion { dx, dy } => {
+                    let origin = inverse_view_matrix * Point::ZERO;
+                    let moved = inverse_view_matrix * Point::from_pixels(dx, dy);
+                    context.mouse_data.movement = (
+                        (moved.x - origin.x).to_pixels(),
+                        (moved.y - origin.y).to_pixels(),
+                    );
+                }
+                _ => {}
+            }
+
+            let button_event = ButtonKeyCode::from_player_event(&event)
                 .map(|key_code| ClipEvent::KeyPress { key_code });
 
             if let PlayerEvent::KeyDown { key_code, key_char }
@@ -1199,7 +1498,7 @@ impl Player {
                 PlayerEvent::KeyUp { .. } => {
                     (Some(ClipEvent::KeyUp), Some(("Key", "onKeyUp", vec![])))
                 }
-                PlayerEvent::MouseMove { .. } => (
+                PlayerEvent::MouseMove { .. } | PlayerEvent::MouseMotion { .. } => (
                     Some(ClipEvent::MouseMove),
                     Some(("Mouse", "onMouseMove", vec![])),
                 ),
@@ -1285,6 +1584,9 @@ impl Player {
                     if let PlayerEvent::TextControl { code } = event {
                         text.text_control_input(code, context);
                     }
+                    if let PlayerEvent::Ime(ime) = &event {
+                        text.ime(ime.clone(), context);
+                    }
                 }
             }
 
@@ -1355,6 +1657,12 @@ impl Player {
             ) {
                 self.needs_render = true;
             }
+        } else if let PlayerEvent::MouseMotion { .. } = event {
+            // The locked mouse doesn't change its position, but the motion is
+            // still reported to the object under it as a mouse move.
+            if self.update_mouse_state(&changed_mouse_buttons, true, &mut player_event_handled) {
+                self.needs_render = true;
+            }
         }
 
         if let PlayerEvent::MouseWheel { delta } = event {
@@ -1384,7 +1692,7 @@ impl Player {
             }
         }
 
-        if self.should_reset_highlight(event) {
+        if self.should_reset_highlight(&event) {
             self.mutate_with_update_context(|context| {
                 context.focus_tracker.reset_highlight();
             });
@@ -1393,7 +1701,7 @@ impl Player {
         player_event_handled
     }
 
-    fn should_reset_highlight(&self, event: PlayerEvent) -> bool {
+    fn should_reset_highlight(&self, event: &PlayerEvent) -> bool {
         if matches!(
             event,
             PlayerEvent::MouseDown {
@@ -1485,7 +1793,7 @@ impl Player {
         is_mouse_moved: bool,
         player_event_handled: &mut bool,
     ) -> bool {
-        let mut new_cursor = self.mouse_cursor;
+        let mut new_cursor = self.mouse_cursor.clone();
         let mut mouse_cursor_needs_check = self.mouse_cursor_needs_check;
         let mouse_in_stage = self.mouse_in_stage();
 
@@ -1789,11 +2097,8 @@ impl Player {
             needs_render
         });
 
-        // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
-        }
+        self.mouse_cursor = new_cursor;
+        self.update_mouse_cursor();
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
 
         needs_render
@@ -1934,6 +2239,7 @@ impl Player {
             return;
         }
 
+        self.gc_scheduler.start_frame();
         self.update(|context| {
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
@@ -1946,6 +2252,8 @@ impl Player {
             for cb in std::mem::take(context.post_frame_callbacks) {
                 (cb.callback)(context, cb.data);
             }
+
+            context.library.flush_persistent_cache_if_due();
         });
 
         self.needs_render = true;
@@ -2006,6 +2314,91 @@ impl Player {
         self.needs_render = false;
     }
 
+    /// Renders the stage into an offscreen bitmap and reads the result back.
+    ///
+    /// Unlike `render`, this works with any render backend that supports
+    /// `render_offscreen`, and doesn't present anything to the screen.
+    /// The resulting bitmap is the size of the viewport, in premultiplied RGBA.
+    /// Returns `None` if the backend can't render offscreen.
+    pub fn render_to_bitmap(&mut self) -> Option<Bitmap> {
+        let invalidated = self.enter_arena(|_, gc_root, _| gc_root.stage.invalidated());
+
+        if invalidated {
+            self.update(|context| {
+                let stage = context.stage;
+                stage.broadcast_render(context);
+            });
+        }
+
+        let viewport = self.renderer.viewport_dimensions();
+        let (width, height) = (viewport.width, viewport.height);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let (background_color, quality, commands) =
+            self.enter_arena_mut(|gc_context, gc_root, this| {
+                let stage = gc_root.stage;
+
+                let mut cache_draws = vec![];
+                let mut render_context = RenderContext {
+                    renderer: this.renderer.deref_mut(),
+                    commands: CommandList::new(),
+                    cache_draws: &mut cache_draws,
+                    gc_context,
+                    library: &gc_root.library,
+                    transform_stack: &mut this.transform_stack,
+                    is_offscreen: true,
+                    use_bitmap_cache: false,
+                    stage,
+                };
+
+                stage.render(&mut render_context);
+
+                let background_color = if stage.window_mode() != WindowMode::Transparent {
+                    stage.background_color().unwrap_or(Color::WHITE)
+                } else {
+                    Color::from_rgba(0)
+                };
+
+                (background_color, stage.quality(), render_context.commands)
+            });
+
+        // Offscreen targets aren't cleared, so start from the background color.
+        let background = [
+            background_color.r,
+            background_color.g,
+            background_color.b,
+            background_color.a,
+        ];
+        let data = background.repeat((width * height) as usize);
+        let handle = self
+            .renderer
+            .register_bitmap(Bitmap::new(width, height, BitmapFormat::Rgba, data))
+            .ok()?;
+
+        let sync_handle = self.renderer.render_offscreen(
+            handle,
+            commands,
+            quality,
+            PixelRegion::for_whole_size(width, height),
+        )?;
+
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        self.renderer
+            .resolve_sync_handle(
+                sync_handle,
+                Box::new(|buffer, buffer_width| {
+                    for row in buffer.chunks(buffer_width as usize).take(height as usize) {
+                        pixels.extend_from_slice(&row[..(width * 4) as usize]);
+                    }
+                }),
+            )
+            .ok()?;
+
+        Some(Bitmap::new(width, height, BitmapFormat::Rgba, pixels))
+    }
+
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     pub fn current_frame(&self) -> Option<u16> {
@@ -2186,6 +2579,7 @@ impl Player {
                 local_connections,
                 post_frame_callbacks,
                 mouse_data,
+                touch_data,
                 dynamic_root,
             ) = gc_root.update_context_params();
 
@@ -2203,6 +2597,7 @@ impl Player {
                 strings: StringContext::from_parts(gc_context, interner),
                 stage,
                 mouse_data,
+                touch_data,
                 input: &this.input,
                 mouse_position: &this.mouse_position,
                 drag_object,
@@ -2214,6 +2609,7 @@ impl Player {
                 storage: this.storage.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
+                workers: this.workers.as_deref(),
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -2223,7 +2619,8 @@ impl Player {
                 avm1,
                 avm2,
                 external_interface,
-                start_time: this.start_time,
+                clock: this.clock,
+                gc_scheduler: &mut this.gc_scheduler,
                 update_start: Instant::now(),
                 max_execution_duration: this.max_execution_duration,
                 focus_tracker: stage.focus_tracker(),
@@ -2307,12 +2704,53 @@ impl Player {
         });
         self.update_mouse_state(&HashSet::new(), false, &mut false);
 
-        // GC
-        self.gc_arena.borrow_mut().collect_debt();
+        self.collect_garbage();
 
         rval
     }
 
+    /// Runs the garbage collection work that is due, and any full collection
+    /// requested by scripts.
+    fn collect_garbage(&mut self) {
+        let mut arena = self.gc_arena.borrow_mut();
+        let metrics = arena.metrics().clone();
+        metrics.set_pacing(self.gc_scheduler.arena_pacing());
+
+        let start = Instant::now();
+        if self.gc_scheduler.take_requested_collection(&metrics) {
+            arena.collect_all();
+            self.gc_scheduler.finish_cycle(&metrics);
+        } else {
+            // The work owed for this frame is added to the collector's debt as
+            // if it was memory allocated outside of the arena, and then freed.
+            let frame_debt = self.gc_scheduler.take_frame_debt();
+            metrics.mark_external_allocation(frame_debt);
+            arena.collect_debt();
+            metrics.mark_external_deallocation(frame_debt);
+        }
+        let collecting = !matches!(arena.collection_phase(), CollectionPhase::Sleeping);
+
+        self.gc_scheduler
+            .record_collection(&metrics, start.elapsed(), collecting);
+    }
+
+    /// Returns how garbage collection is paced.
+    pub fn gc_pacing(&self) -> GcPacing {
+        self.gc_scheduler.pacing()
+    }
+
+    /// Returns statistics about garbage collection so far.
+    pub fn gc_stats(&self) -> GcStats {
+        self.gc_scheduler.stats()
+    }
+
+    /// Writes any new entries of the persistent cache to its backend.
+    pub fn flush_persistent_cache(&mut self) {
+        self.mutate_with_update_context(|context| {
+            context.library.flush_persistent_cache();
+        });
+    }
+
     pub fn flush_shared_objects(&mut self) {
         self.update(|context| {
             if let Some(mut avm1_activation) =
@@ -2402,6 +2840,11 @@ impl Player {
         &self.log
     }
 
+    /// All stubs that have been encountered by this player so far.
+    pub fn stub_tracker(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -2446,6 +2889,7 @@ pub struct PlayerBuilder {
     storage: Option<Storage>,
     ui: Option<Ui>,
     video: Option<Video>,
+    workers: Option<Workers>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -2473,7 +2917,22 @@ pub struct PlayerBuilder {
     fs_command_provider: Box<dyn FsCommandProvider>,
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
+    #[cfg(feature = "known_stubs")]
+    stub_coverage_output: Option<std::path::PathBuf>,
+    #[cfg(feature = "known_stubs")]
+    stub_coverage_reference: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    avm2_inline_caches_enabled: bool,
+    #[cfg(feature = "jit")]
+    avm2_jit_mode: crate::avm2::JitMode,
+    gc_pacing: GcPacing,
+    timezone: Option<Timezone>,
+    deterministic_options: Option<DeterministicOptions>,
+    #[cfg(feature = "input_log")]
+    input_replay: Option<InputInjector>,
+    #[cfg(feature = "input_log")]
+    record_input: bool,
+    persistent_cache: Option<Box<dyn CacheBackend>>,
 }
 
 impl PlayerBuilder {
@@ -2493,6 +2952,7 @@ impl PlayerBuilder {
             storage: None,
             ui: None,
             video: None,
+            workers: None,
 
             autoplay: false,
             align: StageAlign::default(),
@@ -2524,7 +2984,22 @@ impl PlayerBuilder {
             fs_command_provider: Box::new(NullFsCommandProvider),
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
+            #[cfg(feature = "known_stubs")]
+            stub_coverage_output: None,
+            #[cfg(feature = "known_stubs")]
+            stub_coverage_reference: None,
             avm2_optimizer_enabled: true,
+            avm2_inline_caches_enabled: true,
+            #[cfg(feature = "jit")]
+            avm2_jit_mode: Default::default(),
+            gc_pacing: Default::default(),
+            timezone: None,
+            deterministic_options: None,
+            #[cfg(feature = "input_log")]
+            input_replay: None,
+            #[cfg(feature = "input_log")]
+            record_input: false,
+            persistent_cache: None,
         }
     }
 
@@ -2598,6 +3073,14 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the worker backend of the player, used to tessellate shapes and
+    /// decode bitmaps off the main thread while movies are preloading.
+    #[inline]
+    pub fn with_workers(mut self, workers: impl 'static + WorkerBackend) -> Self {
+        self.workers = Some(Box::new(workers));
+        self
+    }
+
     /// Sets the stage scale mode and optionally prevents movies from changing it.
     #[inline]
     pub fn with_align(mut self, align: StageAlign, force: bool) -> Self {
@@ -2728,11 +3211,92 @@ impl PlayerBuilder {
         self
     }
 
+    #[cfg(feature = "known_stubs")]
+    /// Sets the output path for the API coverage report. When set, the player
+    /// will write the report to this path and exit the process.
+    pub fn with_stub_coverage_output(mut self, output: std::path::PathBuf) -> Self {
+        self.stub_coverage_output = Some(output);
+        self
+    }
+
+    #[cfg(feature = "known_stubs")]
+    /// Sets the specification of the full Flash Player API to compare against
+    /// in the API coverage report, in the same format as the stub report.
+    /// Without it, only APIs declared in our `playerglobal` are reported.
+    pub fn with_stub_coverage_reference(mut self, reference: std::path::PathBuf) -> Self {
+        self.stub_coverage_reference = Some(reference);
+        self
+    }
+
     pub fn with_avm2_optimizer_enabled(mut self, value: bool) -> Self {
         self.avm2_optimizer_enabled = value;
         self
     }
 
+    /// Sets whether the AVM2 optimizer attaches inline caches to property
+    /// accesses on receivers of an unknown type. Has no effect when the
+    /// optimizer is disabled.
+    pub fn with_avm2_inline_caches_enabled(mut self, value: bool) -> Self {
+        self.avm2_inline_caches_enabled = value;
+        self
+    }
+
+    #[cfg(feature = "jit")]
+    /// Sets which AVM2 methods are compiled to native code. Methods using
+    /// anything the compiler doesn't support are always interpreted.
+    pub fn with_avm2_jit_mode(mut self, mode: crate::avm2::JitMode) -> Self {
+        self.avm2_jit_mode = mode;
+        self
+    }
+
+    /// Sets how garbage collection is paced, trading pause length against
+    /// memory usage.
+    pub fn with_gc_pacing(mut self, pacing: GcPacing) -> Self {
+        self.gc_pacing = pacing;
+        self
+    }
+
+    /// Sets the timezone used to compute the local time of dates.
+    /// Defaults to the timezone of the host system.
+    ///
+    /// When running deterministically, the timezone of the deterministic options is used instead.
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Runs the player deterministically: `Math.random()` is seeded with the given seed,
+    /// and scripts see time from a virtual clock which only advances as frames run.
+    pub fn with_deterministic_options(mut self, options: DeterministicOptions) -> Self {
+        self.deterministic_options = Some(options);
+        self
+    }
+
+    /// Replays recorded input, one frame's worth of events before each frame.
+    /// Input sent by the host is ignored until the replay has finished.
+    ///
+    /// This is only reproducible when running deterministically.
+    #[cfg(feature = "input_log")]
+    pub fn with_input_replay(mut self, replay: InputInjector) -> Self {
+        self.input_replay = Some(replay);
+        self
+    }
+
+    /// Records the input delivered to the player, so that the session can be replayed later.
+    #[cfg(feature = "input_log")]
+    pub fn with_input_recording(mut self, enabled: bool) -> Self {
+        self.record_input = enabled;
+        self
+    }
+
+    /// Keeps verified AVM2 methods, tessellated shapes and decoded bitmaps of
+    /// each movie in the given backend, so that they can be reused when the
+    /// same movie is played again.
+    pub fn with_persistent_cache(mut self, backend: Box<dyn CacheBackend>) -> Self {
+        self.persistent_cache = Some(backend);
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2773,7 +3337,11 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                cursor_name: None,
+                registered_cursors: HashMap::new(),
+                movement: (0.0, 0.0),
             },
+            touch_data: TouchData::default(),
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
             stage: Stage::empty(gc_context, fullscreen, fake_movie),
@@ -2839,6 +3407,7 @@ impl PlayerBuilder {
                 storage,
                 ui,
                 video,
+                workers: self.workers,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2850,7 +3419,10 @@ impl PlayerBuilder {
                 frame_phase: Default::default(),
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
-                start_time: Instant::now(),
+                clock: self.deterministic_options.as_ref().map_or_else(
+                    || Clock::real(self.timezone.unwrap_or_else(get_timezone)),
+                    Clock::new_virtual,
+                ),
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
@@ -2862,9 +3434,19 @@ impl PlayerBuilder {
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                displayed_cursor: MouseCursor::Arrow,
+                cursor_time: 0.0,
+                time_til_next_cursor_frame: None,
+                #[cfg(feature = "input_log")]
+                input_replay: self.input_replay,
+                #[cfg(feature = "input_log")]
+                input_recorder: self.record_input.then(InputRecorder::new),
 
                 // Misc. state
-                rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
+                rng: SmallRng::seed_from_u64(self.deterministic_options.as_ref().map_or_else(
+                    || get_current_date_time().timestamp_millis() as u64,
+                    |options| options.seed,
+                )),
                 system: SystemProperties::new(),
                 page_url: self.page_url.clone(),
                 transform_stack: TransformStack::new(),
@@ -2897,6 +3479,7 @@ impl PlayerBuilder {
                         self.fs_command_provider,
                     )
                 }))),
+                gc_scheduler: GcScheduler::new(self.gc_pacing),
             })
         });
 
@@ -2945,6 +3528,16 @@ impl PlayerBuilder {
             context
                 .avm2
                 .set_optimizer_enabled(self.avm2_optimizer_enabled);
+            context
+                .avm2
+                .set_inline_caches_enabled(self.avm2_inline_caches_enabled);
+            #[cfg(feature = "jit")]
+            context.avm2.set_jit_mode(self.avm2_jit_mode);
+            if let Some(backend) = self.persistent_cache {
+                context
+                    .library
+                    .set_persistent_cache(PersistentCache::new(backend));
+            }
             Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
 
             let stage = context.stage;
@@ -2956,8 +3549,13 @@ impl PlayerBuilder {
             stage.post_instantiation(context, None, Instantiator::Movie, false);
             stage.build_matrices(context);
             #[cfg(feature = "known_stubs")]
-            if let Some(stub_path) = self.stub_report_output {
-                crate::avm2::specification::capture_specification(context, &stub_path);
+            if self.stub_report_output.is_some() || self.stub_coverage_output.is_some() {
+                crate::avm2::specification::capture_specification(
+                    context,
+                    self.stub_report_output.as_deref(),
+                    self.stub_coverage_output.as_deref(),
+                    self.stub_coverage_reference.as_deref(),
+                );
             }
         });
         player_lock.audio.set_frame_rate(frame_rate);
@@ -3010,13 +3608,23 @@ pub struct DragObject<'gc> {
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'gc>,
     require_button_mode: bool,
+) -> Option<InteractiveObject<'gc>> {
+    let point = *context.mouse_position;
+    run_mouse_pick_at(context, point, require_button_mode)
+}
+
+/// Find the topmost interactive object at the given point in stage coordinates.
+pub(crate) fn run_mouse_pick_at<'gc>(
+    context: &mut UpdateContext<'gc>,
+    point: Point<Twips>,
+    require_button_mode: bool,
 ) -> Option<InteractiveObject<'gc>> {
     context.stage.iter_render_list().rev().find_map(|level| {
         level.as_interactive().and_then(|l| {
             if l.as_displayobject().movie().is_action_script_3() {
                 let mut res = None;
                 if let Avm2MousePick::Hit(target) =
-                    l.mouse_pick_avm2(context, *context.mouse_position, require_button_mode)
+                    l.mouse_pick_avm2(context, point, require_button_mode)
                 {
                     // Flash Player appears to never target events at the root object
                     if !target.as_displayobject().is_root() {
@@ -3026,7 +3634,7 @@ fn run_mouse_pick<'gc>(
 
                 res
             } else {
-                l.mouse_pick_avm1(context, *context.mouse_position, require_button_mode)
+                l.mouse_pick_avm1(context, point, require_button_mode)
             }
         })
     })
@@ -3055,3 +3663,60 @@ impl FromStr for PlayerRuntime {
         Ok(player_runtime)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registered_cursor(frame_count: usize, frame_rate: f64) -> RegisteredCursor {
+        let frames: Vec<_> = (0..frame_count)
+            .map(|_| CursorImage {
+                width: 1,
+                height: 1,
+                rgba: vec![0; 4],
+            })
+            .collect();
+        RegisteredCursor {
+            frames: frames.into(),
+            hotspot: (0, 0),
+            frame_rate,
+        }
+    }
+
+    /// The displayed frame and the time until the next one.
+    fn frame_at(cursor: &RegisteredCursor, time: f64) -> (usize, Option<f64>) {
+        match cursor.cursor_at(time) {
+            (MouseCursor::Custom(custom), time_til_next_frame) => {
+                (custom.frame, time_til_next_frame)
+            }
+            (cursor, _) => panic!("Expected a custom cursor, got {cursor:?}"),
+        }
+    }
+
+    #[test]
+    fn animated_cursor() {
+        // At 4 frames per second, each frame is displayed for 250ms.
+        let cursor = registered_cursor(3, 4.0);
+        assert_eq!(cursor.frame_duration(), Some(250.0));
+        assert_eq!(frame_at(&cursor, 0.0), (0, Some(250.0)));
+        assert_eq!(frame_at(&cursor, 100.0), (0, Some(150.0)));
+        assert_eq!(frame_at(&cursor, 250.0), (1, Some(250.0)));
+        assert_eq!(frame_at(&cursor, 600.0), (2, Some(150.0)));
+        // The animation loops back to the first frame.
+        assert_eq!(frame_at(&cursor, 800.0), (0, Some(200.0)));
+    }
+
+    #[test]
+    fn static_cursor() {
+        for cursor in [
+            registered_cursor(1, 4.0),
+            registered_cursor(3, 0.0),
+            registered_cursor(3, -4.0),
+            registered_cursor(3, f64::NAN),
+            registered_cursor(3, f64::INFINITY),
+        ] {
+            assert_eq!(cursor.frame_duration(), None);
+            assert_eq!(frame_at(&cursor, 600.0), (0, None));
+        }
+    }
+}
@@ -22,17 +22,17 @@ use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
-    FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
-    SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
-    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Header as FlvHeader, Keyframe as FlvKeyframe, KeyframeIndex as FlvKeyframeIndex,
+    ScriptData as FlvScriptData, SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate,
+    SoundSize as FlvSoundSize, SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData,
+    Value as FlvValue, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::bitmap::BitmapInfo;
 use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
-use std::io::{Seek, SeekFrom};
+use std::io::Seek;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -164,7 +164,6 @@ impl Eq for NetStream<'_> {}
 pub enum NetStreamType {
     /// The stream is an FLV.
     Flv {
-        #[allow(dead_code)] // set but never read
         header: FlvHeader,
 
         /// The currently playing video track's stream instance.
@@ -179,6 +178,101 @@ pub enum NetStreamType {
     },
 }
 
+/// The seek points of the part of an FLV that was loaded when they were
+/// indexed.
+#[derive(Clone, Debug)]
+struct FlvSeekIndex {
+    /// The length of the buffer when it was indexed.
+    buffer_length: usize,
+
+    /// The buffer position of the first tag.
+    start: usize,
+
+    /// The buffer position after the last complete tag.
+    end: usize,
+
+    /// The largest timestamp of any tag, in milliseconds.
+    duration: i32,
+
+    /// The tags that seeks snap to, in file order.
+    ///
+    /// These are the video keyframes, or every audio tag if the FLV has no
+    /// video keyframes.
+    points: Vec<FlvKeyframe>,
+}
+
+impl FlvSeekIndex {
+    fn build(buffer: &[u8], data_offset: usize) -> Result<Self, FlvError> {
+        let mut reader = FlvReader::from_parts(buffer, data_offset);
+        let FlvKeyframeIndex {
+            keyframes,
+            duration,
+        } = FlvKeyframeIndex::build(&mut reader)?;
+        let end = reader.into_parts().1;
+
+        let points = if keyframes.is_empty() {
+            // Any audio tag can be played from, so audio-only streams seek
+            // by tag timestamp.
+            let mut reader = FlvReader::from_parts(buffer, data_offset);
+            let mut points: Vec<FlvKeyframe> = vec![];
+            loop {
+                let position = reader.stream_position()?;
+                let tag = match FlvTag::parse(&mut reader) {
+                    Ok(tag) => tag,
+                    Err(FlvError::EndOfData) => break,
+                    Err(e) => return Err(e),
+                };
+
+                if matches!(tag.data, FlvTagData::Audio(_))
+                    && points
+                        .last()
+                        .is_none_or(|last| last.timestamp != tag.timestamp)
+                {
+                    points.push(FlvKeyframe {
+                        timestamp: tag.timestamp,
+                        position,
+                    });
+                }
+            }
+            points
+        } else {
+            keyframes
+        };
+
+        Ok(Self {
+            buffer_length: buffer.len(),
+            start: data_offset,
+            end,
+            duration,
+            points,
+        })
+    }
+
+    /// Find the buffer position and time to seek to.
+    ///
+    /// Seeking backward snaps to the prior seek point, or the first tag if
+    /// there is none. Seeking forward snaps to the next seek point, or the
+    /// end of the data if there is none.
+    fn seek_point(&self, from: f64, to: f64) -> (usize, f64) {
+        if from > to {
+            self.points
+                .iter()
+                .rev()
+                .find(|point| f64::from(point.timestamp) <= to)
+                .map_or((self.start, 0.0), |point| {
+                    (point.position as usize, f64::from(point.timestamp))
+                })
+        } else {
+            self.points
+                .iter()
+                .find(|point| f64::from(point.timestamp) >= to)
+                .map_or((self.end, f64::from(self.duration)), |point| {
+                    (point.position as usize, f64::from(point.timestamp))
+                })
+        }
+    }
+}
+
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub struct NetStreamData<'gc> {
@@ -218,6 +312,10 @@ pub struct NetStreamData<'gc> {
     /// Seeks are only executed on the next stream tick.
     queued_seek_time: Option<f64>,
 
+    /// The keyframes of an FLV, built on the first seek.
+    #[collect(require_static)]
+    flv_seek_index: Option<FlvSeekIndex>,
+
     /// The number of seconds of video data that should be buffered. This is
     /// currently unsupported and changing it has no effect.
     buffer_time: f64,
@@ -267,6 +365,7 @@ impl<'gc> NetStream<'gc> {
                 stream_type: None,
                 stream_time: 0.0,
                 queued_seek_time: None,
+                flv_seek_index: None,
                 buffer_time: 0.1,
                 last_decoded_bitmap: None,
                 avm_object,
@@ -319,6 +418,7 @@ impl<'gc> NetStream<'gc> {
         write.stream_type = None;
         write.stream_time = 0.0;
         write.queued_seek_time = None;
+        write.flv_seek_index = None;
         write.audio_stream = None;
         write.sound_instance = None;
         write.expected_length = Some(0);
@@ -415,7 +515,8 @@ impl<'gc> NetStream<'gc> {
     /// Seek to a new position in the stream.
     ///
     /// All existing audio will be paused. The stream offset will be snapped to
-    /// either the prior or next keyframe depending on seek direction. If the
+    /// either the prior or next keyframe depending on seek direction, or to
+    /// the prior or next audio tag in streams without video keyframes. If the
     /// stream is playing then new tag processing will occur when the stream
     /// ticks next.
     ///
@@ -453,77 +554,29 @@ impl<'gc> NetStream<'gc> {
             write.audio_stream = None;
         }
 
-        if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
+        if let Some(NetStreamType::Flv { header, .. }) = &write.stream_type {
+            let data_offset = header.data_offset as usize;
             let slice = write.buffer.to_full_slice();
             let buffer = slice.data();
-            let mut reader = FlvReader::from_parts(&buffer, write.offset);
-            let skipping_back = write.stream_time > offset;
 
-            loop {
-                if skipping_back {
-                    let res = FlvTag::skip_back(&mut reader);
-                    if matches!(res, Err(FlvError::EndOfData)) {
-                        //At start of video, can't skip further back
-                        break;
-                    }
-
-                    if let Err(e) = res {
-                        tracing::error!("FLV tag parsing failed during seek backward: {}", e);
-                        break;
+            // The index only covers the data loaded when it was built.
+            let index = match write.flv_seek_index.take() {
+                Some(index) if index.buffer_length == buffer.len() => Some(index),
+                _ => match FlvSeekIndex::build(&buffer, data_offset) {
+                    Ok(index) => Some(index),
+                    Err(e) => {
+                        tracing::error!("FLV tag parsing failed during seek: {}", e);
+                        None
                     }
-                }
-
-                let old_position = reader
-                    .stream_position()
-                    .expect("valid stream position when seeking");
-
-                let tag = FlvTag::parse(&mut reader);
-                if matches!(tag, Err(FlvError::EndOfData)) {
-                    //At end of video, can't skip further forward
-                    break;
-                }
-
-                if let Err(e) = tag {
-                    tracing::error!("FLV tag parsing failed during seek forward: {}", e);
-                    break;
-                }
-
-                if skipping_back {
-                    //Tag position won't actually move backwards if we don't do this.
-                    reader
-                        .seek(SeekFrom::Start(old_position))
-                        .expect("valid backseek position");
-                }
-
-                let tag = tag.unwrap();
-                write.stream_time = tag.timestamp as f64;
-
-                if skipping_back && write.stream_time > offset
-                    || !skipping_back && write.stream_time < offset
-                {
-                    continue;
-                }
+                },
+            };
 
-                match tag.data {
-                    FlvTagData::Video(FlvVideoData {
-                        frame_type: FlvFrameType::Keyframe,
-                        ..
-                    }) => {
-                        // If we don't backseek when we find the keyframe,
-                        // we will miss the keyframe.
-                        reader
-                            .seek(SeekFrom::Start(old_position))
-                            .expect("valid backseek position");
-
-                        break;
-                    }
-                    _ => continue,
-                }
+            if let Some(index) = index {
+                let (position, time) = index.seek_point(write.stream_time, offset);
+                write.offset = position;
+                write.stream_time = time;
+                write.flv_seek_index = Some(index);
             }
-
-            write.offset = reader
-                .stream_position()
-                .expect("FLV reader stream position") as usize;
         }
 
         drop(write);
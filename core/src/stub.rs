@@ -5,23 +5,41 @@ use std::fmt::{Debug, Display, Formatter};
 
 #[cfg(feature = "known_stubs")]
 #[linkme::distributed_slice]
-pub static KNOWN_STUBS: [Stub] = [..];
+pub static KNOWN_STUBS: [KnownStub] = [..];
 
 #[cfg(feature = "known_stubs")]
 mod external {
     include!(concat!(env!("OUT_DIR"), "/actionscript_stubs.rs"));
 }
 
+/// A stub along with the place in Ruffle's source where it's declared.
 #[cfg(feature = "known_stubs")]
-pub fn get_known_stubs() -> FnvHashSet<&'static Stub> {
-    let mut result = FnvHashSet::default();
-    for stub in KNOWN_STUBS.iter() {
-        result.insert(stub);
-    }
-    for stub in external::AS_DEFINED_STUBS {
-        result.insert(stub);
+#[derive(Debug)]
+pub struct KnownStub {
+    pub stub: &'static Stub,
+    pub file: &'static str,
+    pub line: u32,
+}
+
+#[cfg(feature = "known_stubs")]
+impl KnownStub {
+    /// The `file:line` where this stub is declared.
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
     }
-    result
+}
+
+/// Every stub declared in Rust or in our ActionScript globals, with their call sites.
+#[cfg(feature = "known_stubs")]
+pub fn get_known_stub_locations() -> impl Iterator<Item = &'static KnownStub> {
+    KNOWN_STUBS.iter().chain(external::AS_DEFINED_STUBS)
+}
+
+#[cfg(feature = "known_stubs")]
+pub fn get_known_stubs() -> FnvHashSet<&'static Stub> {
+    get_known_stub_locations()
+        .map(|known_stub| known_stub.stub)
+        .collect()
 }
 
 #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Clone)]
@@ -151,15 +169,28 @@ impl StubCollection {
     }
 }
 
+/// Registers a stub static in `KNOWN_STUBS`, recording the call site of the
+/// outermost stub macro.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! register_known_stub {
+    ($stub: ident) => {
+        #[cfg(feature = "known_stubs")]
+        #[linkme::distributed_slice($crate::stub::KNOWN_STUBS)]
+        static KNOWN_STUB: $crate::stub::KnownStub = $crate::stub::KnownStub {
+            stub: &$stub,
+            file: file!(),
+            line: line!(),
+        };
+    };
+}
+
 #[macro_export]
 macro_rules! context_stub {
     ($context: ident, $message: literal) => {
-        #[cfg_attr(
-            feature = "known_stubs",
-            linkme::distributed_slice($crate::stub::KNOWN_STUBS)
-        )]
         static STUB: $crate::stub::Stub =
             $crate::stub::Stub::Other(std::borrow::Cow::Borrowed($message));
+        $crate::register_known_stub!(STUB);
         $context.stub_tracker.encounter(&STUB);
     };
 }
@@ -0,0 +1,672 @@
+//! Touch input handling and gesture recognition
+
+use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
+use crate::context::UpdateContext;
+use crate::display_object::{
+    lowest_common_ancestor, DisplayObject, InteractiveObject, TDisplayObject, TInteractiveObject,
+};
+use crate::player::run_mouse_pick_at;
+use gc_arena::Collect;
+use std::time::Duration;
+use swf::{Point, Twips};
+
+/// The longest time a touch point may be in contact to be recognized as a swipe.
+const SWIPE_MAX_DURATION: Duration = Duration::from_millis(500);
+
+/// The shortest distance (in pixels) a touch point has to travel to be recognized as a swipe.
+const SWIPE_MIN_DISTANCE: f64 = 50.0;
+
+/// Determines how touch input is interpreted, see `flash.ui.Multitouch.inputMode`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MultitouchInputMode {
+    /// Touch input is recognized as gestures, such as zooming or rotating.
+    #[default]
+    Gesture,
+
+    /// Touch input is interpreted only as mouse input.
+    None,
+
+    /// Touch points are reported as they are, using touch events.
+    TouchPoint,
+}
+
+impl MultitouchInputMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "gesture" => MultitouchInputMode::Gesture,
+            "none" => MultitouchInputMode::None,
+            "touchPoint" => MultitouchInputMode::TouchPoint,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MultitouchInputMode::Gesture => "gesture",
+            MultitouchInputMode::None => "none",
+            MultitouchInputMode::TouchPoint => "touchPoint",
+        }
+    }
+}
+
+/// The phase of a touch point's contact with the screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchPhase {
+    Begin,
+    Move,
+    End,
+}
+
+/// The state of touch input, i.e. touch points and gestures in progress.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct TouchData<'gc> {
+    #[collect(require_static)]
+    input_mode: MultitouchInputMode,
+
+    /// Touch points which are currently in contact with the screen.
+    points: Vec<TouchPoint<'gc>>,
+
+    /// The two-finger gesture in progress, if any.
+    gesture: Option<TransformGesture<'gc>>,
+}
+
+impl TouchData<'_> {
+    pub fn input_mode(&self) -> MultitouchInputMode {
+        self.input_mode
+    }
+
+    pub fn set_input_mode(&mut self, input_mode: MultitouchInputMode) {
+        if self.input_mode != input_mode {
+            // Touch points and gestures in progress are not carried over.
+            self.points.clear();
+            self.gesture = None;
+        }
+        self.input_mode = input_mode;
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TouchPoint<'gc> {
+    #[collect(require_static)]
+    id: i32,
+
+    /// Whether this is the first point of contact, which is also mapped to mouse events.
+    #[collect(require_static)]
+    is_primary: bool,
+
+    /// The current position of the touch point in stage coordinates.
+    #[collect(require_static)]
+    position: Point<Twips>,
+
+    #[collect(require_static)]
+    start_position: Point<Twips>,
+
+    /// The time since the SWF was launched at which the touch point was put down.
+    #[collect(require_static)]
+    start_time: Duration,
+
+    /// The object which the touch point has started on.
+    begin_target: Option<InteractiveObject<'gc>>,
+
+    /// The object which is currently under the touch point.
+    over_target: Option<InteractiveObject<'gc>>,
+
+    /// Whether this touch point has taken part in a two-finger gesture.
+    #[collect(require_static)]
+    in_gesture: bool,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TransformGesture<'gc> {
+    /// The object which receives gesture events.
+    target: DisplayObject<'gc>,
+
+    /// The distance between touch points (in pixels) at the last update.
+    #[collect(require_static)]
+    distance: f64,
+
+    /// The angle between touch points (in degrees) at the last update.
+    #[collect(require_static)]
+    angle: f64,
+
+    /// The point between touch points at the last update.
+    #[collect(require_static)]
+    center: Point<Twips>,
+
+    #[collect(require_static)]
+    zooming: bool,
+
+    #[collect(require_static)]
+    rotating: bool,
+
+    #[collect(require_static)]
+    panning: bool,
+}
+
+/// Information about a touch point carried by touch events.
+#[derive(Clone, Copy)]
+struct TouchInfo {
+    id: i32,
+    is_primary: bool,
+    position: Point<Twips>,
+    pressure: Option<f64>,
+}
+
+/// A single transform gesture event waiting to be dispatched.
+struct PendingGesture {
+    event_type: &'static str,
+    phase: &'static str,
+    scale: f64,
+    rotation: f64,
+    offset: (f64, f64),
+}
+
+/// Handle a touch point event, dispatching touch or gesture events
+/// depending on the current `Multitouch.inputMode`.
+///
+/// The position is given in stage coordinates.
+/// Returns `true` if any of the dispatched events have been handled.
+pub fn handle_touch<'gc>(
+    context: &mut UpdateContext<'gc>,
+    phase: TouchPhase,
+    id: i32,
+    position: Point<Twips>,
+    pressure: Option<f64>,
+) -> bool {
+    // Touch and gesture events are available only in AVM2.
+    if !context.swf.is_action_script_3() {
+        return false;
+    }
+
+    let input_mode = context.touch_data.input_mode;
+    if input_mode == MultitouchInputMode::None {
+        // The primary touch point is reported by the frontend as mouse input.
+        return false;
+    }
+
+    match phase {
+        TouchPhase::Begin => touch_begin(context, input_mode, id, position, pressure),
+        TouchPhase::Move => touch_move(context, input_mode, id, position, pressure),
+        TouchPhase::End => touch_end(context, input_mode, id, position, pressure),
+    }
+}
+
+fn touch_begin<'gc>(
+    context: &mut UpdateContext<'gc>,
+    input_mode: MultitouchInputMode,
+    id: i32,
+    position: Point<Twips>,
+    pressure: Option<f64>,
+) -> bool {
+    let target = run_mouse_pick_at(context, position, true);
+
+    // A touch point with the same ID may be left over when its end was never reported.
+    context.touch_data.points.retain(|point| point.id != id);
+    let is_primary = context.touch_data.points.is_empty();
+    context.touch_data.points.push(TouchPoint {
+        id,
+        is_primary,
+        position,
+        start_position: position,
+        start_time: context.clock.elapsed(),
+        begin_target: target,
+        over_target: target,
+        in_gesture: false,
+    });
+
+    let info = TouchInfo {
+        id,
+        is_primary,
+        position,
+        pressure,
+    };
+
+    match input_mode {
+        MultitouchInputMode::TouchPoint => {
+            let mut handled = dispatch_touch_over(context, info, None, target);
+            handled |= dispatch_touch_event(
+                context,
+                "touchBegin",
+                target_or_stage(context, target),
+                info,
+                None,
+                true,
+            );
+            handled
+        }
+        MultitouchInputMode::Gesture => {
+            begin_gesture(context);
+            false
+        }
+        MultitouchInputMode::None => false,
+    }
+}
+
+fn touch_move<'gc>(
+    context: &mut UpdateContext<'gc>,
+    input_mode: MultitouchInputMode,
+    id: i32,
+    position: Point<Twips>,
+    pressure: Option<f64>,
+) -> bool {
+    let Some(point) = context
+        .touch_data
+        .points
+        .iter_mut()
+        .find(|point| point.id == id)
+    else {
+        return false;
+    };
+    point.position = position;
+    let is_primary = point.is_primary;
+    let prev_over_target = point.over_target;
+
+    let info = TouchInfo {
+        id,
+        is_primary,
+        position,
+        pressure,
+    };
+
+    match input_mode {
+        MultitouchInputMode::TouchPoint => {
+            let over_target = run_mouse_pick_at(context, position, true);
+            let mut handled = false;
+            if !InteractiveObject::option_ptr_eq(prev_over_target, over_target) {
+                if let Some(point) = find_point_mut(context, id) {
+                    point.over_target = over_target;
+                }
+                if let Some(prev_over_target) = prev_over_target {
+                    handled |= dispatch_touch_out(context, info, prev_over_target, over_target);
+                }
+                handled |= dispatch_touch_over(context, info, prev_over_target, over_target);
+            }
+            handled |= dispatch_touch_event(
+                context,
+                "touchMove",
+                target_or_stage(context, over_target),
+                info,
+                None,
+                true,
+            );
+            handled
+        }
+        MultitouchInputMode::Gesture => update_gesture(context),
+        MultitouchInputMode::None => false,
+    }
+}
+
+fn touch_end<'gc>(
+    context: &mut UpdateContext<'gc>,
+    input_mode: MultitouchInputMode,
+    id: i32,
+    position: Point<Twips>,
+    pressure: Option<f64>,
+) -> bool {
+    let Some(index) = context
+        .touch_data
+        .points
+        .iter()
+        .position(|point| point.id == id)
+    else {
+        return false;
+    };
+    let mut point = context.touch_data.points.remove(index);
+    point.position = position;
+
+    let info = TouchInfo {
+        id,
+        is_primary: point.is_primary,
+        position,
+        pressure,
+    };
+
+    match input_mode {
+        MultitouchInputMode::TouchPoint => {
+            let over_target = run_mouse_pick_at(context, position, true);
+            let mut handled = dispatch_touch_event(
+                context,
+                "touchEnd",
+                target_or_stage(context, over_target),
+                info,
+                None,
+                true,
+            );
+
+            // Lifting the finger over the object it was put on is a tap.
+            if InteractiveObject::option_ptr_eq(point.begin_target, over_target) {
+                handled |= dispatch_touch_event(
+                    context,
+                    "touchTap",
+                    target_or_stage(context, over_target),
+                    info,
+                    None,
+                    true,
+                );
+            }
+
+            if let Some(over_target) = over_target.or(point.over_target) {
+                handled |= dispatch_touch_out(context, info, over_target, None);
+            }
+            handled
+        }
+        MultitouchInputMode::Gesture => {
+            if point.in_gesture {
+                end_gesture(context)
+            } else {
+                dispatch_swipe(context, &point)
+            }
+        }
+        MultitouchInputMode::None => false,
+    }
+}
+
+fn find_point_mut<'a, 'gc>(
+    context: &'a mut UpdateContext<'gc>,
+    id: i32,
+) -> Option<&'a mut TouchPoint<'gc>> {
+    context
+        .touch_data
+        .points
+        .iter_mut()
+        .find(|point| point.id == id)
+}
+
+fn target_or_stage<'gc>(
+    context: &UpdateContext<'gc>,
+    target: Option<InteractiveObject<'gc>>,
+) -> DisplayObject<'gc> {
+    target
+        .map(|target| target.as_displayobject())
+        .unwrap_or_else(|| context.stage.into())
+}
+
+fn dispatch_touch_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    event_type: &'static str,
+    target: DisplayObject<'gc>,
+    info: TouchInfo,
+    related_object: Option<InteractiveObject<'gc>>,
+    bubbles: bool,
+) -> bool {
+    let Some(object) = target.object2().as_object() else {
+        return false;
+    };
+
+    let mut activation = Avm2Activation::from_nothing(context);
+    let event = Avm2EventObject::touch_event(
+        &mut activation,
+        event_type,
+        target,
+        info.id,
+        info.is_primary,
+        info.position,
+        info.pressure,
+        related_object,
+        bubbles,
+    );
+    Avm2::dispatch_event(activation.context, event, object)
+}
+
+/// Dispatch `touchOut` on the object which is no longer under the touch point,
+/// and `touchRollOut` on it and its ancestors which are not shared with the new object.
+fn dispatch_touch_out<'gc>(
+    context: &mut UpdateContext<'gc>,
+    info: TouchInfo,
+    from: InteractiveObject<'gc>,
+    to: Option<InteractiveObject<'gc>>,
+) -> bool {
+    let mut handled =
+        dispatch_touch_event(context, "touchOut", from.as_displayobject(), info, to, true);
+
+    let lca = lowest_common_ancestor(from.as_displayobject(), target_or_stage(context, to));
+    let mut rollout_target = Some(from.as_displayobject());
+    while let Some(target) = rollout_target {
+        if DisplayObject::option_ptr_eq(rollout_target, lca) {
+            break;
+        }
+
+        handled |= dispatch_touch_event(context, "touchRollOut", target, info, to, false);
+        rollout_target = target.parent();
+    }
+
+    handled
+}
+
+/// Dispatch `touchRollOver` on the object which is now under the touch point
+/// and its ancestors which are not shared with the previous object, followed by `touchOver`.
+fn dispatch_touch_over<'gc>(
+    context: &mut UpdateContext<'gc>,
+    info: TouchInfo,
+    from: Option<InteractiveObject<'gc>>,
+    to: Option<InteractiveObject<'gc>>,
+) -> bool {
+    let Some(to) = to else {
+        return false;
+    };
+
+    let lca = lowest_common_ancestor(to.as_displayobject(), target_or_stage(context, from));
+    let mut handled = false;
+    let mut rollover_target = Some(to.as_displayobject());
+    while let Some(target) = rollover_target {
+        if DisplayObject::option_ptr_eq(rollover_target, lca) {
+            break;
+        }
+
+        handled |= dispatch_touch_event(context, "touchRollOver", target, info, from, false);
+        rollover_target = target.parent();
+    }
+
+    handled |= dispatch_touch_event(
+        context,
+        "touchOver",
+        to.as_displayobject(),
+        info,
+        from,
+        true,
+    );
+    handled
+}
+
+/// Returns the distance (in pixels), the angle (in degrees)
+/// and the center point between two touch points.
+fn measure(a: Point<Twips>, b: Point<Twips>) -> (f64, f64, Point<Twips>) {
+    let dx = (b.x - a.x).to_pixels();
+    let dy = (b.y - a.y).to_pixels();
+    let center = Point::from_pixels(
+        (a.x.to_pixels() + b.x.to_pixels()) / 2.0,
+        (a.y.to_pixels() + b.y.to_pixels()) / 2.0,
+    );
+    (dx.hypot(dy), dy.atan2(dx).to_degrees(), center)
+}
+
+/// Start a two-finger gesture when the second touch point is in contact.
+fn begin_gesture<'gc>(context: &mut UpdateContext<'gc>) {
+    if context.touch_data.gesture.is_some() || context.touch_data.points.len() != 2 {
+        return;
+    }
+
+    let points = &mut context.touch_data.points;
+    let (distance, angle, center) = measure(points[0].position, points[1].position);
+    for point in points.iter_mut() {
+        point.in_gesture = true;
+    }
+
+    let target = run_mouse_pick_at(context, center, false);
+    let target = target_or_stage(context, target);
+    context.touch_data.gesture = Some(TransformGesture {
+        target,
+        distance,
+        angle,
+        center,
+        zooming: false,
+        rotating: false,
+        panning: false,
+    });
+}
+
+/// Dispatch zoom, rotate and pan gestures according to the movement of touch points.
+fn update_gesture<'gc>(context: &mut UpdateContext<'gc>) -> bool {
+    let touch_data = &mut *context.touch_data;
+    let (Some(gesture), [a, b, ..]) = (&mut touch_data.gesture, &touch_data.points[..]) else {
+        return false;
+    };
+
+    let (distance, angle, center) = measure(a.position, b.position);
+    let scale = if gesture.distance > 0.0 {
+        distance / gesture.distance
+    } else {
+        1.0
+    };
+    let mut rotation = angle - gesture.angle;
+    if rotation > 180.0 {
+        rotation -= 360.0;
+    } else if rotation <= -180.0 {
+        rotation += 360.0;
+    }
+    let offset = (
+        (center.x - gesture.center.x).to_pixels(),
+        (center.y - gesture.center.y).to_pixels(),
+    );
+
+    let mut pending = Vec::new();
+    if scale != 1.0 {
+        pending.push(PendingGesture {
+            event_type: "gestureZoom",
+            phase: if gesture.zooming { "update" } else { "begin" },
+            scale,
+            rotation: 0.0,
+            offset: (0.0, 0.0),
+        });
+        gesture.zooming = true;
+    }
+    if rotation != 0.0 {
+        pending.push(PendingGesture {
+            event_type: "gestureRotate",
+            phase: if gesture.rotating { "update" } else { "begin" },
+            scale: 1.0,
+            rotation,
+            offset: (0.0, 0.0),
+        });
+        gesture.rotating = true;
+    }
+    if offset != (0.0, 0.0) {
+        pending.push(PendingGesture {
+            event_type: "gesturePan",
+            phase: if gesture.panning { "update" } else { "begin" },
+            scale: 1.0,
+            rotation: 0.0,
+            offset,
+        });
+        gesture.panning = true;
+    }
+
+    gesture.distance = distance;
+    gesture.angle = angle;
+    gesture.center = center;
+    let target = gesture.target;
+
+    let mut handled = false;
+    for pending in pending {
+        handled |= dispatch_transform_gesture(context, target, center, pending);
+    }
+    handled
+}
+
+/// Finish the two-finger gesture when one of its touch points is lifted.
+fn end_gesture<'gc>(context: &mut UpdateContext<'gc>) -> bool {
+    let Some(gesture) = context.touch_data.gesture.take() else {
+        return false;
+    };
+
+    let ended = [
+        (gesture.zooming, "gestureZoom"),
+        (gesture.rotating, "gestureRotate"),
+        (gesture.panning, "gesturePan"),
+    ];
+    if !ended.iter().any(|(active, _)| *active) {
+        // Both fingers have been put and lifted without moving.
+        let Some(object) = gesture.target.object2().as_object() else {
+            return false;
+        };
+        let mut activation = Avm2Activation::from_nothing(context);
+        let event = Avm2EventObject::gesture_event(
+            &mut activation,
+            "gestureTwoFingerTap",
+            gesture.target,
+            "all",
+            gesture.center,
+        );
+        return Avm2::dispatch_event(activation.context, event, object);
+    }
+
+    let mut handled = false;
+    for (_, event_type) in ended.into_iter().filter(|(active, _)| *active) {
+        let pending = PendingGesture {
+            event_type,
+            phase: "end",
+            scale: 1.0,
+            rotation: 0.0,
+            offset: (0.0, 0.0),
+        };
+        handled |= dispatch_transform_gesture(context, gesture.target, gesture.center, pending);
+    }
+    handled
+}
+
+/// Dispatch a swipe gesture if the lifted touch point has moved quickly enough.
+fn dispatch_swipe<'gc>(context: &mut UpdateContext<'gc>, point: &TouchPoint<'gc>) -> bool {
+    if context.clock.elapsed().saturating_sub(point.start_time) > SWIPE_MAX_DURATION {
+        return false;
+    }
+
+    let dx = (point.position.x - point.start_position.x).to_pixels();
+    let dy = (point.position.y - point.start_position.y).to_pixels();
+    if dx.hypot(dy) < SWIPE_MIN_DISTANCE {
+        return false;
+    }
+
+    // Swipes are reported only along the dominant axis.
+    let offset = if dx.abs() >= dy.abs() {
+        (dx.signum(), 0.0)
+    } else {
+        (0.0, dy.signum())
+    };
+
+    let target = target_or_stage(context, point.begin_target);
+    let pending = PendingGesture {
+        event_type: "gestureSwipe",
+        phase: "all",
+        scale: 1.0,
+        rotation: 0.0,
+        offset,
+    };
+    dispatch_transform_gesture(context, target, point.position, pending)
+}
+
+fn dispatch_transform_gesture<'gc>(
+    context: &mut UpdateContext<'gc>,
+    target: DisplayObject<'gc>,
+    position: Point<Twips>,
+    pending: PendingGesture,
+) -> bool {
+    let Some(object) = target.object2().as_object() else {
+        return false;
+    };
+
+    let mut activation = Avm2Activation::from_nothing(context);
+    let event = Avm2EventObject::transform_gesture_event(
+        &mut activation,
+        pending.event_type,
+        target,
+        pending.phase,
+        position,
+        pending.scale,
+        pending.rotation,
+        pending.offset,
+    );
+    Avm2::dispatch_event(activation.context, event, object)
+}
@@ -39,6 +39,7 @@ debug-menu-open-stage = View Stage Info
 debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
+debug-menu-open-memory = Show Memory Usage
 debug-menu-search-display-objects = Search Display Objects...
 
 view-menu = View
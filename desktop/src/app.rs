@@ -1,3 +1,4 @@
+use crate::backends::DesktopUiBackend;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
@@ -16,7 +17,10 @@ use std::time::Instant;
 use url::Url;
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
-use winit::event::{ElementState, KeyEvent, Modifiers, StartCause, WindowEvent};
+use winit::event::{
+    DeviceEvent, DeviceId, ElementState, KeyEvent, Modifiers, StartCause, Touch, TouchPhase,
+    WindowEvent,
+};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Fullscreen, Icon, WindowAttributes, WindowId};
@@ -48,9 +52,9 @@ impl MainWindow {
                 if let Some(mut player) = self.player.get() {
                     // Even if the movie is paused, user interaction with debug tools can change the render output
                     player.render();
-                    self.gui.render(Some(player));
+                    self.gui.render(Some(player), event_loop);
                 } else {
-                    self.gui.render(None);
+                    self.gui.render(None, event_loop);
                 }
                 plot_stats_in_tracy(&self.gui.descriptors().wgpu_instance);
             }
@@ -193,6 +197,43 @@ impl MainWindow {
                 self.player.handle_event(PlayerEvent::MouseLeave);
                 self.check_redraw();
             }
+            WindowEvent::Ime(ime) => {
+                use ruffle_core::events::ImeEvent;
+                use winit::event::Ime;
+                let event = match ime {
+                    Ime::Preedit(text, cursor) => ImeEvent::Preedit(text, cursor),
+                    Ime::Commit(text) => ImeEvent::Commit(text),
+                    // Disabling the IME clears any ongoing composition.
+                    Ime::Disabled => ImeEvent::Preedit(String::new(), None),
+                    Ime::Enabled => return,
+                };
+                self.player.handle_event(PlayerEvent::Ime(event));
+                self.check_redraw();
+            }
+            WindowEvent::Touch(Touch {
+                phase,
+                location,
+                force,
+                id,
+                ..
+            }) => {
+                if self.gui.is_context_menu_visible() {
+                    return;
+                }
+
+                // Touch point IDs are only required to be unique among active touches.
+                let id = id as i32;
+                let x = location.x;
+                let y = location.y - height_offset;
+                let pressure = force.map(|force| force.normalized());
+                let event = match phase {
+                    TouchPhase::Started => PlayerEvent::TouchBegin { id, x, y, pressure },
+                    TouchPhase::Moved => PlayerEvent::TouchMove { id, x, y, pressure },
+                    TouchPhase::Ended | TouchPhase::Cancelled => PlayerEvent::TouchEnd { id, x, y },
+                };
+                self.player.handle_event(event);
+                self.check_redraw();
+            }
             WindowEvent::ModifiersChanged(new_modifiers) => {
                 self.modifiers = new_modifiers;
             }
@@ -340,7 +381,24 @@ impl MainWindow {
         }
     }
 
-    fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
+    fn device_event(&mut self, event: DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta: (dx, dy) } = event {
+            // Relative motion is only reported while the movie has locked the mouse.
+            let mouse_locked = self.player.get().is_some_and(|player| {
+                player
+                    .ui()
+                    .downcast_ref::<DesktopUiBackend>()
+                    .is_some_and(|ui| ui.mouse_locked())
+            });
+            if mouse_locked {
+                self.player
+                    .handle_event(PlayerEvent::MouseMotion { dx, dy });
+                self.check_redraw();
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop, gilrs: Option<&mut Gilrs>) {
         if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
             match event {
                 EventType::ButtonPressed(button, _) => {
@@ -372,6 +430,7 @@ impl MainWindow {
                 if let Some(mut player) = self.player.get() {
                     player.tick(dt as f64 / 1000.0);
                     self.next_frame_time = Some(new_time + player.time_til_next_frame());
+                    self.gui.animate_custom_cursor(&player, event_loop);
                 } else {
                     self.next_frame_time = None;
                 }
@@ -593,9 +652,20 @@ impl ApplicationHandler<RuffleEvent> for App {
         }
     }
 
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let Some(main_window) = &mut self.main_window {
+            main_window.device_event(event);
+        }
+    }
+
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         if let Some(main_window) = &mut self.main_window {
-            main_window.about_to_wait(self.gilrs.as_mut());
+            main_window.about_to_wait(event_loop, self.gilrs.as_mut());
 
             // The event loop is finished; let's find out how long we need to wait for
             // (but don't change something that's already requesting a sooner update, or we'll delay it)
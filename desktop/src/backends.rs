@@ -1,10 +1,14 @@
+mod cache;
 mod external_interface;
 mod fscommand;
 mod navigator;
 mod ui;
+mod workers;
 
+pub use cache::DiskCacheBackend;
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
 pub use ui::DesktopUiBackend;
+pub use workers::ThreadPoolWorkerBackend;
@@ -0,0 +1,163 @@
+use ruffle_core::backend::cache::CacheBackend;
+use std::fs::{self, File};
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Keeps the persistent cache of movies as files in a directory.
+///
+/// The modification time of each file is updated whenever it's read, and the
+/// least recently used files are removed when the directory grows larger than
+/// the size limit.
+pub struct DiskCacheBackend {
+    directory: PathBuf,
+    max_size: u64,
+}
+
+impl DiskCacheBackend {
+    pub fn new(directory: PathBuf, max_size: u64) -> Self {
+        Self {
+            directory,
+            max_size,
+        }
+    }
+
+    fn evict(&self) -> io::Result<()> {
+        let mut files = Vec::new();
+        let mut total_size = 0;
+        for entry in fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            total_size += metadata.len();
+            files.push((metadata.modified()?, metadata.len(), entry.path()));
+        }
+
+        files.sort_unstable_by_key(|(modified, _, _)| *modified);
+        for (_, size, path) in files {
+            if total_size <= self.max_size {
+                break;
+            }
+
+            fs::remove_file(&path)?;
+            total_size -= size;
+        }
+
+        Ok(())
+    }
+}
+
+impl CacheBackend for DiskCacheBackend {
+    fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        let path = self.directory.join(key);
+        let data = fs::read(&path).ok()?;
+
+        // Mark this movie as recently used.
+        if let Err(e) = File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            tracing::warn!("Couldn't update cache file {}: {e}", path.display());
+        }
+
+        Some(data)
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) {
+        if let Err(e) = fs::create_dir_all(&self.directory) {
+            tracing::warn!(
+                "Couldn't create cache directory {}: {e}",
+                self.directory.display()
+            );
+            return;
+        }
+
+        // Write to a temporary file first, so that a crash never leaves a
+        // partially written cache file behind.
+        let path = self.directory.join(key);
+        let temp_path = self.directory.join(format!("{key}.tmp"));
+        if let Err(e) = fs::write(&temp_path, value).and_then(|_| fs::rename(&temp_path, &path)) {
+            tracing::warn!("Couldn't write cache file {}: {e}", path.display());
+            let _ = fs::remove_file(&temp_path);
+            return;
+        }
+
+        if let Err(e) = self.evict() {
+            tracing::warn!("Couldn't clean up cache directory: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn set_age(path: &Path, secs: u64) {
+        File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(secs)))
+            .unwrap();
+    }
+
+    fn file_names(directory: &Path) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn keeps_files_within_size_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskCacheBackend::new(tmp_dir.path().join("cache"), 8);
+        backend.put("a", b"1234");
+        backend.put("b", b"5678");
+
+        assert_eq!(file_names(&tmp_dir.path().join("cache")), ["a", "b"]);
+        assert_eq!(backend.get("a").as_deref(), Some(&b"1234"[..]));
+        assert_eq!(backend.get("c"), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskCacheBackend::new(tmp_dir.path().to_path_buf(), 10);
+        backend.put("a", b"1234");
+        backend.put("b", b"5678");
+        set_age(&tmp_dir.path().join("a"), 200);
+        set_age(&tmp_dir.path().join("b"), 100);
+
+        // Reading "a" makes "b" the least recently used file.
+        assert!(backend.get("a").is_some());
+        backend.put("c", b"9012");
+
+        assert_eq!(file_names(tmp_dir.path()), ["a", "c"]);
+        assert_eq!(backend.get("b"), None);
+    }
+
+    #[test]
+    fn evicts_until_within_size_limit() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskCacheBackend::new(tmp_dir.path().to_path_buf(), 6);
+        backend.put("a", b"12");
+        backend.put("b", b"34");
+        backend.put("c", b"56");
+        set_age(&tmp_dir.path().join("a"), 300);
+        set_age(&tmp_dir.path().join("b"), 200);
+        set_age(&tmp_dir.path().join("c"), 100);
+
+        backend.put("d", b"7890");
+
+        assert_eq!(file_names(tmp_dir.path()), ["c", "d"]);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::cli::OpenUrlMode;
 use crate::custom_event::RuffleEvent;
 use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
-use crate::gui::{DialogDescriptor, FilePicker, LocalizableText};
+use crate::gui::{DialogDescriptor, FilePicker, LocalizableText, MENU_HEIGHT};
 use crate::preferences::GlobalPreferences;
 use anyhow::Error;
 use chrono::{DateTime, Utc};
@@ -11,16 +11,18 @@ use rfd::{
     AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    CustomCursor, DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
 };
+use ruffle_core::swf::{Rectangle, Twips};
 use std::rc::Rc;
 use std::sync::Arc;
 use tracing::error;
 use url::Url;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event_loop::EventLoopProxy;
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::{Fullscreen, Window};
+use winit::window::{CursorGrabMode, Fullscreen, Window};
 
 pub struct DesktopFileDialogResult {
     handle: Option<FileHandle>,
@@ -120,6 +122,7 @@ pub struct DesktopUiBackend {
     window: Arc<Window>,
     event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
+    mouse_locked: bool,
     clipboard: Clipboard,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
@@ -148,6 +151,7 @@ impl DesktopUiBackend {
             window,
             event_loop,
             cursor_visible: true,
+            mouse_locked: false,
             clipboard,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
@@ -156,13 +160,30 @@ impl DesktopUiBackend {
         })
     }
 
+    /// The custom cursor set by the movie, if it should be displayed.
+    pub fn custom_cursor(&self) -> Option<&CustomCursor> {
+        match &self.preferred_cursor {
+            MouseCursor::Custom(cursor) if self.cursor_visible && !self.mouse_locked => {
+                Some(cursor)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the movie has locked the mouse, see `Stage.mouseLock`.
+    pub fn mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+
     pub fn cursor(&self) -> egui::CursorIcon {
-        if self.cursor_visible {
+        if self.cursor_visible && !self.mouse_locked {
             match self.preferred_cursor {
                 MouseCursor::Arrow => egui::CursorIcon::Default,
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
                 MouseCursor::IBeam => egui::CursorIcon::Text,
                 MouseCursor::Grab => egui::CursorIcon::Grab,
+                // The window cursor is replaced by `GuiController`.
+                MouseCursor::Custom(_) => egui::CursorIcon::Default,
             }
         } else {
             egui::CursorIcon::None
@@ -183,6 +204,24 @@ impl UiBackend for DesktopUiBackend {
         self.preferred_cursor = cursor;
     }
 
+    fn set_mouse_lock(&mut self, locked: bool) -> bool {
+        let result = if locked {
+            // Not every platform supports locking the cursor in place.
+            self.window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined))
+        } else {
+            self.window.set_cursor_grab(CursorGrabMode::None)
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Couldn't change the mouse lock: {e}");
+            return false;
+        }
+        self.mouse_locked = locked;
+        true
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.get().unwrap_or_default()
     }
@@ -200,6 +239,11 @@ impl UiBackend for DesktopUiBackend {
         Ok(())
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        let size = self.window.current_monitor()?.size();
+        Some((size.width, size.height))
+    }
+
     fn display_root_movie_download_failed_message(&self, _invalid_swf: bool) {
         let _ = self
             .event_loop
@@ -322,6 +366,25 @@ impl UiBackend for DesktopUiBackend {
 
     fn close_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, enabled: bool) {
+        self.window.set_ime_allowed(enabled);
+    }
+
+    fn set_ime_cursor_area(&mut self, area: Rectangle<Twips>) {
+        let height_offset = if self.window.fullscreen().is_some() || self.preferences.cli.no_gui {
+            0.0
+        } else {
+            MENU_HEIGHT as f64 * self.window.scale_factor()
+        };
+        self.window.set_ime_cursor_area(
+            PhysicalPosition::new(
+                area.x_min.to_pixels(),
+                area.y_min.to_pixels() + height_offset,
+            ),
+            PhysicalSize::new(area.width().to_pixels(), area.height().to_pixels()),
+        );
+    }
+
     fn language(&self) -> LanguageIdentifier {
         self.preferences.language().clone()
     }
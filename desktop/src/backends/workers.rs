@@ -0,0 +1,83 @@
+use ruffle_core::backend::worker::{Job, WorkerBackend};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Runs preloading jobs on a fixed pool of threads.
+///
+/// The threads exit once the player, and with it this backend, is dropped.
+/// Jobs which didn't start by then are dropped without running.
+pub struct ThreadPoolWorkerBackend {
+    sender: Sender<Job>,
+
+    /// Set once the backend is dropped.
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ThreadPoolWorkerBackend {
+    /// Creates a pool with one thread per core, minus one for the main thread.
+    pub fn new() -> Self {
+        let num_threads = thread::available_parallelism()
+            .map_or(1, |n| n.get().saturating_sub(1))
+            .max(1);
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        for i in 0..num_threads {
+            let receiver = receiver.clone();
+            let shutdown = shutdown.clone();
+            if let Err(e) = thread::Builder::new()
+                .name(format!("Ruffle worker {i}"))
+                .spawn(move || Self::run(&receiver, &shutdown))
+            {
+                tracing::error!("Couldn't spawn worker thread: {e}");
+            }
+        }
+
+        Self { sender, shutdown }
+    }
+
+    fn run(receiver: &Mutex<Receiver<Job>>, shutdown: &AtomicBool) {
+        loop {
+            let job = receiver.lock().expect("Worker lock poisoned").recv();
+            let Ok(job) = job else {
+                // The backend was dropped, and no jobs are left.
+                return;
+            };
+            if shutdown.load(Ordering::Relaxed) {
+                // The backend was dropped, so nobody waits for the remaining
+                // jobs anymore.
+                return;
+            }
+
+            // A panicking job drops its result, so it's handled like a failed one.
+            if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                tracing::error!("Worker job panicked");
+            }
+        }
+    }
+}
+
+impl WorkerBackend for ThreadPoolWorkerBackend {
+    fn spawn(&self, job: Job) {
+        if let Err(mpsc::SendError(job)) = self.sender.send(job) {
+            // All worker threads are gone, so run the job right away instead.
+            job();
+        }
+    }
+}
+
+impl Drop for ThreadPoolWorkerBackend {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Default for ThreadPoolWorkerBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,7 +5,7 @@ use clap::{Parser, ValueEnum};
 use ruffle_core::backend::navigator::SocketMode;
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
-use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode, Timezone};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::path::Path;
@@ -238,11 +238,26 @@ pub struct Opt {
     )]
     pub gamepad_button: Vec<(GamepadButton, KeyCode)>,
 
+    /// The timezone used to compute the local time of dates, such as `Europe/London`.
+    /// Defaults to the timezone of the system.
+    #[clap(long)]
+    pub timezone: Option<Timezone>,
+
     /// Disable AVM2 optimizer.
     /// Note that some early opcode conversions
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Keep verified scripts, tessellated shapes and decoded bitmaps of played movies
+    /// in the cache directory, so that they load faster the next time.
+    #[clap(long)]
+    pub persistent_cache: bool,
+
+    /// Maximum size of the persistent cache, in megabytes.
+    /// The least recently used movies are removed when it grows larger than this.
+    #[clap(long, default_value = "512")]
+    pub persistent_cache_size: u64,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
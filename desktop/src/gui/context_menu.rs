@@ -25,7 +25,7 @@ impl ContextMenu {
     }
 
     pub fn close_event(&self) -> PlayerEvent {
-        self.close_event
+        self.close_event.clone()
     }
 
     pub fn show(
@@ -8,6 +8,7 @@ use crate::preferences::GlobalPreferences;
 use anyhow::anyhow;
 use egui::{Context, ViewportId};
 use fontdb::{Database, Family, Query, Source};
+use ruffle_core::backend::ui::CustomCursor as MovieCustomCursor;
 use ruffle_core::{Player, PlayerEvent};
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -19,9 +20,9 @@ use url::Url;
 use wgpu::SurfaceError;
 use winit::dpi::PhysicalSize;
 use winit::event::WindowEvent;
-use winit::event_loop::EventLoopProxy;
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::keyboard::{Key, NamedKey};
-use winit::window::{Theme, Window};
+use winit::window::{CursorIcon, CustomCursor, Theme, Window};
 
 use super::{DialogDescriptor, FilePicker};
 
@@ -43,6 +44,15 @@ pub struct GuiController {
     /// If this is set, we should not render the main menu.
     no_gui: bool,
     theme_controller: ThemeController,
+    /// The custom cursor of the movie which is currently applied to the window.
+    custom_cursor: Option<AppliedCustomCursor>,
+}
+
+struct AppliedCustomCursor {
+    cursor: MovieCustomCursor,
+
+    /// Window cursors created for frames of the cursor, as they get displayed.
+    frames: Vec<Option<CustomCursor>>,
 }
 
 impl GuiController {
@@ -148,9 +158,78 @@ impl GuiController {
             size,
             no_gui,
             theme_controller,
+            custom_cursor: None,
         })
     }
 
+    /// Shows the current frame of the custom cursor applied by the last render.
+    ///
+    /// Animated cursors advance as the player ticks, which doesn't always
+    /// cause a redraw. Switching to a different cursor still waits for one.
+    pub fn animate_custom_cursor(&mut self, player: &Player, event_loop: &ActiveEventLoop) {
+        let Some(applied) = &self.custom_cursor else {
+            return;
+        };
+        let ui = player
+            .ui()
+            .downcast_ref::<DesktopUiBackend>()
+            .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"));
+        if let Some(cursor) = ui.custom_cursor() {
+            if Arc::ptr_eq(&applied.cursor.frames, &cursor.frames) {
+                self.apply_custom_cursor(cursor.clone(), event_loop);
+            }
+        }
+    }
+
+    fn apply_custom_cursor(&mut self, cursor: MovieCustomCursor, event_loop: &ActiveEventLoop) {
+        let applied = match self.custom_cursor.take() {
+            Some(applied) if applied.cursor == cursor => {
+                self.custom_cursor = Some(applied);
+                return;
+            }
+            // Another frame of the same cursor, window cursors can be reused.
+            Some(mut applied)
+                if Arc::ptr_eq(&applied.cursor.frames, &cursor.frames)
+                    && applied.cursor.hotspot == cursor.hotspot =>
+            {
+                applied.cursor = cursor;
+                self.custom_cursor.insert(applied)
+            }
+            _ => self.custom_cursor.insert(AppliedCustomCursor {
+                frames: vec![None; cursor.frames.len()],
+                cursor,
+            }),
+        };
+
+        let frame = applied.cursor.frame;
+        if applied.frames[frame].is_none() {
+            let image = applied.cursor.image();
+            let (hotspot_x, hotspot_y) = applied.cursor.hotspot;
+            let source = u16::try_from(image.width)
+                .ok()
+                .zip(u16::try_from(image.height).ok())
+                .and_then(|(width, height)| {
+                    CustomCursor::from_rgba(
+                        image.rgba.clone(),
+                        width,
+                        height,
+                        hotspot_x.min(image.width.saturating_sub(1)) as u16,
+                        hotspot_y.min(image.height.saturating_sub(1)) as u16,
+                    )
+                    .map_err(|e| tracing::warn!("Couldn't create custom cursor: {e}"))
+                    .ok()
+                });
+            let Some(source) = source else {
+                return;
+            };
+            applied.frames[frame] = Some(event_loop.create_custom_cursor(source));
+        }
+
+        if let Some(custom_cursor) = &applied.frames[frame] {
+            self.window.set_cursor(custom_cursor.clone());
+        }
+    }
+
     pub fn set_theme(&self, theme: Theme) {
         self.theme_controller.set_theme(theme);
     }
@@ -255,7 +334,7 @@ impl GuiController {
         );
     }
 
-    pub fn render(&mut self, mut player: Option<MutexGuard<Player>>) {
+    pub fn render(&mut self, mut player: Option<MutexGuard<Player>>, event_loop: &ActiveEventLoop) {
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(e @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
@@ -305,17 +384,28 @@ impl GuiController {
             .repaint_delay;
 
         // If we're not in a UI, tell egui which cursor we prefer to use instead
+        let mut custom_cursor = None;
         if !self.egui_winit.egui_ctx().wants_pointer_input() {
             if let Some(player) = player.as_deref() {
-                full_output.platform_output.cursor_icon = player
+                let ui = player
                     .ui()
                     .downcast_ref::<DesktopUiBackend>()
-                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"))
-                    .cursor();
+                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"));
+                full_output.platform_output.cursor_icon = ui.cursor();
+                custom_cursor = ui.custom_cursor().cloned();
             }
         }
+
+        // egui only updates the window cursor when its icon changes,
+        // so restore the icon it expects before handing the cursor back.
+        if custom_cursor.is_none() && self.custom_cursor.take().is_some() {
+            self.window.set_cursor(CursorIcon::Default);
+        }
         self.egui_winit
             .handle_platform_output(&self.window, full_output.platform_output);
+        if let Some(custom_cursor) = custom_cursor {
+            self.apply_custom_cursor(custom_cursor, event_loop);
+        }
 
         let clipped_primitives = self
             .egui_winit
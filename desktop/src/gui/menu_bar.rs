@@ -153,6 +153,12 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::ShowDomains);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-open-memory")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowMemory);
+                            }
+                        }
                         if Button::new(text(locale, "debug-menu-search-display-objects")).ui(ui).clicked() {
                             ui.close_menu();
                             if let Some(player) = &mut player {
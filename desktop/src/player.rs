@@ -1,6 +1,6 @@
 use crate::backends::{
     DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    DesktopUiBackend, DiskCacheBackend, ThreadPoolWorkerBackend,
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
@@ -12,7 +12,7 @@ use anyhow::anyhow;
 use ruffle_core::backend::navigator::SocketMode;
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
-use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent};
+use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent, Timezone};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
 use ruffle_frontend_utils::backends::executor::{AsyncExecutor, PollRequester};
 use ruffle_frontend_utils::backends::navigator::ExternalNavigatorBackend;
@@ -51,6 +51,9 @@ pub struct LaunchOptions {
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub timezone: Option<Timezone>,
+    /// Maximum size of the persistent cache in bytes, or `None` if it's disabled.
+    pub persistent_cache_size: Option<u64>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -101,6 +104,11 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            timezone: value.cli.timezone,
+            persistent_cache_size: value
+                .cli
+                .persistent_cache
+                .then_some(value.cli.persistent_cache_size * 1024 * 1024),
         }
     }
 }
@@ -207,6 +215,8 @@ impl ActivePlayer {
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    timezone: opt.timezone,
+                    persistent_cache_size: opt.persistent_cache_size,
                 })
             }
         };
@@ -290,9 +300,21 @@ impl ActivePlayer {
             builder = builder.with_gamepad_button_mapping(opt.gamepad_button_mapping.clone());
         }
 
+        if let Some(timezone) = opt.timezone {
+            builder = builder.with_timezone(timezone);
+        }
+
+        if let Some(max_size) = opt.persistent_cache_size {
+            builder = builder.with_persistent_cache(Box::new(DiskCacheBackend::new(
+                opt.cache_directory.join("movies"),
+                max_size,
+            )));
+        }
+
         builder = builder
             .with_navigator(navigator)
             .with_renderer(renderer)
+            .with_workers(ThreadPoolWorkerBackend::new())
             .with_storage(preferences.storage_backend().create_backend(&opt))
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
@@ -413,6 +435,14 @@ impl ActivePlayer {
     }
 }
 
+impl Drop for ActivePlayer {
+    fn drop(&mut self) {
+        if let Ok(mut player) = self.player.lock() {
+            player.flush_persistent_cache();
+        }
+    }
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
@@ -0,0 +1,102 @@
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, AudioMixerProxy, DecodeError, RegisterError, SoundHandle,
+    SoundInstanceHandle, SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+
+pub const NUM_CHANNELS: u8 = 2;
+pub const SAMPLE_RATE: u32 = 44100;
+
+/// An audio backend that never plays anything by itself.
+///
+/// Sound is instead pulled out of the mixer by an `AudioCapture`, one frame
+/// at a time, so that the captured audio lines up exactly with captured frames.
+pub struct ExportAudioBackend {
+    mixer: AudioMixer,
+}
+
+impl ExportAudioBackend {
+    pub fn new() -> (Self, AudioCapture) {
+        let mixer = AudioMixer::new(NUM_CHANNELS, SAMPLE_RATE);
+        let capture = AudioCapture {
+            proxy: mixer.proxy(),
+            samples: vec![],
+            frames_mixed: 0,
+        };
+        (Self { mixer }, capture)
+    }
+}
+
+impl AudioBackend for ExportAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+}
+
+/// Mixes the audio of an `ExportAudioBackend` in lockstep with the frames of
+/// the movie.
+pub struct AudioCapture {
+    proxy: AudioMixerProxy,
+    samples: Vec<i16>,
+    frames_mixed: u64,
+}
+
+impl AudioCapture {
+    /// Mixes one frame's worth of audio.
+    ///
+    /// The number of samples per frame is rounded so that the total stays in
+    /// sync with the frame count, even when the sample rate isn't a multiple
+    /// of the frame rate. When `keep` is false, the audio is mixed (so that
+    /// sounds keep progressing) but discarded.
+    pub fn mix_frame(&mut self, frame_rate: f64, keep: bool) {
+        let sample_at = |frames: u64| (frames as f64 * SAMPLE_RATE as f64 / frame_rate).round();
+        let start = sample_at(self.frames_mixed) as usize;
+        let end = sample_at(self.frames_mixed + 1) as usize;
+        self.frames_mixed += 1;
+
+        let mut buffer = vec![0i16; (end - start) * NUM_CHANNELS as usize];
+        self.proxy.mix(&mut buffer);
+
+        if keep {
+            self.samples.extend_from_slice(&buffer);
+        }
+    }
+
+    /// All interleaved samples captured so far.
+    pub fn into_samples(self) -> Vec<i16> {
+        self.samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_frame_keeps_sample_count_in_sync() {
+        let (_backend, mut capture) = ExportAudioBackend::new();
+
+        // 44100 samples per second at 24 frames per second is 1837.5 samples
+        // per frame, so frames alternate between 1838 and 1837 samples.
+        let mut lengths = vec![];
+        for _ in 0..24 {
+            let before = capture.samples.len();
+            capture.mix_frame(24.0, true);
+            lengths.push((capture.samples.len() - before) / usize::from(NUM_CHANNELS));
+        }
+        assert_eq!(lengths[..4], [1838, 1837, 1838, 1837]);
+        assert_eq!(capture.samples.len(), 44100 * usize::from(NUM_CHANNELS));
+    }
+
+    #[test]
+    fn mix_frame_discards_skipped_frames() {
+        let (_backend, mut capture) = ExportAudioBackend::new();
+
+        capture.mix_frame(24.0, false);
+        capture.mix_frame(24.0, true);
+        assert_eq!(
+            capture.into_samples().len(),
+            1837 * usize::from(NUM_CHANNELS)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::audio::{NUM_CHANNELS, SAMPLE_RATE};
+use crate::matroska::write_matroska;
+use anyhow::Result;
+use clap::ValueEnum;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::io::Write;
+use std::time::Duration;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// One PNG image per frame
+    #[default]
+    Png,
+
+    /// A single animated GIF
+    Gif,
+
+    /// A single animated PNG
+    Apng,
+
+    /// A single raw YUV4MPEG2 (4:4:4) video, without transparency.
+    ///
+    /// Frames are converted to limited range YUV, so colors are not kept exactly.
+    Y4m,
+
+    /// A single Matroska video with uncompressed RGBA frames, which also stores the audio.
+    Mkv,
+}
+
+impl OutputFormat {
+    /// The file extension used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::Apng => "png",
+            OutputFormat::Gif => "gif",
+            OutputFormat::Y4m => "y4m",
+            OutputFormat::Mkv => "mkv",
+        }
+    }
+
+    /// Whether all frames are stored in a single file.
+    pub fn is_animated(self) -> bool {
+        self != OutputFormat::Png
+    }
+
+    /// Whether the captured audio is stored in the same file as the frames.
+    pub fn stores_audio(self) -> bool {
+        self == OutputFormat::Mkv
+    }
+}
+
+/// Writes all frames as a single animated file in the given format.
+///
+/// The audio is only written if the format stores it.
+pub fn write_animation(
+    format: OutputFormat,
+    frames: &[RgbaImage],
+    frame_rate: f64,
+    audio: Option<&[i16]>,
+    writer: impl Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Png => unreachable!("PNG output is written frame by frame"),
+        OutputFormat::Gif => write_gif(frames, frame_rate, writer),
+        OutputFormat::Apng => write_apng(frames, frame_rate, writer),
+        OutputFormat::Y4m => write_y4m(frames, frame_rate, writer),
+        OutputFormat::Mkv => write_matroska(frames, frame_rate, audio, writer),
+    }
+}
+
+fn write_gif(frames: &[RgbaImage], frame_rate: f64, writer: impl Write) -> Result<()> {
+    let delay = Delay::from_saturating_duration(Duration::from_secs_f64(1.0 / frame_rate));
+    let mut encoder = GifEncoder::new_with_speed(writer, 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(
+        frames
+            .iter()
+            .map(|image| Frame::from_parts(image.clone(), 0, 0, delay)),
+    )?;
+    Ok(())
+}
+
+fn write_apng(frames: &[RgbaImage], frame_rate: f64, writer: impl Write) -> Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+
+    let mut encoder = png::Encoder::new(writer, first.width(), first.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    // Frame delays are stored as a fraction of a second in 16-bit parts.
+    encoder.set_frame_delay(
+        1000,
+        (frame_rate * 1000.0).round().clamp(1.0, 65535.0) as u16,
+    )?;
+
+    let mut writer = encoder.write_header()?;
+    for image in frames {
+        writer.write_image_data(image.as_raw())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn write_y4m(frames: &[RgbaImage], frame_rate: f64, mut writer: impl Write) -> Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+
+    let (width, height) = first.dimensions();
+    let frame_rate = (frame_rate * 1000.0).round() as u32;
+    writeln!(
+        writer,
+        "YUV4MPEG2 W{width} H{height} F{frame_rate}:1000 Ip A1:1 C444"
+    )?;
+
+    let plane_size = (width * height) as usize;
+    let mut planes = vec![0u8; plane_size * 3];
+    for image in frames {
+        // BT.601 limited range, which is what players assume for Y4M.
+        for (i, pixel) in image.pixels().enumerate() {
+            let [r, g, b, _] = pixel.0.map(f64::from);
+            let y = 16.0 + 0.257 * r + 0.504 * g + 0.098 * b;
+            let u = 128.0 - 0.148 * r - 0.291 * g + 0.439 * b;
+            let v = 128.0 + 0.439 * r - 0.368 * g - 0.071 * b;
+            planes[i] = y.round() as u8;
+            planes[plane_size + i] = u.round() as u8;
+            planes[plane_size * 2 + i] = v.round() as u8;
+        }
+
+        writer.write_all(b"FRAME\n")?;
+        writer.write_all(&planes)?;
+    }
+    Ok(())
+}
+
+/// Writes interleaved 16-bit samples as a PCM WAV file.
+pub fn write_wav(samples: &[i16], mut writer: impl Write) -> Result<()> {
+    let channels = u16::from(NUM_CHANNELS);
+    let block_align = channels * 2;
+    let byte_rate = SAMPLE_RATE * u32::from(block_align);
+    let data_len = (samples.len() * 2) as u32;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_len).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?; // PCM
+    writer.write_all(&channels.to_le_bytes())?;
+    writer.write_all(&SAMPLE_RATE.to_le_bytes())?;
+    writer.write_all(&byte_rate.to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&16u16.to_le_bytes())?;
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.to_le_bytes())?;
+    for sample in samples {
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use std::io::Cursor;
+
+    #[test]
+    fn wav() {
+        let mut data = vec![];
+        write_wav(&[1, -1, 0x1234, -0x1234], &mut data).unwrap();
+
+        let u16_at = |i: usize| u16::from_le_bytes([data[i], data[i + 1]]);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        assert_eq!(data.len(), 44 + 8);
+        assert_eq!(&data[0..4], b"RIFF");
+        assert_eq!(u32_at(4), 36 + 8);
+        assert_eq!(&data[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(16), 16);
+        assert_eq!(u16_at(20), 1, "format is PCM");
+        assert_eq!(u16_at(22), 2, "channels");
+        assert_eq!(u32_at(24), 44100, "sample rate");
+        assert_eq!(u32_at(28), 44100 * 4, "byte rate");
+        assert_eq!(u16_at(32), 4, "block align");
+        assert_eq!(u16_at(34), 16, "bits per sample");
+        assert_eq!(&data[36..40], b"data");
+        assert_eq!(u32_at(40), 8, "data length");
+        assert_eq!(&data[44..], [1, 0, 0xFF, 0xFF, 0x34, 0x12, 0xCC, 0xED]);
+    }
+
+    #[test]
+    fn y4m() {
+        let mut first = RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 255]));
+        first.put_pixel(1, 0, Rgba([255, 255, 255, 255]));
+        let second = RgbaImage::from_pixel(2, 1, Rgba([255, 0, 0, 255]));
+
+        let mut data = vec![];
+        write_y4m(&[first, second], 24.0, &mut data).unwrap();
+
+        let header = b"YUV4MPEG2 W2 H1 F24000:1000 Ip A1:1 C444\n";
+        assert_eq!(&data[..header.len()], header);
+        // Each frame has a full resolution Y, U and V plane, in that order.
+        let frames = &data[header.len()..];
+        assert_eq!(
+            frames,
+            [
+                b"FRAME\n".as_slice(),
+                &[16, 235, 128, 128, 128, 128],
+                b"FRAME\n",
+                &[82, 82, 90, 90, 240, 240],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn apng() {
+        let frames = [
+            RgbaImage::from_pixel(1, 1, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(1, 1, Rgba([0, 255, 0, 128])),
+            RgbaImage::from_pixel(1, 1, Rgba([0, 0, 255, 0])),
+        ];
+
+        let mut data = vec![];
+        write_apng(&frames, 24.0, &mut data).unwrap();
+
+        let mut reader = png::Decoder::new(Cursor::new(data)).read_info().unwrap();
+        let animation = reader.info().animation_control.unwrap();
+        assert_eq!(animation.num_frames, 3);
+        assert_eq!(animation.num_plays, 0, "loops forever");
+
+        let mut buffer = [0; 4];
+        for frame in &frames {
+            reader.next_frame(&mut buffer).unwrap();
+            let control = reader.info().frame_control.unwrap();
+            assert_eq!((control.delay_num, control.delay_den), (1000, 24000));
+            assert_eq!(buffer, frame.as_raw().as_slice());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use ruffle_core::external::FsCommandProvider;
+use std::sync::mpsc;
+
+/// Forwards the fscommands understood by the exporter to the capture loop.
+pub struct ExportFsCommandProvider {
+    sender: mpsc::Sender<FsCommand>,
+}
+
+impl ExportFsCommandProvider {
+    pub fn new() -> (Self, mpsc::Receiver<FsCommand>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { sender }, receiver)
+    }
+}
+
+impl FsCommandProvider for ExportFsCommandProvider {
+    fn on_fs_command(&self, command: &str, args: &str) -> bool {
+        if let Some(command) = FsCommand::from_command(command, args) {
+            // The receiver only goes away once capturing is over.
+            let _ = self.sender.send(command);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum FsCommand {
+    Quit,
+    CaptureImage(String),
+}
+
+impl FsCommand {
+    pub fn from_command(command: &str, args: &str) -> Option<Self> {
+        match command {
+            "quit" => Some(Self::Quit),
+            "captureImage" => Some(Self::CaptureImage(sanitize(args))),
+            _ => None,
+        }
+    }
+}
+
+/// Turns a capture name given by the movie into a safe file name,
+/// so that it can't point outside of the output directory.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture_name(args: &str) -> String {
+        match FsCommand::from_command("captureImage", args) {
+            Some(FsCommand::CaptureImage(name)) => name,
+            other => panic!("Expected a captureImage command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn capture_name_is_kept() {
+        assert_eq!(capture_name("frame-1_final.v2"), "frame-1_final.v2");
+    }
+
+    #[test]
+    fn capture_name_cannot_escape_output() {
+        assert_eq!(capture_name("../../etc/passwd"), ".._.._etc_passwd");
+        assert_eq!(capture_name("..\\windows\\win.ini"), ".._windows_win.ini");
+        assert_eq!(capture_name("/absolute"), "_absolute");
+        assert_eq!(capture_name("C:evil"), "C_evil");
+    }
+}
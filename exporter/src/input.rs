@@ -0,0 +1,18 @@
+use ruffle_core::input_log::player_event;
+use ruffle_core::Player;
+use ruffle_input_format::AutomatedEvent;
+
+/// Replays a single scripted input event into the player.
+///
+/// Assertions in the script (such as `assert_handled`) only make sense for
+/// tests, and are ignored here.
+pub fn inject_event(player: &mut Player, event: &AutomatedEvent) {
+    if let AutomatedEvent::SetClipboardText { text } = event {
+        player.ui_mut().set_clipboard_content(text.to_owned());
+        return;
+    }
+
+    if let Some(event) = player_event(event) {
+        player.handle_event(event);
+    }
+}
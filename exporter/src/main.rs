@@ -1,3 +1,7 @@
+use crate::audio::ExportAudioBackend;
+use crate::encode::{write_animation, write_wav, OutputFormat};
+use crate::fs_commands::{ExportFsCommandProvider, FsCommand};
+use crate::input::inject_event;
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
@@ -6,18 +10,25 @@ use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_input_format::InputInjector;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
-use std::io::{self, Write};
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use walkdir::{DirEntry, WalkDir};
 
+mod audio;
+mod encode;
+mod fs_commands;
+mod input;
+mod matroska;
+
 #[derive(Parser, Debug, Copy, Clone)]
 struct SizeOpt {
     /// The amount to scale the page size with
@@ -76,21 +87,88 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// The format to store captured frames in
+    #[clap(long, default_value = "png")]
+    format: OutputFormat,
+
+    /// Also capture the audio mix of each movie. It's stored in the video for the `mkv` format,
+    /// or as a WAV file next to the frames otherwise
+    #[clap(long, action)]
+    audio: bool,
+
+    /// Advance timers by exactly one frame interval per frame, so that time-based content
+    /// is exported the same way every time
+    #[clap(long, action)]
+    fixed_timestep: bool,
+
+    /// An input script (in the format used by the test suite) to replay while the movie runs
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    /// Only capture frames when the movie calls `fscommand("captureImage", name)`,
+    /// naming them after the given name. Capturing ends after `--frames` images,
+    /// `fscommand("quit")` or `--max-frames` frames, whichever comes first.
+    #[clap(long, action)]
+    capture_on_fscommand: bool,
+
+    /// The maximum number of frames to run when capturing on fscommand
+    #[clap(long, default_value = "10000")]
+    max_frames: u32,
+}
+
+/// The frames (and optionally audio) captured from a single movie.
+struct Capture {
+    frames: Vec<RgbaImage>,
+    /// The file name (without extension) of each frame, when stored one image per frame.
+    names: Vec<String>,
+    frame_rate: f64,
+    audio: Option<Vec<i16>>,
+}
+
+impl Capture {
+    /// Writes this capture as a single animated file, including its audio if the format
+    /// stores it.
+    fn write_animation(&self, format: OutputFormat, writer: impl Write) -> Result<()> {
+        write_animation(
+            format,
+            &self.frames,
+            self.frame_rate,
+            self.audio.as_deref(),
+            writer,
+        )
+    }
+
+    /// Saves this capture as a single animated file. If the format doesn't store audio, the
+    /// audio is saved in a WAV file next to it.
+    fn save_animation(&self, format: OutputFormat, path: &Path) -> Result<()> {
+        self.write_animation(format, BufWriter::new(File::create(path)?))?;
+        if format.stores_audio() {
+            return Ok(());
+        }
+        self.save_audio(&path.with_extension("wav"))
+    }
+
+    fn save_audio(&self, path: &Path) -> Result<()> {
+        if let Some(audio) = &self.audio {
+            write_wav(audio, BufWriter::new(File::create(path)?))?;
+        }
+        Ok(())
+    }
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
     descriptors: Arc<Descriptors>,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
+    opt: &Opt,
     progress: &Option<ProgressBar>,
-    size: SizeOpt,
-    skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+) -> Result<Capture> {
+    let size = opt.size;
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let frame_rate = movie.frame_rate().to_f64();
 
-    if movie.is_action_script_3() && skip_unsupported {
+    if movie.is_action_script_3() && opt.skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
     }
 
@@ -108,16 +186,37 @@ fn take_screenshot(
 
     let target = TextureTarget::new(&descriptors.device, (width, height))
         .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
+    let mut builder = PlayerBuilder::new()
         .with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
         .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
+        .with_viewport_dimensions(width, height, size.scale);
+
+    let mut audio_capture = None;
+    if opt.audio {
+        let (audio, capture) = ExportAudioBackend::new();
+        builder = builder.with_audio(audio);
+        audio_capture = Some(capture);
+    }
+
+    let mut injector = match &opt.input {
+        Some(path) => InputInjector::from_file(path)?,
+        None => InputInjector::empty(),
+    };
+
+    let (fs_command_provider, fs_commands) = ExportFsCommandProvider::new();
+    let player = builder
+        .with_fs_commands(Box::new(fs_command_provider))
         .build();
 
     let mut result = Vec::new();
-    let totalframes = frames + skipframes;
+    let mut names = Vec::new();
+    let totalframes = if opt.capture_on_fscommand {
+        opt.max_frames
+    } else {
+        opt.frames + opt.skipframes
+    };
 
     for i in 0..totalframes {
         if let Some(progress) = &progress {
@@ -130,8 +229,32 @@ fn take_screenshot(
 
         player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
+        injector.next(|event, _buttons| {
+            inject_event(&mut player.lock().unwrap(), event);
+        });
+
         player.lock().unwrap().run_frame();
-        if i >= skipframes {
+        if opt.fixed_timestep {
+            player.lock().unwrap().update_timers(1000.0 / frame_rate);
+        }
+        if let Some(audio_capture) = &mut audio_capture {
+            audio_capture.mix_frame(frame_rate, i >= opt.skipframes);
+        }
+
+        let mut quit = false;
+        let mut captures = vec![];
+        for command in fs_commands.try_iter() {
+            match command {
+                FsCommand::Quit => quit = true,
+                FsCommand::CaptureImage(name) if opt.capture_on_fscommand => captures.push(name),
+                FsCommand::CaptureImage(_) => {}
+            }
+        }
+        if !opt.capture_on_fscommand && i >= opt.skipframes {
+            captures.push((i - opt.skipframes).to_string());
+        }
+
+        if !captures.is_empty() {
             let image = || {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
@@ -142,7 +265,12 @@ fn take_screenshot(
                 renderer.capture_frame()
             };
             match catch_unwind(image) {
-                Ok(Some(image)) => result.push(image),
+                Ok(Some(image)) => {
+                    for name in captures {
+                        result.push(image.clone());
+                        names.push(name);
+                    }
+                }
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
                 Err(e) => {
                     return Err(anyhow!(
@@ -158,8 +286,17 @@ fn take_screenshot(
         if let Some(progress) = &progress {
             progress.inc(1);
         }
+
+        if quit || (opt.capture_on_fscommand && result.len() >= opt.frames as usize) {
+            break;
+        }
     }
-    Ok(result)
+    Ok(Capture {
+        frames: result,
+        names,
+        frame_rate,
+        audio: audio_capture.map(|capture| capture.into_samples()),
+    })
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -193,16 +330,22 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
 }
 
 fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+    if opt.audio && opt.output_path == Some(PathBuf::from("-")) && !opt.format.stores_audio() {
+        return Err(anyhow!(
+            "Only the mkv format can write audio to stdout, as there's nowhere else to put it"
+        ));
+    }
+
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
+        if opt.frames == 1 || opt.format.is_animated() {
+            result.set_extension(opt.format.extension());
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && !opt.format.is_animated() {
         let _ = create_dir_all(&output);
     }
 
@@ -220,21 +363,21 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
-        &opt.swf,
-        opt.frames,
-        opt.skipframes,
-        &progress,
-        opt.size,
-        opt.skip_unsupported,
-    )?;
+    let capture = take_screenshot(descriptors, &opt.swf, opt, &progress)?;
+    let frames = &capture.frames;
 
     if let Some(progress) = &progress {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
-    if frames.len() == 1 {
+    if opt.format.is_animated() {
+        if opt.output_path == Some(PathBuf::from("-")) {
+            let stdout = io::stdout();
+            capture.write_animation(opt.format, stdout.lock())?;
+        } else {
+            capture.save_animation(opt.format, &output)?;
+        }
+    } else if frames.len() == 1 {
         let image = frames.first().unwrap();
         if opt.output_path == Some(PathBuf::from("-")) {
             let mut bytes: Vec<u8> = Vec::new();
@@ -246,13 +389,15 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
                 .expect("Writing to stdout failed");
         } else {
             image.save(&output)?;
+            capture.save_audio(&output.with_extension("wav"))?;
         }
     } else {
-        for (frame, image) in frames.iter().enumerate() {
+        for (name, image) in capture.names.iter().zip(frames) {
             let mut path: PathBuf = (&output).into();
-            path.push(format!("{frame}.png"));
+            path.push(format!("{name}.png"));
             image.save(&path)?;
         }
+        capture.save_audio(&output.join("audio.wav"))?;
     }
 
     let message = if frames.len() == 1 {
@@ -314,22 +459,23 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
-            file.path(),
-            opt.frames,
-            opt.skipframes,
-            &progress,
-            opt.size,
-            opt.skip_unsupported,
-        ) {
+        if let Ok(capture) = take_screenshot(descriptors.clone(), file.path(), opt, &progress) {
+            let frames = &capture.frames;
             let mut relative_path = file
                 .path()
                 .strip_prefix(&opt.swf)
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
+            if opt.format.is_animated() {
+                let mut destination: PathBuf = (&output).into();
+                relative_path.set_extension(opt.format.extension());
+                destination.push(relative_path);
+                if let Some(parent) = destination.parent() {
+                    let _ = create_dir_all(parent);
+                }
+                capture.save_animation(opt.format, &destination)?;
+            } else if frames.len() == 1 {
                 let mut destination: PathBuf = (&output).into();
                 relative_path.set_extension("png");
                 destination.push(relative_path);
@@ -337,16 +483,18 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     let _ = create_dir_all(parent);
                 }
                 frames.first().unwrap().save(&destination)?;
+                capture.save_audio(&destination.with_extension("wav"))?;
             } else {
                 let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
                 parent.push(&relative_path);
                 let _ = create_dir_all(&parent);
-                for (frame, image) in frames.iter().enumerate() {
+                for (name, image) in capture.names.iter().zip(frames) {
                     let mut destination = parent.clone();
-                    destination.push(format!("{frame}.png"));
+                    destination.push(format!("{name}.png"));
                     image.save(&destination)?;
                 }
+                capture.save_audio(&parent.join("audio.wav"))?;
             }
         }
 
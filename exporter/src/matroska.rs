@@ -0,0 +1,352 @@
+//! A minimal Matroska muxer for uncompressed RGBA video and 16-bit PCM audio.
+//!
+//! Every frame gets its own cluster, holding the frame and the audio mixed
+//! while it was shown. The segment is written with an unknown size, so the
+//! output can be streamed without seeking back.
+
+use crate::audio::{NUM_CHANNELS, SAMPLE_RATE};
+use anyhow::Result;
+use image::RgbaImage;
+use std::io::Write;
+
+const EBML: u32 = 0x1A45DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+
+const SEGMENT: u32 = 0x18538067;
+const INFO: u32 = 0x1549A966;
+const TIMESTAMP_SCALE: u32 = 0x2AD7B1;
+const DURATION: u32 = 0x4489;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+
+const TRACKS: u32 = 0x1654AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const FLAG_LACING: u32 = 0x9C;
+const CODEC_ID: u32 = 0x86;
+const DEFAULT_DURATION: u32 = 0x23E383;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const COLOUR_SPACE: u32 = 0x2EB524;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const BIT_DEPTH: u32 = 0x6264;
+
+const CLUSTER: u32 = 0x1F43B675;
+const TIMESTAMP: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+/// An element size of all ones, which marks the size as unknown.
+const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+const VIDEO_TRACK: u8 = 1;
+const AUDIO_TRACK: u8 = 2;
+
+const APP_NAME: &str = "Ruffle Exporter";
+
+/// Writes all frames as a Matroska video, along with the interleaved audio
+/// samples captured over those frames, if any.
+///
+/// Timestamps are stored in milliseconds.
+pub fn write_matroska(
+    frames: &[RgbaImage],
+    frame_rate: f64,
+    audio: Option<&[i16]>,
+    mut writer: impl Write,
+) -> Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+
+    let mut header = vec![];
+    master(&mut header, EBML, |out| {
+        uint(out, EBML_VERSION, 1);
+        uint(out, EBML_READ_VERSION, 1);
+        uint(out, EBML_MAX_ID_LENGTH, 4);
+        uint(out, EBML_MAX_SIZE_LENGTH, 8);
+        string(out, DOC_TYPE, "matroska");
+        uint(out, DOC_TYPE_VERSION, 4);
+        uint(out, DOC_TYPE_READ_VERSION, 2);
+    });
+    write_id(&mut header, SEGMENT);
+    header.extend_from_slice(&UNKNOWN_SIZE);
+    master(&mut header, INFO, |out| {
+        uint(out, TIMESTAMP_SCALE, 1_000_000);
+        float(out, DURATION, frames.len() as f64 * 1000.0 / frame_rate);
+        string(out, MUXING_APP, APP_NAME);
+        string(out, WRITING_APP, APP_NAME);
+    });
+    master(&mut header, TRACKS, |out| {
+        master(out, TRACK_ENTRY, |out| {
+            uint(out, TRACK_NUMBER, VIDEO_TRACK.into());
+            uint(out, TRACK_UID, VIDEO_TRACK.into());
+            uint(out, TRACK_TYPE, 1);
+            uint(out, FLAG_LACING, 0);
+            string(out, CODEC_ID, "V_UNCOMPRESSED");
+            uint(out, DEFAULT_DURATION, (1e9 / frame_rate).round() as u64);
+            master(out, VIDEO, |out| {
+                uint(out, PIXEL_WIDTH, first.width().into());
+                uint(out, PIXEL_HEIGHT, first.height().into());
+                binary(out, COLOUR_SPACE, b"RGBA");
+            });
+        });
+        if audio.is_some() {
+            master(out, TRACK_ENTRY, |out| {
+                uint(out, TRACK_NUMBER, AUDIO_TRACK.into());
+                uint(out, TRACK_UID, AUDIO_TRACK.into());
+                uint(out, TRACK_TYPE, 2);
+                uint(out, FLAG_LACING, 0);
+                string(out, CODEC_ID, "A_PCM/INT/LIT");
+                master(out, AUDIO, |out| {
+                    float(out, SAMPLING_FREQUENCY, SAMPLE_RATE.into());
+                    uint(out, CHANNELS, NUM_CHANNELS.into());
+                    uint(out, BIT_DEPTH, 16);
+                });
+            });
+        }
+    });
+    writer.write_all(&header)?;
+
+    let audio = audio.unwrap_or_default();
+    let frame_time = |frame: usize| (frame as f64 * 1000.0 / frame_rate).round() as i64;
+    // The same rounding as `AudioCapture::mix_frame`, so each frame gets the
+    // samples mixed while it was shown. The last one gets any remainder.
+    let frame_sample = |frame: usize| {
+        if frame == frames.len() {
+            return audio.len() / usize::from(NUM_CHANNELS);
+        }
+        let sample = (frame as f64 * f64::from(SAMPLE_RATE) / frame_rate).round() as usize;
+        sample.min(audio.len() / usize::from(NUM_CHANNELS))
+    };
+
+    for (i, image) in frames.iter().enumerate() {
+        let time = frame_time(i);
+        let mut cluster = vec![];
+        master(&mut cluster, CLUSTER, |out| {
+            uint(out, TIMESTAMP, time as u64);
+            simple_block(out, VIDEO_TRACK, 0, image.as_raw());
+
+            let (start, end) = (frame_sample(i), frame_sample(i + 1));
+            if start < end {
+                let audio_time = (start as f64 * 1000.0 / f64::from(SAMPLE_RATE)).round() as i64;
+                let data: Vec<u8> = audio
+                    [start * usize::from(NUM_CHANNELS)..end * usize::from(NUM_CHANNELS)]
+                    .iter()
+                    .flat_map(|sample| sample.to_le_bytes())
+                    .collect();
+                simple_block(out, AUDIO_TRACK, (audio_time - time) as i16, &data);
+            }
+        });
+        writer.write_all(&cluster)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_id(out: &mut Vec<u8>, id: u32) {
+    // IDs carry their own length marker, so they're written without padding.
+    let skip = id.leading_zeros() as usize / 8;
+    out.extend_from_slice(&id.to_be_bytes()[skip..]);
+}
+
+fn write_size(out: &mut Vec<u8>, size: usize) {
+    let size = size as u64;
+    // All ones is reserved for unknown sizes, so that needs a longer size.
+    let len = (1..=8)
+        .find(|len| size < (1 << (7 * len)) - 1)
+        .expect("Element too large for Matroska");
+    let marked = size | (1 << (7 * len));
+    out.extend_from_slice(&marked.to_be_bytes()[8 - len..]);
+}
+
+fn binary(out: &mut Vec<u8>, id: u32, data: &[u8]) {
+    write_id(out, id);
+    write_size(out, data.len());
+    out.extend_from_slice(data);
+}
+
+fn master(out: &mut Vec<u8>, id: u32, write_children: impl FnOnce(&mut Vec<u8>)) {
+    let mut children = vec![];
+    write_children(&mut children);
+    binary(out, id, &children);
+}
+
+fn uint(out: &mut Vec<u8>, id: u32, value: u64) {
+    let skip = (value.leading_zeros() as usize / 8).min(7);
+    binary(out, id, &value.to_be_bytes()[skip..]);
+}
+
+fn float(out: &mut Vec<u8>, id: u32, value: f64) {
+    binary(out, id, &value.to_be_bytes());
+}
+
+fn string(out: &mut Vec<u8>, id: u32, value: &str) {
+    binary(out, id, value.as_bytes());
+}
+
+/// Writes a block with a timestamp relative to its cluster. Every block is a
+/// keyframe, as neither track is compressed.
+fn simple_block(out: &mut Vec<u8>, track: u8, time: i16, data: &[u8]) {
+    write_id(out, SIMPLE_BLOCK);
+    write_size(out, data.len() + 4);
+    out.push(0x80 | track);
+    out.extend_from_slice(&time.to_be_bytes());
+    out.push(0x80);
+    out.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    /// Reads the elements of an EBML body as `(id, body)` pairs.
+    fn elements(mut data: &[u8]) -> Vec<(u32, &[u8])> {
+        let mut elements = vec![];
+        while !data.is_empty() {
+            let id_len = data[0].leading_zeros() as usize + 1;
+            let id = data[..id_len]
+                .iter()
+                .fold(0, |id, byte| (id << 8) | u32::from(*byte));
+            data = &data[id_len..];
+
+            let size_len = data[0].leading_zeros() as usize + 1;
+            let size = data[..size_len]
+                .iter()
+                .fold(0, |size, byte| (size << 8) | *byte as usize)
+                & !(1 << (8 * size_len - size_len));
+            data = &data[size_len..];
+
+            if size == (1 << (7 * size_len)) - 1 {
+                // Unknown sizes span the rest of the data.
+                elements.push((id, data));
+                break;
+            }
+            elements.push((id, &data[..size]));
+            data = &data[size..];
+        }
+        elements
+    }
+
+    fn child(data: &[u8], id: u32) -> &[u8] {
+        elements(data)
+            .into_iter()
+            .find(|(child_id, _)| *child_id == id)
+            .expect("Element is present")
+            .1
+    }
+
+    #[test]
+    fn video_and_audio() {
+        let frames = [
+            RgbaImage::from_pixel(2, 1, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(2, 1, Rgba([0, 0, 255, 128])),
+        ];
+        // 44100 samples per second at 24 frames per second is 1837.5 samples
+        // per frame, which rounds to 1838 and 1837.
+        let audio: Vec<i16> = (0..3675 * 2).map(|i| i as i16).collect();
+
+        let mut data = vec![];
+        write_matroska(&frames, 24.0, Some(&audio), &mut data).unwrap();
+
+        let top = elements(&data);
+        assert_eq!(top.len(), 2);
+        assert_eq!(child(top[0].1, DOC_TYPE), b"matroska");
+        assert_eq!(top[1].0, SEGMENT);
+
+        let segment = elements(top[1].1);
+        let info = child(top[1].1, INFO);
+        assert_eq!(
+            child(info, TIMESTAMP_SCALE),
+            &1_000_000u32.to_be_bytes()[1..]
+        );
+        assert_eq!(child(info, DURATION), (2000.0f64 / 24.0).to_be_bytes());
+
+        let tracks = elements(child(top[1].1, TRACKS));
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(child(tracks[0].1, CODEC_ID), b"V_UNCOMPRESSED");
+        assert_eq!(
+            child(tracks[0].1, DEFAULT_DURATION),
+            41_666_667u32.to_be_bytes()
+        );
+        let video = child(tracks[0].1, VIDEO);
+        assert_eq!(child(video, PIXEL_WIDTH), [2]);
+        assert_eq!(child(video, PIXEL_HEIGHT), [1]);
+        assert_eq!(child(video, COLOUR_SPACE), b"RGBA");
+        assert_eq!(child(tracks[1].1, CODEC_ID), b"A_PCM/INT/LIT");
+        let audio_settings = child(tracks[1].1, AUDIO);
+        assert_eq!(
+            child(audio_settings, SAMPLING_FREQUENCY),
+            44100.0f64.to_be_bytes()
+        );
+        assert_eq!(child(audio_settings, CHANNELS), [2]);
+        assert_eq!(child(audio_settings, BIT_DEPTH), [16]);
+
+        let clusters: Vec<_> = segment
+            .iter()
+            .filter(|(id, _)| *id == CLUSTER)
+            .map(|(_, cluster)| elements(cluster))
+            .collect();
+        assert_eq!(clusters.len(), 2);
+
+        let mut audio_data = vec![];
+        for (i, (cluster, frame)) in clusters.iter().zip(&frames).enumerate() {
+            let time = [0, 42][i];
+            assert_eq!(cluster[0], (TIMESTAMP, &[time][..]));
+
+            let (video_id, video_block) = cluster[1];
+            assert_eq!(video_id, SIMPLE_BLOCK);
+            assert_eq!(video_block[..4], [0x81, 0, 0, 0x80]);
+            assert_eq!(&video_block[4..], frame.as_raw().as_slice());
+
+            let (audio_id, audio_block) = cluster[2];
+            assert_eq!(audio_id, SIMPLE_BLOCK);
+            assert_eq!(audio_block[0], 0x82);
+            assert_eq!(audio_block[3], 0x80);
+            audio_data.extend_from_slice(&audio_block[4..]);
+        }
+        assert_eq!(clusters[0][2].1.len() - 4, 1838 * 4);
+
+        let expected: Vec<u8> = audio
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        assert_eq!(audio_data, expected);
+    }
+
+    #[test]
+    fn video_only() {
+        let frames = [RgbaImage::new(1, 1)];
+
+        let mut data = vec![];
+        write_matroska(&frames, 30.0, None, &mut data).unwrap();
+
+        let top = elements(&data);
+        let tracks = elements(child(top[1].1, TRACKS));
+        assert_eq!(tracks.len(), 1);
+        let cluster = elements(child(top[1].1, CLUSTER));
+        assert_eq!(cluster.len(), 2);
+    }
+
+    #[test]
+    fn element_sizes() {
+        let mut out = vec![];
+        write_size(&mut out, 0);
+        write_size(&mut out, 126);
+        write_size(&mut out, 127);
+        write_size(&mut out, 0x3FFE);
+        write_size(&mut out, 0x3FFF);
+        assert_eq!(out, [0x80, 0xFE, 0x40, 0x7F, 0x7F, 0xFE, 0x20, 0x3F, 0xFF]);
+    }
+}
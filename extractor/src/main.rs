@@ -0,0 +1,455 @@
+mod sound;
+mod svg;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, ValueEnum};
+use ruffle_core::interpolate_morph_shape;
+use ruffle_core::tag_utils::{SwfMovie, SwfSlice};
+use ruffle_render::bitmap::Bitmap;
+use ruffle_render::utils::{
+    decode_define_bits_jpeg, decode_define_bits_lossless, glue_tables_to_jpeg,
+    remove_invalid_jpeg_data, unmultiply_alpha_rgba,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use svg::BitmapFile;
+use swf::read::Reader;
+use swf::{CharacterId, Encoding, SwfStr, Tag, TagCode};
+
+#[derive(Parser, Debug)]
+#[clap(name = "Ruffle Extractor", author, version)]
+struct Opt {
+    /// The SWF file to extract assets from
+    swf: PathBuf,
+
+    /// The directory to write the assets to
+    output: PathBuf,
+
+    /// Only extract these kinds of assets
+    #[clap(long, value_enum, value_delimiter = ',')]
+    only: Vec<AssetKind>,
+
+    /// How many frames of each morph shape to export, evenly spaced from its
+    /// start shape to its end shape
+    #[clap(long, default_value_t = 2, value_parser = clap::value_parser!(u16).range(2..))]
+    morph_frames: u16,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum AssetKind {
+    Shapes,
+    Morphs,
+    Bitmaps,
+    Sounds,
+    Fonts,
+    Binary,
+}
+
+impl AssetKind {
+    /// The directory that assets of this kind are written to.
+    fn directory(self) -> &'static str {
+        match self {
+            AssetKind::Shapes => "shapes",
+            AssetKind::Morphs => "morphs",
+            AssetKind::Bitmaps => "bitmaps",
+            AssetKind::Sounds => "sounds",
+            AssetKind::Fonts => "fonts",
+            AssetKind::Binary => "binary",
+        }
+    }
+}
+
+/// The image data of a bitmap character.
+enum BitmapData<'a> {
+    Jpeg {
+        data: Cow<'a, [u8]>,
+        alpha: Option<&'a [u8]>,
+    },
+    Lossless(swf::DefineBitsLossless<'a>),
+}
+
+/// A font character, in any of its versions.
+enum FontData<'a> {
+    /// A `DefineFont`, which gets its name and character codes from a
+    /// `DefineFontInfo` tag.
+    V1(swf::FontV1),
+    Glyphs(swf::Font<'a>),
+    OpenType(swf::Font4<'a>),
+}
+
+/// The extractable characters of a SWF, gathered from all of its timelines.
+struct Assets<'a> {
+    movie: Arc<SwfMovie>,
+    encoding: &'static Encoding,
+    /// Export and class names of characters.
+    names: HashMap<CharacterId, String>,
+    jpeg_tables: Option<Vec<u8>>,
+    shapes: Vec<swf::Shape>,
+    morph_shapes: Vec<swf::DefineMorphShape>,
+    bitmaps: Vec<(CharacterId, BitmapData<'a>)>,
+    sounds: Vec<swf::Sound<'a>>,
+    /// The stream sound of each timeline, keyed by the id of its sprite, or
+    /// `None` for the main timeline.
+    streams: Vec<(Option<CharacterId>, swf::SoundStreamHead, &'a [u8])>,
+    fonts: Vec<(CharacterId, FontData<'a>)>,
+    font_infos: HashMap<CharacterId, swf::FontInfo<'a>>,
+    binary_data: Vec<swf::DefineBinaryData<'a>>,
+}
+
+impl<'a> Assets<'a> {
+    fn new(movie: Arc<SwfMovie>) -> Self {
+        Self {
+            encoding: movie.encoding(),
+            movie,
+            names: HashMap::new(),
+            jpeg_tables: None,
+            shapes: vec![],
+            morph_shapes: vec![],
+            bitmaps: vec![],
+            sounds: vec![],
+            streams: vec![],
+            fonts: vec![],
+            font_infos: HashMap::new(),
+            binary_data: vec![],
+        }
+    }
+
+    /// Gathers the characters defined in a timeline, recursing into sprites.
+    ///
+    /// Like the player, a tag that fails to parse is skipped rather than
+    /// ending the whole timeline.
+    fn read_timeline(&mut self, data: &'a [u8], version: u8, sprite: Option<CharacterId>) {
+        let mut reader = Reader::new(data, version);
+        let mut has_stream = false;
+        loop {
+            let tag_start = reader.get_ref();
+            let Ok((tag_code, length)) = reader.read_tag_code_and_length() else {
+                break;
+            };
+            let header_len = tag_start.len() - reader.get_ref().len();
+            let Some(tag_data) = tag_start.get(..header_len + length) else {
+                eprintln!("Warning: Truncated tag {tag_code}");
+                break;
+            };
+            *reader.get_mut() = &tag_start[header_len + length..];
+
+            if tag_code == TagCode::End as u16 {
+                break;
+            } else if tag_code == TagCode::DefineSprite as u16 {
+                // Sprite tags are read one by one, so that a bad tag doesn't
+                // lose the rest of the sprite.
+                let body = &tag_data[header_len..];
+                if let [id_lo, id_hi, _, _, tags @ ..] = body {
+                    let id = u16::from_le_bytes([*id_lo, *id_hi]);
+                    self.read_timeline(tags, version, Some(id));
+                }
+                continue;
+            }
+
+            match Reader::new(tag_data, version).read_tag() {
+                Ok(Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head)) if !has_stream => {
+                    has_stream = true;
+                    self.streams.push((sprite, *head, data));
+                }
+                Ok(tag) => self.add_tag(tag),
+                Err(e) => eprintln!("Warning: Skipping tag: {e}"),
+            }
+        }
+    }
+
+    fn add_tag(&mut self, tag: Tag<'a>) {
+        match tag {
+            Tag::ExportAssets(assets) => {
+                for asset in assets {
+                    let name = asset.name.to_str_lossy(self.encoding).into_owned();
+                    self.names.insert(asset.id, name);
+                }
+            }
+            Tag::SymbolClass(links) => {
+                for link in links {
+                    // Character 0 is the main timeline, which isn't extracted.
+                    if link.id != 0 {
+                        let name = link.class_name.to_str_lossy(self.encoding).into_owned();
+                        self.names.entry(link.id).or_insert(name);
+                    }
+                }
+            }
+            // Only the first `JpegTables` tag counts, as in the player.
+            Tag::JpegTables(data) if self.jpeg_tables.is_none() && !data.is_empty() => {
+                self.jpeg_tables = Some(remove_invalid_jpeg_data(data).into_owned());
+            }
+            Tag::DefineBits { id, jpeg_data } => {
+                let data = glue_tables_to_jpeg(jpeg_data, self.jpeg_tables.as_deref());
+                let data = Cow::Owned(data.into_owned());
+                self.bitmaps
+                    .push((id, BitmapData::Jpeg { data, alpha: None }));
+            }
+            Tag::DefineBitsJpeg2 { id, jpeg_data } => {
+                let data = Cow::Borrowed(jpeg_data);
+                self.bitmaps
+                    .push((id, BitmapData::Jpeg { data, alpha: None }));
+            }
+            Tag::DefineBitsJpeg3(jpeg) => {
+                let data = Cow::Borrowed(jpeg.data);
+                let alpha = Some(jpeg.alpha_data);
+                self.bitmaps
+                    .push((jpeg.id, BitmapData::Jpeg { data, alpha }));
+            }
+            Tag::DefineBitsLossless(lossless) => {
+                self.bitmaps
+                    .push((lossless.id, BitmapData::Lossless(lossless)));
+            }
+            Tag::DefineShape(shape) => self.shapes.push(shape),
+            Tag::DefineMorphShape(morph_shape) => self.morph_shapes.push(*morph_shape),
+            Tag::DefineSound(sound) => self.sounds.push(*sound),
+            Tag::DefineFont(font) => self.fonts.push((font.id, FontData::V1(*font))),
+            Tag::DefineFont2(font) => self.fonts.push((font.id, FontData::Glyphs(*font))),
+            Tag::DefineFont4(font) => self.fonts.push((font.id, FontData::OpenType(font))),
+            Tag::DefineFontInfo(info) => {
+                self.font_infos.insert(info.id, *info);
+            }
+            Tag::DefineBinaryData(binary_data) => self.binary_data.push(binary_data),
+            _ => {}
+        }
+    }
+}
+
+/// Writes the extracted assets to a directory.
+struct Extractor<'a> {
+    assets: &'a Assets<'a>,
+    output: &'a Path,
+    kinds: &'a [AssetKind],
+    morph_frames: u16,
+    /// The bitmaps that have been written, for bitmap fills of shapes.
+    bitmap_files: HashMap<CharacterId, BitmapFile>,
+    count: usize,
+}
+
+impl Extractor<'_> {
+    /// The base name of the files of a character, from its id and export name.
+    fn file_stem(&self, id: CharacterId) -> String {
+        match self.assets.names.get(&id) {
+            Some(name) => format!("{id}_{}", sanitize(name)),
+            None => id.to_string(),
+        }
+    }
+
+    /// Writes an asset, creating its directory as needed.
+    fn write(&mut self, kind: AssetKind, file_name: &str, data: &[u8]) -> Result<()> {
+        let directory = self.output.join(kind.directory());
+        std::fs::create_dir_all(&directory)?;
+        let path = directory.join(file_name);
+        std::fs::write(&path, data).with_context(|| format!("Couldn't write {path:?}"))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Runs an extraction step, reporting failures without stopping the
+    /// other assets from being extracted.
+    fn report(&self, what: &str, result: Result<()>) {
+        if let Err(e) = result {
+            eprintln!("Warning: Couldn't extract {what}: {e:#}");
+        }
+    }
+
+    fn wants(&self, kind: AssetKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+
+    fn extract(&mut self) {
+        let assets = self.assets;
+        // Bitmaps are written first, so that shapes can refer to them.
+        if self.wants(AssetKind::Bitmaps)
+            || self.wants(AssetKind::Shapes)
+            || self.wants(AssetKind::Morphs)
+        {
+            for (id, bitmap) in &assets.bitmaps {
+                let result = self.extract_bitmap(*id, bitmap);
+                self.report(&format!("bitmap {id}"), result);
+            }
+        }
+        if self.wants(AssetKind::Shapes) {
+            for shape in &assets.shapes {
+                let svg = svg::shape_to_svg(shape, &self.bitmap_files);
+                let file_name = format!("{}.svg", self.file_stem(shape.id));
+                let result = self.write(AssetKind::Shapes, &file_name, svg.as_bytes());
+                self.report(&format!("shape {}", shape.id), result);
+            }
+        }
+        if self.wants(AssetKind::Morphs) {
+            for morph_shape in &assets.morph_shapes {
+                let result = self.extract_morph_shape(morph_shape);
+                self.report(&format!("morph shape {}", morph_shape.id), result);
+            }
+        }
+        if self.wants(AssetKind::Sounds) {
+            for sound in &assets.sounds {
+                let file_name = format!("{}.wav", self.file_stem(sound.id));
+                let result = sound::decode_sound(sound)
+                    .and_then(|pcm| self.write(AssetKind::Sounds, &file_name, &pcm.to_wav()));
+                self.report(&format!("sound {}", sound.id), result);
+            }
+            for (sprite, stream_info, clip_data) in &assets.streams {
+                let (what, file_name) = match sprite {
+                    Some(id) => (
+                        format!("stream sound of sprite {id}"),
+                        format!("stream_{}.wav", self.file_stem(*id)),
+                    ),
+                    None => (
+                        "stream sound of the main timeline".to_string(),
+                        "stream_root.wav".to_string(),
+                    ),
+                };
+                let clip_data = SwfSlice::from(assets.movie.clone()).to_subslice(clip_data);
+                let result = sound::decode_stream(stream_info, clip_data)
+                    .and_then(|pcm| self.write(AssetKind::Sounds, &file_name, &pcm.to_wav()));
+                self.report(&what, result);
+            }
+        }
+        if self.wants(AssetKind::Fonts) {
+            for (id, font) in &assets.fonts {
+                let result = self.extract_font(*id, font);
+                self.report(&format!("font {id}"), result);
+            }
+        }
+        if self.wants(AssetKind::Binary) {
+            for binary_data in &assets.binary_data {
+                let file_name = format!("{}.bin", self.file_stem(binary_data.id));
+                let result = self.write(AssetKind::Binary, &file_name, binary_data.data);
+                self.report(&format!("binary data {}", binary_data.id), result);
+            }
+        }
+    }
+
+    fn extract_bitmap(&mut self, id: CharacterId, bitmap: &BitmapData) -> Result<()> {
+        let bitmap: Bitmap = match bitmap {
+            BitmapData::Jpeg { data, alpha } => decode_define_bits_jpeg(data, *alpha)?,
+            BitmapData::Lossless(lossless) => decode_define_bits_lossless(lossless)?,
+        };
+        let (width, height) = (bitmap.width(), bitmap.height());
+        let mut rgba = bitmap.to_rgba().data().to_vec();
+        // Decoded bitmaps have premultiplied alpha, while PNGs don't.
+        unmultiply_alpha_rgba(&mut rgba);
+        let image = image::RgbaImage::from_raw(width, height, rgba)
+            .ok_or_else(|| anyhow!("Bitmap has the wrong size"))?;
+
+        let file_name = format!("{}.png", self.file_stem(id));
+        if self.wants(AssetKind::Bitmaps) {
+            let mut png = std::io::Cursor::new(vec![]);
+            image.write_to(&mut png, image::ImageFormat::Png)?;
+            self.write(AssetKind::Bitmaps, &file_name, png.get_ref())?;
+        }
+        self.bitmap_files.insert(
+            id,
+            BitmapFile {
+                href: format!("../{}/{file_name}", AssetKind::Bitmaps.directory()),
+                width,
+                height,
+            },
+        );
+        Ok(())
+    }
+
+    fn extract_morph_shape(&mut self, morph_shape: &swf::DefineMorphShape) -> Result<()> {
+        let stem = self.file_stem(morph_shape.id);
+        let last_frame = u32::from(self.morph_frames - 1);
+        for frame in 0..=last_frame {
+            let ratio = (frame * u32::from(u16::MAX) / last_frame) as u16;
+            let mut shape = interpolate_morph_shape(&morph_shape.start, &morph_shape.end, ratio);
+            shape.id = morph_shape.id;
+            let svg = svg::shape_to_svg(&shape, &self.bitmap_files);
+            self.write(
+                AssetKind::Morphs,
+                &format!("{stem}_{frame}.svg"),
+                svg.as_bytes(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn extract_font(&mut self, id: CharacterId, font: &FontData) -> Result<()> {
+        let stem = self.file_stem(id);
+        let encoding = self.assets.encoding;
+        match font {
+            FontData::V1(font) => {
+                let info = self.assets.font_infos.get(&id);
+                let glyphs = font
+                    .glyphs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, shape_records)| swf::Glyph {
+                        shape_records: shape_records.clone(),
+                        code: info
+                            .and_then(|info| info.code_table.get(i).copied())
+                            .unwrap_or(0),
+                        advance: 0,
+                        bounds: None,
+                    })
+                    .collect();
+                let font = swf::Font {
+                    version: 1,
+                    id,
+                    name: info.map_or(SwfStr::from_bytes(b""), |info| info.name),
+                    language: swf::Language::Unknown,
+                    layout: None,
+                    glyphs,
+                    flags: swf::FontFlag::empty(),
+                };
+                let name = font.name.to_str_lossy(encoding);
+                let svg = svg::font_to_svg(&font, &name, 1024);
+                self.write(AssetKind::Fonts, &format!("{stem}.svg"), svg.as_bytes())
+            }
+            FontData::Glyphs(font) => {
+                let em_size = if font.version >= 3 { 20480 } else { 1024 };
+                let name = font.name.to_str_lossy(encoding);
+                let svg = svg::font_to_svg(font, &name, em_size);
+                self.write(AssetKind::Fonts, &format!("{stem}.svg"), svg.as_bytes())
+            }
+            FontData::OpenType(font) => {
+                let data = font
+                    .data
+                    .ok_or_else(|| anyhow!("DefineFont4 has no font data"))?;
+                // DefineFont4 embeds a whole CFF-flavored OpenType font.
+                self.write(AssetKind::Fonts, &format!("{stem}.otf"), data)
+            }
+        }
+    }
+}
+
+/// Makes an export name safe to use in a file name.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+
+    let movie = SwfMovie::from_path(&opt.swf, None)
+        .map_err(|e| anyhow!("Couldn't load {:?}: {e}", opt.swf))?;
+    let movie = Arc::new(movie);
+    let mut assets = Assets::new(movie.clone());
+    assets.read_timeline(movie.data(), movie.version(), None);
+
+    let mut extractor = Extractor {
+        assets: &assets,
+        output: &opt.output,
+        kinds: &opt.only,
+        morph_frames: opt.morph_frames,
+        bitmap_files: HashMap::new(),
+        count: 0,
+    };
+    extractor.extract();
+    println!("Extracted {} assets to {:?}", extractor.count, opt.output);
+
+    Ok(())
+}
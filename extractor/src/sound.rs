@@ -0,0 +1,156 @@
+//! Decoding of event and stream sounds to WAV.
+
+use anyhow::{anyhow, Result};
+use ruffle_core::backend::audio::decoders::{make_decoder, make_stream_decoder, Decoder};
+use ruffle_core::tag_utils::SwfSlice;
+use std::io::Cursor;
+use swf::AudioCompression;
+
+/// Decoded 16-bit audio.
+pub struct Pcm {
+    num_channels: u16,
+    sample_rate: u32,
+    /// Interleaved samples.
+    samples: Vec<i16>,
+}
+
+impl Pcm {
+    fn from_decoder(decoder: impl Decoder, num_sample_frames: Option<usize>) -> Self {
+        let num_channels = u16::from(decoder.num_channels());
+        let sample_rate = u32::from(decoder.sample_rate());
+        let frames = decoder.take(num_sample_frames.unwrap_or(usize::MAX));
+        let samples = if num_channels == 1 {
+            frames.map(|[left, _]| left).collect()
+        } else {
+            frames.flatten().collect()
+        };
+        Self {
+            num_channels,
+            sample_rate,
+            samples,
+        }
+    }
+
+    /// Encodes the audio as a PCM WAV file.
+    pub fn to_wav(&self) -> Vec<u8> {
+        let block_align = self.num_channels * 2;
+        let byte_rate = self.sample_rate * u32::from(block_align);
+        let data_len = (self.samples.len() * 2) as u32;
+
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&self.num_channels.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&byte_rate.to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+}
+
+/// Decodes the audio of a `DefineSound` tag.
+pub fn decode_sound(sound: &swf::Sound) -> Result<Pcm> {
+    // MP3 data starts with the number of samples to skip to bypass the
+    // encoder delay, like the audio mixer does.
+    let (skip_sample_frames, data) = if sound.format.compression == AudioCompression::Mp3 {
+        let [a, b, data @ ..] = sound.data else {
+            return Err(anyhow!("MP3 sound is too short"));
+        };
+        (u16::from_le_bytes([*a, *b]), data)
+    } else {
+        (0, sound.data)
+    };
+
+    let mut decoder = make_decoder(&sound.format, Cursor::new(data.to_vec()))?;
+    for _ in 0..skip_sample_frames {
+        decoder.next();
+    }
+    let num_sample_frames = (sound.num_samples > 0).then_some(sound.num_samples as usize);
+    Ok(Pcm::from_decoder(decoder, num_sample_frames))
+}
+
+/// Decodes the stream sound of a timeline, given its `SoundStreamHead` and
+/// the tags of the timeline.
+pub fn decode_stream(stream_info: &swf::SoundStreamHead, clip_data: SwfSlice) -> Result<Pcm> {
+    let decoder = make_stream_decoder(stream_info, clip_data)?;
+    Ok(Pcm::from_decoder(decoder, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::SoundFormat;
+
+    #[test]
+    fn wav_header() {
+        let pcm = Pcm {
+            num_channels: 2,
+            sample_rate: 44100,
+            samples: vec![1, -2, 0x1234, i16::MIN],
+        };
+        let wav = pcm.to_wav();
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[4..8], &44u32.to_le_bytes());
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(&wav[16..20], &16u32.to_le_bytes());
+        assert_eq!(&wav[20..22], &1u16.to_le_bytes());
+        assert_eq!(&wav[22..24], &2u16.to_le_bytes());
+        assert_eq!(&wav[24..28], &44100u32.to_le_bytes());
+        assert_eq!(&wav[28..32], &(44100u32 * 4).to_le_bytes());
+        assert_eq!(&wav[32..34], &4u16.to_le_bytes());
+        assert_eq!(&wav[34..36], &16u16.to_le_bytes());
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(&wav[40..44], &8u32.to_le_bytes());
+        assert_eq!(&wav[44..], &[1, 0, 0xfe, 0xff, 0x34, 0x12, 0x00, 0x80]);
+    }
+
+    #[test]
+    fn decode_uncompressed_sound() {
+        // Four mono samples, of which the sound only plays three.
+        let data = [1, 0, 2, 0, 3, 0, 4, 0];
+        let sound = swf::Sound {
+            id: 1,
+            format: SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate: 5512,
+                is_stereo: false,
+                is_16_bit: true,
+            },
+            num_samples: 3,
+            data: &data,
+        };
+        let pcm = decode_sound(&sound).unwrap();
+        assert_eq!(pcm.num_channels, 1);
+        assert_eq!(pcm.sample_rate, 5512);
+        assert_eq!(pcm.samples, [1, 2, 3]);
+    }
+
+    #[test]
+    fn truncated_mp3_sound() {
+        let sound = swf::Sound {
+            id: 1,
+            format: SoundFormat {
+                compression: AudioCompression::Mp3,
+                sample_rate: 44100,
+                is_stereo: true,
+                is_16_bit: true,
+            },
+            num_samples: 0,
+            data: &[0],
+        };
+        assert!(decode_sound(&sound).is_err());
+    }
+}
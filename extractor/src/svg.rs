@@ -0,0 +1,547 @@
+//! Conversion of shapes and font glyphs to SVG.
+
+use ruffle_render::shape_utils::{
+    swf_glyph_to_shape, DistilledShape, DrawCommand, DrawPath, FillRule,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+use swf::{
+    CharacterId, Color, FillStyle, Gradient, GradientInterpolation, GradientSpread, LineCapStyle,
+    LineJoinStyle, LineStyle, Matrix, Twips,
+};
+
+/// A bitmap that has been written to disk, for use by bitmap fills.
+pub struct BitmapFile {
+    /// The path of the image, relative to the SVG files.
+    pub href: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The size of the gradient square in pixels. Gradients span from -16384 to
+/// 16384 twips before their matrix is applied.
+const GRADIENT_SIZE: f64 = 16384.0 / 20.0;
+
+/// Converts a shape to an SVG document, measured in pixels.
+pub fn shape_to_svg(shape: &swf::Shape, bitmaps: &HashMap<CharacterId, BitmapFile>) -> String {
+    let bounds = &shape.shape_bounds;
+    let (x, y, width, height) = if bounds.x_min <= bounds.x_max && bounds.y_min <= bounds.y_max {
+        (
+            bounds.x_min.to_pixels(),
+            bounds.y_min.to_pixels(),
+            (bounds.x_max - bounds.x_min).to_pixels(),
+            (bounds.y_max - bounds.y_min).to_pixels(),
+        )
+    } else {
+        (0.0, 0.0, 0.0, 0.0)
+    };
+
+    let mut defs = String::new();
+    let mut paths = String::new();
+    let mut paint = Paint {
+        defs: &mut defs,
+        bitmaps,
+        next_id: 0,
+    };
+    for path in DistilledShape::from(shape).paths {
+        match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
+                let fill = paint.paint("fill", style);
+                let fill_rule = match winding_rule {
+                    FillRule::EvenOdd => "evenodd",
+                    FillRule::NonZero => "nonzero",
+                };
+                let _ = writeln!(
+                    paths,
+                    r#"  <path d="{}" {fill} fill-rule="{fill_rule}"/>"#,
+                    path_data(&commands, false, pixels),
+                );
+            }
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            } => {
+                let stroke = stroke_attributes(style, &mut paint);
+                let _ = writeln!(
+                    paths,
+                    r#"  <path d="{}" fill="none" {stroke}/>"#,
+                    path_data(&commands, is_closed, pixels),
+                );
+            }
+        }
+    }
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{width}" height="{height}" viewBox="{x} {y} {width} {height}">"#
+    );
+    svg.push('\n');
+    if !defs.is_empty() {
+        let _ = write!(svg, "  <defs>\n{defs}  </defs>\n");
+    }
+    svg.push_str(&paths);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Converts the glyphs of a font to an SVG font.
+///
+/// `em_size` is the size of the EM square that the glyphs are defined in:
+/// 1024 units for `DefineFont` and `DefineFont2`, and 20 times that for
+/// `DefineFont3`.
+pub fn font_to_svg(font: &swf::Font, name: &str, em_size: u32) -> String {
+    let mut svg = String::from("<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <defs>\n");
+    let (ascent, descent) = font
+        .layout
+        .as_ref()
+        .map(|layout| (u32::from(layout.ascent), u32::from(layout.descent)))
+        .unwrap_or((em_size * 4 / 5, em_size / 5));
+    let _ = writeln!(
+        svg,
+        r#"    <font id="font{}" horiz-adv-x="{em_size}">"#,
+        font.id
+    );
+    let _ = writeln!(
+        svg,
+        r#"      <font-face font-family="{}" font-weight="{}" font-style="{}" units-per-em="{em_size}" ascent="{ascent}" descent="-{descent}"/>"#,
+        escape(name),
+        if font.flags.contains(swf::FontFlag::IS_BOLD) {
+            "bold"
+        } else {
+            "normal"
+        },
+        if font.flags.contains(swf::FontFlag::IS_ITALIC) {
+            "italic"
+        } else {
+            "normal"
+        },
+    );
+    let _ = writeln!(svg, r#"      <missing-glyph horiz-adv-x="{em_size}"/>"#);
+
+    for (i, glyph) in font.glyphs.iter().enumerate() {
+        let shape = swf_glyph_to_shape(glyph);
+        let advance = if font.layout.is_some() {
+            i32::from(glyph.advance)
+        } else {
+            shape.shape_bounds.x_max.get().max(0)
+        };
+        let mut d = String::new();
+        for path in DistilledShape::from(&shape).paths {
+            if let DrawPath::Fill { commands, .. } = path {
+                d.push_str(&path_data(&commands, false, font_units));
+            }
+        }
+        let _ = writeln!(
+            svg,
+            r#"      <glyph glyph-name="glyph{i}"{} horiz-adv-x="{advance}" d="{d}"/>"#,
+            unicode_attribute("unicode", glyph.code),
+        );
+    }
+
+    if let Some(layout) = &font.layout {
+        for kerning in &layout.kerning {
+            // SVG kerning is subtracted from the advance, while SWF kerning is added.
+            let _ = writeln!(
+                svg,
+                r#"      <hkern{}{} k="{}"/>"#,
+                unicode_attribute("u1", kerning.left_code),
+                unicode_attribute("u2", kerning.right_code),
+                -kerning.adjustment.get()
+            );
+        }
+    }
+
+    svg.push_str("    </font>\n  </defs>\n</svg>\n");
+    svg
+}
+
+/// Writes the fills of a shape into the `<defs>` of its SVG.
+struct Paint<'a> {
+    defs: &'a mut String,
+    bitmaps: &'a HashMap<CharacterId, BitmapFile>,
+    next_id: u32,
+}
+
+impl Paint<'_> {
+    /// Returns the attributes for painting with a fill style, where `property`
+    /// is `fill` or `stroke`.
+    fn paint(&mut self, property: &str, style: &FillStyle) -> String {
+        match style {
+            FillStyle::Color(color) => {
+                color_attributes(property, &format!("{property}-opacity"), color)
+            }
+            FillStyle::LinearGradient(gradient) => {
+                let geometry = format!(
+                    r#"x1="{}" y1="0" x2="{GRADIENT_SIZE}" y2="0""#,
+                    -GRADIENT_SIZE
+                );
+                let id = self.gradient("linearGradient", gradient, &geometry);
+                format!(r#"{property}="url(#{id})""#)
+            }
+            FillStyle::RadialGradient(gradient) => {
+                let geometry = format!(r#"cx="0" cy="0" r="{GRADIENT_SIZE}""#);
+                let id = self.gradient("radialGradient", gradient, &geometry);
+                format!(r#"{property}="url(#{id})""#)
+            }
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => {
+                let geometry = format!(
+                    r#"cx="0" cy="0" r="{GRADIENT_SIZE}" fx="{}" fy="0""#,
+                    focal_point.to_f64() * GRADIENT_SIZE
+                );
+                let id = self.gradient("radialGradient", gradient, &geometry);
+                format!(r#"{property}="url(#{id})""#)
+            }
+            FillStyle::Bitmap {
+                id,
+                matrix,
+                is_smoothed,
+                is_repeating: _,
+            } => {
+                let Some(bitmap) = self.bitmaps.get(id) else {
+                    // Flash doesn't draw fills of missing bitmaps.
+                    return format!(r#"{property}="none""#);
+                };
+                // Bitmap matrices map pixels of the bitmap to twips.
+                let transform = format!(
+                    "matrix({} {} {} {} {} {})",
+                    matrix.a.to_f64() / 20.0,
+                    matrix.b.to_f64() / 20.0,
+                    matrix.c.to_f64() / 20.0,
+                    matrix.d.to_f64() / 20.0,
+                    matrix.tx.to_pixels(),
+                    matrix.ty.to_pixels(),
+                );
+                let rendering = if *is_smoothed { "auto" } else { "pixelated" };
+                let pattern_id = self.next_id();
+                let _ = writeln!(
+                    self.defs,
+                    r#"    <pattern id="{pattern_id}" patternUnits="userSpaceOnUse" width="{}" height="{}" patternTransform="{transform}">"#,
+                    bitmap.width, bitmap.height
+                );
+                let _ = writeln!(
+                    self.defs,
+                    r#"      <image xlink:href="{}" width="{}" height="{}" image-rendering="{rendering}"/>"#,
+                    escape(&bitmap.href),
+                    bitmap.width,
+                    bitmap.height
+                );
+                self.defs.push_str("    </pattern>\n");
+                format!(r#"{property}="url(#{pattern_id})""#)
+            }
+        }
+    }
+
+    /// Defines a gradient, returning its id. `geometry` holds the attributes
+    /// that place the gradient within the gradient square.
+    fn gradient(&mut self, element: &str, gradient: &Gradient, geometry: &str) -> String {
+        let id = self.next_id();
+        let spread = match gradient.spread {
+            GradientSpread::Pad => "pad",
+            GradientSpread::Reflect => "reflect",
+            GradientSpread::Repeat => "repeat",
+        };
+        let interpolation = match gradient.interpolation {
+            GradientInterpolation::Rgb => "sRGB",
+            GradientInterpolation::LinearRgb => "linearRGB",
+        };
+        let _ = writeln!(
+            self.defs,
+            r#"    <{element} id="{id}" gradientUnits="userSpaceOnUse" {geometry} gradientTransform="{}" spreadMethod="{spread}" color-interpolation="{interpolation}">"#,
+            matrix_transform(&gradient.matrix),
+        );
+        for record in &gradient.records {
+            let _ = writeln!(
+                self.defs,
+                r#"      <stop offset="{}" {}/>"#,
+                f64::from(record.ratio) / 255.0,
+                color_attributes("stop-color", "stop-opacity", &record.color)
+            );
+        }
+        let _ = writeln!(self.defs, "    </{element}>");
+        id
+    }
+
+    fn next_id(&mut self) -> String {
+        let id = format!("paint{}", self.next_id);
+        self.next_id += 1;
+        id
+    }
+}
+
+fn stroke_attributes(style: &LineStyle, paint: &mut Paint) -> String {
+    let mut attributes = paint.paint("stroke", style.fill_style());
+    if style.width() == Twips::ZERO {
+        // Hairlines are always one pixel wide.
+        attributes.push_str(r#" stroke-width="1" vector-effect="non-scaling-stroke""#);
+    } else {
+        let _ = write!(
+            attributes,
+            r#" stroke-width="{}""#,
+            style.width().to_pixels()
+        );
+    }
+    let cap = match style.start_cap() {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "butt",
+        LineCapStyle::Square => "square",
+    };
+    let _ = write!(attributes, r#" stroke-linecap="{cap}""#);
+    match style.join_style() {
+        LineJoinStyle::Round => attributes.push_str(r#" stroke-linejoin="round""#),
+        LineJoinStyle::Bevel => attributes.push_str(r#" stroke-linejoin="bevel""#),
+        LineJoinStyle::Miter(limit) => {
+            let _ = write!(
+                attributes,
+                r#" stroke-linejoin="miter" stroke-miterlimit="{}""#,
+                limit.to_f32().max(1.0)
+            );
+        }
+    }
+    attributes
+}
+
+fn color_attributes(color_property: &str, opacity_property: &str, color: &Color) -> String {
+    let mut attributes = format!(
+        r##"{color_property}="#{:02x}{:02x}{:02x}""##,
+        color.r, color.g, color.b
+    );
+    if color.a != 255 {
+        let _ = write!(
+            attributes,
+            r#" {opacity_property}="{}""#,
+            f64::from(color.a) / 255.0
+        );
+    }
+    attributes
+}
+
+fn matrix_transform(matrix: &Matrix) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        matrix.a.to_f64(),
+        matrix.b.to_f64(),
+        matrix.c.to_f64(),
+        matrix.d.to_f64(),
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels(),
+    )
+}
+
+/// Maps a point of a shape to pixels.
+fn pixels(point: swf::Point<Twips>) -> (f64, f64) {
+    (point.x.to_pixels(), point.y.to_pixels())
+}
+
+/// Maps a point of a glyph to font units, which have the y axis pointing up.
+fn font_units(point: swf::Point<Twips>) -> (f64, f64) {
+    (f64::from(point.x.get()), f64::from(-point.y.get()))
+}
+
+fn path_data(
+    commands: &[DrawCommand],
+    close: bool,
+    map: impl Fn(swf::Point<Twips>) -> (f64, f64),
+) -> String {
+    let mut d = String::new();
+    for command in commands {
+        let _ = match command {
+            DrawCommand::MoveTo(point) => {
+                let (x, y) = map(*point);
+                write!(d, "M{x} {y}")
+            }
+            DrawCommand::LineTo(point) => {
+                let (x, y) = map(*point);
+                write!(d, "L{x} {y}")
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                let (cx, cy) = map(*control);
+                let (x, y) = map(*anchor);
+                write!(d, "Q{cx} {cy} {x} {y}")
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                let (ax, ay) = map(*control_a);
+                let (bx, by) = map(*control_b);
+                let (x, y) = map(*anchor);
+                write!(d, "C{ax} {ay} {bx} {by} {x} {y}")
+            }
+        };
+    }
+    if close {
+        d.push('Z');
+    }
+    d
+}
+
+/// Formats a character code as an attribute, or nothing if the code isn't a
+/// valid character.
+fn unicode_attribute(name: &str, code: u16) -> String {
+    match char::from_u32(code.into()) {
+        Some(c) if c != '\0' => format!(r#" {name}="&#x{:x};""#, u32::from(c)),
+        _ => String::new(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::shape_utils::calculate_shape_bounds;
+    use swf::{Fixed16, PointDelta, ShapeRecord};
+
+    /// The edges of a 10x10 pixel square at (10, 10), drawn with the first fill style.
+    fn square() -> Vec<ShapeRecord> {
+        vec![
+            ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                move_to: Some(swf::Point::from_pixels(10.0, 10.0)),
+                fill_style_0: None,
+                fill_style_1: Some(1),
+                line_style: None,
+                new_styles: None,
+            })),
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(10.0, 0.0),
+            },
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(0.0, 10.0),
+            },
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(-10.0, 0.0),
+            },
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(0.0, -10.0),
+            },
+        ]
+    }
+
+    fn build_shape(fill_style: FillStyle) -> swf::Shape {
+        let records = square();
+        let bounds = calculate_shape_bounds(&records);
+        swf::Shape {
+            version: 3,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            flags: swf::ShapeFlag::HAS_SCALING_STROKES,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![fill_style],
+                line_styles: vec![],
+            },
+            shape: records,
+        }
+    }
+
+    #[test]
+    fn solid_fill() {
+        let shape = build_shape(FillStyle::Color(Color::from_rgba(0x80ff0000)));
+        let svg = shape_to_svg(&shape, &HashMap::new());
+        assert_eq!(
+            svg.lines().collect::<Vec<_>>(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10" viewBox="10 10 10 10">"#,
+                r##"  <path d="M10 10L20 10L20 20L10 20L10 10" fill="#ff0000" fill-opacity="0.5019607843137255" fill-rule="evenodd"/>"##,
+                r#"</svg>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_bitmap_fill() {
+        let shape = build_shape(FillStyle::Bitmap {
+            id: 2,
+            matrix: Matrix::IDENTITY,
+            is_smoothed: true,
+            is_repeating: true,
+        });
+        let svg = shape_to_svg(&shape, &HashMap::new());
+        assert_eq!(
+            svg.lines().collect::<Vec<_>>(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10" viewBox="10 10 10 10">"#,
+                r#"  <path d="M10 10L20 10L20 20L10 20L10 10" fill="none" fill-rule="evenodd"/>"#,
+                r#"</svg>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn bitmap_fill() {
+        let shape = build_shape(FillStyle::Bitmap {
+            id: 2,
+            matrix: Matrix::scale(Fixed16::from_f64(20.0), Fixed16::from_f64(20.0)),
+            is_smoothed: false,
+            is_repeating: true,
+        });
+        let bitmaps = HashMap::from([(
+            2,
+            BitmapFile {
+                href: "bitmaps/2.png".to_string(),
+                width: 4,
+                height: 2,
+            },
+        )]);
+        let svg = shape_to_svg(&shape, &bitmaps);
+        assert_eq!(
+            svg.lines().collect::<Vec<_>>(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10" viewBox="10 10 10 10">"#,
+                r#"  <defs>"#,
+                r#"    <pattern id="paint0" patternUnits="userSpaceOnUse" width="4" height="2" patternTransform="matrix(1 0 0 1 0 0)">"#,
+                r#"      <image xlink:href="bitmaps/2.png" width="4" height="2" image-rendering="pixelated"/>"#,
+                r#"    </pattern>"#,
+                r#"  </defs>"#,
+                r#"  <path d="M10 10L20 10L20 20L10 20L10 10" fill="url(#paint0)" fill-rule="evenodd"/>"#,
+                r#"</svg>"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn font_glyphs() {
+        let font = swf::Font {
+            version: 2,
+            id: 3,
+            name: "Square".into(),
+            language: swf::Language::Unknown,
+            layout: None,
+            glyphs: vec![swf::Glyph {
+                shape_records: square(),
+                code: u16::from(b'A'),
+                advance: 0,
+                bounds: None,
+            }],
+            flags: swf::FontFlag::IS_BOLD,
+        };
+        let svg = font_to_svg(&font, "Square & Co", 1024);
+        assert_eq!(
+            svg.lines().collect::<Vec<_>>(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+                r#"  <defs>"#,
+                r#"    <font id="font3" horiz-adv-x="1024">"#,
+                r#"      <font-face font-family="Square &amp; Co" font-weight="bold" font-style="normal" units-per-em="1024" ascent="819" descent="-204"/>"#,
+                r#"      <missing-glyph horiz-adv-x="1024"/>"#,
+                r#"      <glyph glyph-name="glyph0" unicode="&#x41;" horiz-adv-x="400" d="M200 -200L400 -200L400 -400L200 -400L200 -200"/>"#,
+                r#"    </font>"#,
+                r#"  </defs>"#,
+                r#"</svg>"#,
+            ]
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
+use crate::writer::FlvWriter;
 use bitflags::bitflags;
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 bitflags! {
     #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -19,6 +20,9 @@ pub struct Header {
 }
 
 impl Header {
+    /// The size of an FLV header, and thus the usual data offset.
+    pub const SIZE: u64 = 9;
+
     /// Parse an FLV header.
     ///
     /// The header must, at a minimum, contain the FLV magic, version number,
@@ -56,12 +60,35 @@ impl Header {
             }
         }
     }
+
+    /// Write an FLV header.
+    ///
+    /// Any space between the end of the header and the data offset is filled
+    /// with zeroes, so that the writer is left at the start of the data tags.
+    /// Data offsets that point inside the header itself are rejected.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        if (self.data_offset as u64) < Self::SIZE + writer.position() {
+            return Err(Error::DataOffsetTooSmall);
+        }
+
+        writer.write_u24(0x464C56)?;
+        writer.write_u8(self.version)?;
+        writer.write_u8(self.type_flags.bits())?;
+        writer.write_u32(self.data_offset)?;
+
+        let padding = self.data_offset as u64 - writer.position();
+        writer.write(&vec![0; padding as usize])?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::header::{Header, TypeFlags};
     use crate::reader::FlvReader;
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_header() {
@@ -77,4 +104,37 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn write_header() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 12,
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(
+            data,
+            [0x46, 0x4C, 0x56, 0x01, 0x05, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00]
+        );
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Header::parse(&mut reader), Ok(header));
+        assert_eq!(reader.into_parts().1, 12);
+    }
+
+    #[test]
+    fn write_header_offset_too_small() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_VIDEO,
+            data_offset: 4,
+        };
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(header.write(&mut writer), Err(Error::DataOffsetTooSmall));
+    }
 }
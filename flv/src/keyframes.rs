@@ -0,0 +1,383 @@
+use crate::error::Error;
+use crate::reader::FlvReader;
+use crate::script::{ScriptData, Value, Variable};
+use crate::tag::{Tag, TagData};
+use crate::video::{FrameType, VideoData};
+use std::io::Seek;
+
+/// A point in an FLV that playback can be seeked to.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Keyframe {
+    /// The timestamp of the keyframe, in milliseconds.
+    pub timestamp: i32,
+
+    /// The position of the keyframe's tag.
+    ///
+    /// This is the position that `Tag::parse` must be called at to read the
+    /// keyframe, i.e. it points to the back pointer preceding the tag. This
+    /// matches how `FlvReader` positions are stored by players.
+    pub position: u64,
+}
+
+/// An index of every keyframe in an FLV.
+///
+/// Seeking snaps to the nearest keyframe, which requires either scanning
+/// through every tag in the file or consulting the `keyframes` object of the
+/// `onMetaData` script tag. Encoders frequently omit or mangle the latter, so
+/// this allows rebuilding it from the tags themselves.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct KeyframeIndex {
+    /// All keyframes in the FLV, in file order.
+    pub keyframes: Vec<Keyframe>,
+
+    /// The largest timestamp of any tag in the FLV, in milliseconds.
+    pub duration: i32,
+}
+
+impl KeyframeIndex {
+    /// Build a keyframe index by scanning all remaining tags in an FLV.
+    ///
+    /// The reader should be positioned at the start of the data tags, e.g.
+    /// just after parsing the header. Video tags with the keyframe frame type
+    /// are indexed; where several keyframes share a timestamp (such as an AVC
+    /// sequence header and the frame following it), only the first is kept,
+    /// as that is where a seek must land.
+    ///
+    /// Scanning stops at the end of the data. Tags with invalid data are
+    /// skipped over, but errors reading tag headers are returned.
+    pub fn build(reader: &mut FlvReader<'_>) -> Result<Self, Error> {
+        let mut index = Self::default();
+
+        loop {
+            let position = reader.stream_position()?;
+            let tag = match Tag::parse(reader) {
+                Ok(tag) => tag,
+                Err(Error::EndOfData) => break,
+                Err(e) => return Err(e),
+            };
+
+            index.duration = index.duration.max(tag.timestamp);
+
+            if let TagData::Video(VideoData {
+                frame_type: FrameType::Keyframe,
+                ..
+            }) = tag.data
+            {
+                if index
+                    .keyframes
+                    .last()
+                    .is_none_or(|last| last.timestamp != tag.timestamp)
+                {
+                    index.keyframes.push(Keyframe {
+                        timestamp: tag.timestamp,
+                        position,
+                    });
+                }
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Find the keyframe to seek to for a given time, in milliseconds.
+    ///
+    /// This is the last keyframe at or before the given time, or the first
+    /// keyframe if the time precedes all of them.
+    pub fn seek_point(&self, time: f64) -> Option<Keyframe> {
+        let after = self
+            .keyframes
+            .partition_point(|keyframe| (keyframe.timestamp as f64) <= time);
+
+        self.keyframes
+            .get(after.saturating_sub(1))
+            .or_else(|| self.keyframes.first())
+            .copied()
+    }
+
+    /// Shift all keyframe positions by a number of bytes.
+    ///
+    /// This is necessary when tags preceding the keyframes change size, such
+    /// as when the `onMetaData` tag is rewritten with a new index.
+    pub fn offset_positions(&mut self, delta: i64) {
+        for keyframe in &mut self.keyframes {
+            keyframe.position = keyframe.position.saturating_add_signed(delta);
+        }
+    }
+
+    /// Produce the `keyframes` object stored in `onMetaData`.
+    ///
+    /// Times are stored in seconds. File positions are stored per the usual
+    /// convention, which points past the back pointer to the tag header.
+    pub fn to_value(&self) -> Value<'static> {
+        Value::Object(vec![
+            Variable {
+                name: b"times",
+                data: Value::StrictArray(
+                    self.keyframes
+                        .iter()
+                        .map(|keyframe| Value::Number(keyframe.timestamp as f64 / 1000.0))
+                        .collect(),
+                ),
+            },
+            Variable {
+                name: b"filepositions",
+                data: Value::StrictArray(
+                    self.keyframes
+                        .iter()
+                        .map(|keyframe| Value::Number((keyframe.position + 4) as f64))
+                        .collect(),
+                ),
+            },
+        ])
+    }
+
+    /// Rewrite the `duration` and `keyframes` entries of an `onMetaData`
+    /// script tag to match this index.
+    ///
+    /// All other metadata is retained. If the script data has no usable
+    /// `onMetaData` variable, one is added.
+    pub fn update_metadata(&self, script: &mut ScriptData<'_>) {
+        let entries = [
+            (
+                &b"duration"[..],
+                Value::Number(self.duration as f64 / 1000.0),
+            ),
+            (&b"keyframes"[..], self.to_value()),
+        ];
+
+        match script.get_mut(b"onMetaData") {
+            Some(Value::EcmaArray(variables) | Value::Object(variables)) => {
+                for (name, data) in entries {
+                    match variables.iter_mut().find(|variable| variable.name == name) {
+                        Some(variable) => variable.data = data,
+                        None => variables.push(Variable { name, data }),
+                    }
+                }
+            }
+            Some(value) => {
+                *value = Value::EcmaArray(
+                    entries
+                        .into_iter()
+                        .map(|(name, data)| Variable { name, data })
+                        .collect(),
+                );
+            }
+            None => script.0.push(Variable {
+                name: b"onMetaData",
+                data: Value::EcmaArray(
+                    entries
+                        .into_iter()
+                        .map(|(name, data)| Variable { name, data })
+                        .collect(),
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::header::{Header, TypeFlags};
+    use crate::keyframes::{Keyframe, KeyframeIndex};
+    use crate::reader::FlvReader;
+    use crate::script::{ScriptData, Value, Variable};
+    use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::tag::{Tag, TagData};
+    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+
+    fn video_tag(timestamp: i32, frame_type: FrameType) -> Tag<'static> {
+        Tag {
+            timestamp,
+            stream_id: 0,
+            data: TagData::Video(VideoData {
+                frame_type,
+                codec_id: CodecId::SorensonH263,
+                data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+            }),
+        }
+    }
+
+    fn audio_tag(timestamp: i32) -> Tag<'static> {
+        Tag {
+            timestamp,
+            stream_id: 0,
+            data: TagData::Audio(AudioData {
+                format: SoundFormat::MP3,
+                rate: SoundRate::R44_000,
+                size: SoundSize::Bits16,
+                sound_type: SoundType::Stereo,
+                data: AudioDataType::Raw(&[0x12, 0x34]),
+            }),
+        }
+    }
+
+    fn write_flv(tags: &[Tag<'_>]) -> Vec<u8> {
+        let mut writer = FlvWriter::new(vec![]);
+        Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: Header::SIZE as u32,
+        }
+        .write(&mut writer)
+        .unwrap();
+
+        for tag in tags {
+            tag.write(&mut writer).unwrap();
+        }
+
+        writer.finish().unwrap()
+    }
+
+    fn metadata_tag(script: ScriptData<'_>) -> Tag<'_> {
+        Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Script(script),
+        }
+    }
+
+    #[test]
+    fn build_index() {
+        let data = write_flv(&[
+            video_tag(0, FrameType::Keyframe),
+            video_tag(0, FrameType::Keyframe),
+            audio_tag(20),
+            video_tag(40, FrameType::Interframe),
+            video_tag(80, FrameType::Keyframe),
+            audio_tag(100),
+        ]);
+        let mut reader = FlvReader::from_source(&data);
+        Header::parse(&mut reader).unwrap();
+        let index = KeyframeIndex::build(&mut reader).unwrap();
+
+        assert_eq!(index.duration, 100);
+        assert_eq!(
+            index.keyframes,
+            [
+                Keyframe {
+                    timestamp: 0,
+                    position: 9
+                },
+                Keyframe {
+                    timestamp: 80,
+                    position: 87
+                }
+            ]
+        );
+
+        // Index positions must be usable for seeking.
+        let mut reader = FlvReader::from_parts(&data, 87);
+        assert_eq!(
+            Tag::parse(&mut reader),
+            Ok(video_tag(80, FrameType::Keyframe))
+        );
+
+        assert_eq!(index.seek_point(-10.0), Some(index.keyframes[0]));
+        assert_eq!(index.seek_point(79.0), Some(index.keyframes[0]));
+        assert_eq!(index.seek_point(80.0), Some(index.keyframes[1]));
+        assert_eq!(index.seek_point(1000.0), Some(index.keyframes[1]));
+        assert_eq!(KeyframeIndex::default().seek_point(0.0), None);
+    }
+
+    #[test]
+    fn update_metadata() {
+        let mut script = ScriptData(vec![Variable {
+            name: b"onMetaData",
+            data: Value::EcmaArray(vec![
+                Variable {
+                    name: b"duration",
+                    data: Value::Number(0.0),
+                },
+                Variable {
+                    name: b"width",
+                    data: Value::Number(320.0),
+                },
+            ]),
+        }]);
+        let index = KeyframeIndex {
+            keyframes: vec![
+                Keyframe {
+                    timestamp: 0,
+                    position: 9,
+                },
+                Keyframe {
+                    timestamp: 1500,
+                    position: 200,
+                },
+            ],
+            duration: 2000,
+        };
+        index.update_metadata(&mut script);
+
+        assert_eq!(
+            script,
+            ScriptData(vec![Variable {
+                name: b"onMetaData",
+                data: Value::EcmaArray(vec![
+                    Variable {
+                        name: b"duration",
+                        data: Value::Number(2.0),
+                    },
+                    Variable {
+                        name: b"width",
+                        data: Value::Number(320.0),
+                    },
+                    Variable {
+                        name: b"keyframes",
+                        data: Value::Object(vec![
+                            Variable {
+                                name: b"times",
+                                data: Value::StrictArray(vec![
+                                    Value::Number(0.0),
+                                    Value::Number(1.5)
+                                ]),
+                            },
+                            Variable {
+                                name: b"filepositions",
+                                data: Value::StrictArray(vec![
+                                    Value::Number(13.0),
+                                    Value::Number(204.0)
+                                ]),
+                            },
+                        ]),
+                    },
+                ]),
+            }])
+        );
+    }
+
+    #[test]
+    fn rewrite_metadata() {
+        let tags = [
+            video_tag(0, FrameType::Keyframe),
+            video_tag(40, FrameType::Interframe),
+            video_tag(80, FrameType::Keyframe),
+        ];
+        let data = write_flv(&tags);
+        let mut reader = FlvReader::from_source(&data);
+        Header::parse(&mut reader).unwrap();
+        let mut index = KeyframeIndex::build(&mut reader).unwrap();
+
+        // Prepend a metadata tag, accounting for the space it takes up. The
+        // size of the index doesn't depend on the positions within it.
+        let mut script = ScriptData(vec![]);
+        index.update_metadata(&mut script);
+        let metadata_size = write_flv(&[metadata_tag(script)]).len() - write_flv(&[]).len();
+        index.offset_positions(metadata_size as i64);
+
+        let mut script = ScriptData(vec![]);
+        index.update_metadata(&mut script);
+        let mut rewritten = vec![metadata_tag(script.clone())];
+        rewritten.extend(tags.iter().cloned());
+        let data = write_flv(&rewritten);
+
+        let mut reader = FlvReader::from_source(&data);
+        Header::parse(&mut reader).unwrap();
+        assert_eq!(Tag::parse(&mut reader), Ok(metadata_tag(script)));
+
+        let mut reader = FlvReader::from_source(&data);
+        Header::parse(&mut reader).unwrap();
+        assert_eq!(KeyframeIndex::build(&mut reader), Ok(index));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<&'a [u8], Error> {
     let length = if is_long_string {
@@ -12,6 +13,33 @@ fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<
     reader.read(length as usize)
 }
 
+fn write_string<W: Write>(
+    writer: &mut FlvWriter<W>,
+    string: &[u8],
+    is_long_string: bool,
+) -> Result<(), Error> {
+    if is_long_string {
+        writer.write_u32(string.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+    } else {
+        writer.write_u16(string.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+    }
+
+    writer.write(string)
+}
+
+/// Write the list of variables in an object or ECMA array, followed by an
+/// object terminator.
+fn write_variables<W: Write>(
+    writer: &mut FlvWriter<W>,
+    variables: &[Variable<'_>],
+) -> Result<(), Error> {
+    for variable in variables {
+        variable.write(writer)?;
+    }
+
+    writer.write_u24(9)
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum Value<'a> {
@@ -98,6 +126,72 @@ impl<'a> Value<'a> {
             _ => Err(Error::UnknownValueType),
         }
     }
+
+    /// Write a script value.
+    ///
+    /// Strings longer than their length field allows yield `ValueTooLarge`;
+    /// callers with long text should use `LongString` instead of `String`.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        match self {
+            Self::Number(value) => {
+                writer.write_u8(0)?;
+                writer.write_f64(*value)
+            }
+            Self::Boolean(value) => {
+                writer.write_u8(1)?;
+                writer.write_u8(*value as u8)
+            }
+            Self::String(string) => {
+                writer.write_u8(2)?;
+                write_string(writer, string, false)
+            }
+            Self::Object(variables) => {
+                writer.write_u8(3)?;
+                write_variables(writer, variables)
+            }
+            Self::MovieClip(path) => {
+                writer.write_u8(4)?;
+                write_string(writer, path, false)
+            }
+            Self::Null => writer.write_u8(5),
+            Self::Undefined => writer.write_u8(6),
+            Self::Reference(index) => {
+                writer.write_u8(7)?;
+                writer.write_u16(*index)
+            }
+            Self::EcmaArray(variables) => {
+                writer.write_u8(8)?;
+                writer.write_u32(
+                    variables
+                        .len()
+                        .try_into()
+                        .map_err(|_| Error::ValueTooLarge)?,
+                )?;
+                write_variables(writer, variables)
+            }
+            Self::StrictArray(values) => {
+                writer.write_u8(10)?;
+                writer.write_u32(values.len().try_into().map_err(|_| Error::ValueTooLarge)?)?;
+                for value in values {
+                    value.write(writer)?;
+                }
+
+                Ok(())
+            }
+            Self::Date {
+                unix_time,
+                local_offset,
+            } => {
+                writer.write_u8(11)?;
+                writer.write_f64(*unix_time)?;
+                writer.write_i16(*local_offset)
+            }
+            Self::LongString(string) => {
+                writer.write_u8(12)?;
+                write_string(writer, string, true)
+            }
+        }
+    }
 }
 
 /// An individual object in a ScriptData tag.
@@ -118,6 +212,11 @@ impl<'a> Variable<'a> {
             data: Value::parse(reader)?,
         })
     }
+
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        write_string(writer, self.name, false)?;
+        self.data.write(writer)
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -149,12 +248,41 @@ impl<'a> ScriptData<'a> {
             vars.push(Variable::parse(reader)?);
         }
     }
+
+    /// Write a script data structure.
+    ///
+    /// The variable list is always terminated, even though `parse` does not
+    /// require it.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        // This is the type marker of the first variable name, which `parse`
+        // skips over.
+        writer.write_u8(2)?;
+        write_variables(writer, &self.0)
+    }
+
+    /// Find the value of a named variable.
+    pub fn get(&self, name: &[u8]) -> Option<&Value<'a>> {
+        self.0
+            .iter()
+            .find(|variable| variable.name == name)
+            .map(|variable| &variable.data)
+    }
+
+    /// Find the value of a named variable for modification.
+    pub fn get_mut(&mut self, name: &[u8]) -> Option<&mut Value<'a>> {
+        self.0
+            .iter_mut()
+            .find(|variable| variable.name == name)
+            .map(|variable| &mut variable.data)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::reader::FlvReader;
-    use crate::script::{parse_string, ScriptData, Value, Variable};
+    use crate::script::{parse_string, write_string, ScriptData, Value, Variable};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_string() {
@@ -386,4 +514,126 @@ mod tests {
             ]))
         );
     }
+
+    fn round_trip(value: Value<'_>) -> Vec<u8> {
+        let mut writer = FlvWriter::new(vec![]);
+        value.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Value::parse(&mut reader), Ok(value));
+
+        data
+    }
+
+    #[test]
+    fn write_string_too_long() {
+        let string = vec![0x20; 0x10000];
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(
+            write_string(&mut writer, &string, false),
+            Err(Error::ValueTooLarge)
+        );
+        assert_eq!(write_string(&mut writer, &string, true), Ok(()));
+    }
+
+    #[test]
+    fn write_value_scalars() {
+        assert_eq!(
+            round_trip(Value::Number(12.3)),
+            [0x00, 0x40, 0x28, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]
+        );
+        assert_eq!(round_trip(Value::Boolean(true)), [0x01, 0x01]);
+        assert_eq!(round_trip(Value::Null), [0x05]);
+        assert_eq!(round_trip(Value::Undefined), [0x06]);
+        assert_eq!(round_trip(Value::Reference(0x2438)), [0x07, 0x24, 0x38]);
+        assert_eq!(
+            round_trip(Value::Date {
+                unix_time: 12.3,
+                local_offset: -2
+            }),
+            [0x0B, 0x40, 0x28, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a, 0xFF, 0xFE]
+        );
+    }
+
+    #[test]
+    fn write_value_strings() {
+        assert_eq!(
+            round_trip(Value::String(&[0x01, 0x02, 0x03])),
+            [0x02, 0x00, 0x03, 0x01, 0x02, 0x03]
+        );
+        assert_eq!(
+            round_trip(Value::MovieClip(&[0x01, 0x02, 0x03])),
+            [0x04, 0x00, 0x03, 0x01, 0x02, 0x03]
+        );
+        assert_eq!(
+            round_trip(Value::LongString(&[0x01, 0x02, 0x03])),
+            [0x0C, 0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03]
+        );
+    }
+
+    #[test]
+    fn write_value_containers() {
+        let variables = vec![
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            },
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Null,
+            },
+        ];
+
+        assert_eq!(
+            round_trip(Value::Object(variables.clone())),
+            [
+                0x03, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01, 0x02, 0x03, 0x05, 0x00,
+                0x00, 0x09,
+            ]
+        );
+        assert_eq!(
+            round_trip(Value::EcmaArray(variables)),
+            [
+                0x08, 0x00, 0x00, 0x00, 0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01,
+                0x02, 0x03, 0x05, 0x00, 0x00, 0x09,
+            ]
+        );
+        assert_eq!(
+            round_trip(Value::StrictArray(vec![Value::Undefined, Value::Null])),
+            [0x0A, 0x00, 0x00, 0x00, 0x02, 0x06, 0x05]
+        );
+    }
+
+    #[test]
+    fn write_scriptdata() {
+        let script = ScriptData(vec![
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            },
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Null,
+            },
+        ]);
+        let mut writer = FlvWriter::new(vec![]);
+        script.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(
+            data,
+            [
+                0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01, 0x02, 0x03, 0x05, 0x00,
+                0x00, 0x09,
+            ]
+        );
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(
+            ScriptData::parse(&mut reader, data.len() as u32),
+            Ok(script)
+        );
+    }
 }
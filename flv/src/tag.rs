@@ -3,8 +3,9 @@ use crate::reader::FlvReader;
 use crate::script::ScriptData;
 use crate::sound::AudioData;
 use crate::video::VideoData;
+use crate::writer::FlvWriter;
 
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
@@ -28,6 +29,10 @@ pub struct Tag<'a> {
 }
 
 impl<'a> Tag<'a> {
+    /// The size of a tag header, not including the back pointer to the prior
+    /// tag.
+    pub const HEADER_SIZE: u32 = 11;
+
     /// Parse a single FLV tag structure.
     ///
     /// FLV files are constructed as a list of tags. Back pointers to prior
@@ -114,6 +119,56 @@ impl<'a> Tag<'a> {
         }
     }
 
+    /// Write a single FLV tag structure.
+    ///
+    /// The back pointer to the previous tag written is written first, so that
+    /// the written data can be read back with repeated calls to `parse`. Tags
+    /// should be written after the FLV header, and the writer must be
+    /// finished once all tags have been written.
+    ///
+    /// Invalid tag data cannot be written; the error that made the tag
+    /// invalid will be returned instead. Tags whose data does not fit within
+    /// the 24-bit size field yield `ValueTooLarge`. In either case, nothing
+    /// is written.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        let mut body = FlvWriter::new(vec![]);
+        let tag_type = match &self.data {
+            TagData::Audio(data) => {
+                data.write(&mut body)?;
+                8
+            }
+            TagData::Video(data) => {
+                data.write(&mut body)?;
+                9
+            }
+            TagData::Script(data) => {
+                data.write(&mut body)?;
+                18
+            }
+            TagData::Invalid(e) => return Err(e.clone()),
+        };
+        let body = body.into_inner();
+
+        let data_size: u32 = body.len().try_into().map_err(|_| Error::ValueTooLarge)?;
+        if data_size > 0xFF_FFFF || self.stream_id > 0xFF_FFFF {
+            return Err(Error::ValueTooLarge);
+        }
+
+        let timestamp = self.timestamp as u32;
+
+        writer.write_u32(writer.previous_tag_size())?;
+        writer.write_u8(tag_type)?;
+        writer.write_u24(data_size)?;
+        writer.write_u24(timestamp & 0xFF_FFFF)?;
+        writer.write_u8((timestamp >> 24) as u8)?;
+        writer.write_u24(self.stream_id)?;
+        writer.write(&body)?;
+
+        writer.set_previous_tag_size(Self::HEADER_SIZE + data_size);
+
+        Ok(())
+    }
+
     /// Skip back to the prior tag in the FLV.
     ///
     /// FLV files are constructed as a list of tags. Back pointers to prior
@@ -146,6 +201,7 @@ mod tests {
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
     use crate::tag::{Tag, TagData};
     use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_tag_sounddata() {
@@ -332,4 +388,82 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn write_tags() {
+        let tags = [
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Script(ScriptData(vec![Variable {
+                    name: b"onMetaData",
+                    data: Value::EcmaArray(vec![Variable {
+                        name: b"duration",
+                        data: Value::Number(1.07),
+                    }]),
+                }])),
+            },
+            Tag {
+                timestamp: 0x12345678,
+                stream_id: 0x5000,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Speex,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78]),
+                }),
+            },
+            Tag {
+                timestamp: 83,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+                }),
+            },
+        ];
+
+        let mut writer = FlvWriter::new(vec![]);
+        for tag in &tags {
+            tag.write(&mut writer).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        assert_eq!(
+            &data[0x3A..0x52],
+            [
+                0x00, 0x00, 0x00, 0x36, 0x08, 0x00, 0x00, 0x05, 0x34, 0x56, 0x78, 0x12, 0x00, 0x50,
+                0x00, 0xBF, 0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0x00, 0x10,
+            ]
+        );
+
+        let mut reader = FlvReader::from_source(&data);
+        for tag in &tags {
+            assert_eq!(Tag::parse(&mut reader).as_ref(), Ok(tag));
+        }
+        assert_eq!(Tag::parse(&mut reader), Err(Error::EndOfData));
+
+        // The final back pointer must allow seeking back through every tag.
+        for tag in tags.iter().rev() {
+            Tag::skip_back(&mut reader).unwrap();
+            assert_eq!(Tag::parse(&mut reader).as_ref(), Ok(tag));
+            Tag::skip_back(&mut reader).unwrap();
+        }
+        assert_eq!(Tag::skip_back(&mut reader), Err(Error::EndOfData));
+    }
+
+    #[test]
+    fn write_tag_invalid() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Invalid(Error::UnknownTagType(0x20)),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(tag.write(&mut writer), Err(Error::UnknownTagType(0x20)));
+        assert_eq!(writer.position(), 0);
+    }
 }
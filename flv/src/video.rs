@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -161,13 +162,57 @@ impl<'a> VideoData<'a> {
             data: packet,
         })
     }
+
+    /// Write a video data structure.
+    ///
+    /// AVC composition time offsets must fit within a signed 24-bit integer.
+    /// Sequence headers and end-of-sequence packets are always written with
+    /// an offset of zero.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8((self.frame_type as u8) << 4 | self.codec_id as u8)?;
+
+        match self.data {
+            VideoPacket::Data(data) => writer.write(data),
+            VideoPacket::Vp6Data {
+                hadjust,
+                vadjust,
+                data,
+            } => {
+                writer.write_u8((vadjust & 0x0F) << 4 | hadjust & 0x0F)?;
+                writer.write(data)
+            }
+            VideoPacket::AvcSequenceHeader(data) => {
+                writer.write_u8(0)?;
+                writer.write_u24(0)?;
+                writer.write(data)
+            }
+            VideoPacket::AvcNalu {
+                composition_time_offset,
+                data,
+            } => {
+                if !(-0x80_0000..=0x7F_FFFF).contains(&composition_time_offset) {
+                    return Err(Error::ValueTooLarge);
+                }
+
+                writer.write_u8(1)?;
+                writer.write_u24(composition_time_offset as u32 & 0xFF_FFFF)?;
+                writer.write(data)
+            }
+            VideoPacket::AvcEndOfSequence => {
+                writer.write_u8(2)?;
+                writer.write_u24(0)
+            }
+            VideoPacket::CommandFrame(command) => writer.write_u8(command as u8),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::error::Error;
     use crate::reader::FlvReader;
-    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_videodata() {
@@ -324,4 +369,97 @@ mod tests {
             Err(Error::UnknownAvcPacketType(0xFF))
         );
     }
+
+    fn round_trip(video: VideoData<'_>) -> Vec<u8> {
+        let mut writer = FlvWriter::new(vec![]);
+        video.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(VideoData::parse(&mut reader, data.len() as u32), Ok(video));
+
+        data
+    }
+
+    #[test]
+    fn write_videodata() {
+        let data = round_trip(VideoData {
+            frame_type: FrameType::Keyframe,
+            codec_id: CodecId::SorensonH263,
+            data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+        });
+
+        assert_eq!(data, [0x12, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn write_videodata_vp6() {
+        let data = round_trip(VideoData {
+            frame_type: FrameType::Keyframe,
+            codec_id: CodecId::On2Vp6,
+            data: VideoPacket::Vp6Data {
+                hadjust: 0x07,
+                vadjust: 0x03,
+                data: &[0x12, 0x34, 0x56, 0x78],
+            },
+        });
+
+        assert_eq!(data, [0x14, 0x37, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn write_videodata_avcnalu_negative() {
+        let data = round_trip(VideoData {
+            frame_type: FrameType::Keyframe,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::AvcNalu {
+                composition_time_offset: -2,
+                data: &[0x12, 0x34, 0x56, 0x78],
+            },
+        });
+
+        assert_eq!(data, [0x17, 0x01, 0xFF, 0xFF, 0xFE, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn write_videodata_avcsequence() {
+        round_trip(VideoData {
+            frame_type: FrameType::Keyframe,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::AvcSequenceHeader(&[0x12, 0x34, 0x56, 0x78]),
+        });
+    }
+
+    #[test]
+    fn write_videodata_avceos() {
+        round_trip(VideoData {
+            frame_type: FrameType::Keyframe,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::AvcEndOfSequence,
+        });
+    }
+
+    #[test]
+    fn write_videodata_commandframe() {
+        round_trip(VideoData {
+            frame_type: FrameType::CommandFrame,
+            codec_id: CodecId::SorensonH263,
+            data: VideoPacket::CommandFrame(CommandFrame::EndOfClientSideSeek),
+        });
+    }
+
+    #[test]
+    fn write_videodata_avc_offset_too_large() {
+        let video = VideoData {
+            frame_type: FrameType::Interframe,
+            codec_id: CodecId::Avc,
+            data: VideoPacket::AvcNalu {
+                composition_time_offset: 0x800000,
+                data: &[],
+            },
+        };
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(video.write(&mut writer), Err(Error::ValueTooLarge));
+    }
 }
@@ -0,0 +1,142 @@
+use crate::error::Error as FlvError;
+use std::io::Write;
+
+/// A writer that allows muxing an FLV container.
+///
+/// The writer keeps track of the size of the last tag written so that each
+/// tag can be preceded by the correct back pointer. Once all tags have been
+/// written, `finish` must be called to write the back pointer of the final
+/// tag.
+pub struct FlvWriter<W: Write> {
+    output: W,
+
+    position: u64,
+
+    previous_tag_size: u32,
+}
+
+impl<W: Write> FlvWriter<W> {
+    pub fn new(output: W) -> Self {
+        FlvWriter {
+            output,
+            position: 0,
+            previous_tag_size: 0,
+        }
+    }
+
+    /// The number of bytes written so far.
+    ///
+    /// This is the position that an `FlvReader` over the written data would
+    /// need to be at in order to read back whatever is written next.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The size of the last tag written, including its header.
+    pub fn previous_tag_size(&self) -> u32 {
+        self.previous_tag_size
+    }
+
+    pub(crate) fn set_previous_tag_size(&mut self, size: u32) {
+        self.previous_tag_size = size;
+    }
+
+    /// Write the back pointer to the last tag and yield the underlying output.
+    pub fn finish(mut self) -> Result<W, FlvError> {
+        let previous_tag_size = self.previous_tag_size;
+        self.write_u32(previous_tag_size)?;
+        self.output.flush()?;
+
+        Ok(self.output)
+    }
+
+    /// Yield the underlying output without writing a final back pointer.
+    ///
+    /// This is intended for serializing individual structures (such as tag
+    /// bodies) rather than complete FLV files.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> Result<(), FlvError> {
+        self.output.write_all(data)?;
+        self.position += data.len() as u64;
+
+        Ok(())
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Result<(), FlvError> {
+        self.write(&[value])
+    }
+
+    pub fn write_u16(&mut self, value: u16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    /// Write the low 24 bits of a value.
+    ///
+    /// Values that do not fit in 24 bits yield `ValueTooLarge`.
+    pub fn write_u24(&mut self, value: u32) -> Result<(), FlvError> {
+        if value > 0xFF_FFFF {
+            return Err(FlvError::ValueTooLarge);
+        }
+
+        self.write(&value.to_be_bytes()[1..])
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_f64(&mut self, value: f64) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::reader::FlvReader;
+    use crate::writer::FlvWriter;
+
+    #[test]
+    fn write_numbers() {
+        let mut writer = FlvWriter::new(vec![]);
+        writer.write_u8(0x12).unwrap();
+        writer.write_u16(0x3456).unwrap();
+        writer.write_i16(-2).unwrap();
+        writer.write_u24(0x789ABC).unwrap();
+        writer.write_u32(0xDEADBEEF).unwrap();
+        writer.write_f64(12.3).unwrap();
+        assert_eq!(writer.position(), 20);
+
+        let data = writer.into_inner();
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(reader.read_u8(), Ok(0x12));
+        assert_eq!(reader.read_u16(), Ok(0x3456));
+        assert_eq!(reader.read_i16(), Ok(-2));
+        assert_eq!(reader.read_u24(), Ok(0x789ABC));
+        assert_eq!(reader.read_u32(), Ok(0xDEADBEEF));
+        assert_eq!(reader.read_f64(), Ok(12.3));
+    }
+
+    #[test]
+    fn write_u24_too_large() {
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(writer.write_u24(0x1000000), Err(Error::ValueTooLarge));
+        assert_eq!(writer.position(), 0);
+    }
+
+    #[test]
+    fn finish_writes_back_pointer() {
+        let mut writer = FlvWriter::new(vec![]);
+        writer.set_previous_tag_size(0x1234);
+
+        assert_eq!(writer.finish(), Ok(vec![0x00, 0x00, 0x12, 0x34]));
+    }
+}
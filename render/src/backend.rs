@@ -14,7 +14,7 @@ use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::sync::Arc;
-use swf::{Color, Rectangle, Twips};
+use swf::{CharacterId, Color, Rectangle, Twips};
 
 pub struct BitmapCacheEntry {
     pub handle: BitmapHandle,
@@ -34,6 +34,28 @@ pub trait RenderBackend: Downcast {
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle;
 
+    /// Whether this backend draws shapes from the meshes built by a
+    /// `ShapeTessellator`, meaning that they can be registered with
+    /// `register_tessellated_shape`.
+    #[cfg(feature = "tessellator")]
+    fn supports_tessellated_shapes(&self) -> bool {
+        false
+    }
+
+    /// Registers a shape that was already tessellated, such as one restored
+    /// from a cache.
+    ///
+    /// Returns `None` if this backend doesn't support tessellated shapes.
+    #[cfg(feature = "tessellator")]
+    fn register_tessellated_shape(
+        &mut self,
+        _shape_id: CharacterId,
+        _mesh: crate::tessellator::Mesh,
+        _bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        None
+    }
+
     fn render_offscreen(
         &mut self,
         handle: BitmapHandle,
@@ -17,13 +17,13 @@ use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
-    Gradient as TessGradient, ShapeTessellator, Vertex as TessVertex,
+    Gradient as TessGradient, Mesh as TessMesh, ShapeTessellator, Vertex as TessVertex,
 };
 use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color, Twips};
+use swf::{BlendMode, CharacterId, Color, Twips};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -567,11 +567,37 @@ impl WebGlRenderBackend {
         shape: DistilledShape,
         bitmap_source: &dyn BitmapSource,
     ) -> Result<Vec<Draw>, Error> {
-        use ruffle_render::tessellator::DrawType as TessDrawType;
-
         let lyon_mesh = self
             .shape_tessellator
             .tessellate_shape(shape, bitmap_source);
+        self.register_mesh_internal(lyon_mesh, bitmap_source)
+    }
+
+    fn shape_handle(&self, draws: Result<Vec<Draw>, Error>) -> ShapeHandle {
+        let mesh = match draws {
+            Ok(draws) => Mesh {
+                draws,
+                gl2: self.gl2.clone(),
+                vao_ext: self.vao_ext.clone(),
+            },
+            Err(e) => {
+                log::error!("Couldn't register shape: {:?}", e);
+                Mesh {
+                    draws: vec![],
+                    gl2: self.gl2.clone(),
+                    vao_ext: self.vao_ext.clone(),
+                }
+            }
+        };
+        ShapeHandle(Arc::new(mesh))
+    }
+
+    fn register_mesh_internal(
+        &mut self,
+        lyon_mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Result<Vec<Draw>, Error> {
+        use ruffle_render::tessellator::DrawType as TessDrawType;
 
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         for draw in lyon_mesh.draws {
@@ -1039,22 +1065,22 @@ impl RenderBackend for WebGlRenderBackend {
         shape: DistilledShape,
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle {
-        let mesh = match self.register_shape_internal(shape, bitmap_source) {
-            Ok(draws) => Mesh {
-                draws,
-                gl2: self.gl2.clone(),
-                vao_ext: self.vao_ext.clone(),
-            },
-            Err(e) => {
-                log::error!("Couldn't register shape: {:?}", e);
-                Mesh {
-                    draws: vec![],
-                    gl2: self.gl2.clone(),
-                    vao_ext: self.vao_ext.clone(),
-                }
-            }
-        };
-        ShapeHandle(Arc::new(mesh))
+        let draws = self.register_shape_internal(shape, bitmap_source);
+        self.shape_handle(draws)
+    }
+
+    fn supports_tessellated_shapes(&self) -> bool {
+        true
+    }
+
+    fn register_tessellated_shape(
+        &mut self,
+        _shape_id: CharacterId,
+        mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        let draws = self.register_mesh_internal(mesh, bitmap_source);
+        Some(self.shape_handle(draws))
     }
 
     fn submit_frame(
@@ -30,12 +30,12 @@ use ruffle_render::pixel_bender::{
 };
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::DistilledShape;
-use ruffle_render::tessellator::ShapeTessellator;
+use ruffle_render::tessellator::{Mesh as LyonMesh, ShapeTessellator};
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::path::Path;
 use std::sync::Arc;
-use swf::Color;
+use swf::{CharacterId, Color};
 use tracing::instrument;
 use wgpu::SubmissionIndex;
 
@@ -241,7 +241,15 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         let lyon_mesh = self
             .shape_tessellator
             .tessellate_shape(shape, bitmap_source);
+        self.register_mesh_internal(shape_id, lyon_mesh, bitmap_source)
+    }
 
+    fn register_mesh_internal(
+        &mut self,
+        shape_id: CharacterId,
+        lyon_mesh: LyonMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         let mut uniform_buffer = BufferBuilder::new_for_uniform(&self.descriptors.limits);
         let mut vertex_buffer = BufferBuilder::new_for_vertices(&self.descriptors.limits);
@@ -487,6 +495,21 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         ShapeHandle(Arc::new(mesh))
     }
 
+    fn supports_tessellated_shapes(&self) -> bool {
+        true
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    fn register_tessellated_shape(
+        &mut self,
+        shape_id: CharacterId,
+        mesh: LyonMesh,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Option<ShapeHandle> {
+        let mesh = self.register_mesh_internal(shape_id, mesh, bitmap_source);
+        Some(ShapeHandle(Arc::new(mesh)))
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn submit_frame(
         &mut self,
@@ -2,8 +2,22 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
+/// How many of the most common stubs to list.
+const TOP_STUBS: usize = 25;
+
+/// How often a single stub was hit across a scan.
+#[derive(Default)]
+struct StubUsage {
+    /// Number of movies that hit this stub.
+    files: usize,
+
+    /// Number of movies that hit this stub and did not complete.
+    failing: usize,
+}
+
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut total = 0;
@@ -13,10 +27,20 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut stubs: HashMap<String, StubUsage> = HashMap::new();
 
     for result in results {
         total += 1;
 
+        let failed = !matches!(result.progress, Step::Complete);
+        for stub in result.stubs {
+            let usage = stubs.entry(stub).or_default();
+            usage.files += 1;
+            if failed {
+                usage.failing += 1;
+            }
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -48,6 +72,26 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
     println!();
+
+    if !stubs.is_empty() {
+        let mut stubs: Vec<_> = stubs.into_iter().collect();
+        stubs.sort_by(|(a_name, a), (b_name, b)| {
+            b.files
+                .cmp(&a.files)
+                .then(b.failing.cmp(&a.failing))
+                .then(a_name.cmp(b_name))
+        });
+
+        println!("{} distinct stubs were hit.", stubs.len());
+        println!("Most common stubs (movies / failing movies):");
+        for (name, usage) in stubs.iter().take(TOP_STUBS) {
+            println!(
+                "{:>digits$} / {:<digits$} {name}",
+                usage.files, usage.failing
+            );
+        }
+        println!();
+    }
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
@@ -1,6 +1,7 @@
 //! CLI Options
 
 use clap::Parser;
+use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -20,6 +21,12 @@ pub enum Mode {
 
     /// Execute a single SWF file and generate a machine-readable report
     ExecuteReport(ExecuteReportOpt),
+
+    /// Render an entire directory of SWF files with two builds or configurations and report differences
+    Compare(CompareOpt),
+
+    /// Render a single SWF file and store its frames and trace output
+    RenderReport(RenderReportOpt),
 }
 
 #[derive(Parser, Debug)]
@@ -35,6 +42,9 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
     pub ignore: Vec<String>,
+
+    #[clap(flatten)]
+    pub execution: ExecutionOpt,
 }
 
 #[derive(Parser, Debug)]
@@ -49,4 +59,101 @@ pub struct ExecuteReportOpt {
     /// The single SWF file to parse and run
     #[clap(name = "file")]
     pub input_path: PathBuf,
+
+    #[clap(flatten)]
+    pub execution: ExecutionOpt,
+}
+
+/// Options controlling how each SWF is executed.
+#[derive(Parser, Debug, Clone)]
+pub struct ExecutionOpt {
+    /// Number of frames to run each movie for
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Feed simulated mouse and keyboard input to the movie while it runs
+    #[clap(long = "simulate-input")]
+    pub simulate_input: bool,
+}
+
+impl ExecutionOpt {
+    /// The command line arguments that reproduce these options for a child
+    /// `execute-report` process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--frames".to_string(), self.frames.to_string()];
+        if self.simulate_input {
+            args.push("--simulate-input".to_string());
+        }
+        args
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CompareOpt {
+    /// The directory (containing SWF files) to compare
+    #[clap(name = "directory")]
+    pub input_path: PathBuf,
+
+    /// The directory to store rendered frames, traces and the report in
+    #[clap(name = "output")]
+    pub output_path: PathBuf,
+
+    /// Filenames to ignore
+    #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
+    pub ignore: Vec<String>,
+
+    /// Number of frames to render of each movie
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Scanner executable used to render the baseline (defaults to this one)
+    #[clap(long = "baseline")]
+    pub baseline: Option<PathBuf>,
+
+    /// Scanner executable used to render the candidate (defaults to this one)
+    #[clap(long = "candidate")]
+    pub candidate: Option<PathBuf>,
+
+    /// Extra argument passed to `render-report` for the baseline, e.g. `--no-avm2-optimizer`
+    #[clap(long = "baseline-arg", allow_hyphen_values = true, action = clap::ArgAction::Append)]
+    pub baseline_args: Vec<String>,
+
+    /// Extra argument passed to `render-report` for the candidate, e.g. `--no-avm2-optimizer`
+    #[clap(long = "candidate-arg", allow_hyphen_values = true, action = clap::ArgAction::Append)]
+    pub candidate_args: Vec<String>,
+
+    /// How much a single color channel may differ before a pixel counts as different
+    #[clap(long = "tolerance", default_value = "0")]
+    pub tolerance: u8,
+
+    /// Fraction of pixels in a frame that may differ before the frame counts as divergent
+    #[clap(long = "threshold", default_value = "0.0")]
+    pub threshold: f64,
+}
+
+#[derive(Parser, Debug)]
+pub struct RenderReportOpt {
+    /// The single SWF file to render
+    #[clap(name = "file")]
+    pub input_path: PathBuf,
+
+    /// The directory to store rendered frames and trace output in
+    #[clap(name = "output")]
+    pub output_path: PathBuf,
+
+    /// Number of frames to render
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Disable the AVM2 optimizer
+    #[clap(long = "no-avm2-optimizer")]
+    pub no_avm2_optimizer: bool,
+
+    /// Type of graphics backend to use
+    #[clap(long, short, default_value = "default")]
+    pub graphics: GraphicsBackend,
+
+    /// Power preference for the graphics device used
+    #[clap(long, short, default_value = "high")]
+    pub power: PowerPreference,
 }
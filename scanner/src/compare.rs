@@ -0,0 +1,340 @@
+//! Visual regression comparison between two builds or configurations
+
+use crate::cli_options::CompareOpt;
+use crate::render::{frame_file_name, TRACE_FILE_NAME};
+use crate::scan::find_files;
+use crate::ser_bridge::SerBridge;
+use image::{Rgba, RgbaImage};
+use indicatif::{ProgressBar, ProgressStyle};
+use path_slash::PathExt;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Write as _;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The subdirectory of the output that baseline renders are stored in.
+const BASELINE_DIR: &str = "baseline";
+
+/// The subdirectory of the output that candidate renders are stored in.
+const CANDIDATE_DIR: &str = "candidate";
+
+/// The subdirectory of the output that difference images are stored in.
+const DIFF_DIR: &str = "diff";
+
+/// The result of comparing a single SWF.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompareResults {
+    /// The file name compared (relative to the scanned directory).
+    #[serde(rename = "Filename")]
+    pub name: String,
+
+    /// How many frames were rendered by both sides.
+    #[serde(rename = "Frames Compared")]
+    pub frames_compared: u32,
+
+    /// How many frames differ by more than the threshold.
+    #[serde(rename = "Divergent Frames")]
+    pub divergent_frames: u32,
+
+    /// The first frame that differs by more than the threshold.
+    #[serde(rename = "First Divergent Frame")]
+    pub first_divergent_frame: Option<u32>,
+
+    /// The largest fraction of differing pixels in any frame.
+    #[serde(rename = "Max Pixel Difference")]
+    pub max_difference: f64,
+
+    /// Whether or not the trace output differs.
+    #[serde(rename = "Trace Diverged")]
+    pub trace_diverged: bool,
+
+    /// Any errors encountered while rendering either side.
+    #[serde(rename = "Error")]
+    pub error: Option<String>,
+}
+
+impl CompareResults {
+    pub fn new(name: &str) -> Self {
+        CompareResults {
+            name: name.to_string(),
+            frames_compared: 0,
+            divergent_frames: 0,
+            first_divergent_frame: None,
+            max_difference: 0.0,
+            trace_diverged: false,
+            error: None,
+        }
+    }
+
+    /// Whether or not this file needs to be looked at.
+    pub fn diverged(&self) -> bool {
+        self.divergent_frames > 0 || self.trace_diverged || self.error.is_some()
+    }
+}
+
+/// Render a single SWF with the given scanner executable in a child process.
+fn render_with(
+    exec_path: &Path,
+    extra_args: &[String],
+    file: &Path,
+    output: &Path,
+    frames: u32,
+) -> Result<(), String> {
+    let subproc = Command::new(exec_path)
+        .arg("render-report")
+        .arg(file)
+        .arg(output)
+        .args(["--frames", &frames.to_string()])
+        .args(extra_args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if subproc.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&subproc.stderr).into_owned())
+    }
+}
+
+/// Compare two frames, returning the fraction of differing pixels and an
+/// image highlighting them.
+fn diff_frames(baseline: &RgbaImage, candidate: &RgbaImage, tolerance: u8) -> (f64, RgbaImage) {
+    if baseline.dimensions() != candidate.dimensions() {
+        return (1.0, candidate.clone());
+    }
+
+    let mut differing = 0;
+    let diff = RgbaImage::from_fn(baseline.width(), baseline.height(), |x, y| {
+        let a = baseline.get_pixel(x, y);
+        let b = candidate.get_pixel(x, y);
+
+        if a.0
+            .iter()
+            .zip(b.0.iter())
+            .any(|(a, b)| a.abs_diff(*b) > tolerance)
+        {
+            differing += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            // Dim matching pixels so that differences stand out.
+            let luma = (u32::from(a[0]) + u32::from(a[1]) + u32::from(a[2])) / 3;
+            let luma = (luma / 4 + 192) as u8;
+            Rgba([luma, luma, luma, 255])
+        }
+    });
+
+    let total = u64::from(baseline.width()) * u64::from(baseline.height());
+    (differing as f64 / total.max(1) as f64, diff)
+}
+
+/// Render and compare a single SWF.
+fn compare_file(
+    opt: &CompareOpt,
+    baseline: &Path,
+    candidate: &Path,
+    file: &Path,
+    name: &str,
+) -> CompareResults {
+    let mut results = CompareResults::new(name);
+
+    let baseline_dir = opt.output_path.join(BASELINE_DIR).join(name);
+    let candidate_dir = opt.output_path.join(CANDIDATE_DIR).join(name);
+    let diff_dir = opt.output_path.join(DIFF_DIR).join(name);
+
+    let mut errors = vec![];
+    if let Err(e) = render_with(
+        baseline,
+        &opt.baseline_args,
+        file,
+        &baseline_dir,
+        opt.frames,
+    ) {
+        errors.push(format!("Baseline: {e}"));
+    }
+    if let Err(e) = render_with(
+        candidate,
+        &opt.candidate_args,
+        file,
+        &candidate_dir,
+        opt.frames,
+    ) {
+        errors.push(format!("Candidate: {e}"));
+    }
+    if !errors.is_empty() {
+        results.error = Some(errors.join("\n"));
+    }
+
+    for frame in 0..opt.frames {
+        let file_name = frame_file_name(frame);
+        let (Ok(a), Ok(b)) = (
+            image::open(baseline_dir.join(&file_name)),
+            image::open(candidate_dir.join(&file_name)),
+        ) else {
+            break;
+        };
+
+        results.frames_compared += 1;
+
+        let (difference, diff) = diff_frames(&a.into_rgba8(), &b.into_rgba8(), opt.tolerance);
+        results.max_difference = results.max_difference.max(difference);
+
+        if difference > opt.threshold {
+            results.divergent_frames += 1;
+            results.first_divergent_frame.get_or_insert(frame);
+
+            let saved = create_dir_all(&diff_dir)
+                .map_err(|e| e.to_string())
+                .and_then(|_| {
+                    diff.save(diff_dir.join(&file_name))
+                        .map_err(|e| e.to_string())
+                });
+            if let Err(e) = saved {
+                results.error = Some(format!("Unable to save difference image: {e}"));
+            }
+        }
+    }
+
+    let baseline_trace = read_to_string(baseline_dir.join(TRACE_FILE_NAME));
+    let candidate_trace = read_to_string(candidate_dir.join(TRACE_FILE_NAME));
+    if let (Ok(a), Ok(b)) = (baseline_trace, candidate_trace) {
+        results.trace_diverged = a != b;
+    }
+
+    results
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Generate an HTML report listing every divergent file.
+fn html_report(opt: &CompareOpt, results: &[CompareResults]) -> String {
+    let mut html = String::new();
+    let diverged: Vec<_> = results.iter().filter(|r| r.diverged()).collect();
+
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Ruffle comparison report</title>\n<style>\n");
+    html.push_str("table { border-collapse: collapse; }\n");
+    html.push_str("td, th { border: 1px solid #888; padding: 4px; vertical-align: top; }\n");
+    html.push_str("img { max-width: 240px; max-height: 240px; }\n");
+    html.push_str("pre { max-width: 480px; white-space: pre-wrap; }\n");
+    html.push_str("</style>\n</head>\n<body>\n");
+    let _ = writeln!(
+        html,
+        "<p>{} of {} movies diverged ({} frames, tolerance {}, threshold {}).</p>",
+        diverged.len(),
+        results.len(),
+        opt.frames,
+        opt.tolerance,
+        opt.threshold
+    );
+    html.push_str("<table>\n<tr><th>File</th><th>Divergent frames</th><th>Max difference</th>");
+    html.push_str("<th>Trace</th><th>Baseline</th><th>Candidate</th><th>Difference</th></tr>\n");
+
+    for result in diverged {
+        let name = escape_html(&result.name);
+        let _ = write!(
+            html,
+            "<tr><td>{name}</td><td>{}/{}</td><td>{:.2}%</td><td>{}</td>",
+            result.divergent_frames,
+            result.frames_compared,
+            result.max_difference * 100.0,
+            if result.trace_diverged {
+                "differs"
+            } else {
+                "same"
+            },
+        );
+
+        match (&result.error, result.first_divergent_frame) {
+            (Some(error), _) => {
+                let _ = write!(
+                    html,
+                    "<td colspan=\"3\"><pre>{}</pre></td>",
+                    escape_html(error)
+                );
+            }
+            (None, Some(frame)) => {
+                let file_name = frame_file_name(frame);
+                for dir in [BASELINE_DIR, CANDIDATE_DIR, DIFF_DIR] {
+                    let _ = write!(html, "<td><img src=\"{dir}/{name}/{file_name}\"></td>");
+                }
+            }
+            (None, None) => html.push_str("<td colspan=\"3\"></td>"),
+        }
+
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// The comparison process.
+///
+/// Should be called with parsed options corresponding to the `compare`
+/// command.
+pub fn compare_main(opt: CompareOpt) -> Result<(), std::io::Error> {
+    let binary_path = env::current_exe()?;
+    let baseline: PathBuf = opt.baseline.clone().unwrap_or_else(|| binary_path.clone());
+    let candidate: PathBuf = opt.candidate.clone().unwrap_or(binary_path);
+
+    let to_scan = find_files(&opt.input_path, &opt.ignore);
+    create_dir_all(&opt.output_path)?;
+    let mut writer = csv::Writer::from_path(opt.output_path.join("report.csv"))?;
+
+    let progress = ProgressBar::new(to_scan.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} [{eta_precise}] {pos:>7}/{len:7} {msg}",
+        )
+        .unwrap()
+        .progress_chars("##-"),
+    );
+
+    let opt = std::sync::Arc::new(opt);
+    let worker_opt = opt.clone();
+    let results: Vec<CompareResults> = to_scan
+        .into_par_iter()
+        .map(move |file| {
+            let name = file
+                .path()
+                .strip_prefix(&worker_opt.input_path)
+                .unwrap_or_else(|_| file.path())
+                .to_slash_lossy()
+                .into_owned();
+            let result = compare_file(&worker_opt, &baseline, &candidate, file.path(), &name);
+
+            progress.inc(1);
+            progress.set_message(name);
+
+            result
+        })
+        .ser_bridge()
+        .inspect(|result| {
+            if let Err(e) = writer.serialize(result.clone()) {
+                eprintln!("{e}");
+            };
+        })
+        .collect();
+
+    write(
+        opt.output_path.join("report.html"),
+        html_report(&opt, &results),
+    )?;
+
+    let diverged = results.iter().filter(|r| r.diverged()).count();
+    println!("Compared {} swf files.", results.len());
+    println!(
+        "{diverged} movies diverged; see the report in {}",
+        opt.output_path.display()
+    );
+
+    Ok(())
+}
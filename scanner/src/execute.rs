@@ -1,36 +1,143 @@
 //! Child/executor process impls
 
-use crate::cli_options::ExecuteReportOpt;
+use crate::cli_options::{ExecuteReportOpt, ExecutionOpt};
 use crate::file_results::{AvmType, FileResults, Step};
 use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_LOGGER};
+use crate::profile::MovieProfile;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::events::{KeyCode, MouseButton};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::swf::{decompress_swf, parse_swf};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
+use ruffle_core::{PlayerBuilder, PlayerEvent};
 use sha2::{Digest, Sha256};
+use std::any::Any;
 use std::io::{stdout, Write};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+/// The outcome of running a movie for a number of frames.
+///
+/// If the movie panicked, this holds everything up to the panic.
+#[derive(Default)]
+struct ExecutionResults {
+    frames_executed: u32,
+    stubs: Vec<String>,
+    panic: Option<String>,
+}
+
+/// Formats the payload of a caught panic as a scan error.
+fn panic_error(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("PANIC: {message}"),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("PANIC: {message}"),
+            Err(_) => "PANIC".to_string(),
+        },
+    }
+}
+
+/// Generate the simulated input for a given frame.
+///
+/// The mouse sweeps diagonally across the stage, clicking every few frames,
+/// while a handful of commonly used keys are tapped in turn.
+fn simulated_input(frame: u32, width: f64, height: f64) -> Vec<PlayerEvent> {
+    const KEYS: [(KeyCode, Option<char>); 5] = [
+        (KeyCode::SPACE, Some(' ')),
+        (KeyCode::LEFT, None),
+        (KeyCode::RIGHT, None),
+        (KeyCode::UP, None),
+        (KeyCode::DOWN, None),
+    ];
+
+    let progress = f64::from(frame % 64) / 64.0;
+    let x = width * progress;
+    let y = height * (1.0 - progress);
+    let mut events = vec![PlayerEvent::MouseMove { x, y }];
+
+    if frame % 8 == 4 {
+        events.push(PlayerEvent::MouseDown {
+            x,
+            y,
+            button: MouseButton::Left,
+            index: None,
+        });
+        events.push(PlayerEvent::MouseUp {
+            x,
+            y,
+            button: MouseButton::Left,
+        });
+    }
+
+    if frame % 8 == 0 {
+        let (key_code, key_char) = KEYS[(frame / 8) as usize % KEYS.len()];
+        events.push(PlayerEvent::KeyDown { key_code, key_char });
+        events.push(PlayerEvent::KeyUp { key_code, key_char });
+    }
+
+    events
+}
+
+fn execute_swf(file: &Path, opt: &ExecutionOpt) -> ExecutionResults {
+    let mut results = ExecutionResults::default();
+
     let base_path = file.parent().unwrap();
     let executor = NullExecutor::new();
-    let movie = SwfMovie::from_path(file, None).unwrap();
-    let frame_time = 1000.0 / movie.frame_rate().to_f64();
-    let player = PlayerBuilder::new()
-        .with_log(ScanLogBackend::new())
-        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
-        .with_max_execution_duration(Duration::from_secs(300))
-        .with_movie(movie)
-        .build();
-
-    player.lock().unwrap().preload(&mut ExecutionLimit::none());
-
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
-    //executor.poll_all().unwrap();
+    let player = catch_unwind(AssertUnwindSafe(|| {
+        let movie = SwfMovie::from_path(file, None).unwrap();
+        let player = PlayerBuilder::new()
+            .with_log(ScanLogBackend::new())
+            .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
+            .with_max_execution_duration(Duration::from_secs(300))
+            .with_movie(movie)
+            .build();
+        player.lock().unwrap().preload(&mut ExecutionLimit::none());
+        player
+    }));
+    let player = match player {
+        Ok(player) => player,
+        Err(payload) => {
+            results.panic = Some(panic_error(payload));
+            return results;
+        }
+    };
+
+    let mut player = player.lock().unwrap();
+    let frame_time = 1000.0 / player.frame_rate();
+    let viewport = player.viewport_dimensions();
+    let (width, height) = (f64::from(viewport.width), f64::from(viewport.height));
+
+    // Each frame is run on its own, so that the frames and stubs before a
+    // panic are still reported.
+    for frame in 0..opt.frames {
+        let frame_result = catch_unwind(AssertUnwindSafe(|| {
+            if opt.simulate_input {
+                for event in simulated_input(frame, width, height) {
+                    player.handle_event(event);
+                }
+            }
+
+            player.run_frame();
+            player.update_timers(frame_time);
+            //executor.poll_all().unwrap();
+        }));
+        if let Err(payload) = frame_result {
+            results.panic = Some(panic_error(payload));
+            break;
+        }
+
+        results.frames_executed += 1;
+    }
+
+    results.stubs = player
+        .stub_tracker()
+        .iter()
+        .map(|stub| stub.to_string())
+        .collect();
+    results.stubs.sort();
+
+    results
 }
 
 fn checkpoint<W: Write>(
@@ -128,6 +235,11 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                     true => AvmType::Avm2,
                     false => AvmType::Avm1,
                 });
+
+                let profile = MovieProfile::from_swf(&swf_buf);
+                file_result.tags = profile.tags.into_iter().collect();
+                file_result.classes = profile.classes.into_iter().collect();
+                file_result.opcodes = profile.opcodes.into_iter().collect();
             }
             Err(e) => {
                 file_result.error = Some(format!("Parse error: {e}"));
@@ -149,18 +261,13 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Execute;
 
-    //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
-        match e.downcast::<String>() {
-            Ok(e) => {
-                file_result.error = Some(format!("PANIC: {e}"));
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-            Err(_) => {
-                file_result.error = Some("PANIC".to_string());
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-        }
+    //Run the requested number of frames of the movie in Ruffle.
+    let results = execute_swf(&file_path, &execute_report_opt.execution);
+    file_result.frames_executed = Some(results.frames_executed);
+    file_result.stubs = results.stubs;
+    if let Some(panic) = results.panic {
+        file_result.error = Some(panic);
+        checkpoint(&mut file_result, &start, &mut writer)?;
     }
 
     let errors = LOCAL_LOGGER.with(|log_buffer| {
@@ -126,6 +126,42 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// How many frames of the movie were run before execution stopped.
+    #[serde(rename = "Frames Executed")]
+    pub frames_executed: Option<u32>,
+
+    /// Every stub hit while executing the movie.
+    #[serde(
+        rename = "Stubs",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub stubs: Vec<String>,
+
+    /// Every tag type present in the movie.
+    #[serde(
+        rename = "Tags",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub tags: Vec<String>,
+
+    /// Every player-provided AVM2 class referenced by the movie.
+    #[serde(
+        rename = "Classes",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub classes: Vec<String>,
+
+    /// Every AVM1 action and AVM2 instruction present in the movie.
+    #[serde(
+        rename = "Opcodes",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub opcodes: Vec<String>,
 }
 
 impl Default for FileResults {
@@ -153,6 +189,11 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            frames_executed: None,
+            stubs: vec![],
+            tags: vec![],
+            classes: vec![],
+            opcodes: vec![],
         }
     }
 }
@@ -207,3 +248,25 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+/// Formats a list of strings as newline-separated text
+fn into_list<S>(list: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&list.join("\n"))
+}
+
+/// Parses newline-separated text into a list of strings
+fn from_list<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(d)?;
+
+    Ok(text
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
@@ -18,6 +18,31 @@ impl LogBackend for ScanLogBackend {
     fn avm_trace(&self, _message: &str) {}
 }
 
+/// Log backend that collects AVM trace output
+#[derive(Clone, Default)]
+pub struct TraceLogBackend {
+    trace_output: Rc<RefCell<String>>,
+}
+
+impl TraceLogBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All trace output collected so far, one line per trace.
+    pub fn trace_output(&self) -> String {
+        self.trace_output.borrow().clone()
+    }
+}
+
+impl LogBackend for TraceLogBackend {
+    fn avm_trace(&self, message: &str) {
+        let mut trace_output = self.trace_output.borrow_mut();
+        trace_output.push_str(message);
+        trace_output.push('\n');
+    }
+}
+
 thread_local! {
     /// Thread local log buffer.
     pub static LOCAL_LOGGER: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
@@ -1,14 +1,19 @@
 use crate::analyze::analyze_main;
 use crate::cli_options::{Mode, Opt};
+use crate::compare::compare_main;
 use crate::execute::execute_report_main;
+use crate::render::render_report_main;
 use crate::scan::scan_main;
 use clap::Parser;
 
 mod analyze;
 mod cli_options;
+mod compare;
 mod execute;
 mod file_results;
 mod logging;
+mod profile;
+mod render;
 mod scan;
 mod ser_bridge;
 
@@ -27,5 +32,7 @@ fn main() -> Result<(), std::io::Error> {
             // CSV output.
             Ok(())
         }
+        Mode::Compare(compare_opt) => compare_main(compare_opt),
+        Mode::RenderReport(render_report_opt) => render_report_main(render_report_opt),
     }
 }
@@ -0,0 +1,201 @@
+//! Static movie profiling
+//!
+//! Walks the tag stream of a decompressed SWF and records which tags,
+//! ActionScript opcodes and AVM2 classes it uses, without running it.
+
+use std::collections::BTreeSet;
+use swf::avm2::types::{AbcFile, Index, Multiname, Namespace, Op};
+use swf::extensions::ReadSwfExt;
+use swf::{SwfBuf, TagCode};
+
+/// The set of features used by a single movie.
+#[derive(Default, Debug)]
+pub struct MovieProfile {
+    /// Names of every tag present in the movie, including inside sprites.
+    pub tags: BTreeSet<String>,
+
+    /// Names of every AVM1 action and AVM2 instruction present in the movie.
+    pub opcodes: BTreeSet<String>,
+
+    /// Fully qualified names of classes referenced by the movie's ABC code
+    /// but not defined by it, i.e. classes provided by the player.
+    pub classes: BTreeSet<String>,
+}
+
+impl MovieProfile {
+    pub fn from_swf(swf_buf: &SwfBuf) -> Self {
+        let mut profile = Self::default();
+        let mut defined_classes = BTreeSet::new();
+
+        profile.scan_tags(
+            &swf_buf.data,
+            swf_buf.header.version(),
+            &mut defined_classes,
+        );
+        profile.classes.retain(|c| !defined_classes.contains(c));
+
+        profile
+    }
+
+    fn scan_tags(&mut self, data: &[u8], version: u8, defined_classes: &mut BTreeSet<String>) {
+        let mut reader = swf::read::Reader::new(data, version);
+
+        while let Ok((tag_code, tag_len)) = reader.read_tag_code_and_length() {
+            let remaining = reader.as_slice();
+            if tag_len > remaining.len() {
+                break;
+            }
+
+            let (tag_data, rest) = remaining.split_at(tag_len);
+            *reader.get_mut() = rest;
+
+            let Some(tag) = TagCode::from_u16(tag_code) else {
+                self.tags.insert(TagCode::format(tag_code));
+                continue;
+            };
+
+            self.tags.insert(format!("{tag:?}"));
+
+            match tag {
+                TagCode::End => break,
+                TagCode::DefineSprite => {
+                    // Skip the character ID and frame count.
+                    if let Some(sprite_data) = tag_data.get(4..) {
+                        self.scan_tags(sprite_data, version, defined_classes);
+                    }
+                }
+                TagCode::DoAction => self.scan_avm1(tag_data, version),
+                TagCode::DoInitAction => {
+                    // Skip the sprite ID.
+                    if let Some(action_data) = tag_data.get(2..) {
+                        self.scan_avm1(action_data, version);
+                    }
+                }
+                TagCode::DoAbc => self.scan_abc(tag_data, defined_classes),
+                TagCode::DoAbc2 => {
+                    // Skip the flags and the null-terminated name.
+                    let abc_data = tag_data.get(4..).and_then(|data| {
+                        let name_end = data.iter().position(|b| *b == 0)?;
+                        data.get(name_end + 1..)
+                    });
+                    if let Some(abc_data) = abc_data {
+                        self.scan_abc(abc_data, defined_classes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn scan_avm1(&mut self, data: &[u8], version: u8) {
+        let mut reader = swf::avm1::read::Reader::new(data, version);
+
+        // Function bodies directly follow their definitions, so a linear
+        // walk over the actions visits every one of them.
+        while !reader.get_ref().is_empty() {
+            match reader.read_action() {
+                Ok(action) => {
+                    self.opcodes
+                        .insert(format!("AVM1 {}", variant_name(&action)));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    fn scan_abc(&mut self, data: &[u8], defined_classes: &mut BTreeSet<String>) {
+        let Ok(abc) = swf::avm2::read::Reader::new(data).read() else {
+            return;
+        };
+
+        for instance in &abc.instances {
+            if let Some(name) = multiname_name(&abc, &instance.name) {
+                defined_classes.insert(name);
+            }
+            if let Some(name) = multiname_name(&abc, &instance.super_name) {
+                self.classes.insert(name);
+            }
+            for interface in &instance.interfaces {
+                if let Some(name) = multiname_name(&abc, interface) {
+                    self.classes.insert(name);
+                }
+            }
+        }
+
+        for body in &abc.method_bodies {
+            let mut reader = swf::avm2::read::Reader::new(&body.code);
+
+            while !reader.as_slice().is_empty() {
+                let Ok(op) = reader.read_op() else {
+                    break;
+                };
+
+                // Classes are almost always looked up with `getlex` before
+                // being constructed or having static members accessed.
+                if let Op::GetLex { index } = &op {
+                    if let Some(name) = multiname_name(&abc, index) {
+                        if name.rsplit('.').next().is_some_and(starts_uppercase) {
+                            self.classes.insert(name);
+                        }
+                    }
+                }
+
+                self.opcodes.insert(format!("AVM2 {}", variant_name(&op)));
+            }
+        }
+    }
+}
+
+/// Returns the name of an enum variant from its `Debug` representation.
+fn variant_name(value: &impl std::fmt::Debug) -> String {
+    let debug = format!("{value:?}");
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn starts_uppercase(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn pool_string(abc: &AbcFile, index: &Index<String>) -> Option<String> {
+    let string = abc
+        .constant_pool
+        .strings
+        .get((index.0 as usize).checked_sub(1)?)?;
+    Some(String::from_utf8_lossy(string).into_owned())
+}
+
+/// Resolves a multiname to a fully qualified `package.Name` string.
+///
+/// Only `QName`s in a package namespace are resolved, since any other kind of
+/// multiname can't be attributed to a single class without running the code.
+fn multiname_name(abc: &AbcFile, index: &Index<Multiname>) -> Option<String> {
+    let multiname = abc
+        .constant_pool
+        .multinames
+        .get((index.0 as usize).checked_sub(1)?)?;
+
+    let Multiname::QName { namespace, name } = multiname else {
+        return None;
+    };
+    let namespace = abc
+        .constant_pool
+        .namespaces
+        .get((namespace.0 as usize).checked_sub(1)?)?;
+
+    let name = pool_string(abc, name)?;
+    match namespace {
+        Namespace::Package(package) => {
+            let package = pool_string(abc, package).unwrap_or_default();
+            if package.is_empty() {
+                Some(name)
+            } else {
+                Some(format!("{package}.{name}"))
+            }
+        }
+        _ => None,
+    }
+}
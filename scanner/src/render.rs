@@ -0,0 +1,110 @@
+//! Renderer process impls
+//!
+//! Renders the first few frames of a single SWF and stores them, along with
+//! the movie's trace output, so that another process can compare them.
+
+use crate::cli_options::RenderReportOpt;
+use crate::logging::TraceLogBackend;
+use image::RgbaImage;
+use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::PlayerBuilder;
+use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
+use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::TextureTarget;
+use ruffle_render_wgpu::wgpu;
+use std::fs::{create_dir_all, write};
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The name of the file that trace output is stored in.
+pub const TRACE_FILE_NAME: &str = "trace.txt";
+
+/// The name of the file that a given frame is stored in.
+pub fn frame_file_name(frame: u32) -> String {
+    format!("frame_{frame}.png")
+}
+
+fn other_error(error: impl ToString) -> Error {
+    Error::new(ErrorKind::Other, error.to_string())
+}
+
+/// Converts a premultiplied RGBA bitmap into a straight alpha image.
+fn to_image(width: u32, height: u32, mut data: Vec<u8>) -> RgbaImage {
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha != 0 && alpha != 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((u16::from(*channel) * 255 + u16::from(alpha) / 2) / u16::from(alpha))
+                    .min(255) as u8;
+            }
+        }
+    }
+    RgbaImage::from_raw(width, height, data).expect("Bitmap size matches its data")
+}
+
+/// The render process.
+///
+/// Should be called with parsed options corresponding to the `render-report`
+/// command.
+pub fn render_report_main(opt: RenderReportOpt) -> Result<(), Error> {
+    let movie = SwfMovie::from_path(&opt.input_path, None).map_err(other_error)?;
+    let frame_time = 1000.0 / movie.frame_rate().to_f64();
+    let width = movie.width().to_pixels().round().max(1.0) as u32;
+    let height = movie.height().to_pixels().round().max(1.0) as u32;
+
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: opt.graphics.into(),
+        ..Default::default()
+    });
+    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+        opt.graphics.into(),
+        &instance,
+        None,
+        opt.power.into(),
+        None,
+    ))
+    .map_err(other_error)?;
+    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+
+    let target = TextureTarget::new(&descriptors.device, (width, height)).map_err(other_error)?;
+    let renderer = WgpuRenderBackend::new(descriptors, target).map_err(other_error)?;
+
+    let base_path = opt.input_path.parent().unwrap_or(Path::new("."));
+    let executor = NullExecutor::new();
+    let log = TraceLogBackend::new();
+    let player = PlayerBuilder::new()
+        .with_log(log.clone())
+        .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor)?)
+        .with_renderer(renderer)
+        .with_max_execution_duration(Duration::from_secs(300))
+        .with_avm2_optimizer_enabled(!opt.no_avm2_optimizer)
+        .with_viewport_dimensions(width, height, 1.0)
+        .with_movie(movie)
+        .build();
+
+    create_dir_all(&opt.output_path)?;
+
+    let mut player = player.lock().unwrap();
+    player.preload(&mut ExecutionLimit::none());
+
+    for frame in 0..opt.frames {
+        player.run_frame();
+        player.update_timers(frame_time);
+
+        let bitmap = player
+            .render_to_bitmap()
+            .ok_or_else(|| other_error("Renderer does not support offscreen rendering"))?;
+        let image = to_image(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        image
+            .save(opt.output_path.join(frame_file_name(frame)))
+            .map_err(other_error)?;
+    }
+
+    write(opt.output_path.join(TRACE_FILE_NAME), log.trace_output())?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 //! Main/scanner process impls
 
 use crate::analyze::analyze;
-use crate::cli_options::ScanOpt;
+use crate::cli_options::{ExecutionOpt, ScanOpt};
 use crate::file_results::FileResults;
 use crate::ser_bridge::SerBridge;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -38,12 +38,18 @@ pub fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) -> FileResults {
+pub fn scan_file<P: AsRef<OsStr>>(
+    exec_path: P,
+    file: &DirEntry,
+    name: &str,
+    execution: &ExecutionOpt,
+) -> FileResults {
     let start = Instant::now();
     let mut file_results = FileResults::new(name);
 
     let subproc = Command::new(exec_path)
         .args(["execute-report", &file.path().to_string_lossy()])
+        .args(execution.to_args())
         .output();
     match subproc {
         Ok(output) => {
@@ -68,6 +74,11 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                             use_gpu,
                             use_network_sandbox,
                             vm_type,
+                            frames_executed,
+                            stubs,
+                            tags,
+                            classes,
+                            opcodes,
                         } = child_results;
 
                         file_results.hash = hash;
@@ -85,6 +96,11 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                         file_results.use_gpu = use_gpu;
                         file_results.use_network_sandbox = use_network_sandbox;
                         file_results.vm_type = vm_type;
+                        file_results.frames_executed = frames_executed;
+                        file_results.stubs = stubs;
+                        file_results.tags = tags;
+                        file_results.classes = classes;
+                        file_results.opcodes = opcodes;
                     }
                     Err(e) => {
                         file_results.error = Some(e.to_string());
@@ -135,7 +151,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
                 .strip_prefix(&opt.input_path)
                 .unwrap_or_else(|_| file.path())
                 .to_slash_lossy();
-            let result = scan_file(&binary_path, &file, &name);
+            let result = scan_file(&binary_path, &file, &name, &opt.execution);
 
             progress.inc(1);
             progress.set_message(name.into_owned());
@@ -8,14 +8,32 @@ use ruffle_core::PlayerBuilder;
 #[clap(name = "Ruffle Stub Report Generator", author, version)]
 struct Opt {
     /// The file to store the stub report output
-    #[clap(name = "output")]
-    output_path: PathBuf,
+    #[clap(name = "output", required_unless_present = "coverage_path")]
+    output_path: Option<PathBuf>,
+
+    /// The file to store the API coverage report, listing every API as
+    /// implemented, stubbed or missing along with where its stubs are declared
+    #[clap(long = "coverage")]
+    coverage_path: Option<PathBuf>,
+
+    /// A specification of the full Flash Player API, in the same format as
+    /// the stub report, used to find APIs missing from the coverage report
+    #[clap(long = "reference", requires = "coverage_path")]
+    reference_path: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    PlayerBuilder::new()
-        .with_stub_report_output(opt.output_path)
-        .build();
+    let mut builder = PlayerBuilder::new();
+    if let Some(output_path) = opt.output_path {
+        builder = builder.with_stub_report_output(output_path);
+    }
+    if let Some(coverage_path) = opt.coverage_path {
+        builder = builder.with_stub_coverage_output(coverage_path);
+    }
+    if let Some(reference_path) = opt.reference_path {
+        builder = builder.with_stub_coverage_reference(reference_path);
+    }
+    builder.build();
     Ok(())
 }
@@ -0,0 +1,134 @@
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use swf::write::write_swf;
+use swf::{decompress_swf, parse_swf, Header, SerdeBuffer, Tag};
+
+#[derive(Parser, Debug)]
+#[clap(name = "Ruffle SWF Dump", author, version)]
+struct Opt {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Dump the header and tags of a SWF
+    Dump {
+        /// The SWF file to dump
+        swf: PathBuf,
+
+        /// The file to write the dump to, instead of stdout
+        output: Option<PathBuf>,
+
+        /// The format of the dump. Defaults to the extension of the output
+        /// file, or YAML when writing to stdout
+        #[clap(long, short, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// Encode a dump back into a SWF
+    Encode {
+        /// The dump to encode
+        input: PathBuf,
+
+        /// The SWF file to write
+        output: PathBuf,
+
+        /// The format of the dump. Defaults to the extension of the input file
+        #[clap(long, short, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum Format {
+    Json,
+    Yaml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// The contents of a dump file.
+#[derive(Serialize, Deserialize)]
+struct Dump<'a> {
+    header: Header,
+    #[serde(borrow)]
+    tags: Vec<Tag<'a>>,
+}
+
+fn dump(swf: &Path, output: Option<&Path>, format: Format) -> Result<()> {
+    let data = std::fs::read(swf).with_context(|| format!("Couldn't read {swf:?}"))?;
+    let swf_buf = decompress_swf(&data[..])?;
+    let swf = parse_swf(&swf_buf)?;
+    let dump = Dump {
+        header: swf.header.swf_header().clone(),
+        tags: swf.tags,
+    };
+
+    let mut output: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut output, &dump)?;
+            writeln!(output)?;
+        }
+        Format::Yaml => serde_yaml::to_writer(&mut output, &dump)?,
+    }
+    output.flush()?;
+    Ok(())
+}
+
+fn encode(input: &Path, output: &Path, format: Format) -> Result<()> {
+    let text =
+        std::fs::read_to_string(input).with_context(|| format!("Couldn't read {input:?}"))?;
+    let buffer: SerdeBuffer = match format {
+        Format::Json => serde_json::from_str(&text)?,
+        Format::Yaml => serde_yaml::from_str(&text)?,
+    };
+    let dump: Dump = buffer.deserialize()?;
+
+    let file = BufWriter::new(File::create(output)?);
+    write_swf(&dump.header, &dump.tags, file)?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+
+    match opt.command {
+        Command::Dump {
+            swf,
+            output,
+            format,
+        } => {
+            let format = format
+                .or_else(|| output.as_deref().and_then(Format::from_path))
+                .unwrap_or(Format::Yaml);
+            dump(&swf, output.as_deref(), format)
+        }
+        Command::Encode {
+            input,
+            output,
+            format,
+        } => {
+            let format = format
+                .or_else(|| Format::from_path(&input))
+                .ok_or_else(|| anyhow!("Couldn't tell the format of {input:?}; use --format"))?;
+            encode(&input, &output, format)
+        }
+    }
+}
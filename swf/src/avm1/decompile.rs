@@ -0,0 +1,780 @@
+//! Decompiling AVM1 bytecode to ActionScript 2 source.
+//!
+//! Bytecode is split into basic blocks, the stack effects of each action are
+//! lifted into expressions and statements, and the control flow between blocks
+//! is structured into `if`, `while`, `do..while`, `for`, `for..in` and `switch`
+//! statements following the shapes the Flash IDE compiles them to. Control flow
+//! that can't be structured is left as commented gotos and labels.
+//!
+//! The output is meant for reading, and isn't guaranteed to compile back to the
+//! same bytecode.
+
+mod ast;
+mod cfg;
+mod lift;
+mod print;
+mod structure;
+
+pub use ast::{BinaryOp, Case, Expr, Function, Stmt, UnaryOp};
+pub use print::print;
+
+use crate::string::SwfStr;
+use crate::types::{ButtonActionCondition, ClipEventFlag, PlaceObjectAction, Tag};
+use cfg::ControlFlowGraph;
+use std::collections::HashMap;
+use std::fmt;
+use structure::Decompiler;
+
+/// Decompile a block of AVM1 bytecode, such as the contents of a `DoAction`
+/// tag, to a syntax tree.
+pub fn decompile_to_ast(data: &[u8], version: u8) -> Vec<Stmt> {
+    let encoding = SwfStr::encoding_for_version(version);
+    let cfg = ControlFlowGraph::new(data, version, false);
+    Decompiler::new(cfg, version, encoding, vec![], HashMap::new()).run()
+}
+
+/// Decompile a block of AVM1 bytecode to ActionScript 2 source.
+pub fn decompile(data: &[u8], version: u8) -> String {
+    print(&decompile_to_ast(data, version))
+}
+
+/// What runs a script.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScriptKind {
+    /// Frame actions, from `DoAction`.
+    Frame,
+
+    /// Actions run before a sprite is first placed, from `DoInitAction`.
+    InitClip,
+
+    /// A button or clip event handler, such as `on (release)` or
+    /// `onClipEvent (load)`.
+    Handler(String),
+}
+
+/// A block of AVM1 bytecode within a movie.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Script<'a> {
+    /// Where the script is found, such as `frame 1` or `sprite 5, frame 2`.
+    pub location: String,
+    pub kind: ScriptKind,
+    pub action_data: &'a [u8],
+}
+
+impl Script<'_> {
+    /// Decompile the script, wrapped in its event handler or `#initclip`
+    /// block, if any.
+    pub fn decompile(&self, version: u8) -> String {
+        let body = decompile(self.action_data, version);
+        let indented = || -> String {
+            body.lines()
+                .map(|line| {
+                    if line.is_empty() {
+                        "\n".to_string()
+                    } else {
+                        format!("    {line}\n")
+                    }
+                })
+                .collect()
+        };
+
+        match &self.kind {
+            ScriptKind::Frame => body,
+            ScriptKind::InitClip => format!("#initclip\n{body}#endinitclip\n"),
+            ScriptKind::Handler(handler) => format!("{handler} {{\n{}}}\n", indented()),
+        }
+    }
+}
+
+impl fmt::Display for Script<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ScriptKind::Frame => write!(f, "{}", self.location),
+            ScriptKind::InitClip => write!(f, "{} #initclip", self.location),
+            ScriptKind::Handler(handler) => write!(f, "{} {handler}", self.location),
+        }
+    }
+}
+
+/// Find every script in a list of tags, including those nested in sprites,
+/// buttons and placed clips.
+///
+/// `version` is the SWF version, which determines the encoding of names.
+pub fn find_scripts<'a>(tags: &[Tag<'a>], version: u8) -> Vec<Script<'a>> {
+    let mut scripts = vec![];
+    find_scripts_in(tags, "", version, &mut scripts);
+    scripts
+}
+
+fn find_scripts_in<'a>(tags: &[Tag<'a>], prefix: &str, version: u8, out: &mut Vec<Script<'a>>) {
+    let encoding = SwfStr::encoding_for_version(version);
+    let mut frame = 1;
+
+    for tag in tags {
+        match tag {
+            Tag::ShowFrame => frame += 1,
+            Tag::DoAction(action_data) => out.push(Script {
+                location: format!("{prefix}frame {frame}"),
+                kind: ScriptKind::Frame,
+                action_data,
+            }),
+            Tag::DoInitAction { id, action_data } => out.push(Script {
+                location: format!("sprite {id}"),
+                kind: ScriptKind::InitClip,
+                action_data,
+            }),
+            Tag::DefineSprite(sprite) => {
+                let prefix = format!("sprite {}, ", sprite.id);
+                find_scripts_in(&sprite.tags, &prefix, version, out);
+            }
+            Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                for action in &button.actions {
+                    out.push(Script {
+                        location: format!("button {}", button.id),
+                        kind: ScriptKind::Handler(button_handler(action.conditions)),
+                        action_data: action.action_data,
+                    });
+                }
+            }
+            Tag::PlaceObject(place_object) => {
+                let Some(clip_actions) = &place_object.clip_actions else {
+                    continue;
+                };
+                let character = match place_object.action {
+                    PlaceObjectAction::Place(id) | PlaceObjectAction::Replace(id) => {
+                        format!(" (character {id})")
+                    }
+                    PlaceObjectAction::Modify => String::new(),
+                };
+                let name = place_object
+                    .name
+                    .map(|name| format!(" \"{}\"", name.to_string_lossy(encoding)))
+                    .unwrap_or_default();
+                for clip_action in clip_actions {
+                    out.push(Script {
+                        location: format!(
+                            "{prefix}frame {frame}, depth {}{character}{name}",
+                            place_object.depth
+                        ),
+                        kind: ScriptKind::Handler(clip_handler(
+                            clip_action.events,
+                            clip_action.key_code,
+                        )),
+                        action_data: clip_action.action_data,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The names of the events in `on (...)` handlers, by the button state
+/// transitions they correspond to.
+const BUTTON_EVENTS: [(ButtonActionCondition, &str); 9] = [
+    (ButtonActionCondition::OVER_UP_TO_OVER_DOWN, "press"),
+    (ButtonActionCondition::OVER_DOWN_TO_OVER_UP, "release"),
+    (ButtonActionCondition::OUT_DOWN_TO_IDLE, "releaseOutside"),
+    (ButtonActionCondition::IDLE_TO_OVER_UP, "rollOver"),
+    (ButtonActionCondition::OVER_UP_TO_IDLE, "rollOut"),
+    (ButtonActionCondition::OUT_DOWN_TO_OVER_DOWN, "dragOver"),
+    (ButtonActionCondition::OVER_DOWN_TO_OUT_DOWN, "dragOut"),
+    // Only used by buttons tracked as menu items.
+    (ButtonActionCondition::IDLE_TO_OVER_DOWN, "dragOver"),
+    (ButtonActionCondition::OVER_DOWN_TO_IDLE, "dragOut"),
+];
+
+const CLIP_EVENTS: [(ClipEventFlag, &str); 11] = [
+    (ClipEventFlag::LOAD, "load"),
+    (ClipEventFlag::ENTER_FRAME, "enterFrame"),
+    (ClipEventFlag::UNLOAD, "unload"),
+    (ClipEventFlag::MOUSE_MOVE, "mouseMove"),
+    (ClipEventFlag::MOUSE_DOWN, "mouseDown"),
+    (ClipEventFlag::MOUSE_UP, "mouseUp"),
+    (ClipEventFlag::KEY_DOWN, "keyDown"),
+    (ClipEventFlag::KEY_UP, "keyUp"),
+    (ClipEventFlag::DATA, "data"),
+    (ClipEventFlag::INITIALIZE, "initialize"),
+    (ClipEventFlag::CONSTRUCT, "construct"),
+];
+
+/// Clip events that are written as `on (...)` rather than `onClipEvent`.
+const CLIP_BUTTON_EVENTS: [(ClipEventFlag, &str); 7] = [
+    (ClipEventFlag::PRESS, "press"),
+    (ClipEventFlag::RELEASE, "release"),
+    (ClipEventFlag::RELEASE_OUTSIDE, "releaseOutside"),
+    (ClipEventFlag::ROLL_OVER, "rollOver"),
+    (ClipEventFlag::ROLL_OUT, "rollOut"),
+    (ClipEventFlag::DRAG_OVER, "dragOver"),
+    (ClipEventFlag::DRAG_OUT, "dragOut"),
+];
+
+fn button_handler(conditions: ButtonActionCondition) -> String {
+    let mut events = vec![];
+    for (condition, name) in BUTTON_EVENTS {
+        if conditions.contains(condition) && !events.contains(&name.to_string()) {
+            events.push(name.to_string());
+        }
+    }
+
+    let key_code = ((conditions & ButtonActionCondition::KEY_PRESS).bits() >> 9) as u8;
+    if key_code != 0 {
+        events.push(key_press(key_code));
+    }
+
+    format!("on ({})", events.join(", "))
+}
+
+fn clip_handler(flags: ClipEventFlag, key_code: Option<u8>) -> String {
+    let is_button_event = |flag: ClipEventFlag| {
+        CLIP_BUTTON_EVENTS.iter().any(|(f, _)| *f == flag) || flag == ClipEventFlag::KEY_PRESS
+    };
+
+    if flags.iter().any(is_button_event) {
+        let mut events: Vec<String> = CLIP_BUTTON_EVENTS
+            .iter()
+            .filter(|(flag, _)| flags.contains(*flag))
+            .map(|(_, name)| name.to_string())
+            .collect();
+        if let Some(key_code) = key_code.filter(|_| flags.contains(ClipEventFlag::KEY_PRESS)) {
+            events.push(key_press(key_code));
+        }
+        format!("on ({})", events.join(", "))
+    } else {
+        let events: Vec<&str> = CLIP_EVENTS
+            .iter()
+            .filter(|(flag, _)| flags.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect();
+        format!("onClipEvent ({})", events.join(", "))
+    }
+}
+
+/// The `keyPress` event for a key code, which is either a special key or the
+/// character typed.
+fn key_press(key_code: u8) -> String {
+    let key = match key_code {
+        1 => "<Left>".to_string(),
+        2 => "<Right>".to_string(),
+        3 => "<Home>".to_string(),
+        4 => "<End>".to_string(),
+        5 => "<Insert>".to_string(),
+        6 => "<Delete>".to_string(),
+        8 => "<Backspace>".to_string(),
+        13 => "<Enter>".to_string(),
+        14 => "<Up>".to_string(),
+        15 => "<Down>".to_string(),
+        16 => "<PageUp>".to_string(),
+        17 => "<PageDown>".to_string(),
+        18 => "<Tab>".to_string(),
+        19 => "<Escape>".to_string(),
+        32 => "<Space>".to_string(),
+        b'"' => "\\\"".to_string(),
+        b'\\' => "\\\\".to_string(),
+        code => char::from(code).to_string(),
+    };
+    format!("keyPress \"{key}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::types::*;
+    use crate::avm1::write::Writer;
+    use crate::types::{Button, ButtonAction, ClipAction, PlaceObject, Sprite};
+
+    /// A bytecode item, with jumps and branches referring to labels.
+    enum Item<'a> {
+        Action(Action<'a>),
+        Jump(usize),
+        If(usize),
+        Label(usize),
+    }
+
+    use Item::{If, Jump, Label};
+
+    fn a(action: Action) -> Item {
+        Item::Action(action)
+    }
+
+    fn push<'a>(values: impl Into<Vec<Value<'a>>>) -> Item<'a> {
+        a(Action::Push(Push {
+            values: values.into(),
+        }))
+    }
+
+    fn s(value: &str) -> Value<'_> {
+        Value::Str(SwfStr::from_utf8_str(value))
+    }
+
+    /// Assemble bytecode, resolving labels to jump offsets.
+    fn assemble(items: &[Item]) -> Vec<u8> {
+        let write = |action: &Action| {
+            let mut out = vec![];
+            Writer::new(&mut out, 8).write_action(action).unwrap();
+            out
+        };
+
+        // Jumps and branches are always 5 bytes.
+        let mut labels = HashMap::new();
+        let mut offset = 0;
+        for item in items {
+            match item {
+                Item::Action(action) => offset += write(action).len(),
+                Item::Jump(_) | Item::If(_) => offset += 5,
+                Item::Label(label) => {
+                    labels.insert(*label, offset);
+                }
+            }
+        }
+
+        let mut out = vec![];
+        for item in items {
+            let offset_to =
+                |label: &usize| (labels[label] as isize - out.len() as isize - 5) as i16;
+            let action = match item {
+                Item::Action(action) => action.clone(),
+                Item::Jump(label) => Action::Jump(crate::avm1::types::Jump {
+                    offset: offset_to(label),
+                }),
+                Item::If(label) => Action::If(crate::avm1::types::If {
+                    offset: offset_to(label),
+                }),
+                Item::Label(_) => continue,
+            };
+            out.extend(write(&action));
+        }
+        out
+    }
+
+    fn check(items: &[Item], expected: &str) {
+        let data = assemble(items);
+        assert_eq!(decompile(&data, 8), expected);
+    }
+
+    #[test]
+    fn expressions() {
+        check(
+            &[
+                push([s("x"), s("a")]),
+                a(Action::GetVariable),
+                push([s("b")]),
+                a(Action::GetVariable),
+                a(Action::Add2),
+                push([Value::Int(2)]),
+                a(Action::Multiply),
+                a(Action::SetVariable),
+                push([s("y"), Value::Double(1.5), s("x")]),
+                a(Action::GetVariable),
+                a(Action::Subtract),
+                a(Action::SetVariable),
+                push([s("hello\n"), Value::Int(1), s("trace")]),
+                a(Action::CallFunction),
+                a(Action::Pop),
+                push([Value::Int(0), s("_root.clip")]),
+                a(Action::GetVariable),
+                push([s("stop")]),
+                a(Action::CallMethod),
+                a(Action::Pop),
+            ],
+            "x = (a + b) * 2;\n\
+             y = 1.5 - x;\n\
+             trace(\"hello\\n\");\n\
+             _root.clip.stop();\n",
+        );
+    }
+
+    #[test]
+    fn constant_pool_and_objects() {
+        check(
+            &[
+                a(Action::ConstantPool(ConstantPool {
+                    strings: vec![
+                        SwfStr::from_utf8_str("o"),
+                        SwfStr::from_utf8_str("a"),
+                        SwfStr::from_utf8_str("b c"),
+                    ],
+                })),
+                push([
+                    Value::ConstantPool(0),
+                    Value::ConstantPool(1),
+                    Value::Int(1),
+                    Value::ConstantPool(2),
+                    Value::Bool(true),
+                    Value::Int(2),
+                ]),
+                a(Action::InitObject),
+                a(Action::DefineLocal),
+                push([Value::ConstantPool(0)]),
+                a(Action::GetVariable),
+                push([Value::ConstantPool(1)]),
+                push([Value::ConstantPool(0)]),
+                a(Action::GetVariable),
+                push([Value::ConstantPool(1)]),
+                a(Action::GetMember),
+                push([Value::Int(1)]),
+                a(Action::Add2),
+                a(Action::SetMember),
+            ],
+            "var o = {a: 1, \"b c\": true};\n\
+             o.a += 1;\n",
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        check(
+            &[
+                push([s("a")]),
+                a(Action::GetVariable),
+                a(Action::Not),
+                If(0),
+                push([s("b"), Value::Int(1)]),
+                a(Action::SetVariable),
+                Jump(1),
+                Label(0),
+                push([s("b"), Value::Int(2)]),
+                a(Action::SetVariable),
+                Label(1),
+                a(Action::Stop),
+            ],
+            "if (a) {\n    b = 1;\n} else {\n    b = 2;\n}\nstop();\n",
+        );
+    }
+
+    #[test]
+    fn while_loop() {
+        check(
+            &[
+                Label(0),
+                push([s("i")]),
+                a(Action::GetVariable),
+                push([Value::Int(10)]),
+                a(Action::Less2),
+                a(Action::Not),
+                If(1),
+                push([s("i"), s("i")]),
+                a(Action::GetVariable),
+                a(Action::Increment),
+                a(Action::SetVariable),
+                push([s("i")]),
+                a(Action::GetVariable),
+                push([Value::Int(5)]),
+                a(Action::Equals2),
+                a(Action::Not),
+                If(2),
+                Jump(1),
+                Label(2),
+                Jump(0),
+                Label(1),
+            ],
+            "while (i < 10) {\n    i++;\n    if (i == 5) {\n        break;\n    }\n}\n",
+        );
+    }
+
+    #[test]
+    fn do_while_loop() {
+        check(
+            &[
+                Label(0),
+                push([s("i"), s("i")]),
+                a(Action::GetVariable),
+                a(Action::Decrement),
+                a(Action::SetVariable),
+                push([s("i")]),
+                a(Action::GetVariable),
+                push([Value::Int(0)]),
+                a(Action::Greater),
+                If(0),
+            ],
+            "do {\n    i--;\n} while (i > 0);\n",
+        );
+    }
+
+    #[test]
+    fn for_in_loop() {
+        check(
+            &[
+                push([s("o")]),
+                a(Action::GetVariable),
+                a(Action::Enumerate2),
+                Label(0),
+                a(Action::StoreRegister(StoreRegister { register: 0 })),
+                push([Value::Null]),
+                a(Action::Equals2),
+                If(1),
+                push([s("k"), Value::Register(0)]),
+                a(Action::DefineLocal),
+                push([s("k")]),
+                a(Action::GetVariable),
+                a(Action::Trace),
+                Jump(0),
+                Label(1),
+            ],
+            "for (var k in o) {\n    trace(k);\n}\n",
+        );
+    }
+
+    #[test]
+    fn for_loops() {
+        let increment_i = || {
+            [
+                push([s("i"), s("i")]),
+                a(Action::GetVariable),
+                a(Action::Increment),
+                a(Action::SetVariable),
+            ]
+        };
+        let test_i = || {
+            [
+                push([s("i")]),
+                a(Action::GetVariable),
+                push([Value::Int(3)]),
+                a(Action::Less2),
+                a(Action::Not),
+            ]
+        };
+
+        // As compiled by the Flash IDE.
+        let mut items = vec![
+            push([s("i"), Value::Int(0)]),
+            a(Action::SetVariable),
+            Label(0),
+        ];
+        items.extend(test_i());
+        items.extend([
+            If(1),
+            push([s("i")]),
+            a(Action::GetVariable),
+            a(Action::Trace),
+        ]);
+        items.extend(increment_i());
+        items.extend([Jump(0), Label(1)]);
+        check(&items, "for (i = 0; i < 3; i++) {\n    trace(i);\n}\n");
+
+        // As compiled by MTASC.
+        let mut items = vec![
+            push([s("i"), Value::Int(0)]),
+            a(Action::SetVariable),
+            Jump(1),
+            Label(0),
+        ];
+        items.extend(increment_i());
+        items.push(Label(1));
+        items.extend(test_i());
+        items.extend([
+            If(2),
+            push([s("i")]),
+            a(Action::GetVariable),
+            a(Action::Trace),
+        ]);
+        items.extend([Jump(0), Label(2)]);
+        check(&items, "for (i = 0; i < 3; i++) {\n    trace(i);\n}\n");
+    }
+
+    #[test]
+    fn try_catch() {
+        // The jump over the `catch` block leaves the `try` block.
+        let try_body = assemble(&[
+            push([s("a")]),
+            a(Action::Trace),
+            a(Action::Jump(crate::avm1::types::Jump { offset: 10 })),
+        ]);
+        let catch_body = assemble(&[push([s("e")]), a(Action::GetVariable), a(Action::Trace)]);
+        check(
+            &[a(Action::Try(Try {
+                try_body: &try_body,
+                catch_body: Some((CatchVar::Var(SwfStr::from_utf8_str("e")), &catch_body)),
+                finally_body: None,
+            }))],
+            "try {\n    trace(\"a\");\n} catch (e) {\n    trace(e);\n}\n",
+        );
+    }
+
+    #[test]
+    fn short_circuit_and_ternary() {
+        check(
+            &[
+                push([s("x"), s("a")]),
+                a(Action::GetVariable),
+                a(Action::PushDuplicate),
+                a(Action::Not),
+                If(0),
+                a(Action::Pop),
+                push([s("b")]),
+                a(Action::GetVariable),
+                Label(0),
+                a(Action::SetVariable),
+                push([s("y"), s("c")]),
+                a(Action::GetVariable),
+                If(1),
+                push([Value::Int(2)]),
+                Jump(2),
+                Label(1),
+                push([Value::Int(1)]),
+                Label(2),
+                a(Action::SetVariable),
+            ],
+            "x = a && b;\ny = c ? 1 : 2;\n",
+        );
+    }
+
+    #[test]
+    fn switch() {
+        check(
+            &[
+                push([s("x")]),
+                a(Action::GetVariable),
+                a(Action::StoreRegister(StoreRegister { register: 0 })),
+                push([Value::Int(1)]),
+                a(Action::StrictEquals),
+                If(1),
+                push([Value::Register(0), Value::Int(2)]),
+                a(Action::StrictEquals),
+                If(2),
+                Jump(3),
+                Label(1),
+                push([s("one")]),
+                a(Action::Trace),
+                Jump(4),
+                Label(2),
+                push([s("two")]),
+                a(Action::Trace),
+                Label(3),
+                push([s("other")]),
+                a(Action::Trace),
+                Label(4),
+            ],
+            "switch (x) {\n\
+             \x20   case 1:\n\
+             \x20       trace(\"one\");\n\
+             \x20       break;\n\
+             \x20   case 2:\n\
+             \x20       trace(\"two\");\n\
+             \x20   default:\n\
+             \x20       trace(\"other\");\n\
+             }\n",
+        );
+    }
+
+    #[test]
+    fn functions() {
+        let body = assemble(&[
+            push([Value::Register(1), Value::Register(2)]),
+            a(Action::Add2),
+            a(Action::Return),
+        ]);
+        check(
+            &[a(Action::DefineFunction2(DefineFunction2 {
+                name: SwfStr::from_utf8_str("add"),
+                register_count: 3,
+                params: vec![
+                    FunctionParam {
+                        name: SwfStr::from_utf8_str("a"),
+                        register_index: std::num::NonZeroU8::new(1),
+                    },
+                    FunctionParam {
+                        name: SwfStr::from_utf8_str("b"),
+                        register_index: std::num::NonZeroU8::new(2),
+                    },
+                ],
+                flags: FunctionFlags::SUPPRESS_THIS
+                    | FunctionFlags::SUPPRESS_ARGUMENTS
+                    | FunctionFlags::SUPPRESS_SUPER,
+                actions: &body,
+            }))],
+            "function add(a, b) {\n    return a + b;\n}\n",
+        );
+    }
+
+    #[test]
+    fn unstructured_jump() {
+        check(
+            &[
+                Label(0),
+                a(Action::Play),
+                push([s("a")]),
+                a(Action::GetVariable),
+                If(1),
+                a(Action::Stop),
+                Label(1),
+                a(Action::NextFrame),
+                Jump(0),
+            ],
+            "while (true) {\n    play();\n    if (!a) {\n        stop();\n    }\n    nextFrame();\n}\n",
+        );
+    }
+
+    #[test]
+    fn scripts() {
+        let tags = vec![
+            Tag::DoAction(&[0]),
+            Tag::ShowFrame,
+            Tag::DefineButton2(Box::new(Button {
+                id: 3,
+                is_track_as_menu: false,
+                records: vec![],
+                actions: vec![ButtonAction {
+                    conditions: ButtonActionCondition::OVER_DOWN_TO_OVER_UP
+                        | ButtonActionCondition::from_key_code(1),
+                    action_data: &[0],
+                }],
+            })),
+            Tag::DefineSprite(Sprite {
+                id: 4,
+                num_frames: 1,
+                tags: vec![Tag::DoAction(&[0]), Tag::ShowFrame],
+            }),
+            Tag::PlaceObject(Box::new(PlaceObject {
+                version: 2,
+                action: PlaceObjectAction::Place(4),
+                depth: 1,
+                matrix: None,
+                color_transform: None,
+                ratio: None,
+                name: Some(SwfStr::from_utf8_str("clip")),
+                clip_depth: None,
+                class_name: None,
+                filters: None,
+                background_color: None,
+                blend_mode: None,
+                clip_actions: Some(vec![ClipAction {
+                    events: ClipEventFlag::LOAD | ClipEventFlag::ENTER_FRAME,
+                    key_code: None,
+                    action_data: &[0],
+                }]),
+                has_image: false,
+                is_bitmap_cached: None,
+                is_visible: None,
+                amf_data: None,
+            })),
+            Tag::DoInitAction {
+                id: 4,
+                action_data: &[0],
+            },
+        ];
+
+        let names: Vec<String> = find_scripts(&tags, 8)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "frame 1",
+                "button 3 on (release, keyPress \"<Left>\")",
+                "sprite 4, frame 1",
+                "frame 2, depth 1 (character 4) \"clip\" onClipEvent (load, enterFrame)",
+                "sprite 4 #initclip",
+            ]
+        );
+
+        let stop = [0x07, 0x00];
+        let script = Script {
+            location: "button 3".into(),
+            kind: ScriptKind::Handler("on (press)".into()),
+            action_data: &stop,
+        };
+        assert_eq!(script.decompile(8), "on (press) {\n    stop();\n}\n");
+    }
+}
@@ -0,0 +1,207 @@
+//! The syntax tree produced by the decompiler.
+
+/// A binary operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    BitAnd,
+    BitOr,
+    BitXor,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    Equals,
+    StrictEquals,
+    Less,
+    Greater,
+    And,
+    Or,
+    InstanceOf,
+    /// The SWF4 `eq` string comparison.
+    StringEquals,
+    /// The SWF4 `lt` string comparison.
+    StringLess,
+    /// The SWF4 `gt` string comparison.
+    StringGreater,
+    /// The SWF4 `add` string concatenation.
+    StringAdd,
+}
+
+/// A unary operator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOp {
+    Not,
+    TypeOf,
+    Increment,
+    Decrement,
+}
+
+/// An expression recovered from the AVM1 stack.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+
+    /// An unnamed register.
+    Register(u8),
+
+    /// A variable or path read with a constant name, such as `foo` or
+    /// `_root.clip`.
+    Name(String),
+
+    /// A variable read with a computed name (`eval`).
+    Eval(Box<Expr>),
+
+    Member(Box<Expr>, Box<Expr>),
+
+    /// A display object property read with `getProperty`.
+    Property(Box<Expr>, Box<Expr>),
+
+    Call(Box<Expr>, Vec<Expr>),
+    New(Box<Expr>, Vec<Expr>),
+
+    /// A call to one of the global functions that AVM1 has dedicated actions
+    /// for, such as `getTimer` or `substring`.
+    Builtin(&'static str, Vec<Expr>),
+
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    Delete(Box<Expr>),
+
+    Array(Vec<Expr>),
+    Object(Vec<(Expr, Expr)>),
+    Function(Box<Function>),
+
+    Assign(Box<Expr>, Box<Expr>),
+
+    /// The values left on the stack by `Enumerate`, to be consumed by a
+    /// `for..in` loop.
+    Enumeration(Box<Expr>),
+}
+
+impl Expr {
+    /// Negates a condition, removing redundant double negations.
+    pub fn negate(self) -> Expr {
+        match self {
+            Expr::Unary(UnaryOp::Not, inner) => *inner,
+            other => Expr::Unary(UnaryOp::Not, Box::new(other)),
+        }
+    }
+
+    /// Whether evaluating this expression may have side effects.
+    ///
+    /// Expressions without side effects can be dropped when their value is
+    /// discarded.
+    pub fn has_side_effects(&self) -> bool {
+        match self {
+            Expr::Undefined
+            | Expr::Null
+            | Expr::Bool(_)
+            | Expr::Number(_)
+            | Expr::String(_)
+            | Expr::Register(_)
+            | Expr::Name(_)
+            | Expr::Function(_) => false,
+            Expr::Eval(e) | Expr::Unary(_, e) | Expr::Enumeration(e) => e.has_side_effects(),
+            Expr::Member(a, b) | Expr::Property(a, b) | Expr::Binary(_, a, b) => {
+                a.has_side_effects() || b.has_side_effects()
+            }
+            Expr::Ternary(a, b, c) => {
+                a.has_side_effects() || b.has_side_effects() || c.has_side_effects()
+            }
+            Expr::Array(values) => values.iter().any(Expr::has_side_effects),
+            Expr::Object(values) => values
+                .iter()
+                .any(|(k, v)| k.has_side_effects() || v.has_side_effects()),
+            Expr::Call(..)
+            | Expr::New(..)
+            | Expr::Builtin(..)
+            | Expr::Delete(_)
+            | Expr::Assign(..) => true,
+        }
+    }
+
+    /// The value of a literal used as an argument count.
+    pub fn as_count(&self) -> Option<usize> {
+        match self {
+            Expr::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            _ => None,
+        }
+    }
+}
+
+/// A function literal or declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    /// The name of the function; empty for function expressions.
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Vec<Stmt>,
+}
+
+/// A single `case` of a `switch` statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    /// The values that lead to this case; empty for the `default` case.
+    pub values: Vec<Expr>,
+    pub body: Vec<Stmt>,
+}
+
+/// A statement recovered from the control flow and stack effects of a script.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stmt {
+    Expr(Expr),
+    Var(String, Option<Expr>),
+    Function(Function),
+    Return(Expr),
+    Throw(Expr),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    DoWhile(Vec<Stmt>, Expr),
+    For {
+        init: Option<Box<Stmt>>,
+        condition: Expr,
+        /// Expression statements run after each iteration.
+        update: Vec<Stmt>,
+        body: Vec<Stmt>,
+    },
+    ForIn {
+        variable: Expr,
+        /// Whether the loop declares its variable with `var`.
+        declare: bool,
+        object: Expr,
+        body: Vec<Stmt>,
+    },
+    Switch(Expr, Vec<Case>),
+    Break,
+    Continue,
+    With(Expr, Vec<Stmt>),
+    Try {
+        body: Vec<Stmt>,
+        catch: Option<(String, Vec<Stmt>)>,
+        finally: Option<Vec<Stmt>>,
+    },
+
+    /// The SWF7 `extends` action, emitted for `class A extends B`.
+    Extends(Expr, Expr),
+
+    /// The SWF7 `implements` action, emitted for `class A implements B, C`.
+    Implements(Expr, Vec<Expr>),
+
+    /// A jump that could not be structured, to the given byte offset.
+    Goto(usize),
+
+    /// The target of a `Goto`.
+    Label(usize),
+
+    /// Anything else that doesn't have a source form, such as unknown actions.
+    Comment(String),
+}
@@ -0,0 +1,245 @@
+//! Splitting AVM1 bytecode into basic blocks.
+
+use crate::avm1::read::Reader;
+use crate::avm1::types::Action;
+use crate::error::Error;
+use std::collections::{BTreeSet, HashMap};
+
+/// A single decoded action.
+#[derive(Clone, Debug)]
+pub struct Instruction<'a> {
+    /// The byte offset of the action.
+    pub offset: usize,
+
+    /// The byte offset of the following action.
+    pub next_offset: usize,
+
+    pub action: Action<'a>,
+}
+
+/// How control leaves a basic block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Terminator {
+    /// Control falls through to the next block.
+    Next,
+
+    /// Control unconditionally moves to the given block.
+    Jump(usize),
+
+    /// Control moves to the given block if the condition on top of the stack
+    /// is true, and falls through to the next block otherwise.
+    Branch(usize),
+
+    /// Control leaves the script (`Return`, `Throw` or `End`).
+    Exit,
+
+    /// A jump or branch to an offset outside of the script, or into the
+    /// middle of an action.
+    Invalid { conditional: bool, offset: isize },
+}
+
+#[derive(Clone, Debug)]
+pub struct Block {
+    /// The byte offset of the first action in this block.
+    pub offset: usize,
+
+    /// The range of instructions in this block, including the terminating
+    /// jump or branch, if any.
+    pub instructions: std::ops::Range<usize>,
+
+    pub terminator: Terminator,
+}
+
+/// The control flow graph of a single script or function body.
+///
+/// Blocks are stored in the order they appear in the bytecode, and are
+/// referred to by index. The index `blocks.len()` refers to the end of the
+/// script.
+#[derive(Debug)]
+pub struct ControlFlowGraph<'a> {
+    pub instructions: Vec<Instruction<'a>>,
+    pub blocks: Vec<Block>,
+
+    /// The error that stopped decoding early, if any.
+    pub error: Option<Error>,
+}
+
+/// Where a jump, branch or skip lands.
+enum Target {
+    Offset(isize),
+    Instruction(usize),
+}
+
+fn target_of(instructions: &[Instruction], index: usize) -> Option<Target> {
+    let instruction = &instructions[index];
+    match &instruction.action {
+        Action::Jump(jump) => Some(Target::Offset(
+            instruction.next_offset as isize + jump.offset as isize,
+        )),
+        Action::If(branch) => Some(Target::Offset(
+            instruction.next_offset as isize + branch.offset as isize,
+        )),
+        Action::WaitForFrame(wait) => Some(Target::Instruction(
+            index + 1 + wait.num_actions_to_skip as usize,
+        )),
+        Action::WaitForFrame2(wait) => Some(Target::Instruction(
+            index + 1 + wait.num_actions_to_skip as usize,
+        )),
+        _ => None,
+    }
+}
+
+fn ends_block(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Jump(_)
+            | Action::If(_)
+            | Action::WaitForFrame(_)
+            | Action::WaitForFrame2(_)
+            | Action::Return
+            | Action::Throw
+            | Action::End
+    )
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    /// Decode a script and split it into blocks.
+    ///
+    /// `leaves_past_end` treats jumps past the end of the script as jumps to
+    /// its end. This is the case for the bodies of `try` blocks, which are
+    /// followed by the `catch` and `finally` blocks in the same action stream.
+    pub fn new(data: &'a [u8], version: u8, leaves_past_end: bool) -> Self {
+        let mut reader = Reader::new(data, version);
+        let mut instructions = vec![];
+        let mut error = None;
+
+        while !reader.get_ref().is_empty() {
+            let offset = data.len() - reader.get_ref().len();
+            match reader.read_action() {
+                Ok(action) => {
+                    let is_end = action == Action::End;
+                    instructions.push(Instruction {
+                        offset,
+                        next_offset: data.len() - reader.get_ref().len(),
+                        action,
+                    });
+                    if is_end {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        let end = instructions.last().map_or(0, |i| i.next_offset);
+        let by_offset: HashMap<usize, usize> = instructions
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| (instruction.offset, index))
+            .collect();
+
+        // Resolve every target to the index of the instruction it lands on,
+        // with `instructions.len()` meaning the end of the script.
+        let resolve = |target: Target| -> Result<usize, isize> {
+            match target {
+                Target::Offset(offset) if offset == end as isize => Ok(instructions.len()),
+                Target::Offset(offset) if leaves_past_end && offset > end as isize => {
+                    Ok(instructions.len())
+                }
+                Target::Offset(offset) => usize::try_from(offset)
+                    .ok()
+                    .and_then(|offset| by_offset.get(&offset).copied())
+                    .ok_or(offset),
+                Target::Instruction(index) => Ok(index.min(instructions.len())),
+            }
+        };
+
+        let mut leaders = BTreeSet::from([0]);
+        for (index, instruction) in instructions.iter().enumerate() {
+            if ends_block(&instruction.action) {
+                leaders.insert(index + 1);
+            }
+            if let Some(Ok(target)) = target_of(&instructions, index).map(resolve) {
+                leaders.insert(target);
+            }
+        }
+        leaders.retain(|leader| *leader < instructions.len());
+
+        let leaders: Vec<usize> = leaders.into_iter().collect();
+        let block_of = |instruction: usize| -> usize {
+            leaders
+                .binary_search(&instruction)
+                .expect("every target is a leader")
+        };
+
+        let blocks = leaders
+            .iter()
+            .enumerate()
+            .map(|(block, &start)| {
+                let end_index = leaders
+                    .get(block + 1)
+                    .copied()
+                    .unwrap_or(instructions.len());
+                let last = end_index - 1;
+                let target = target_of(&instructions, last).map(resolve);
+                let target_block = |target: usize| {
+                    if target == instructions.len() {
+                        leaders.len()
+                    } else {
+                        block_of(target)
+                    }
+                };
+
+                let terminator = match (&instructions[last].action, target) {
+                    (_, Some(Err(offset))) => Terminator::Invalid {
+                        conditional: !matches!(instructions[last].action, Action::Jump(_)),
+                        offset,
+                    },
+                    (Action::Jump(_), Some(Ok(target))) => Terminator::Jump(target_block(target)),
+                    (_, Some(Ok(target))) => Terminator::Branch(target_block(target)),
+                    (Action::Return | Action::Throw | Action::End, _) => Terminator::Exit,
+                    _ => Terminator::Next,
+                };
+
+                Block {
+                    offset: instructions[start].offset,
+                    instructions: start..end_index,
+                    terminator,
+                }
+            })
+            .collect();
+
+        Self {
+            instructions,
+            blocks,
+            error,
+        }
+    }
+
+    /// The byte offset of a block, or the end of the script.
+    pub fn offset_of(&self, block: usize) -> usize {
+        self.blocks.get(block).map_or_else(
+            || self.instructions.last().map_or(0, |i| i.next_offset),
+            |block| block.offset,
+        )
+    }
+
+    /// The instructions of a block.
+    pub fn instructions_of(&self, block: usize) -> &[Instruction<'a>] {
+        &self.instructions[self.blocks[block].instructions.clone()]
+    }
+
+    /// The last block within `from..to` that jumps or branches back to
+    /// `header`, making it the end of a loop starting at `header`.
+    pub fn loop_latch(&self, header: usize, to: usize) -> Option<usize> {
+        (header..to).rev().find(|&block| {
+            matches!(
+                self.blocks[block].terminator,
+                Terminator::Jump(target) | Terminator::Branch(target) if target == header
+            )
+        })
+    }
+}
@@ -0,0 +1,557 @@
+//! Recovering expressions and statements from the effects of individual
+//! actions on the AVM1 stack.
+
+use crate::avm1::decompile::ast::{BinaryOp, Expr, Function, Stmt, UnaryOp};
+use crate::avm1::decompile::cfg::ControlFlowGraph;
+use crate::avm1::decompile::structure::{pop, Decompiler};
+use crate::avm1::types::{Action, CatchVar, DefineFunction2, FunctionFlags, SendVarsMethod, Value};
+use std::collections::HashMap;
+
+impl Decompiler<'_> {
+    /// Lift the actions of a block, leaving its terminating jump or branch
+    /// for the structuring pass.
+    pub(super) fn lift_block(&mut self, block: usize, stack: &mut Vec<Expr>, out: &mut Vec<Stmt>) {
+        let range = self.cfg.blocks[block].instructions.clone();
+        let mut index = range.start;
+        while index < range.end {
+            let action = self.cfg.instructions[index].action.clone();
+            index += 1;
+
+            // `gotoAndPlay` is a `GotoFrame` or `GotoLabel` followed by `Play`.
+            let next_is_play =
+                index < range.end && matches!(self.cfg.instructions[index].action, Action::Play);
+            match action {
+                Action::GotoFrame(goto) if next_is_play => {
+                    index += 1;
+                    statement(
+                        out,
+                        "gotoAndPlay",
+                        vec![Expr::Number(f64::from(goto.frame) + 1.0)],
+                    );
+                }
+                Action::GotoLabel(goto) if next_is_play => {
+                    index += 1;
+                    let label = goto.label.to_string_lossy(self.encoding);
+                    statement(out, "gotoAndPlay", vec![Expr::String(label)]);
+                }
+                action => self.lift(action, stack, out),
+            }
+        }
+    }
+
+    fn lift(&mut self, action: Action, stack: &mut Vec<Expr>, out: &mut Vec<Stmt>) {
+        match action {
+            Action::Add | Action::Add2 => binary(stack, BinaryOp::Add),
+            Action::And => binary(stack, BinaryOp::And),
+            Action::BitAnd => binary(stack, BinaryOp::BitAnd),
+            Action::BitLShift => binary(stack, BinaryOp::LeftShift),
+            Action::BitOr => binary(stack, BinaryOp::BitOr),
+            Action::BitRShift => binary(stack, BinaryOp::RightShift),
+            Action::BitURShift => binary(stack, BinaryOp::UnsignedRightShift),
+            Action::BitXor => binary(stack, BinaryOp::BitXor),
+            Action::Divide => binary(stack, BinaryOp::Divide),
+            Action::Equals | Action::Equals2 => binary(stack, BinaryOp::Equals),
+            Action::Greater => binary(stack, BinaryOp::Greater),
+            Action::InstanceOf => binary(stack, BinaryOp::InstanceOf),
+            Action::Less | Action::Less2 => binary(stack, BinaryOp::Less),
+            Action::Modulo => binary(stack, BinaryOp::Modulo),
+            Action::Multiply => binary(stack, BinaryOp::Multiply),
+            Action::Or => binary(stack, BinaryOp::Or),
+            Action::StrictEquals => binary(stack, BinaryOp::StrictEquals),
+            Action::StringAdd => binary(stack, BinaryOp::StringAdd),
+            Action::StringEquals => binary(stack, BinaryOp::StringEquals),
+            Action::StringGreater => binary(stack, BinaryOp::StringGreater),
+            Action::StringLess => binary(stack, BinaryOp::StringLess),
+            Action::Subtract => binary(stack, BinaryOp::Subtract),
+
+            Action::Not => {
+                let value = pop(stack);
+                stack.push(value.negate());
+            }
+            Action::Increment => unary(stack, UnaryOp::Increment),
+            Action::Decrement => unary(stack, UnaryOp::Decrement),
+            Action::TypeOf => unary(stack, UnaryOp::TypeOf),
+
+            Action::AsciiToChar => builtin(stack, "chr", 1),
+            Action::CharToAscii => builtin(stack, "ord", 1),
+            Action::MBAsciiToChar => builtin(stack, "mbchr", 1),
+            Action::MBCharToAscii => builtin(stack, "mbord", 1),
+            Action::MBStringExtract => builtin(stack, "mbsubstring", 3),
+            Action::MBStringLength => builtin(stack, "mblength", 1),
+            Action::RandomNumber => builtin(stack, "random", 1),
+            Action::StringExtract => builtin(stack, "substring", 3),
+            Action::StringLength => builtin(stack, "length", 1),
+            Action::TargetPath => builtin(stack, "targetPath", 1),
+            Action::ToInteger => builtin(stack, "int", 1),
+            Action::ToNumber => builtin(stack, "Number", 1),
+            Action::ToString => builtin(stack, "String", 1),
+            Action::GetTime => builtin(stack, "getTimer", 0),
+
+            Action::Call => {
+                let frame = pop(stack);
+                statement(out, "call", vec![frame]);
+            }
+            Action::CallFunction => {
+                let name = pop(stack);
+                let args = pop_args(stack);
+                stack.push(Expr::Call(Box::new(variable(name)), args));
+            }
+            Action::CallMethod => {
+                let name = pop(stack);
+                let object = pop(stack);
+                let args = pop_args(stack);
+                stack.push(Expr::Call(Box::new(method(object, name)), args));
+            }
+            Action::CastOp => {
+                let object = pop(stack);
+                let constructor = pop(stack);
+                stack.push(Expr::Call(Box::new(constructor), vec![object]));
+            }
+            Action::NewObject => {
+                let name = pop(stack);
+                let args = pop_args(stack);
+                stack.push(Expr::New(Box::new(variable(name)), args));
+            }
+            Action::NewMethod => {
+                let name = pop(stack);
+                let object = pop(stack);
+                let args = pop_args(stack);
+                stack.push(Expr::New(Box::new(method(object, name)), args));
+            }
+
+            Action::InitArray => {
+                let values = pop_args(stack);
+                stack.push(Expr::Array(values));
+            }
+            Action::InitObject => {
+                let count = pop(stack).as_count().unwrap_or(0).min(stack.len() / 2);
+                let mut properties: Vec<(Expr, Expr)> = (0..count)
+                    .map(|_| {
+                        let value = pop(stack);
+                        (pop(stack), value)
+                    })
+                    .collect();
+                properties.reverse();
+                stack.push(Expr::Object(properties));
+            }
+
+            Action::GetMember => {
+                let name = pop(stack);
+                let object = pop(stack);
+                stack.push(Expr::Member(Box::new(object), Box::new(name)));
+            }
+            Action::SetMember => {
+                let value = pop(stack);
+                let name = pop(stack);
+                let object = pop(stack);
+                assign(out, Expr::Member(Box::new(object), Box::new(name)), value);
+            }
+            Action::GetVariable => {
+                let name = pop(stack);
+                stack.push(variable(name));
+            }
+            Action::SetVariable => {
+                let value = pop(stack);
+                let name = pop(stack);
+                assign(out, variable(name), value);
+            }
+            Action::DefineLocal => {
+                let value = pop(stack);
+                match pop(stack) {
+                    Expr::String(name) => out.push(Stmt::Var(name, Some(value))),
+                    name => assign(out, variable(name), value),
+                }
+            }
+            Action::DefineLocal2 => match pop(stack) {
+                Expr::String(name) => out.push(Stmt::Var(name, None)),
+                name => out.push(Stmt::Expr(variable(name))),
+            },
+            Action::Delete => {
+                let name = pop(stack);
+                let object = pop(stack);
+                let member = Expr::Member(Box::new(object), Box::new(name));
+                stack.push(Expr::Delete(Box::new(member)));
+            }
+            Action::Delete2 => {
+                let name = pop(stack);
+                stack.push(Expr::Delete(Box::new(variable(name))));
+            }
+            Action::GetProperty => {
+                let property = pop(stack);
+                let target = pop(stack);
+                stack.push(Expr::Property(Box::new(target), Box::new(property)));
+            }
+            Action::SetProperty => {
+                let value = pop(stack);
+                let property = pop(stack);
+                let target = pop(stack);
+                assign(
+                    out,
+                    Expr::Property(Box::new(target), Box::new(property)),
+                    value,
+                );
+            }
+
+            Action::Push(push) => {
+                for value in push.values {
+                    let value = self.value(value);
+                    stack.push(value);
+                }
+            }
+            Action::PushDuplicate => {
+                let value = stack.last().cloned().unwrap_or(Expr::Undefined);
+                stack.push(value);
+            }
+            Action::Pop => {
+                let value = pop(stack);
+                if value.has_side_effects() {
+                    out.push(Stmt::Expr(value));
+                }
+            }
+            Action::StackSwap => {
+                let a = pop(stack);
+                let b = pop(stack);
+                stack.push(a);
+                stack.push(b);
+            }
+            Action::StoreRegister(store) => {
+                let value = pop(stack);
+                let register = self.register(store.register);
+                stack.push(Expr::Assign(Box::new(register), Box::new(value)));
+            }
+            Action::ConstantPool(pool) => {
+                self.constant_pool = pool
+                    .strings
+                    .iter()
+                    .map(|s| s.to_string_lossy(self.encoding))
+                    .collect();
+            }
+
+            Action::Enumerate => {
+                let name = pop(stack);
+                stack.push(Expr::Enumeration(Box::new(variable(name))));
+            }
+            Action::Enumerate2 => {
+                let object = pop(stack);
+                stack.push(Expr::Enumeration(Box::new(object)));
+            }
+            Action::Extends => {
+                let superclass = pop(stack);
+                let subclass = pop(stack);
+                out.push(Stmt::Extends(subclass, superclass));
+            }
+            Action::ImplementsOp => {
+                let constructor = pop(stack);
+                let interfaces = pop_args(stack);
+                out.push(Stmt::Implements(constructor, interfaces));
+            }
+
+            Action::Trace => {
+                let value = pop(stack);
+                statement(out, "trace", vec![value]);
+            }
+            Action::Return => out.push(Stmt::Return(pop(stack))),
+            Action::Throw => out.push(Stmt::Throw(pop(stack))),
+
+            Action::GotoFrame(goto) => statement(
+                out,
+                "gotoAndStop",
+                vec![Expr::Number(f64::from(goto.frame) + 1.0)],
+            ),
+            Action::GotoFrame2(goto) => {
+                let mut frame = pop(stack);
+                if goto.scene_offset != 0 {
+                    frame = Expr::Binary(
+                        BinaryOp::Add,
+                        Box::new(frame),
+                        Box::new(Expr::Number(goto.scene_offset.into())),
+                    );
+                }
+                let name = if goto.set_playing {
+                    "gotoAndPlay"
+                } else {
+                    "gotoAndStop"
+                };
+                statement(out, name, vec![frame]);
+            }
+            Action::GotoLabel(goto) => {
+                let label = goto.label.to_string_lossy(self.encoding);
+                statement(out, "gotoAndStop", vec![Expr::String(label)]);
+            }
+            Action::NextFrame => statement(out, "nextFrame", vec![]),
+            Action::PreviousFrame => statement(out, "prevFrame", vec![]),
+            Action::Play => statement(out, "play", vec![]),
+            Action::Stop => statement(out, "stop", vec![]),
+            Action::StopSounds => statement(out, "stopAllSounds", vec![]),
+            Action::ToggleQuality => statement(out, "toggleHighQuality", vec![]),
+            Action::EndDrag => statement(out, "stopDrag", vec![]),
+            Action::StartDrag => {
+                let target = pop(stack);
+                let lock_center = pop(stack);
+                let constrain = pop(stack);
+                let mut args = vec![target, lock_center];
+                if !matches!(constrain, Expr::Number(n) if n == 0.0)
+                    && constrain != Expr::Bool(false)
+                {
+                    let bottom = pop(stack);
+                    let right = pop(stack);
+                    let top = pop(stack);
+                    let left = pop(stack);
+                    args.extend([left, top, right, bottom]);
+                }
+                statement(out, "startDrag", args);
+            }
+            Action::CloneSprite => {
+                let depth = pop(stack);
+                let target = pop(stack);
+                let source = pop(stack);
+                statement(out, "duplicateMovieClip", vec![source, target, depth]);
+            }
+            Action::RemoveSprite => {
+                let target = pop(stack);
+                statement(out, "removeMovieClip", vec![target]);
+            }
+            Action::SetTarget(target) => {
+                let target = target.target.to_string_lossy(self.encoding);
+                statement(out, "setTarget", vec![Expr::String(target)]);
+            }
+            Action::SetTarget2 => {
+                let target = pop(stack);
+                statement(out, "setTarget", vec![target]);
+            }
+            Action::GetUrl(get_url) => {
+                let url = get_url.url.to_string_lossy(self.encoding);
+                let target = Expr::String(get_url.target.to_string_lossy(self.encoding));
+                match url.strip_prefix("FSCommand:") {
+                    Some(command) => {
+                        statement(out, "fscommand", vec![Expr::String(command.into()), target])
+                    }
+                    None => statement(out, "getURL", vec![Expr::String(url), target]),
+                }
+            }
+            Action::GetUrl2(get_url) => {
+                let target = pop(stack);
+                let url = pop(stack);
+                let name = if get_url.is_load_vars() {
+                    "loadVariables"
+                } else if get_url.is_target_sprite() {
+                    "loadMovie"
+                } else {
+                    "getURL"
+                };
+                let mut args = vec![url, target];
+                match get_url.send_vars_method() {
+                    SendVarsMethod::None => {}
+                    SendVarsMethod::Get => args.push(Expr::String("GET".into())),
+                    SendVarsMethod::Post => args.push(Expr::String("POST".into())),
+                }
+                statement(out, name, args);
+            }
+            Action::WaitForFrame(wait) => {
+                let frame = Expr::Number(f64::from(wait.frame) + 1.0);
+                let loaded = Expr::Builtin("ifFrameLoaded", vec![frame]);
+                stack.push(loaded.negate());
+            }
+            Action::WaitForFrame2(_) => {
+                let frame = pop(stack);
+                let loaded = Expr::Builtin("ifFrameLoaded", vec![frame]);
+                stack.push(loaded.negate());
+            }
+
+            Action::DefineFunction(function) => {
+                let register_names = self.register_names.clone();
+                self.function(function.into(), register_names, stack, out);
+            }
+            Action::DefineFunction2(function) => {
+                self.function(function, HashMap::new(), stack, out);
+            }
+            Action::With(with) => {
+                let object = pop(stack);
+                let body = self.nested(with.actions, self.register_names.clone(), false);
+                out.push(Stmt::With(object, body));
+            }
+            Action::Try(try_block) => {
+                let body = self.nested(try_block.try_body, self.register_names.clone(), true);
+                let catch = try_block.catch_body.map(|(var, actions)| {
+                    let var = match var {
+                        CatchVar::Var(name) => name.to_string_lossy(self.encoding),
+                        CatchVar::Register(register) => self
+                            .register_names
+                            .get(&register)
+                            .cloned()
+                            .unwrap_or_else(|| format!("register{register}")),
+                    };
+                    (var, self.nested(actions, self.register_names.clone(), true))
+                });
+                let finally = try_block
+                    .finally_body
+                    .map(|actions| self.nested(actions, self.register_names.clone(), false));
+                out.push(Stmt::Try {
+                    body,
+                    catch,
+                    finally,
+                });
+            }
+
+            Action::Unknown(unknown) => out.push(Stmt::Comment(format!(
+                "Unknown action 0x{:02X} ({} bytes)",
+                unknown.opcode,
+                unknown.data.len()
+            ))),
+
+            // Handled by the structuring pass.
+            Action::Jump(_) | Action::If(_) | Action::End => {}
+        }
+    }
+
+    fn value(&self, value: Value) -> Expr {
+        match value {
+            Value::Undefined => Expr::Undefined,
+            Value::Null => Expr::Null,
+            Value::Bool(value) => Expr::Bool(value),
+            Value::Int(value) => Expr::Number(value.into()),
+            // Go through the shortest representation of the `f32`, so that
+            // `0.1` isn't printed as `0.10000000149011612`.
+            Value::Float(value) => Expr::Number(value.to_string().parse().unwrap_or(value.into())),
+            Value::Double(value) => Expr::Number(value),
+            Value::Str(value) => Expr::String(value.to_string_lossy(self.encoding)),
+            Value::Register(register) => self.register(register),
+            Value::ConstantPool(index) => self
+                .constant_pool
+                .get(usize::from(index))
+                .map_or(Expr::Undefined, |s| Expr::String(s.clone())),
+        }
+    }
+
+    /// A register, referred to by name if it holds a parameter or a preloaded
+    /// variable.
+    fn register(&self, register: u8) -> Expr {
+        match self.register_names.get(&register) {
+            Some(name) => Expr::Name(name.clone()),
+            None => Expr::Register(register),
+        }
+    }
+
+    /// Decompile a nested block of actions, such as the body of a `with`.
+    fn nested(
+        &self,
+        data: &[u8],
+        register_names: HashMap<u8, String>,
+        leaves_past_end: bool,
+    ) -> Vec<Stmt> {
+        Decompiler::new(
+            ControlFlowGraph::new(data, self.version, leaves_past_end),
+            self.version,
+            self.encoding,
+            self.constant_pool.clone(),
+            register_names,
+        )
+        .run()
+    }
+
+    fn function(
+        &mut self,
+        function: DefineFunction2,
+        mut register_names: HashMap<u8, String>,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) {
+        // Preloaded variables occupy registers in a fixed order, starting at 1.
+        let preloads = [
+            (FunctionFlags::PRELOAD_THIS, "this"),
+            (FunctionFlags::PRELOAD_ARGUMENTS, "arguments"),
+            (FunctionFlags::PRELOAD_SUPER, "super"),
+            (FunctionFlags::PRELOAD_ROOT, "_root"),
+            (FunctionFlags::PRELOAD_PARENT, "_parent"),
+            (FunctionFlags::PRELOAD_GLOBAL, "_global"),
+        ];
+        let preloaded = preloads
+            .into_iter()
+            .filter(|(flag, _)| function.flags.contains(*flag));
+        for (register, (_, name)) in (1..).zip(preloaded) {
+            register_names.insert(register, name.to_string());
+        }
+
+        let params: Vec<String> = function
+            .params
+            .iter()
+            .map(|param| param.name.to_string_lossy(self.encoding))
+            .collect();
+        for (param, name) in function.params.iter().zip(&params) {
+            if let Some(register) = param.register_index {
+                register_names.insert(register.get(), name.clone());
+            }
+        }
+
+        let function = Function {
+            name: function.name.to_string_lossy(self.encoding),
+            params,
+            body: self.nested(function.actions, register_names, false),
+        };
+        if function.name.is_empty() {
+            stack.push(Expr::Function(Box::new(function)));
+        } else {
+            out.push(Stmt::Function(function));
+        }
+    }
+}
+
+fn binary(stack: &mut Vec<Expr>, op: BinaryOp) {
+    let right = pop(stack);
+    let left = pop(stack);
+    stack.push(Expr::Binary(op, Box::new(left), Box::new(right)));
+}
+
+fn unary(stack: &mut Vec<Expr>, op: UnaryOp) {
+    let value = pop(stack);
+    stack.push(Expr::Unary(op, Box::new(value)));
+}
+
+/// Push a call to a builtin function taking `count` arguments from the stack.
+fn builtin(stack: &mut Vec<Expr>, name: &'static str, count: usize) {
+    let mut args: Vec<Expr> = (0..count).map(|_| pop(stack)).collect();
+    args.reverse();
+    stack.push(Expr::Builtin(name, args));
+}
+
+/// Emit a call to a builtin function as a statement.
+fn statement(out: &mut Vec<Stmt>, name: &'static str, args: Vec<Expr>) {
+    out.push(Stmt::Expr(Expr::Builtin(name, args)));
+}
+
+fn assign(out: &mut Vec<Stmt>, target: Expr, value: Expr) {
+    out.push(Stmt::Expr(Expr::Assign(Box::new(target), Box::new(value))));
+}
+
+/// Pop an argument count followed by that many arguments, as used by calls
+/// and array literals.
+///
+/// Counts beyond the depth of the stack are clamped, as AVM1 would only pop
+/// `undefined` for the rest anyway.
+fn pop_args(stack: &mut Vec<Expr>) -> Vec<Expr> {
+    let count = pop_count(stack);
+    (0..count).map(|_| pop(stack)).collect()
+}
+
+fn pop_count(stack: &mut Vec<Expr>) -> usize {
+    let count = pop(stack).as_count().unwrap_or(0);
+    count.min(stack.len())
+}
+
+/// A variable read by name, with constant names being printed as is.
+fn variable(name: Expr) -> Expr {
+    match name {
+        Expr::String(name) => Expr::Name(name),
+        name => Expr::Eval(Box::new(name)),
+    }
+}
+
+/// The function called by `CallMethod` or `NewMethod`, where an empty or
+/// undefined name means the object itself is called.
+fn method(object: Expr, name: Expr) -> Expr {
+    match name {
+        Expr::Undefined => object,
+        Expr::String(name) if name.is_empty() => object,
+        name => Expr::Member(Box::new(object), Box::new(name)),
+    }
+}
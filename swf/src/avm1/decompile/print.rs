@@ -0,0 +1,677 @@
+//! Printing the decompiled syntax tree as ActionScript 2 source.
+
+use crate::avm1::decompile::ast::{BinaryOp, Case, Expr, Function, Stmt, UnaryOp};
+use std::fmt::Write;
+
+/// The display object properties accessed through `getProperty` and
+/// `setProperty`, by index.
+const PROPERTIES: [&str; 22] = [
+    "_x",
+    "_y",
+    "_xscale",
+    "_yscale",
+    "_currentframe",
+    "_totalframes",
+    "_alpha",
+    "_visible",
+    "_width",
+    "_height",
+    "_rotation",
+    "_target",
+    "_framesloaded",
+    "_name",
+    "_droptarget",
+    "_url",
+    "_highquality",
+    "_focusrect",
+    "_soundbuftime",
+    "_quality",
+    "_xmouse",
+    "_ymouse",
+];
+
+const INDENT: &str = "    ";
+
+/// Operator precedence, from loosest to tightest binding.
+mod precedence {
+    pub const LOWEST: u8 = 0;
+    pub const ASSIGN: u8 = 1;
+    pub const TERNARY: u8 = 2;
+    pub const OR: u8 = 3;
+    pub const AND: u8 = 4;
+    pub const BIT_OR: u8 = 5;
+    pub const BIT_XOR: u8 = 6;
+    pub const BIT_AND: u8 = 7;
+    pub const EQUALITY: u8 = 8;
+    pub const RELATIONAL: u8 = 9;
+    pub const SHIFT: u8 = 10;
+    pub const ADDITIVE: u8 = 11;
+    pub const MULTIPLICATIVE: u8 = 12;
+    pub const UNARY: u8 = 13;
+    pub const CALL: u8 = 15;
+    pub const PRIMARY: u8 = 16;
+}
+
+/// Print a list of statements as ActionScript 2 source.
+pub fn print(statements: &[Stmt]) -> String {
+    let mut printer = Printer::default();
+    printer.statements(statements);
+    printer.output
+}
+
+#[derive(Default)]
+struct Printer {
+    output: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn line_start(&mut self) {
+        for _ in 0..self.indent {
+            self.output.push_str(INDENT);
+        }
+    }
+
+    fn statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            self.line_start();
+            self.statement(statement);
+            self.output.push('\n');
+        }
+    }
+
+    /// Print the body of a block, without the braces around it.
+    fn block(&mut self, statements: &[Stmt]) {
+        self.output.push_str("{\n");
+        self.indent += 1;
+        self.statements(statements);
+        self.indent -= 1;
+        self.line_start();
+        self.output.push('}');
+    }
+
+    fn statement(&mut self, statement: &Stmt) {
+        match statement {
+            Stmt::Expr(expr) => {
+                self.expression_statement(expr);
+                self.output.push(';');
+            }
+            Stmt::Var(name, value) => {
+                self.output.push_str("var ");
+                self.output.push_str(name);
+                if let Some(value) = value {
+                    self.output.push_str(" = ");
+                    self.expr(value, precedence::ASSIGN);
+                }
+                self.output.push(';');
+            }
+            Stmt::Function(function) => self.function(function),
+            Stmt::Return(Expr::Undefined) => self.output.push_str("return;"),
+            Stmt::Return(value) => {
+                self.output.push_str("return ");
+                self.expr(value, precedence::LOWEST);
+                self.output.push(';');
+            }
+            Stmt::Throw(value) => {
+                self.output.push_str("throw ");
+                self.expr(value, precedence::LOWEST);
+                self.output.push(';');
+            }
+            Stmt::If(condition, then_body, else_body) => {
+                self.output.push_str("if (");
+                self.expr(condition, precedence::LOWEST);
+                self.output.push_str(") ");
+                self.block(then_body);
+                match else_body.as_slice() {
+                    [] => {}
+                    [else_if @ Stmt::If(..)] => {
+                        self.output.push_str(" else ");
+                        self.statement(else_if);
+                    }
+                    else_body => {
+                        self.output.push_str(" else ");
+                        self.block(else_body);
+                    }
+                }
+            }
+            Stmt::While(condition, body) => {
+                self.output.push_str("while (");
+                self.expr(condition, precedence::LOWEST);
+                self.output.push_str(") ");
+                self.block(body);
+            }
+            Stmt::DoWhile(body, condition) => {
+                self.output.push_str("do ");
+                self.block(body);
+                self.output.push_str(" while (");
+                self.expr(condition, precedence::LOWEST);
+                self.output.push_str(");");
+            }
+            Stmt::For {
+                init,
+                condition,
+                update,
+                body,
+            } => {
+                self.output.push_str("for (");
+                match init {
+                    Some(init) => self.statement(init),
+                    None => self.output.push(';'),
+                }
+                self.output.push(' ');
+                self.expr(condition, precedence::LOWEST);
+                self.output.push_str("; ");
+                for (index, statement) in update.iter().enumerate() {
+                    if index > 0 {
+                        self.output.push_str(", ");
+                    }
+                    if let Stmt::Expr(expr) = statement {
+                        self.expression_statement(expr);
+                    }
+                }
+                self.output.push_str(") ");
+                self.block(body);
+            }
+            Stmt::ForIn {
+                variable,
+                declare,
+                object,
+                body,
+            } => {
+                self.output.push_str("for (");
+                if *declare {
+                    self.output.push_str("var ");
+                }
+                self.expr(variable, precedence::CALL);
+                self.output.push_str(" in ");
+                self.expr(object, precedence::LOWEST);
+                self.output.push_str(") ");
+                self.block(body);
+            }
+            Stmt::Switch(discriminant, cases) => {
+                self.output.push_str("switch (");
+                self.expr(discriminant, precedence::LOWEST);
+                self.output.push_str(") {\n");
+                for case in cases {
+                    self.case(case);
+                }
+                self.line_start();
+                self.output.push('}');
+            }
+            Stmt::Break => self.output.push_str("break;"),
+            Stmt::Continue => self.output.push_str("continue;"),
+            Stmt::With(object, body) => {
+                self.output.push_str("with (");
+                self.expr(object, precedence::LOWEST);
+                self.output.push_str(") ");
+                self.block(body);
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.output.push_str("try ");
+                self.block(body);
+                if let Some((name, body)) = catch {
+                    let _ = write!(self.output, " catch ({name}) ");
+                    self.block(body);
+                }
+                if let Some(body) = finally {
+                    self.output.push_str(" finally ");
+                    self.block(body);
+                }
+            }
+            Stmt::Extends(subclass, superclass) => {
+                self.expr(subclass, precedence::CALL);
+                self.output.push_str(" extends ");
+                self.expr(superclass, precedence::CALL);
+                self.output.push(';');
+            }
+            Stmt::Implements(constructor, interfaces) => {
+                self.expr(constructor, precedence::CALL);
+                self.output.push_str(" implements ");
+                self.list(interfaces, precedence::CALL);
+                self.output.push(';');
+            }
+            Stmt::Goto(offset) => {
+                let _ = write!(self.output, "// goto label_{offset:04X}");
+            }
+            Stmt::Label(offset) => {
+                let _ = write!(self.output, "// label_{offset:04X}:");
+            }
+            Stmt::Comment(comment) => {
+                let _ = write!(self.output, "// {comment}");
+            }
+        }
+    }
+
+    fn case(&mut self, case: &Case) {
+        self.indent += 1;
+        if case.values.is_empty() {
+            self.line_start();
+            self.output.push_str("default:\n");
+        }
+        for value in &case.values {
+            self.line_start();
+            self.output.push_str("case ");
+            self.expr(value, precedence::LOWEST);
+            self.output.push_str(":\n");
+        }
+        self.indent += 1;
+        self.statements(&case.body);
+        self.indent -= 2;
+    }
+
+    fn function(&mut self, function: &Function) {
+        self.output.push_str("function ");
+        if !function.name.is_empty() {
+            self.output.push_str(&function.name);
+        }
+        self.output.push('(');
+        self.output.push_str(&function.params.join(", "));
+        self.output.push_str(") ");
+        self.block(&function.body);
+    }
+
+    /// Print an expression whose value is discarded, which allows printing
+    /// assignments in their compound forms.
+    fn expression_statement(&mut self, expr: &Expr) {
+        if let Expr::Assign(target, value) = expr {
+            match &**value {
+                Expr::Unary(op @ (UnaryOp::Increment | UnaryOp::Decrement), inner)
+                    if inner == target && is_assignable(target) =>
+                {
+                    self.expr(target, precedence::CALL);
+                    self.output.push_str(match op {
+                        UnaryOp::Increment => "++",
+                        _ => "--",
+                    });
+                    return;
+                }
+                Expr::Binary(op, left, right) if left == target && is_assignable(target) => {
+                    if let Some(symbol) = compound_symbol(*op) {
+                        self.expr(target, precedence::CALL);
+                        let _ = write!(self.output, " {symbol}= ");
+                        self.expr(right, precedence::ASSIGN);
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.expr(expr, precedence::LOWEST);
+    }
+
+    fn list(&mut self, values: &[Expr], precedence: u8) {
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+            self.expr(value, precedence);
+        }
+    }
+
+    /// Print an expression, parenthesizing it if it binds looser than
+    /// `min_precedence`.
+    fn expr(&mut self, expr: &Expr, min_precedence: u8) {
+        let parenthesize = precedence_of(expr) < min_precedence;
+        if parenthesize {
+            self.output.push('(');
+        }
+        self.expr_inner(expr);
+        if parenthesize {
+            self.output.push(')');
+        }
+    }
+
+    fn expr_inner(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Undefined => self.output.push_str("undefined"),
+            Expr::Null => self.output.push_str("null"),
+            Expr::Bool(value) => {
+                let _ = write!(self.output, "{value}");
+            }
+            Expr::Number(value) => self.number(*value),
+            Expr::String(value) => self.string(value),
+            Expr::Register(register) => {
+                let _ = write!(self.output, "register{register}");
+            }
+            Expr::Name(name) => {
+                if is_path(name) {
+                    self.output.push_str(name);
+                } else {
+                    self.output.push_str("eval(");
+                    self.string(name);
+                    self.output.push(')');
+                }
+            }
+            Expr::Eval(name) => {
+                self.output.push_str("eval(");
+                self.expr(name, precedence::ASSIGN);
+                self.output.push(')');
+            }
+            Expr::Member(object, name) => {
+                self.expr(object, precedence::CALL);
+                match &**name {
+                    Expr::String(name) if is_identifier(name) => {
+                        self.output.push('.');
+                        self.output.push_str(name);
+                    }
+                    name => {
+                        self.output.push('[');
+                        self.expr(name, precedence::LOWEST);
+                        self.output.push(']');
+                    }
+                }
+            }
+            Expr::Property(target, property) => {
+                self.output.push_str("getProperty(");
+                self.expr(target, precedence::ASSIGN);
+                self.output.push_str(", ");
+                self.property(property);
+                self.output.push(')');
+            }
+            Expr::Call(callee, args) => {
+                self.expr(callee, precedence::CALL);
+                self.output.push('(');
+                self.list(args, precedence::ASSIGN);
+                self.output.push(')');
+            }
+            Expr::New(callee, args) => {
+                self.output.push_str("new ");
+                // Calls must be parenthesized to not be taken as the
+                // constructor's arguments.
+                let callee_precedence = match **callee {
+                    Expr::Call(..) => precedence::PRIMARY + 1,
+                    _ => precedence::CALL,
+                };
+                self.expr(callee, callee_precedence);
+                self.output.push('(');
+                self.list(args, precedence::ASSIGN);
+                self.output.push(')');
+            }
+            Expr::Builtin(name, args) => {
+                self.output.push_str(name);
+                self.output.push('(');
+                self.list(args, precedence::ASSIGN);
+                self.output.push(')');
+            }
+            Expr::Unary(UnaryOp::Not, inner) => match negated_comparison(inner) {
+                Some((symbol, left, right)) => {
+                    self.binary(symbol, precedence_of(inner), left, right);
+                }
+                None => {
+                    self.output.push('!');
+                    self.expr(inner, precedence::UNARY);
+                }
+            },
+            Expr::Unary(UnaryOp::TypeOf, inner) => {
+                self.output.push_str("typeof ");
+                self.expr(inner, precedence::UNARY);
+            }
+            Expr::Unary(op @ (UnaryOp::Increment | UnaryOp::Decrement), inner) => {
+                let symbol = match op {
+                    UnaryOp::Increment => "+",
+                    _ => "-",
+                };
+                self.binary(symbol, precedence::ADDITIVE, inner, &Expr::Number(1.0));
+            }
+            Expr::Binary(op, left, right) => {
+                self.binary(binary_symbol(*op), precedence_of(expr), left, right);
+            }
+            Expr::Ternary(condition, if_true, if_false) => {
+                self.expr(condition, precedence::OR);
+                self.output.push_str(" ? ");
+                self.expr(if_true, precedence::ASSIGN);
+                self.output.push_str(" : ");
+                self.expr(if_false, precedence::TERNARY);
+            }
+            Expr::Delete(inner) => {
+                self.output.push_str("delete ");
+                self.expr(inner, precedence::UNARY);
+            }
+            Expr::Array(values) => {
+                self.output.push('[');
+                self.list(values, precedence::ASSIGN);
+                self.output.push(']');
+            }
+            Expr::Object(properties) => {
+                self.output.push('{');
+                for (index, (name, value)) in properties.iter().enumerate() {
+                    if index > 0 {
+                        self.output.push_str(", ");
+                    }
+                    match name {
+                        Expr::String(name) if is_identifier(name) => self.output.push_str(name),
+                        name => self.expr(name, precedence::PRIMARY),
+                    }
+                    self.output.push_str(": ");
+                    self.expr(value, precedence::ASSIGN);
+                }
+                self.output.push('}');
+            }
+            Expr::Function(function) => self.function(function),
+            Expr::Assign(target, value) => match &**target {
+                Expr::Name(name) if !is_path(name) => {
+                    self.output.push_str("set(");
+                    self.string(name);
+                    self.output.push_str(", ");
+                    self.expr(value, precedence::ASSIGN);
+                    self.output.push(')');
+                }
+                Expr::Eval(name) => {
+                    self.output.push_str("set(");
+                    self.expr(name, precedence::ASSIGN);
+                    self.output.push_str(", ");
+                    self.expr(value, precedence::ASSIGN);
+                    self.output.push(')');
+                }
+                Expr::Property(object, property) => {
+                    self.output.push_str("setProperty(");
+                    self.expr(object, precedence::ASSIGN);
+                    self.output.push_str(", ");
+                    self.property(property);
+                    self.output.push_str(", ");
+                    self.expr(value, precedence::ASSIGN);
+                    self.output.push(')');
+                }
+                target => {
+                    self.expr(target, precedence::CALL);
+                    self.output.push_str(" = ");
+                    self.expr(value, precedence::ASSIGN);
+                }
+            },
+            Expr::Enumeration(object) => {
+                self.output.push_str("enumerate(");
+                self.expr(object, precedence::ASSIGN);
+                self.output.push(')');
+            }
+        }
+    }
+
+    /// Print a binary operation. Operators are left associative, so the
+    /// right operand must bind tighter.
+    fn binary(&mut self, symbol: &str, precedence: u8, left: &Expr, right: &Expr) {
+        self.expr(left, precedence);
+        let _ = write!(self.output, " {symbol} ");
+        self.expr(right, precedence + 1);
+    }
+
+    fn property(&mut self, property: &Expr) {
+        match property.as_count().and_then(|index| PROPERTIES.get(index)) {
+            Some(name) => self.output.push_str(name),
+            None => self.expr(property, precedence::ASSIGN),
+        }
+    }
+
+    fn number(&mut self, value: f64) {
+        let _ = if value.is_nan() {
+            write!(self.output, "NaN")
+        } else if value.is_infinite() {
+            let sign = if value < 0.0 { "-" } else { "" };
+            write!(self.output, "{sign}Infinity")
+        } else if value.fract() == 0.0 && value.abs() < 1e15 {
+            // Avoid `-0`, which AS2 would parse as negating zero anyway.
+            write!(self.output, "{}", value as i64)
+        } else if value.abs() >= 1e21 || value.abs() < 1e-6 {
+            write!(self.output, "{value:e}")
+        } else {
+            write!(self.output, "{value}")
+        };
+    }
+
+    fn string(&mut self, value: &str) {
+        self.output.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\u{8}' => self.output.push_str("\\b"),
+                '\u{c}' => self.output.push_str("\\f"),
+                c if c.is_control() => {
+                    let _ = write!(self.output, "\\u{:04X}", c as u32);
+                }
+                c => self.output.push(c),
+            }
+        }
+        self.output.push('"');
+    }
+}
+
+fn precedence_of(expr: &Expr) -> u8 {
+    match expr {
+        // Only needs parentheses when called.
+        Expr::Function(_) => precedence::ASSIGN,
+        Expr::Assign(target, _) => match **target {
+            // Printed as calls to `set` or `setProperty`.
+            Expr::Eval(_) | Expr::Property(..) => precedence::CALL,
+            Expr::Name(ref name) if !is_path(name) => precedence::CALL,
+            _ => precedence::ASSIGN,
+        },
+        Expr::Ternary(..) => precedence::TERNARY,
+        Expr::Binary(op, ..) => binary_precedence(*op),
+        Expr::Unary(UnaryOp::Not, inner) => match negated_comparison(inner) {
+            Some(_) => precedence_of(inner),
+            None => precedence::UNARY,
+        },
+        Expr::Unary(UnaryOp::Increment | UnaryOp::Decrement, _) => precedence::ADDITIVE,
+        Expr::Unary(UnaryOp::TypeOf, _) | Expr::Delete(_) => precedence::UNARY,
+        Expr::Number(value) if value.is_sign_negative() && *value != 0.0 => precedence::UNARY,
+        Expr::Member(..) | Expr::Call(..) | Expr::New(..) => precedence::CALL,
+        _ => precedence::PRIMARY,
+    }
+}
+
+fn binary_precedence(op: BinaryOp) -> u8 {
+    match op {
+        BinaryOp::Or => precedence::OR,
+        BinaryOp::And => precedence::AND,
+        BinaryOp::BitOr => precedence::BIT_OR,
+        BinaryOp::BitXor => precedence::BIT_XOR,
+        BinaryOp::BitAnd => precedence::BIT_AND,
+        BinaryOp::Equals | BinaryOp::StrictEquals | BinaryOp::StringEquals => precedence::EQUALITY,
+        BinaryOp::Less
+        | BinaryOp::Greater
+        | BinaryOp::InstanceOf
+        | BinaryOp::StringLess
+        | BinaryOp::StringGreater => precedence::RELATIONAL,
+        BinaryOp::LeftShift | BinaryOp::RightShift | BinaryOp::UnsignedRightShift => {
+            precedence::SHIFT
+        }
+        BinaryOp::Add | BinaryOp::Subtract | BinaryOp::StringAdd => precedence::ADDITIVE,
+        BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo => precedence::MULTIPLICATIVE,
+    }
+}
+
+fn binary_symbol(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Modulo => "%",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::LeftShift => "<<",
+        BinaryOp::RightShift => ">>",
+        BinaryOp::UnsignedRightShift => ">>>",
+        BinaryOp::Equals => "==",
+        BinaryOp::StrictEquals => "===",
+        BinaryOp::Less => "<",
+        BinaryOp::Greater => ">",
+        BinaryOp::And => "&&",
+        BinaryOp::Or => "||",
+        BinaryOp::InstanceOf => "instanceof",
+        BinaryOp::StringEquals => "eq",
+        BinaryOp::StringLess => "lt",
+        BinaryOp::StringGreater => "gt",
+        BinaryOp::StringAdd => "add",
+    }
+}
+
+/// The operator for the compound assignment form of `op`, if there is one.
+fn compound_symbol(op: BinaryOp) -> Option<&'static str> {
+    Some(match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Modulo => "%",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::LeftShift => "<<",
+        BinaryOp::RightShift => ">>",
+        BinaryOp::UnsignedRightShift => ">>>",
+        _ => return None,
+    })
+}
+
+/// The comparison that a negated comparison can be printed as, such as `!=`
+/// for `!(a == b)`.
+fn negated_comparison(expr: &Expr) -> Option<(&'static str, &Expr, &Expr)> {
+    let Expr::Binary(op, left, right) = expr else {
+        return None;
+    };
+    let symbol = match op {
+        BinaryOp::Equals => "!=",
+        BinaryOp::StrictEquals => "!==",
+        BinaryOp::Less => ">=",
+        BinaryOp::Greater => "<=",
+        BinaryOp::StringEquals => "ne",
+        BinaryOp::StringLess => "ge",
+        BinaryOp::StringGreater => "le",
+        _ => return None,
+    };
+    Some((symbol, left, right))
+}
+
+/// Whether the target of an assignment can be printed as a plain `x = ...`,
+/// and so also as `x += ...` or `x++`.
+fn is_assignable(target: &Expr) -> bool {
+    match target {
+        Expr::Name(name) => is_path(name),
+        Expr::Member(..) | Expr::Register(_) => true,
+        _ => false,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Whether a variable name can be printed as is, which is the case for
+/// identifiers and dot-separated paths of identifiers like `_root.clip.x`.
+fn is_path(name: &str) -> bool {
+    name.split('.').all(is_identifier)
+}
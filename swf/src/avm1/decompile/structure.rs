@@ -0,0 +1,815 @@
+//! Recovering structured control flow from the control flow graph.
+//!
+//! AVM1 compilers lay out code in source order, so rather than performing a
+//! general interval analysis, blocks are walked in order and the shapes that
+//! compilers emit for each construct are matched as they are encountered.
+//! Loops are found through back edges, and everything else through forward
+//! branches. Anything that doesn't match falls back to a `Goto`.
+
+use crate::avm1::decompile::ast::{BinaryOp, Case, Expr, Stmt};
+use crate::avm1::decompile::cfg::{ControlFlowGraph, Terminator};
+use crate::avm1::types::{Action, Value};
+use encoding_rs::Encoding;
+use std::collections::{BTreeSet, HashMap};
+
+/// How deeply expression patterns (`&&`, `||`, `?:`, `switch`) may be tried
+/// speculatively before giving up on them, to bound the cost of failed
+/// matches in deeply nested code.
+const MAX_SPECULATION_DEPTH: usize = 8;
+
+/// A construct that can be left with `break` (and maybe `continue`).
+#[derive(Clone, Copy, Debug)]
+struct Breakable {
+    break_to: usize,
+    continue_to: Option<usize>,
+}
+
+pub struct Decompiler<'a> {
+    pub(super) cfg: ControlFlowGraph<'a>,
+    pub(super) version: u8,
+    pub(super) encoding: &'static Encoding,
+    pub(super) constant_pool: Vec<String>,
+    pub(super) register_names: HashMap<u8, String>,
+
+    breakables: Vec<Breakable>,
+    loop_headers: Vec<usize>,
+    speculation_depth: usize,
+
+    /// Blocks that need a label, as they are the target of a `Goto`.
+    labels: BTreeSet<usize>,
+
+    /// Blocks that have been the target of a `Goto` so far.
+    gotos: BTreeSet<usize>,
+}
+
+impl<'a> Decompiler<'a> {
+    pub fn new(
+        cfg: ControlFlowGraph<'a>,
+        version: u8,
+        encoding: &'static Encoding,
+        constant_pool: Vec<String>,
+        register_names: HashMap<u8, String>,
+    ) -> Self {
+        Self {
+            cfg,
+            version,
+            encoding,
+            constant_pool,
+            register_names,
+            breakables: vec![],
+            loop_headers: vec![],
+            speculation_depth: 0,
+            labels: BTreeSet::new(),
+            gotos: BTreeSet::new(),
+        }
+    }
+
+    /// Decompile the whole script.
+    pub fn run(mut self) -> Vec<Stmt> {
+        let initial_pool = self.constant_pool.clone();
+        let mut statements = self.run_once();
+
+        // Gotos may point backwards, so labels are only known after a first
+        // pass. Decompilation is deterministic, so a second pass yields the
+        // same code with the labels in place.
+        if !self.gotos.is_empty() {
+            self.labels = std::mem::take(&mut self.gotos);
+            self.constant_pool = initial_pool;
+            statements = self.run_once();
+        }
+
+        if let Some(error) = &self.cfg.error {
+            statements.push(Stmt::Comment(format!("Decoding stopped early: {error}")));
+        }
+
+        statements
+    }
+
+    fn run_once(&mut self) -> Vec<Stmt> {
+        let end = self.cfg.blocks.len();
+        let mut stack = vec![];
+        let mut out = vec![];
+        self.region(0, end, end, &mut stack, &mut out);
+        out
+    }
+
+    /// Structure the blocks `from..to`.
+    ///
+    /// `follow` is the block that control reaches after leaving the region,
+    /// so that a final jump there needs no statement.
+    fn region(
+        &mut self,
+        from: usize,
+        to: usize,
+        follow: usize,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) {
+        let mut block = from;
+        while block < to {
+            if self.labels.contains(&block) {
+                out.push(Stmt::Label(self.cfg.offset_of(block)));
+            }
+
+            if !self.loop_headers.contains(&block) {
+                if let Some(latch) = self.cfg.loop_latch(block, to) {
+                    block = self.structure_loop(block, latch, stack, out);
+                    continue;
+                }
+            }
+
+            self.lift_block(block, stack, out);
+            block = match self.cfg.blocks[block].terminator {
+                Terminator::Next | Terminator::Exit => block + 1,
+                Terminator::Jump(target) => {
+                    if let Some(next) = self.rotated_loop(block, target, to, stack, out) {
+                        next
+                    } else {
+                        if let Some(statement) = self.jump(block, target, to, follow) {
+                            out.push(statement);
+                        }
+                        block + 1
+                    }
+                }
+                Terminator::Branch(target) => {
+                    let condition = pop(stack);
+                    self.branch(block, target, to, follow, condition, stack, out)
+                }
+                Terminator::Invalid {
+                    conditional,
+                    offset,
+                } => {
+                    let comment = Stmt::Comment(format!("Jump to invalid offset {offset}"));
+                    if conditional {
+                        out.push(Stmt::If(pop(stack), vec![comment], vec![]));
+                    } else {
+                        out.push(comment);
+                    }
+                    block + 1
+                }
+            };
+        }
+    }
+
+    /// The statement needed to jump from the end of `block` to `target`, if
+    /// any.
+    fn jump(&mut self, block: usize, target: usize, to: usize, follow: usize) -> Option<Stmt> {
+        if block + 1 == to && target == follow {
+            return None;
+        }
+
+        self.jump_statement(block, target, to)
+    }
+
+    /// The statement needed to jump from `block` to `target` mid-region.
+    fn jump_statement(&mut self, block: usize, target: usize, to: usize) -> Option<Stmt> {
+        if self.breakables.last().map(|b| b.break_to) == Some(target) {
+            return Some(Stmt::Break);
+        }
+        if self.breakables.iter().rev().find_map(|b| b.continue_to) == Some(target) {
+            return Some(Stmt::Continue);
+        }
+        if target == block + 1 && target < to {
+            return None;
+        }
+        if target == self.cfg.blocks.len() {
+            return Some(Stmt::Return(Expr::Undefined));
+        }
+
+        self.gotos.insert(target);
+        Some(Stmt::Goto(self.cfg.offset_of(target)))
+    }
+
+    /// Whether jumping to `target` would leave an enclosing construct.
+    fn is_exit_target(&self, target: usize) -> bool {
+        self.breakables
+            .iter()
+            .any(|b| b.break_to == target || b.continue_to == Some(target))
+    }
+
+    /// Structure a conditional branch at the end of `block`, returning the
+    /// block to continue from.
+    #[allow(clippy::too_many_arguments)]
+    fn branch(
+        &mut self,
+        block: usize,
+        target: usize,
+        to: usize,
+        follow: usize,
+        condition: Expr,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) -> usize {
+        if self.speculation_depth < MAX_SPECULATION_DEPTH {
+            self.speculation_depth += 1;
+            let next = self
+                .short_circuit(block, target, to, &condition, stack)
+                .or_else(|| self.ternary(block, target, to, &condition, stack))
+                .or_else(|| self.switch(block, target, to, &condition, stack, out));
+            self.speculation_depth -= 1;
+
+            if let Some(next) = next {
+                return next;
+            }
+        }
+
+        let leaves_region = target <= block || target > to || self.is_exit_target(target);
+        if leaves_region {
+            let statement = self
+                .jump_statement(block, target, to)
+                .unwrap_or(Stmt::Break);
+            out.push(Stmt::If(condition, vec![statement], vec![]));
+            return block + 1;
+        }
+
+        if target == block + 1 {
+            if condition.has_side_effects() {
+                out.push(Stmt::Expr(condition));
+            }
+            return target;
+        }
+
+        // `if (a) { b } else { c }` compiles to:
+        //     a; Not; If else; b; Jump end; else: c; end:
+        let then_last = target - 1;
+        if let Terminator::Jump(end) = self.cfg.blocks[then_last].terminator {
+            if then_last > block && end > target && end <= to && !self.is_exit_target(end) {
+                let mut then_body = vec![];
+                self.region(block + 1, target, end, &mut stack.clone(), &mut then_body);
+                let mut else_body = vec![];
+                let else_follow = if end == to { follow } else { end };
+                self.region(target, end, else_follow, &mut stack.clone(), &mut else_body);
+                out.push(Stmt::If(condition.negate(), then_body, else_body));
+                return end;
+            }
+        }
+
+        let mut then_body = vec![];
+        self.region(
+            block + 1,
+            target,
+            target,
+            &mut stack.clone(),
+            &mut then_body,
+        );
+        out.push(Stmt::If(condition.negate(), then_body, vec![]));
+        target
+    }
+
+    /// Whether every block in `from..to` only contains actions that can be
+    /// part of an expression.
+    fn is_expression(&self, from: usize, to: usize) -> bool {
+        (from..to).all(|block| {
+            self.cfg.instructions_of(block).iter().all(|i| {
+                !matches!(
+                    i.action,
+                    Action::SetVariable
+                        | Action::SetMember
+                        | Action::SetProperty
+                        | Action::DefineLocal
+                        | Action::DefineLocal2
+                        | Action::Trace
+                        | Action::Return
+                        | Action::Throw
+                        | Action::End
+                        | Action::With(_)
+                        | Action::Try(_)
+                        | Action::ConstantPool(_)
+                        | Action::Enumerate
+                        | Action::Enumerate2
+                )
+            })
+        })
+    }
+
+    /// Speculatively structure `from..to` as an expression, yielding the
+    /// single value it leaves on top of the first `depth` values of `stack`.
+    fn expression(
+        &mut self,
+        from: usize,
+        to: usize,
+        follow: usize,
+        stack: &[Expr],
+        depth: usize,
+    ) -> Option<Expr> {
+        let gotos = self.gotos.clone();
+        let mut inner = stack.to_vec();
+        let mut out = vec![];
+        self.region(from, to, follow, &mut inner, &mut out);
+
+        if out.is_empty() && inner.len() == depth + 1 && inner[..depth] == stack[..depth] {
+            inner.pop()
+        } else {
+            // Forget about any jumps seen in code that won't be emitted.
+            self.gotos = gotos;
+            None
+        }
+    }
+
+    /// `a && b` and `a || b` compile to:
+    ///     a; PushDuplicate; [Not]; If end; Pop; b; end:
+    fn short_circuit(
+        &mut self,
+        block: usize,
+        target: usize,
+        to: usize,
+        condition: &Expr,
+        stack: &mut Vec<Expr>,
+    ) -> Option<usize> {
+        if target <= block + 1 || target > to || !self.is_expression(block + 1, target) {
+            return None;
+        }
+        let first = self.cfg.instructions_of(block + 1).first()?;
+        if !matches!(first.action, Action::Pop) {
+            return None;
+        }
+
+        let left = stack.last()?.clone();
+        let op = if *condition == left {
+            BinaryOp::Or
+        } else if *condition == left.clone().negate() {
+            BinaryOp::And
+        } else {
+            return None;
+        };
+
+        // The `Pop` discards the duplicated left side, and the right side
+        // takes its place.
+        let right = self.expression(block + 1, target, target, stack, stack.len() - 1)?;
+        stack.pop();
+        stack.push(Expr::Binary(op, Box::new(left), Box::new(right)));
+        Some(target)
+    }
+
+    /// `a ? b : c` compiles to:
+    ///     a; If true; c; Jump end; true: b; end:
+    fn ternary(
+        &mut self,
+        block: usize,
+        target: usize,
+        to: usize,
+        condition: &Expr,
+        stack: &mut Vec<Expr>,
+    ) -> Option<usize> {
+        if target <= block + 1 || target > to {
+            return None;
+        }
+        let Terminator::Jump(end) = self.cfg.blocks[target - 1].terminator else {
+            return None;
+        };
+        if end <= target || end > to || !self.is_expression(block + 1, end) {
+            return None;
+        }
+
+        let if_false = self.expression(block + 1, target, end, stack, stack.len())?;
+        let if_true = self.expression(target, end, end, stack, stack.len())?;
+        stack.push(Expr::Ternary(
+            Box::new(condition.clone()),
+            Box::new(if_true),
+            Box::new(if_false),
+        ));
+        Some(end)
+    }
+
+    /// `switch` compiles to a chain of comparisons against the discriminant,
+    /// stored in a register, followed by the case bodies:
+    ///     x; StoreRegister r; a; StrictEquals; If case_a;
+    ///     r; b; StrictEquals; If case_b;
+    ///     Jump default;
+    ///     case_a: ...; Jump end;
+    ///     case_b: ...; Jump end;
+    ///     default: ...
+    ///     end:
+    fn switch(
+        &mut self,
+        block: usize,
+        target: usize,
+        to: usize,
+        condition: &Expr,
+        stack: &mut [Expr],
+        out: &mut Vec<Stmt>,
+    ) -> Option<usize> {
+        let Expr::Binary(BinaryOp::StrictEquals, first, value) = condition else {
+            return None;
+        };
+        let (discriminant, compared) = match &**first {
+            Expr::Assign(register, value) if matches!(**register, Expr::Register(_)) => {
+                ((**value).clone(), (**register).clone())
+            }
+            other => (other.clone(), other.clone()),
+        };
+
+        let mut cases = vec![((**value).clone(), target)];
+        let mut chain = block + 1;
+        let default = loop {
+            if chain >= to {
+                return None;
+            }
+            match self.cfg.blocks[chain].terminator {
+                Terminator::Branch(case) => {
+                    if !self.is_expression(chain, chain + 1) {
+                        return None;
+                    }
+                    let mut inner = stack.to_vec();
+                    let mut inner_out = vec![];
+                    self.lift_block(chain, &mut inner, &mut inner_out);
+                    let Some(Expr::Binary(BinaryOp::StrictEquals, compared_here, value)) =
+                        inner.pop()
+                    else {
+                        return None;
+                    };
+                    if !inner_out.is_empty() || inner != stack || *compared_here != compared {
+                        return None;
+                    }
+                    cases.push((*value, case));
+                }
+                Terminator::Jump(default) if self.cfg.instructions_of(chain).len() == 1 => {
+                    break default;
+                }
+                _ => return None,
+            }
+            chain += 1;
+        };
+
+        let targets_ordered = cases.windows(2).all(|pair| pair[0].1 <= pair[1].1);
+        if !targets_ordered || cases[0].1 <= chain || default < cases[cases.len() - 1].1 {
+            return None;
+        }
+        if default > to {
+            return None;
+        }
+
+        // Breaks jump past the last case. If there are none, then neither is
+        // there a default case distinct from the end of the switch.
+        let mut end = default;
+        loop {
+            let furthest = (cases[0].1..end)
+                .filter_map(|b| match self.cfg.blocks[b].terminator {
+                    Terminator::Jump(t) if t > end && t <= to && !self.is_exit_target(t) => Some(t),
+                    _ => None,
+                })
+                .max();
+            match furthest {
+                Some(furthest) => end = furthest,
+                None => break,
+            }
+        }
+
+        // Flash stores the discriminant in a register ahead of the chain, in a
+        // statement of its own.
+        let discriminant = match (&discriminant, out.last()) {
+            (Expr::Register(_), Some(Stmt::Expr(Expr::Assign(register, value))))
+                if **register == discriminant =>
+            {
+                let value = (**value).clone();
+                out.pop();
+                value
+            }
+            _ => discriminant,
+        };
+
+        self.breakables.push(Breakable {
+            break_to: end,
+            continue_to: None,
+        });
+
+        let mut sections: Vec<(usize, Vec<Expr>)> = vec![];
+        for (value, case) in cases {
+            match sections.last_mut() {
+                Some((start, values)) if *start == case => values.push(value),
+                _ => sections.push((case, vec![value])),
+            }
+        }
+
+        let mut bodies = vec![];
+        for (index, (start, values)) in sections.iter().enumerate() {
+            let stop = sections.get(index + 1).map_or(default, |s| s.0);
+            let mut body = vec![];
+            self.region(*start, stop, stop, &mut stack.to_vec(), &mut body);
+            bodies.push(Case {
+                values: values.clone(),
+                body,
+            });
+        }
+        if end > default {
+            let mut body = vec![];
+            self.region(default, end, end, &mut stack.to_vec(), &mut body);
+            bodies.push(Case {
+                values: vec![],
+                body,
+            });
+        }
+
+        self.breakables.pop();
+        out.push(Stmt::Switch(discriminant, bodies));
+        Some(end)
+    }
+
+    /// Structure the loop `header..=latch`, returning the block after it.
+    fn structure_loop(
+        &mut self,
+        header: usize,
+        latch: usize,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) -> usize {
+        let exit = latch + 1;
+
+        // Leftovers of breaking out of a `for..in` loop, see `is_drain`.
+        if header == latch && self.is_drain(header) {
+            return exit;
+        }
+
+        self.loop_headers.push(header);
+        let statement = self
+            .pre_tested_loop(header, latch, stack)
+            .unwrap_or_else(|| self.post_tested_loop(header, latch, stack));
+        self.loop_headers.pop();
+
+        let next = match &statement {
+            // Skip past any drain loop that `break`s lead to.
+            Stmt::ForIn { .. } if exit < self.cfg.blocks.len() && self.is_drain(exit) => exit + 1,
+            _ => exit,
+        };
+
+        // The Flash IDE compiles `for` loops as `while` loops with the update
+        // at the end of the body. Without a `continue`, which would have to
+        // jump to the update, that is indistinguishable from a `while` loop.
+        let statement = match statement {
+            Stmt::While(condition, mut body)
+                if condition != Expr::Bool(true)
+                    && !contains_continue(&body)
+                    && initializes(out.last(), body.last()) =>
+            {
+                let update = body.pop().into_iter().collect();
+                for_loop(out, condition, update, body)
+            }
+            statement => statement,
+        };
+        out.push(statement);
+        next
+    }
+
+    /// `for` loops as compiled by MTASC, which jump straight to the condition
+    /// on entry and test it after the update:
+    ///     Jump test; header: update; test: condition; Not; If end;
+    ///     ...; Jump header;
+    ///     end:
+    fn rotated_loop(
+        &mut self,
+        block: usize,
+        test: usize,
+        to: usize,
+        stack: &mut [Expr],
+        out: &mut Vec<Stmt>,
+    ) -> Option<usize> {
+        let header = block + 1;
+        if test <= header || self.loop_headers.contains(&header) {
+            return None;
+        }
+        let latch = self.cfg.loop_latch(header, to)?;
+        let exit = latch + 1;
+        if test >= latch
+            || self.cfg.blocks[latch].terminator != Terminator::Jump(header)
+            || self.cfg.blocks[test].terminator != Terminator::Branch(exit)
+        {
+            return None;
+        }
+
+        let mut test_stack = stack.to_vec();
+        let mut test_out = vec![];
+        self.lift_block(test, &mut test_stack, &mut test_out);
+        let condition = pop(&mut test_stack).negate();
+        if !test_out.is_empty() {
+            return None;
+        }
+
+        self.loop_headers.push(header);
+        let mut update = vec![];
+        self.region(header, test, test, &mut stack.to_vec(), &mut update);
+        if !update.iter().all(|s| matches!(s, Stmt::Expr(_))) {
+            self.loop_headers.pop();
+            return None;
+        }
+
+        self.breakables.push(Breakable {
+            break_to: exit,
+            continue_to: Some(header),
+        });
+        let mut body = vec![];
+        self.region(test + 1, exit, header, &mut test_stack, &mut body);
+        self.breakables.pop();
+        self.loop_headers.pop();
+
+        let statement = for_loop(out, condition, update, body);
+        out.push(statement);
+        Some(exit)
+    }
+
+    /// `while` and `for..in` loops, which test their condition in the loop
+    /// header and jump back to it from the end of the body.
+    fn pre_tested_loop(
+        &mut self,
+        header: usize,
+        latch: usize,
+        stack: &mut Vec<Expr>,
+    ) -> Option<Stmt> {
+        let exit = latch + 1;
+        if latch == header || self.cfg.blocks[latch].terminator != Terminator::Jump(header) {
+            return None;
+        }
+        let Terminator::Branch(target) = self.cfg.blocks[header].terminator else {
+            return None;
+        };
+        // Breaking out of a `for..in` loop needs to clear the stack first, so
+        // `break` jumps to a drain loop that the loop itself exits past.
+        let exits_past_drain =
+            target == exit + 1 && exit < self.cfg.blocks.len() && self.is_drain(exit);
+        if target != exit && !exits_past_drain {
+            return None;
+        }
+
+        let mut header_stack = stack.clone();
+        let mut header_out = vec![];
+        self.lift_block(header, &mut header_stack, &mut header_out);
+        let condition = pop(&mut header_stack);
+        if !header_out.is_empty() {
+            return None;
+        }
+
+        // `for (k in o)` compiles to:
+        //     o; Enumerate2;
+        //     header: StoreRegister r; Push null; Equals2; If end;
+        //     Push "k", r; SetVariable; ...; Jump header;
+        //     end:
+        let enumeration = match &condition {
+            Expr::Binary(BinaryOp::Equals, assign, null) if **null == Expr::Null => match &**assign
+            {
+                Expr::Assign(variable, enumeration) => match &**enumeration {
+                    Expr::Enumeration(object) => Some(((**variable).clone(), (**object).clone())),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        if target != exit && enumeration.is_none() {
+            return None;
+        }
+
+        self.breakables.push(Breakable {
+            break_to: exit,
+            continue_to: Some(header),
+        });
+        let mut body = vec![];
+        self.region(
+            header + 1,
+            exit,
+            header,
+            &mut header_stack.clone(),
+            &mut body,
+        );
+        self.breakables.pop();
+        *stack = header_stack;
+
+        if let Some((variable, object)) = enumeration {
+            return Some(for_in(variable, object, body));
+        }
+        Some(Stmt::While(condition.negate(), body))
+    }
+
+    /// `do..while` loops, which test their condition at the end of the body,
+    /// and any other loop, as `while (true)`.
+    fn post_tested_loop(&mut self, header: usize, latch: usize, stack: &mut Vec<Expr>) -> Stmt {
+        let exit = latch + 1;
+
+        if self.cfg.blocks[latch].terminator == Terminator::Branch(header) {
+            self.breakables.push(Breakable {
+                break_to: exit,
+                continue_to: Some(latch),
+            });
+            let mut body = vec![];
+            self.region(header, latch, latch, stack, &mut body);
+            if self.labels.contains(&latch) {
+                body.push(Stmt::Label(self.cfg.offset_of(latch)));
+            }
+            self.lift_block(latch, stack, &mut body);
+            self.breakables.pop();
+            let condition = pop(stack);
+            return Stmt::DoWhile(body, condition);
+        }
+
+        self.breakables.push(Breakable {
+            break_to: exit,
+            continue_to: Some(header),
+        });
+        let mut body = vec![];
+        self.region(header, exit, header, stack, &mut body);
+        self.breakables.pop();
+        Stmt::While(Expr::Bool(true), body)
+    }
+
+    /// Whether the block pops the stack until it hits the `null` that
+    /// `Enumerate` pushes first, which is emitted after `for..in` loops for
+    /// `break` to jump to:
+    ///     drain: Push null; Equals2; Not; If drain
+    fn is_drain(&self, block: usize) -> bool {
+        if self.cfg.blocks[block].terminator != Terminator::Branch(block) {
+            return false;
+        }
+        matches!(
+            self.cfg.instructions_of(block),
+            [push, equals, not, _]
+                if matches!(&push.action, Action::Push(push) if push.values == [Value::Null])
+                    && matches!(equals.action, Action::Equals2)
+                    && matches!(not.action, Action::Not)
+        )
+    }
+}
+
+/// Build a `for` loop, pulling the initialization of the variable being
+/// updated out of the statements before it.
+fn for_loop(out: &mut Vec<Stmt>, condition: Expr, update: Vec<Stmt>, body: Vec<Stmt>) -> Stmt {
+    let init = if initializes(out.last(), update.first()) {
+        out.pop().map(Box::new)
+    } else {
+        None
+    };
+
+    Stmt::For {
+        init,
+        condition,
+        update,
+        body,
+    }
+}
+
+/// Whether `init` assigns the variable that `update` then assigns to.
+fn initializes(init: Option<&Stmt>, update: Option<&Stmt>) -> bool {
+    let Some(Stmt::Expr(Expr::Assign(updated, _))) = update else {
+        return false;
+    };
+    match init {
+        Some(Stmt::Expr(Expr::Assign(target, _))) => target == updated,
+        Some(Stmt::Var(name, Some(_))) => matches!(&**updated, Expr::Name(n) if n == name),
+        _ => false,
+    }
+}
+
+/// Whether a loop body contains a `continue` for that loop.
+fn contains_continue(body: &[Stmt]) -> bool {
+    body.iter().any(|statement| match statement {
+        Stmt::Continue => true,
+        Stmt::If(_, then_body, else_body) => {
+            contains_continue(then_body) || contains_continue(else_body)
+        }
+        Stmt::Switch(_, cases) => cases.iter().any(|case| contains_continue(&case.body)),
+        Stmt::With(_, body) => contains_continue(body),
+        Stmt::Try {
+            body,
+            catch,
+            finally,
+        } => {
+            contains_continue(body)
+                || catch
+                    .as_ref()
+                    .is_some_and(|(_, body)| contains_continue(body))
+                || finally.as_ref().is_some_and(|body| contains_continue(body))
+        }
+        _ => false,
+    })
+}
+
+/// Build a `for..in` loop, pulling the assignment of the loop variable out of
+/// the body.
+fn for_in(variable: Expr, object: Expr, mut body: Vec<Stmt>) -> Stmt {
+    // Named variables are assigned directly; anonymous registers are
+    // assigned to the real variable in the first statement of the body.
+    let (variable, declare) = match (&variable, body.first()) {
+        (Expr::Register(_), Some(Stmt::Expr(Expr::Assign(target, value))))
+            if **value == variable =>
+        {
+            let target = (**target).clone();
+            body.remove(0);
+            (target, false)
+        }
+        (Expr::Register(_), Some(Stmt::Var(name, Some(value)))) if *value == variable => {
+            let name = Expr::Name(name.clone());
+            body.remove(0);
+            (name, true)
+        }
+        _ => (variable, false),
+    };
+
+    Stmt::ForIn {
+        variable,
+        declare,
+        object,
+        body,
+    }
+}
+
+/// Pop a value, treating an empty stack as `undefined` like AVM1 does.
+pub(super) fn pop(stack: &mut Vec<Expr>) -> Expr {
+    stack.pop().unwrap_or(Expr::Undefined)
+}
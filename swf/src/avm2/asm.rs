@@ -0,0 +1,317 @@
+//! A textual form of ABC files, for reading and hand-editing AS3 bytecode.
+//!
+//! [`disassemble`] turns an [`AbcFile`](crate::avm2::types::AbcFile) into
+//! text, and [`assemble`] parses that text back into an identical `AbcFile`.
+//!
+//! The format is line based, with `;` starting a comment. The constant pools
+//! are listed entry by entry, and everything else refers to them by index,
+//! written as `#1`. Methods, metadata, classes, scripts and method bodies are
+//! blocks ending with `end`:
+//!
+//! ```text
+//! version 46 16
+//!
+//! string 1 "Test"
+//! namespace 1 package #2
+//! multiname 1 qname #1 #1 ; Test
+//!
+//! method 0 name #0 return #0
+//! end
+//!
+//! body 0 method #0 max_stack 1 locals 1 init_scope 0 max_scope 1
+//!   code
+//!     getlocal0
+//!     pushscope
+//!     jump L0
+//!   L0:
+//!     returnvoid
+//! end
+//! ```
+//!
+//! Branch targets and exception ranges use labels. Bytes that can't be
+//! decoded as instructions are kept as `raw` lines, so any method body
+//! survives a round trip.
+
+mod assemble;
+mod disassemble;
+
+pub use assemble::{assemble, AssembleError};
+pub use disassemble::disassemble;
+
+use crate::avm2::opcode::OpCode;
+
+/// The kind of an operand that follows an opcode in a method body.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operand {
+    Byte,
+    /// A byte that the VM sign extends, as used by `pushbyte`.
+    SignedByte,
+    U30,
+    /// A variable-length integer that's interpreted as signed, as used by
+    /// `pushshort`.
+    I30,
+    Int,
+    Uint,
+    Double,
+    String,
+    Namespace,
+    Multiname,
+    Method,
+    Class,
+    Exception,
+    /// A 24-bit offset relative to the end of the instruction.
+    Branch,
+    /// The default and case offsets of `lookupswitch`, relative to the start
+    /// of the instruction.
+    LookupSwitch,
+}
+
+/// The operands that follow an opcode.
+fn operands(opcode: OpCode) -> &'static [Operand] {
+    use Operand::*;
+
+    match opcode {
+        OpCode::ApplyType
+        | OpCode::BkptLine
+        | OpCode::Call
+        | OpCode::Construct
+        | OpCode::ConstructSuper
+        | OpCode::DebugLine
+        | OpCode::DecLocal
+        | OpCode::DecLocalI
+        | OpCode::GetGlobalSlot
+        | OpCode::GetLocal
+        | OpCode::GetOuterScope
+        | OpCode::GetSlot
+        | OpCode::IncLocal
+        | OpCode::IncLocalI
+        | OpCode::Kill
+        | OpCode::NewArray
+        | OpCode::NewObject
+        | OpCode::SetGlobalSlot
+        | OpCode::SetLocal
+        | OpCode::SetSlot => &[U30],
+        OpCode::CallMethod | OpCode::HasNext2 => &[U30, U30],
+        OpCode::AsType
+        | OpCode::Coerce
+        | OpCode::DeleteProperty
+        | OpCode::FindDef
+        | OpCode::FindProperty
+        | OpCode::FindPropStrict
+        | OpCode::GetDescendants
+        | OpCode::GetLex
+        | OpCode::GetProperty
+        | OpCode::GetSuper
+        | OpCode::InitProperty
+        | OpCode::IsType
+        | OpCode::SetProperty
+        | OpCode::SetSuper => &[Multiname],
+        OpCode::CallProperty
+        | OpCode::CallPropLex
+        | OpCode::CallPropVoid
+        | OpCode::CallSuper
+        | OpCode::CallSuperVoid
+        | OpCode::ConstructProp => &[Multiname, U30],
+        OpCode::CallStatic => &[Method, U30],
+        OpCode::NewFunction => &[Method],
+        OpCode::NewClass => &[Class],
+        OpCode::NewCatch => &[Exception],
+        OpCode::DebugFile | OpCode::Dxns | OpCode::PushString => &[String],
+        OpCode::PushInt => &[Int],
+        OpCode::PushUint => &[Uint],
+        OpCode::PushDouble => &[Double],
+        OpCode::PushNamespace => &[Namespace],
+        OpCode::GetScopeObject => &[Byte],
+        OpCode::PushByte => &[SignedByte],
+        OpCode::PushShort => &[I30],
+        OpCode::Debug => &[Byte, String, Byte, U30],
+        OpCode::IfEq
+        | OpCode::IfFalse
+        | OpCode::IfGe
+        | OpCode::IfGt
+        | OpCode::IfLe
+        | OpCode::IfLt
+        | OpCode::IfNe
+        | OpCode::IfNge
+        | OpCode::IfNgt
+        | OpCode::IfNle
+        | OpCode::IfNlt
+        | OpCode::IfStrictEq
+        | OpCode::IfStrictNe
+        | OpCode::IfTrue
+        | OpCode::Jump => &[Branch],
+        OpCode::LookupSwitch => &[LookupSwitch],
+        _ => &[],
+    }
+}
+
+/// The name of an opcode in the text format, such as `callpropvoid`.
+fn mnemonic(opcode: OpCode) -> String {
+    format!("{opcode:?}").to_ascii_lowercase()
+}
+
+/// Quotes a string from the constant pool, escaping anything that isn't
+/// printable. Bytes that aren't valid UTF-8 are written as `\xNN`.
+fn quote(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('"');
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", c as u8)),
+                c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::read::tests::read_abc_from_file;
+    use crate::avm2::read::Reader;
+    use crate::avm2::types::*;
+    use crate::avm2::write::Writer;
+    use crate::test_data;
+
+    fn round_trip(abc_file: &AbcFile) {
+        let text = disassemble(abc_file);
+        let assembled = assemble(&text).unwrap_or_else(|e| panic!("{e}\n\n{text}"));
+        assert_eq!(&assembled, abc_file, "Round trip failed:\n{text}");
+
+        let mut original = vec![];
+        Writer::new(&mut original).write(abc_file.clone()).unwrap();
+        let mut written = vec![];
+        Writer::new(&mut written).write(assembled).unwrap();
+        assert_eq!(original, written);
+    }
+
+    #[test]
+    fn round_trip_test_data() {
+        for (_, abc_file, _) in test_data::avm2_tests() {
+            round_trip(&abc_file);
+        }
+    }
+
+    #[test]
+    fn round_trip_files() {
+        for path in [
+            "tests/swfs/Avm2DefaultValue.swf",
+            "tests/swfs/Avm2Dummy.swf",
+        ] {
+            let bytes = read_abc_from_file(path);
+            let abc_file = Reader::new(&bytes[..]).read().unwrap();
+            round_trip(&abc_file);
+        }
+    }
+
+    #[test]
+    fn round_trip_strings() {
+        let mut abc_file = test_data::avm2_tests().remove(0).1;
+        abc_file.constant_pool.strings = vec![
+            b"quote \" and backslash \\".to_vec(),
+            b"line\nbreak\ttab\r\x00".to_vec(),
+            "unicode \u{e9}\u{2603}\u{85}".as_bytes().to_vec(),
+            b"invalid \xff\xfe utf-8".to_vec(),
+            b"; not a comment".to_vec(),
+        ];
+        round_trip(&abc_file);
+    }
+
+    #[test]
+    fn round_trip_undecodable_code() {
+        let mut abc_file = test_data::avm2_tests().remove(0).1;
+        // A jump into the middle of an instruction, followed by an unknown
+        // opcode and a truncated `pushshort`.
+        abc_file.method_bodies[0].code = vec![0x10, 0x01, 0x00, 0x00, 0x24, 0x05, 0xff, 0x25];
+        round_trip(&abc_file);
+    }
+
+    #[test]
+    fn assemble_code() {
+        let source = r#"
+            version 46 16
+
+            string 1 "e"
+            multiname 1 qname #0 #1 ; e
+
+            method 0 name #0 return #0
+            end
+
+            body 0 method #0 max_stack 2 locals 2 init_scope 0 max_scope 1
+              try start finish handler type #0 name #1
+              code
+              start:
+                pushbyte -1
+                lookupswitch finish start finish
+                iftrue start
+              finish:
+                returnvoid
+              handler:
+                newcatch #0
+                pushshort -2
+                raw 0x02 0x02
+                returnvoid
+            end
+        "#;
+        let abc_file = assemble(source).unwrap();
+        let body = &abc_file.method_bodies[0];
+        assert_eq!(
+            body.code,
+            [
+                0x24, 0xff, // pushbyte -1
+                0x1b, 0x0f, 0x00, 0x00, 0x01, 0xfe, 0xff, 0xff, 0x0f, 0x00,
+                0x00, // lookupswitch
+                0x11, 0xef, 0xff, 0xff, // iftrue start
+                0x47, // returnvoid
+                0x5a, 0x00, // newcatch #0
+                0x25, 0xfe, 0xff, 0xff, 0xff, 0x0f, // pushshort -2
+                0x02, 0x02, // raw
+                0x47, // returnvoid
+            ]
+        );
+        assert_eq!(
+            body.exceptions,
+            [Exception {
+                from_offset: 0,
+                to_offset: 17,
+                target_offset: 18,
+                variable_name: Index::new(1),
+                type_name: Index::new(0),
+            }]
+        );
+        assert_eq!(abc_file.methods[0].body, Some(Index::new(0)));
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+        assert_eq!(
+            error("version 46 16\nstring 2 \"a\""),
+            "line 2: expected string index 1, found 2"
+        );
+        assert_eq!(
+            error("method 0 name #0 return #0\n  param #0\n"),
+            "line 1: `method` is missing its `end`"
+        );
+        assert_eq!(
+            error("method 0 name #0 return #0\nend\nbody 0 method #0 max_stack 0 locals 0 init_scope 0 max_scope 0\n  code\n    jump nowhere\nend"),
+            "line 5: unknown label `nowhere`"
+        );
+        assert_eq!(
+            error("body 0 method #0 max_stack 0 locals 0 init_scope 0 max_scope 0\n  code\n    frobnicate\nend"),
+            "line 3: unknown instruction `frobnicate`"
+        );
+    }
+}
@@ -0,0 +1,869 @@
+//! Parsing the text format back into an `AbcFile`.
+
+use super::{mnemonic, operands, Operand};
+use crate::avm2::opcode::OpCode;
+use crate::avm2::types::*;
+use num_traits::FromPrimitive;
+use std::collections::HashMap;
+use std::{error, fmt};
+
+/// An error in the text given to [`assemble`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssembleError {
+    /// The 1-based line the error was found on.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for AssembleError {}
+
+type Result<T> = std::result::Result<T, AssembleError>;
+
+/// Assemble the text format produced by [`disassemble`](super::disassemble)
+/// into an ABC file.
+pub fn assemble(source: &str) -> Result<AbcFile> {
+    let lines = source
+        .lines()
+        .enumerate()
+        .map(|(i, text)| tokenize(i + 1, text))
+        .filter(|line| !matches!(line, Ok(line) if line.tokens.is_empty()))
+        .collect::<Result<Vec<_>>>()?;
+    Assembler::new(&lines).abc_file()
+}
+
+#[derive(Debug)]
+enum Token {
+    Word(String),
+    String(Vec<u8>),
+}
+
+struct Line {
+    number: usize,
+    tokens: Vec<Token>,
+}
+
+impl Line {
+    fn args(&self) -> Args<'_> {
+        Args {
+            tokens: &self.tokens,
+            pos: 0,
+            line: self.number,
+        }
+    }
+}
+
+fn tokenize(number: usize, text: &str) -> Result<Line> {
+    let error = |message: &str| AssembleError {
+        line: number,
+        message: message.to_string(),
+    };
+
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == ';' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut bytes = vec![];
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => bytes.push(b'\n'),
+                        Some('r') => bytes.push(b'\r'),
+                        Some('t') => bytes.push(b'\t'),
+                        Some('x') => {
+                            let hex: String = chars.by_ref().take(2).collect();
+                            let byte = u8::from_str_radix(&hex, 16)
+                                .map_err(|_| error("invalid `\\x` escape"))?;
+                            bytes.push(byte);
+                        }
+                        Some('u') => {
+                            if chars.next() != Some('{') {
+                                return Err(error("invalid `\\u` escape"));
+                            }
+                            let hex: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                            let c = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| error("invalid `\\u` escape"))?;
+                            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        }
+                        Some(c @ ('"' | '\\')) => bytes.push(c as u8),
+                        _ => return Err(error("invalid escape in string")),
+                    },
+                    Some(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                    None => return Err(error("unterminated string")),
+                }
+            }
+            tokens.push(Token::String(bytes));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == ';' || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(Line { number, tokens })
+}
+
+/// Parses an integer in decimal or, with a `0x` prefix, hexadecimal.
+fn parse_int(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None if digits.starts_with(|c: char| c.is_ascii_digit()) => digits.parse().ok()?,
+        None => return None,
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// The tokens of a single line.
+struct Args<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Args<'a> {
+    fn error(&self, message: impl Into<String>) -> AssembleError {
+        AssembleError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek_word(&self) -> Option<&'a str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+
+    fn word(&mut self, expected: &str) -> Result<&'a str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) => {
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => Err(self.error(format!("expected {expected}"))),
+        }
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>> {
+        match self.tokens.get(self.pos) {
+            Some(Token::String(string)) => {
+                self.pos += 1;
+                Ok(string.clone())
+            }
+            _ => Err(self.error("expected a string")),
+        }
+    }
+
+    fn int(&mut self, expected: &str, min: i64, max: i64) -> Result<i64> {
+        let word = self.word(expected)?;
+        parse_int(word)
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| self.error(format!("expected {expected}, found `{word}`")))
+    }
+
+    fn u30(&mut self) -> Result<u32> {
+        Ok(self.int("a number", 0, u32::MAX.into())? as u32)
+    }
+
+    fn index<T>(&mut self) -> Result<Index<T>> {
+        let word = self.word("an index")?;
+        word.strip_prefix('#')
+            .and_then(|index| index.parse().ok())
+            .map(Index::new)
+            .ok_or_else(|| self.error(format!("expected an index, found `{word}`")))
+    }
+
+    /// Expects `keyword` followed by an index.
+    fn keyed_index<T>(&mut self, keyword: &str) -> Result<Index<T>> {
+        self.keyword(keyword)?;
+        self.index()
+    }
+
+    /// Expects `keyword` followed by a number.
+    fn keyed_u30(&mut self, keyword: &str) -> Result<u32> {
+        self.keyword(keyword)?;
+        self.u30()
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<()> {
+        match self.peek_word() {
+            Some(word) if word == keyword => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error(format!("expected `{keyword}`"))),
+        }
+    }
+
+    /// Expects the position of an entry that must come next in its list.
+    fn position(&mut self, kind: &str, expected: usize) -> Result<()> {
+        let found = self.u30()?;
+        if found as usize != expected {
+            return Err(self.error(format!("expected {kind} index {expected}, found {found}")));
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        match self.tokens.get(self.pos) {
+            None => Ok(()),
+            Some(Token::Word(word)) => Err(self.error(format!("unexpected `{word}`"))),
+            Some(Token::String(_)) => Err(self.error("unexpected string")),
+        }
+    }
+
+    fn default_value(&mut self) -> Result<DefaultValue> {
+        Ok(match self.word("a value")? {
+            "int" => DefaultValue::Int(self.index()?),
+            "uint" => DefaultValue::Uint(self.index()?),
+            "double" => DefaultValue::Double(self.index()?),
+            "string" => DefaultValue::String(self.index()?),
+            "true" => DefaultValue::True,
+            "false" => DefaultValue::False,
+            "null" => DefaultValue::Null,
+            "undefined" => DefaultValue::Undefined,
+            "namespace" => DefaultValue::Namespace(self.index()?),
+            "package" => DefaultValue::Package(self.index()?),
+            "package_internal" => DefaultValue::PackageInternal(self.index()?),
+            "protected" => DefaultValue::Protected(self.index()?),
+            "explicit" => DefaultValue::Explicit(self.index()?),
+            "static_protected" => DefaultValue::StaticProtected(self.index()?),
+            "private" => DefaultValue::Private(self.index()?),
+            other => return Err(self.error(format!("unknown value kind `{other}`"))),
+        })
+    }
+
+    fn trait_(&mut self) -> Result<Trait> {
+        let kind = self.word("a trait kind")?;
+        let name = self.index()?;
+        let id = self.keyed_u30("id")?;
+        let kind = match kind {
+            "slot" | "const" => {
+                let type_name = self.keyed_index("type")?;
+                let value = if self.peek_word() == Some("value") {
+                    self.pos += 1;
+                    Some(self.default_value()?)
+                } else {
+                    None
+                };
+                if kind == "slot" {
+                    TraitKind::Slot {
+                        slot_id: id,
+                        type_name,
+                        value,
+                    }
+                } else {
+                    TraitKind::Const {
+                        slot_id: id,
+                        type_name,
+                        value,
+                    }
+                }
+            }
+            "method" => TraitKind::Method {
+                disp_id: id,
+                method: self.keyed_index("method")?,
+            },
+            "getter" => TraitKind::Getter {
+                disp_id: id,
+                method: self.keyed_index("method")?,
+            },
+            "setter" => TraitKind::Setter {
+                disp_id: id,
+                method: self.keyed_index("method")?,
+            },
+            "class" => TraitKind::Class {
+                slot_id: id,
+                class: self.keyed_index("class")?,
+            },
+            "function" => TraitKind::Function {
+                slot_id: id,
+                function: self.keyed_index("method")?,
+            },
+            other => return Err(self.error(format!("unknown trait kind `{other}`"))),
+        };
+
+        let mut t = Trait {
+            name,
+            kind,
+            metadata: vec![],
+            is_final: false,
+            is_override: false,
+        };
+        while let Some(word) = self.peek_word() {
+            self.pos += 1;
+            match word {
+                "final" => t.is_final = true,
+                "override" => t.is_override = true,
+                "metadata" => {
+                    while !self.is_empty() {
+                        t.metadata.push(self.index()?);
+                    }
+                }
+                other => return Err(self.error(format!("unexpected `{other}`"))),
+            }
+        }
+        self.finish()?;
+        Ok(t)
+    }
+}
+
+struct Assembler<'a> {
+    lines: &'a [Line],
+    pos: usize,
+    abc: AbcFile,
+    opcodes: HashMap<String, OpCode>,
+}
+
+impl<'a> Assembler<'a> {
+    fn new(lines: &'a [Line]) -> Self {
+        Self {
+            lines,
+            pos: 0,
+            abc: AbcFile {
+                major_version: 46,
+                minor_version: 16,
+                constant_pool: ConstantPool {
+                    ints: vec![],
+                    uints: vec![],
+                    doubles: vec![],
+                    strings: vec![],
+                    namespaces: vec![],
+                    namespace_sets: vec![],
+                    multinames: vec![],
+                },
+                methods: vec![],
+                metadata: vec![],
+                instances: vec![],
+                classes: vec![],
+                scripts: vec![],
+                method_bodies: vec![],
+            },
+            opcodes: (0..=u8::MAX)
+                .filter_map(OpCode::from_u8)
+                .map(|opcode| (mnemonic(opcode), opcode))
+                .collect(),
+        }
+    }
+
+    /// The lines of the block started by `start`, up to its `end`.
+    fn block(&mut self, start: &Line, kind: &str) -> Result<&'a [Line]> {
+        let lines = &self.lines[self.pos..];
+        let len = lines
+            .iter()
+            .position(|line| matches!(&line.tokens[..], [Token::Word(word)] if word == "end"))
+            .ok_or_else(|| AssembleError {
+                line: start.number,
+                message: format!("`{kind}` is missing its `end`"),
+            })?;
+        self.pos += len + 1;
+        Ok(&lines[..len])
+    }
+
+    fn abc_file(mut self) -> Result<AbcFile> {
+        let mut body_lines = vec![];
+        while let Some(line) = self.lines.get(self.pos) {
+            self.pos += 1;
+            let mut args = line.args();
+            let keyword = args.word("a declaration")?;
+            let pool = &mut self.abc.constant_pool;
+            match keyword {
+                "version" => {
+                    self.abc.major_version = args.int("a version", 0, u16::MAX.into())? as u16;
+                    self.abc.minor_version = args.int("a version", 0, u16::MAX.into())? as u16;
+                }
+                "int" => {
+                    args.position(keyword, pool.ints.len() + 1)?;
+                    let value = args.int("an integer", i32::MIN.into(), i32::MAX.into())?;
+                    pool.ints.push(value as i32);
+                }
+                "uint" => {
+                    args.position(keyword, pool.uints.len() + 1)?;
+                    pool.uints.push(args.u30()?);
+                }
+                "double" => {
+                    args.position(keyword, pool.doubles.len() + 1)?;
+                    let word = args.word("a number")?;
+                    let value = word
+                        .parse()
+                        .map_err(|_| args.error(format!("expected a number, found `{word}`")))?;
+                    pool.doubles.push(value);
+                }
+                "string" => {
+                    args.position(keyword, pool.strings.len() + 1)?;
+                    pool.strings.push(args.string()?);
+                }
+                "namespace" => {
+                    args.position(keyword, pool.namespaces.len() + 1)?;
+                    let namespace = match args.word("a namespace kind")? {
+                        "namespace" => Namespace::Namespace(args.index()?),
+                        "package" => Namespace::Package(args.index()?),
+                        "package_internal" => Namespace::PackageInternal(args.index()?),
+                        "protected" => Namespace::Protected(args.index()?),
+                        "explicit" => Namespace::Explicit(args.index()?),
+                        "static_protected" => Namespace::StaticProtected(args.index()?),
+                        "private" => Namespace::Private(args.index()?),
+                        other => {
+                            return Err(args.error(format!("unknown namespace kind `{other}`")))
+                        }
+                    };
+                    pool.namespaces.push(namespace);
+                }
+                "namespace_set" => {
+                    args.position(keyword, pool.namespace_sets.len() + 1)?;
+                    let mut namespace_set = vec![];
+                    while !args.is_empty() {
+                        namespace_set.push(args.index()?);
+                    }
+                    pool.namespace_sets.push(namespace_set);
+                }
+                "multiname" => {
+                    args.position(keyword, pool.multinames.len() + 1)?;
+                    let multiname = match args.word("a multiname kind")? {
+                        "qname" => Multiname::QName {
+                            namespace: args.index()?,
+                            name: args.index()?,
+                        },
+                        "qnamea" => Multiname::QNameA {
+                            namespace: args.index()?,
+                            name: args.index()?,
+                        },
+                        "rtqname" => Multiname::RTQName {
+                            name: args.index()?,
+                        },
+                        "rtqnamea" => Multiname::RTQNameA {
+                            name: args.index()?,
+                        },
+                        "rtqnamel" => Multiname::RTQNameL,
+                        "rtqnamela" => Multiname::RTQNameLA,
+                        "multiname" => Multiname::Multiname {
+                            name: args.index()?,
+                            namespace_set: args.index()?,
+                        },
+                        "multinamea" => Multiname::MultinameA {
+                            name: args.index()?,
+                            namespace_set: args.index()?,
+                        },
+                        "multinamel" => Multiname::MultinameL {
+                            namespace_set: args.index()?,
+                        },
+                        "multinamela" => Multiname::MultinameLA {
+                            namespace_set: args.index()?,
+                        },
+                        "typename" => {
+                            let base_type = args.index()?;
+                            let mut parameters = vec![];
+                            while !args.is_empty() {
+                                parameters.push(args.index()?);
+                            }
+                            Multiname::TypeName {
+                                base_type,
+                                parameters,
+                            }
+                        }
+                        other => {
+                            return Err(args.error(format!("unknown multiname kind `{other}`")))
+                        }
+                    };
+                    pool.multinames.push(multiname);
+                }
+                "method" => {
+                    args.position(keyword, self.abc.methods.len())?;
+                    let method = self.method(line, args)?;
+                    self.abc.methods.push(method);
+                    continue;
+                }
+                "metadata" => {
+                    args.position(keyword, self.abc.metadata.len())?;
+                    let name = args.keyed_index("name")?;
+                    args.finish()?;
+                    let mut items = vec![];
+                    for line in self.block(line, keyword)? {
+                        let mut args = line.args();
+                        args.keyword("item")?;
+                        items.push(MetadataItem {
+                            key: args.index()?,
+                            value: args.index()?,
+                        });
+                        args.finish()?;
+                    }
+                    self.abc.metadata.push(Metadata { name, items });
+                    continue;
+                }
+                "instance" => {
+                    args.position(keyword, self.abc.instances.len())?;
+                    let instance = self.instance(line, args)?;
+                    self.abc.instances.push(instance);
+                    continue;
+                }
+                "class" => {
+                    args.position(keyword, self.abc.classes.len())?;
+                    let init_method = args.keyed_index("init")?;
+                    args.finish()?;
+                    let traits = self.traits(line, keyword)?;
+                    self.abc.classes.push(Class {
+                        init_method,
+                        traits,
+                    });
+                    continue;
+                }
+                "script" => {
+                    args.position(keyword, self.abc.scripts.len())?;
+                    let init_method = args.keyed_index("init")?;
+                    args.finish()?;
+                    let traits = self.traits(line, keyword)?;
+                    self.abc.scripts.push(Script {
+                        init_method,
+                        traits,
+                    });
+                    continue;
+                }
+                "body" => {
+                    args.position(keyword, self.abc.method_bodies.len())?;
+                    let body = self.method_body(line, args)?;
+                    self.abc.method_bodies.push(body);
+                    body_lines.push(line.number);
+                    continue;
+                }
+                other => return Err(args.error(format!("unknown declaration `{other}`"))),
+            }
+            args.finish()?;
+        }
+
+        if self.abc.instances.len() != self.abc.classes.len() {
+            return Err(AssembleError {
+                line: self.lines.last().map_or(1, |line| line.number),
+                message: format!(
+                    "found {} instances but {} classes",
+                    self.abc.instances.len(),
+                    self.abc.classes.len()
+                ),
+            });
+        }
+
+        for (i, body) in self.abc.method_bodies.iter().enumerate() {
+            let error = |message: String| AssembleError {
+                line: body_lines[i],
+                message,
+            };
+            let index = body.method.0;
+            match self.abc.methods.get_mut(index as usize) {
+                Some(method) if method.body.is_none() => method.body = Some(Index::new(i as u32)),
+                Some(_) => return Err(error(format!("method #{index} already has a body"))),
+                None => return Err(error(format!("there is no method #{index}"))),
+            }
+        }
+
+        Ok(self.abc)
+    }
+
+    fn method(&mut self, line: &Line, mut args: Args) -> Result<Method> {
+        let name = args.keyed_index("name")?;
+        let return_type = args.keyed_index("return")?;
+        let mut flags = MethodFlags::empty();
+        while !args.is_empty() {
+            let word = args.word("a flag")?;
+            flags |= MethodFlags::from_name(&word.to_ascii_uppercase())
+                .ok_or_else(|| args.error(format!("unknown method flag `{word}`")))?;
+        }
+
+        let mut params = vec![];
+        for line in self.block(line, "method")? {
+            let mut args = line.args();
+            args.keyword("param")?;
+            let mut param = MethodParam {
+                kind: args.index()?,
+                name: None,
+                default_value: None,
+            };
+            if args.peek_word() == Some("name") {
+                param.name = Some(args.keyed_index("name")?);
+            }
+            if args.peek_word() == Some("default") {
+                args.pos += 1;
+                param.default_value = Some(args.default_value()?);
+            }
+            args.finish()?;
+            params.push(param);
+        }
+
+        Ok(Method {
+            name,
+            params,
+            return_type,
+            flags,
+            body: None,
+        })
+    }
+
+    fn instance(&mut self, line: &Line, mut args: Args) -> Result<Instance> {
+        let mut instance = Instance {
+            name: args.keyed_index("name")?,
+            super_name: args.keyed_index("super")?,
+            init_method: args.keyed_index("init")?,
+            is_sealed: false,
+            is_final: false,
+            is_interface: false,
+            protected_namespace: None,
+            interfaces: vec![],
+            traits: vec![],
+        };
+        if args.peek_word() == Some("protected_ns") {
+            instance.protected_namespace = Some(args.keyed_index("protected_ns")?);
+        }
+        while !args.is_empty() {
+            match args.word("a flag")? {
+                "sealed" => instance.is_sealed = true,
+                "final" => instance.is_final = true,
+                "interface" => instance.is_interface = true,
+                other => return Err(args.error(format!("unknown instance flag `{other}`"))),
+            }
+        }
+
+        for line in self.block(line, "instance")? {
+            let mut args = line.args();
+            match args.word("`interface` or `trait`")? {
+                "interface" => {
+                    instance.interfaces.push(args.index()?);
+                    args.finish()?;
+                }
+                "trait" => instance.traits.push(args.trait_()?),
+                other => return Err(args.error(format!("unexpected `{other}`"))),
+            }
+        }
+        Ok(instance)
+    }
+
+    fn traits(&mut self, line: &Line, kind: &str) -> Result<Vec<Trait>> {
+        self.block(line, kind)?
+            .iter()
+            .map(|line| {
+                let mut args = line.args();
+                args.keyword("trait")?;
+                args.trait_()
+            })
+            .collect()
+    }
+
+    fn method_body(&mut self, line: &Line, mut args: Args) -> Result<MethodBody> {
+        let mut body = MethodBody {
+            method: args.keyed_index("method")?,
+            max_stack: args.keyed_u30("max_stack")?,
+            num_locals: args.keyed_u30("locals")?,
+            init_scope_depth: args.keyed_u30("init_scope")?,
+            max_scope_depth: args.keyed_u30("max_scope")?,
+            code: vec![],
+            exceptions: vec![],
+            traits: vec![],
+        };
+        args.finish()?;
+
+        let lines = self.block(line, "body")?;
+        let code_start = lines
+            .iter()
+            .position(|line| matches!(&line.tokens[..], [Token::Word(word)] if word == "code"))
+            .unwrap_or(lines.len());
+
+        let mut exceptions = vec![];
+        for line in &lines[..code_start] {
+            let mut args = line.args();
+            match args.word("`trait` or `try`")? {
+                "trait" => body.traits.push(args.trait_()?),
+                "try" => {
+                    let from = args.word("a label")?;
+                    let to = args.word("a label")?;
+                    let target = args.word("a label")?;
+                    let type_name = args.keyed_index("type")?;
+                    let variable_name = args.keyed_index("name")?;
+                    args.finish()?;
+                    exceptions.push((line.number, [from, to, target], type_name, variable_name));
+                }
+                other => return Err(args.error(format!("unexpected `{other}`"))),
+            }
+        }
+
+        let labels = self.code(lines.get(code_start + 1..).unwrap_or(&[]), &mut body.code)?;
+        for (line, offsets, type_name, variable_name) in exceptions {
+            let [from_offset, to_offset, target_offset] = offsets.map(|offset| {
+                labels
+                    .get(offset)
+                    .map(|o| *o as i64)
+                    .or_else(|| parse_int(offset))
+            });
+            let resolve = |offset: Option<i64>, text: &str| {
+                offset
+                    .and_then(|offset| u32::try_from(offset).ok())
+                    .ok_or_else(|| AssembleError {
+                        line,
+                        message: format!("unknown label `{text}`"),
+                    })
+            };
+            body.exceptions.push(Exception {
+                from_offset: resolve(from_offset, offsets[0])?,
+                to_offset: resolve(to_offset, offsets[1])?,
+                target_offset: resolve(target_offset, offsets[2])?,
+                type_name,
+                variable_name,
+            });
+        }
+
+        Ok(body)
+    }
+
+    /// Encodes the instructions of a method body, returning the offsets of
+    /// its labels.
+    fn code(&self, lines: &'a [Line], code: &mut Vec<u8>) -> Result<HashMap<&'a str, usize>> {
+        struct Fixup<'b> {
+            at: usize,
+            base: usize,
+            label: &'b str,
+            line: usize,
+        }
+
+        fn write_u30(code: &mut Vec<u8>, mut n: u32) {
+            loop {
+                let byte = (n & 0x7f) as u8;
+                n >>= 7;
+                if n == 0 {
+                    code.push(byte);
+                    break;
+                }
+                code.push(byte | 0x80);
+            }
+        }
+
+        let mut labels = HashMap::new();
+        let mut fixups = vec![];
+        for line in lines {
+            let mut args = line.args();
+            let word = args.word("an instruction")?;
+            if let Some(label) = word.strip_suffix(':') {
+                if labels.insert(label, code.len()).is_some() {
+                    return Err(args.error(format!("duplicate label `{label}`")));
+                }
+                args.finish()?;
+                continue;
+            }
+            if word == "raw" {
+                while !args.is_empty() {
+                    code.push(args.int("a byte", 0, u8::MAX.into())? as u8);
+                }
+                continue;
+            }
+
+            let opcode = *self
+                .opcodes
+                .get(word)
+                .ok_or_else(|| args.error(format!("unknown instruction `{word}`")))?;
+            let start = code.len();
+            code.push(opcode as u8);
+            for operand in operands(opcode) {
+                match operand {
+                    Operand::Byte => code.push(args.int("a byte", 0, u8::MAX.into())? as u8),
+                    Operand::SignedByte => {
+                        code.push(args.int("a byte", i8::MIN.into(), u8::MAX.into())? as u8)
+                    }
+                    Operand::U30 => write_u30(code, args.u30()?),
+                    Operand::I30 => write_u30(
+                        code,
+                        args.int("a number", i32::MIN.into(), u32::MAX.into())? as u32,
+                    ),
+                    Operand::Branch | Operand::LookupSwitch => {
+                        let mut targets = vec![];
+                        while !args.is_empty() {
+                            targets.push(args.word("a label")?);
+                        }
+                        let expected = if *operand == Operand::Branch {
+                            1..=1
+                        } else {
+                            2..=usize::MAX
+                        };
+                        if !expected.contains(&targets.len()) {
+                            return Err(
+                                args.error(format!("wrong number of branch targets for `{word}`"))
+                            );
+                        }
+
+                        let mut offsets = vec![];
+                        for i in 0..targets.len() {
+                            if i == 1 {
+                                write_u30(code, targets.len() as u32 - 2);
+                            }
+                            offsets.push(code.len());
+                            code.extend_from_slice(&[0; 3]);
+                        }
+                        // Branches are relative to the end of the instruction,
+                        // and `lookupswitch` targets to its start.
+                        let base = if *operand == Operand::Branch {
+                            code.len()
+                        } else {
+                            start
+                        };
+                        for (at, label) in offsets.into_iter().zip(targets) {
+                            match parse_int(label) {
+                                Some(offset) => write_i24(code, at, offset)
+                                    .map_err(|message| args.error(message))?,
+                                None => fixups.push(Fixup {
+                                    at,
+                                    base,
+                                    label,
+                                    line: line.number,
+                                }),
+                            }
+                        }
+                    }
+                    _ => write_u30(code, args.index::<()>()?.0),
+                }
+            }
+            args.finish()?;
+        }
+
+        for fixup in fixups {
+            let error = |message: String| AssembleError {
+                line: fixup.line,
+                message,
+            };
+            let target = labels
+                .get(fixup.label)
+                .ok_or_else(|| error(format!("unknown label `{}`", fixup.label)))?;
+            write_i24(code, fixup.at, *target as i64 - fixup.base as i64).map_err(error)?;
+        }
+
+        Ok(labels)
+    }
+}
+
+fn write_i24(code: &mut [u8], at: usize, offset: i64) -> std::result::Result<(), String> {
+    if !(-(1 << 23)..1 << 23).contains(&offset) {
+        return Err(format!("branch offset {offset} is out of range"));
+    }
+    code[at..at + 3].copy_from_slice(&(offset as i32).to_le_bytes()[..3]);
+    Ok(())
+}
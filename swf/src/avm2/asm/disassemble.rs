@@ -0,0 +1,622 @@
+//! Writing an `AbcFile` in the text format.
+
+use super::{mnemonic, operands, quote, Operand};
+use crate::avm2::opcode::OpCode;
+use crate::avm2::read::Reader;
+use crate::avm2::types::*;
+use crate::extensions::ReadSwfExt;
+use num_traits::FromPrimitive;
+use std::collections::BTreeMap;
+
+/// Disassemble an ABC file to text that [`assemble`](super::assemble) can
+/// read back.
+pub fn disassemble(abc: &AbcFile) -> String {
+    let mut disassembler = Disassembler {
+        abc,
+        out: String::new(),
+    };
+    disassembler.abc_file();
+    disassembler.out
+}
+
+/// A decoded instruction.
+struct Instruction {
+    offset: usize,
+    end: usize,
+    opcode: OpCode,
+    /// The operand values, in the order given by `operands`. The `lookupswitch`
+    /// operand expands to its default offset followed by the case offsets.
+    args: Vec<i64>,
+}
+
+/// Decodes the instruction at `offset`, or returns `None` if it's unknown or
+/// truncated.
+fn decode(code: &[u8], offset: usize) -> Option<Instruction> {
+    fn read_i24(reader: &mut Reader) -> Option<i64> {
+        let bytes = reader.read_slice(3).ok()?;
+        Some(i64::from(
+            i32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) << 8 >> 8,
+        ))
+    }
+
+    let mut reader = Reader::new(&code[offset..]);
+    let opcode = OpCode::from_u8(reader.read_u8().ok()?)?;
+    let mut args = vec![];
+    for operand in operands(opcode) {
+        match operand {
+            Operand::Byte => args.push(i64::from(reader.read_u8().ok()?)),
+            Operand::SignedByte => args.push(i64::from(reader.read_u8().ok()? as i8)),
+            Operand::I30 => args.push(i64::from(reader.read_encoded_u32().ok()? as i32)),
+            Operand::Branch => args.push(read_i24(&mut reader)?),
+            Operand::LookupSwitch => {
+                args.push(read_i24(&mut reader)?);
+                let num_cases = reader.read_encoded_u32().ok()?;
+                for _ in 0..=num_cases {
+                    args.push(read_i24(&mut reader)?);
+                }
+            }
+            _ => args.push(i64::from(reader.read_encoded_u32().ok()?)),
+        }
+    }
+
+    Some(Instruction {
+        offset,
+        end: code.len() - reader.as_slice().len(),
+        opcode,
+        args,
+    })
+}
+
+struct Disassembler<'a> {
+    abc: &'a AbcFile,
+    out: String,
+}
+
+impl Disassembler<'_> {
+    fn line(&mut self, indent: usize, text: &str, comment: &str) {
+        for _ in 0..indent {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text);
+        if !comment.is_empty() {
+            self.out.push_str(" ; ");
+            self.out.push_str(comment);
+        }
+        self.out.push('\n');
+    }
+
+    fn abc_file(&mut self) {
+        let abc = self.abc;
+        self.line(
+            0,
+            &format!("version {} {}", abc.major_version, abc.minor_version),
+            "",
+        );
+
+        self.constant_pool();
+
+        for (i, method) in abc.methods.iter().enumerate() {
+            self.method(i, method);
+        }
+        for (i, metadata) in abc.metadata.iter().enumerate() {
+            self.out.push('\n');
+            self.line(0, &format!("metadata {i} name #{}", metadata.name.0), "");
+            for item in &metadata.items {
+                self.line(1, &format!("item #{} #{}", item.key.0, item.value.0), "");
+            }
+            self.line(0, "end", "");
+        }
+        for (i, instance) in abc.instances.iter().enumerate() {
+            self.instance(i, instance);
+        }
+        for (i, class) in abc.classes.iter().enumerate() {
+            self.out.push('\n');
+            self.line(0, &format!("class {i} init #{}", class.init_method.0), "");
+            self.traits(&class.traits);
+            self.line(0, "end", "");
+        }
+        for (i, script) in abc.scripts.iter().enumerate() {
+            self.out.push('\n');
+            self.line(0, &format!("script {i} init #{}", script.init_method.0), "");
+            self.traits(&script.traits);
+            self.line(0, "end", "");
+        }
+        for (i, body) in abc.method_bodies.iter().enumerate() {
+            self.method_body(i, body);
+        }
+    }
+
+    fn constant_pool(&mut self) {
+        let pool = &self.abc.constant_pool;
+        if pool.ints.len() + pool.uints.len() + pool.doubles.len() + pool.strings.len() > 0 {
+            self.out.push('\n');
+        }
+        for (i, value) in pool.ints.iter().enumerate() {
+            self.line(0, &format!("int {} {value}", i + 1), "");
+        }
+        for (i, value) in pool.uints.iter().enumerate() {
+            self.line(0, &format!("uint {} {value}", i + 1), "");
+        }
+        for (i, value) in pool.doubles.iter().enumerate() {
+            self.line(0, &format!("double {} {value:?}", i + 1), "");
+        }
+        for (i, value) in pool.strings.iter().enumerate() {
+            self.line(0, &format!("string {} {}", i + 1, quote(value)), "");
+        }
+
+        if !pool.namespaces.is_empty() || !pool.namespace_sets.is_empty() {
+            self.out.push('\n');
+        }
+        for (i, namespace) in pool.namespaces.iter().enumerate() {
+            let (kind, name) = namespace_kind(namespace);
+            let comment = self.string_comment(name.0);
+            self.line(
+                0,
+                &format!("namespace {} {kind} #{}", i + 1, name.0),
+                &comment,
+            );
+        }
+        for (i, namespace_set) in pool.namespace_sets.iter().enumerate() {
+            let mut text = format!("namespace_set {}", i + 1);
+            for namespace in namespace_set {
+                text.push_str(&format!(" #{}", namespace.0));
+            }
+            self.line(0, &text, "");
+        }
+
+        if !pool.multinames.is_empty() {
+            self.out.push('\n');
+        }
+        for (i, multiname) in pool.multinames.iter().enumerate() {
+            let text = match multiname {
+                Multiname::QName { namespace, name } => {
+                    format!("qname #{} #{}", namespace.0, name.0)
+                }
+                Multiname::QNameA { namespace, name } => {
+                    format!("qnamea #{} #{}", namespace.0, name.0)
+                }
+                Multiname::RTQName { name } => format!("rtqname #{}", name.0),
+                Multiname::RTQNameA { name } => format!("rtqnamea #{}", name.0),
+                Multiname::RTQNameL => "rtqnamel".to_string(),
+                Multiname::RTQNameLA => "rtqnamela".to_string(),
+                Multiname::Multiname {
+                    namespace_set,
+                    name,
+                } => format!("multiname #{} #{}", name.0, namespace_set.0),
+                Multiname::MultinameA {
+                    namespace_set,
+                    name,
+                } => format!("multinamea #{} #{}", name.0, namespace_set.0),
+                Multiname::MultinameL { namespace_set } => {
+                    format!("multinamel #{}", namespace_set.0)
+                }
+                Multiname::MultinameLA { namespace_set } => {
+                    format!("multinamela #{}", namespace_set.0)
+                }
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                } => {
+                    let mut text = format!("typename #{}", base_type.0);
+                    for parameter in parameters {
+                        text.push_str(&format!(" #{}", parameter.0));
+                    }
+                    text
+                }
+            };
+            let comment = self.multiname_name(i as u32 + 1, 0);
+            self.line(0, &format!("multiname {} {text}", i + 1), &comment);
+        }
+    }
+
+    fn method(&mut self, index: usize, method: &Method) {
+        self.out.push('\n');
+        let mut text = format!(
+            "method {index} name #{} return #{}",
+            method.name.0, method.return_type.0
+        );
+        for (name, _) in method.flags.iter_names() {
+            text.push(' ');
+            text.push_str(&name.to_ascii_lowercase());
+        }
+        let comment = self.string_comment(method.name.0);
+        self.line(0, &text, &comment);
+
+        for param in &method.params {
+            let mut text = format!("param #{}", param.kind.0);
+            if let Some(name) = param.name {
+                text.push_str(&format!(" name #{}", name.0));
+            }
+            if let Some(value) = &param.default_value {
+                text.push_str(&format!(" default {}", default_value(value)));
+            }
+            let comment = self.multiname_name(param.kind.0, 0);
+            self.line(1, &text, &comment);
+        }
+        self.line(0, "end", "");
+    }
+
+    fn instance(&mut self, index: usize, instance: &Instance) {
+        self.out.push('\n');
+        let mut text = format!(
+            "instance {index} name #{} super #{} init #{}",
+            instance.name.0, instance.super_name.0, instance.init_method.0
+        );
+        if let Some(namespace) = instance.protected_namespace {
+            text.push_str(&format!(" protected_ns #{}", namespace.0));
+        }
+        if instance.is_sealed {
+            text.push_str(" sealed");
+        }
+        if instance.is_final {
+            text.push_str(" final");
+        }
+        if instance.is_interface {
+            text.push_str(" interface");
+        }
+        let comment = self.multiname_name(instance.name.0, 0);
+        self.line(0, &text, &comment);
+
+        for interface in &instance.interfaces {
+            let comment = self.multiname_name(interface.0, 0);
+            self.line(1, &format!("interface #{}", interface.0), &comment);
+        }
+        self.traits(&instance.traits);
+        self.line(0, "end", "");
+    }
+
+    fn traits(&mut self, traits: &[Trait]) {
+        for t in traits {
+            let mut text = match &t.kind {
+                TraitKind::Slot {
+                    slot_id,
+                    type_name,
+                    value,
+                }
+                | TraitKind::Const {
+                    slot_id,
+                    type_name,
+                    value,
+                } => {
+                    let kind = if matches!(t.kind, TraitKind::Slot { .. }) {
+                        "slot"
+                    } else {
+                        "const"
+                    };
+                    let mut text = format!(
+                        "trait {kind} #{} id {slot_id} type #{}",
+                        t.name.0, type_name.0
+                    );
+                    if let Some(value) = value {
+                        text.push_str(&format!(" value {}", default_value(value)));
+                    }
+                    text
+                }
+                TraitKind::Method { disp_id, method } => {
+                    format!(
+                        "trait method #{} id {disp_id} method #{}",
+                        t.name.0, method.0
+                    )
+                }
+                TraitKind::Getter { disp_id, method } => {
+                    format!(
+                        "trait getter #{} id {disp_id} method #{}",
+                        t.name.0, method.0
+                    )
+                }
+                TraitKind::Setter { disp_id, method } => {
+                    format!(
+                        "trait setter #{} id {disp_id} method #{}",
+                        t.name.0, method.0
+                    )
+                }
+                TraitKind::Class { slot_id, class } => {
+                    format!("trait class #{} id {slot_id} class #{}", t.name.0, class.0)
+                }
+                TraitKind::Function { slot_id, function } => format!(
+                    "trait function #{} id {slot_id} method #{}",
+                    t.name.0, function.0
+                ),
+            };
+            if t.is_final {
+                text.push_str(" final");
+            }
+            if t.is_override {
+                text.push_str(" override");
+            }
+            if !t.metadata.is_empty() {
+                text.push_str(" metadata");
+                for metadata in &t.metadata {
+                    text.push_str(&format!(" #{}", metadata.0));
+                }
+            }
+            let comment = self.multiname_name(t.name.0, 0);
+            self.line(1, &text, &comment);
+        }
+    }
+
+    fn method_body(&mut self, index: usize, body: &MethodBody) {
+        self.out.push('\n');
+        let comment = self
+            .abc
+            .methods
+            .get(body.method.0 as usize)
+            .map(|method| self.string_comment(method.name.0))
+            .unwrap_or_default();
+        self.line(
+            0,
+            &format!(
+                "body {index} method #{} max_stack {} locals {} init_scope {} max_scope {}",
+                body.method.0,
+                body.max_stack,
+                body.num_locals,
+                body.init_scope_depth,
+                body.max_scope_depth
+            ),
+            &comment,
+        );
+        self.traits(&body.traits);
+
+        let code = &body.code[..];
+        let mut instructions = vec![];
+        let mut offset = 0;
+        while offset < code.len() {
+            match decode(code, offset) {
+                Some(instruction) => {
+                    offset = instruction.end;
+                    instructions.push(instruction);
+                }
+                None => break,
+            }
+        }
+        let raw_start = offset;
+
+        // Label every branch and exception target that falls on an
+        // instruction boundary; the rest are kept as plain offsets.
+        let mut targets = vec![];
+        for instruction in &instructions {
+            for (operand, base) in instruction_targets(instruction) {
+                targets.push(base as i64 + instruction.args[operand]);
+            }
+        }
+        for exception in &body.exceptions {
+            targets.push(exception.from_offset.into());
+            targets.push(exception.to_offset.into());
+            targets.push(exception.target_offset.into());
+        }
+        let is_boundary = |target: i64| {
+            target == raw_start as i64
+                || target == code.len() as i64
+                || instructions
+                    .binary_search_by_key(&target, |i| i.offset as i64)
+                    .is_ok()
+        };
+        let mut labels: BTreeMap<i64, String> = targets
+            .into_iter()
+            .filter(|target| is_boundary(*target))
+            .map(|target| (target, String::new()))
+            .collect();
+        for (i, label) in labels.values_mut().enumerate() {
+            *label = format!("L{i}");
+        }
+        let target = |target: i64, raw: i64| match labels.get(&target) {
+            Some(label) => label.clone(),
+            None => raw.to_string(),
+        };
+
+        for exception in &body.exceptions {
+            let from = exception.from_offset.into();
+            let to = exception.to_offset.into();
+            let handler = exception.target_offset.into();
+            let comment = self.multiname_name(exception.type_name.0, 0);
+            let text = format!(
+                "try {} {} {} type #{} name #{}",
+                target(from, from),
+                target(to, to),
+                target(handler, handler),
+                exception.type_name.0,
+                exception.variable_name.0
+            );
+            self.line(1, &text, &comment);
+        }
+
+        self.line(1, "code", "");
+        for instruction in &instructions {
+            if let Some(label) = labels.get(&(instruction.offset as i64)) {
+                self.line(1, &format!("{label}:"), "");
+            }
+
+            let mut text = mnemonic(instruction.opcode);
+            let mut comments = vec![];
+            let mut args = instruction.args.iter().copied();
+            for operand in operands(instruction.opcode) {
+                let mut arg = || args.next().unwrap_or_default();
+                let (value, comment) = match operand {
+                    Operand::Branch => {
+                        let value = arg();
+                        (target(instruction.end as i64 + value, value), None)
+                    }
+                    Operand::LookupSwitch => {
+                        let base = instruction.offset as i64;
+                        let targets: Vec<_> = instruction.args[..]
+                            .iter()
+                            .map(|value| target(base + value, *value))
+                            .collect();
+                        (targets.join(" "), None)
+                    }
+                    Operand::Byte | Operand::SignedByte | Operand::U30 | Operand::I30 => {
+                        (arg().to_string(), None)
+                    }
+                    _ => {
+                        let index = arg() as u32;
+                        (format!("#{index}"), self.operand_comment(*operand, index))
+                    }
+                };
+                text.push(' ');
+                text.push_str(&value);
+                comments.extend(comment);
+            }
+            self.line(2, &text, &comments.join(", "));
+        }
+
+        if let Some(label) = labels.get(&(raw_start as i64)) {
+            if raw_start < code.len() {
+                self.line(1, &format!("{label}:"), "");
+            }
+        }
+        for chunk in code[raw_start..].chunks(16) {
+            let bytes: Vec<_> = chunk.iter().map(|b| format!("0x{b:02x}")).collect();
+            self.line(2, &format!("raw {}", bytes.join(" ")), "");
+        }
+        if let Some(label) = labels.get(&(code.len() as i64)) {
+            self.line(1, &format!("{label}:"), "");
+        }
+        self.line(0, "end", "");
+    }
+
+    /// A readable form of a constant pool entry used by an instruction.
+    fn operand_comment(&self, operand: Operand, index: u32) -> Option<String> {
+        let pool = &self.abc.constant_pool;
+        let entry = index.checked_sub(1).map(|i| i as usize);
+        let comment = match operand {
+            Operand::Int => entry.and_then(|i| pool.ints.get(i)).map(i32::to_string),
+            Operand::Uint => entry.and_then(|i| pool.uints.get(i)).map(u32::to_string),
+            Operand::Double => entry
+                .and_then(|i| pool.doubles.get(i))
+                .map(|value| format!("{value:?}")),
+            Operand::String => entry.and_then(|i| pool.strings.get(i)).map(|s| quote(s)),
+            Operand::Namespace => entry.and_then(|i| pool.namespaces.get(i)).map(|ns| {
+                let (kind, name) = namespace_kind(ns);
+                format!("{kind} {}", self.string_comment(name.0))
+            }),
+            Operand::Multiname => Some(self.multiname_name(index, 0)),
+            _ => None,
+        };
+        comment.filter(|comment| !comment.is_empty())
+    }
+
+    fn string_comment(&self, index: u32) -> String {
+        match index.checked_sub(1) {
+            Some(i) => self
+                .abc
+                .constant_pool
+                .strings
+                .get(i as usize)
+                .map(|s| quote(s))
+                .unwrap_or_default(),
+            None => String::new(),
+        }
+    }
+
+    /// A readable form of a multiname, such as `flash.display::Sprite` or
+    /// `Vector.<int>`.
+    fn multiname_name(&self, index: u32, depth: usize) -> String {
+        let pool = &self.abc.constant_pool;
+        let string = |index: &Index<String>| {
+            index
+                .0
+                .checked_sub(1)
+                .and_then(|i| pool.strings.get(i as usize))
+                .map(|s| {
+                    let quoted = quote(s);
+                    quoted[1..quoted.len() - 1].to_string()
+                })
+                .unwrap_or_else(|| "*".to_string())
+        };
+
+        let Some(i) = index.checked_sub(1) else {
+            return String::new();
+        };
+        let Some(multiname) = pool.multinames.get(i as usize) else {
+            return String::new();
+        };
+        match multiname {
+            Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                let prefix = if matches!(multiname, Multiname::QNameA { .. }) {
+                    "@"
+                } else {
+                    ""
+                };
+                let package = namespace
+                    .0
+                    .checked_sub(1)
+                    .and_then(|i| pool.namespaces.get(i as usize))
+                    .map(|ns| string(namespace_kind(ns).1))
+                    .filter(|package| !package.is_empty());
+                match package {
+                    Some(package) => format!("{prefix}{package}::{}", string(name)),
+                    None => format!("{prefix}{}", string(name)),
+                }
+            }
+            Multiname::RTQName { name } | Multiname::Multiname { name, .. } => string(name),
+            Multiname::RTQNameA { name } | Multiname::MultinameA { name, .. } => {
+                format!("@{}", string(name))
+            }
+            Multiname::RTQNameL
+            | Multiname::RTQNameLA
+            | Multiname::MultinameL { .. }
+            | Multiname::MultinameLA { .. } => "[runtime]".to_string(),
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                if depth > 8 {
+                    return "...".to_string();
+                }
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|p| self.multiname_name(p.0, depth + 1))
+                    .collect();
+                format!(
+                    "{}.<{}>",
+                    self.multiname_name(base_type.0, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// The operand indices of an instruction that hold branch targets, with the
+/// offset they're relative to.
+fn instruction_targets(instruction: &Instruction) -> Vec<(usize, usize)> {
+    match operands(instruction.opcode) {
+        [Operand::Branch] => vec![(0, instruction.end)],
+        [Operand::LookupSwitch] => (0..instruction.args.len())
+            .map(|i| (i, instruction.offset))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn namespace_kind(namespace: &Namespace) -> (&'static str, &Index<String>) {
+    match namespace {
+        Namespace::Namespace(name) => ("namespace", name),
+        Namespace::Package(name) => ("package", name),
+        Namespace::PackageInternal(name) => ("package_internal", name),
+        Namespace::Protected(name) => ("protected", name),
+        Namespace::Explicit(name) => ("explicit", name),
+        Namespace::StaticProtected(name) => ("static_protected", name),
+        Namespace::Private(name) => ("private", name),
+    }
+}
+
+fn default_value(value: &DefaultValue) -> String {
+    match value {
+        DefaultValue::Int(i) => format!("int #{}", i.0),
+        DefaultValue::Uint(i) => format!("uint #{}", i.0),
+        DefaultValue::Double(i) => format!("double #{}", i.0),
+        DefaultValue::String(i) => format!("string #{}", i.0),
+        DefaultValue::True => "true".to_string(),
+        DefaultValue::False => "false".to_string(),
+        DefaultValue::Null => "null".to_string(),
+        DefaultValue::Undefined => "undefined".to_string(),
+        DefaultValue::Namespace(i) => format!("namespace #{}", i.0),
+        DefaultValue::Package(i) => format!("package #{}", i.0),
+        DefaultValue::PackageInternal(i) => format!("package_internal #{}", i.0),
+        DefaultValue::Protected(i) => format!("protected #{}", i.0),
+        DefaultValue::Explicit(i) => format!("explicit #{}", i.0),
+        DefaultValue::StaticProtected(i) => format!("static_protected #{}", i.0),
+        DefaultValue::Private(i) => format!("private #{}", i.0),
+    }
+}
@@ -19,6 +19,8 @@ pub mod error;
 // TODO: Make this private?
 pub mod extensions;
 pub mod read;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod string;
 mod tag_code;
 mod types;
@@ -29,6 +31,8 @@ mod test_data;
 
 /// Re-exports
 pub use read::{decompress_swf, parse_swf};
+#[cfg(feature = "serde")]
+pub use serde_impl::{DeserializeError, SerdeBuffer};
 pub use string::*;
 pub use tag_code::TagCode;
 pub use types::*;
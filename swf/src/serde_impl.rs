@@ -0,0 +1,641 @@
+//! Serde support for the SWF types, enabled by the `serde` feature.
+//!
+//! Tags borrow their strings and binary data from the SWF they were parsed
+//! from. When deserializing, they borrow them from a [`SerdeBuffer`] instead,
+//! which owns the decoded data for as long as the tags are used.
+//!
+//! Strings are written as text when they're valid UTF-8, and as a list of
+//! bytes otherwise. Binary data is written as a hex string.
+
+use crate::string::SwfStr;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::fmt;
+
+/// The name of the newtype struct that SWF strings are deserialized as.
+///
+/// This lets a [`SerdeBuffer`] hand out strings given as a list of bytes.
+const STR_NAME: &str = "$swf::SwfStr";
+
+/// The name of the newtype struct that binary data is deserialized as.
+///
+/// This lets a [`SerdeBuffer`] hand out decoded hex strings.
+const BYTES_NAME: &str = "$swf::Bytes";
+
+const NOT_BORROWED: &str =
+    "SWF strings and binary data can only be borrowed, deserialize them from a `SerdeBuffer`";
+
+impl Serialize for SwfStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(self.as_bytes()) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => self.as_bytes().serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a SwfStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SwfStrVisitor;
+
+        impl<'de> Visitor<'de> for SwfStrVisitor {
+            type Value = &'de SwfStr;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string or a list of bytes")
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(SwfStr::from_bytes(v.as_bytes()))
+            }
+
+            fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+                Ok(SwfStr::from_bytes(v))
+            }
+
+            fn visit_str<E: de::Error>(self, _v: &str) -> Result<Self::Value, E> {
+                Err(E::custom(NOT_BORROWED))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, _seq: A) -> Result<Self::Value, A::Error> {
+                Err(de::Error::custom(NOT_BORROWED))
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(STR_NAME, SwfStrVisitor)
+    }
+}
+
+/// Byte data that can be created from decoded binary data.
+pub(crate) trait FromBytes<'a>: Sized {
+    fn from_bytes<E: de::Error>(bytes: Cow<'a, [u8]>) -> Result<Self, E>;
+}
+
+impl<'a> FromBytes<'a> for &'a [u8] {
+    fn from_bytes<E: de::Error>(bytes: Cow<'a, [u8]>) -> Result<Self, E> {
+        match bytes {
+            Cow::Borrowed(bytes) => Ok(bytes),
+            Cow::Owned(_) => Err(E::custom(NOT_BORROWED)),
+        }
+    }
+}
+
+impl<'a> FromBytes<'a> for Cow<'a, [u8]> {
+    fn from_bytes<E: de::Error>(bytes: Cow<'a, [u8]>) -> Result<Self, E> {
+        Ok(bytes)
+    }
+}
+
+struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = Cow<'de, [u8]>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        decode_hex(v).map(Cow::Owned)
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(DIGITS[usize::from(byte >> 4)].into());
+        hex.push(DIGITS[usize::from(byte & 0xf)].into());
+    }
+    hex
+}
+
+fn decode_hex<E: de::Error>(hex: &str) -> Result<Vec<u8>, E> {
+    let digit = |c: u8| {
+        char::from(c)
+            .to_digit(16)
+            .ok_or_else(|| E::custom(format!("invalid hex digit {:?}", char::from(c))))
+    };
+    if !hex.len().is_multiple_of(2) {
+        return Err(E::custom("hex string has an odd length"));
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8))
+        .collect()
+}
+
+/// Serializes byte data as a hex string.
+pub(crate) mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &impl AsRef<[u8]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_hex(bytes.as_ref()))
+    }
+
+    pub fn deserialize<'de: 'a, 'a, D: Deserializer<'de>, T: FromBytes<'a>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let bytes = deserializer.deserialize_newtype_struct(BYTES_NAME, HexVisitor)?;
+        T::from_bytes(bytes)
+    }
+}
+
+/// Serializes optional byte data as a hex string or `null`.
+pub(crate) mod option_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<impl AsRef<[u8]>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => serializer.serialize_some(&encode_hex(bytes.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de: 'a, 'a, D: Deserializer<'de>, T: FromBytes<'a>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        struct OptionVisitor;
+
+        impl<'de> Visitor<'de> for OptionVisitor {
+            type Value = Option<Cow<'de, [u8]>>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a hex string or null")
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                deserializer
+                    .deserialize_newtype_struct(BYTES_NAME, HexVisitor)
+                    .map(Some)
+            }
+        }
+
+        match deserializer.deserialize_option(OptionVisitor)? {
+            Some(bytes) => T::from_bytes(bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Serialized SWF data, such as a dump of SWF tags, decoded from any
+/// self-describing format.
+///
+/// SWF types borrow their strings and binary data from the buffer when
+/// they're deserialized from it with [`SerdeBuffer::deserialize`].
+pub struct SerdeBuffer(Node);
+
+impl SerdeBuffer {
+    /// Deserializes a value borrowing its strings and binary data from this buffer.
+    pub fn deserialize<'a, T: Deserialize<'a>>(&'a self) -> Result<T, DeserializeError> {
+        T::deserialize(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeBuffer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Node::deserialize(deserializer).map(SerdeBuffer)
+    }
+}
+
+/// An error that occurred while deserializing from a [`SerdeBuffer`].
+#[derive(Debug)]
+pub struct DeserializeError(String);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// A value in a [`SerdeBuffer`].
+///
+/// Strings and lists keep the binary data decoded from them, so that it can be
+/// borrowed by the deserialized values.
+enum Node {
+    Unit,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Str(String, OnceCell<Vec<u8>>),
+    Seq(Vec<Node>, OnceCell<Vec<u8>>),
+    Map(Vec<(Node, Node)>),
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NodeVisitor;
+
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = Node;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("any value")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<Node, E> {
+                Ok(Node::Bool(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Node, E> {
+                Ok(Node::I64(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Node, E> {
+                Ok(Node::U64(v))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Node, E> {
+                Ok(Node::F64(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Node, E> {
+                Ok(Node::Str(v.to_string(), OnceCell::new()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Node, E> {
+                Ok(Node::Str(v, OnceCell::new()))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Node, E> {
+                let items = v.iter().map(|byte| Node::U64(u64::from(*byte))).collect();
+                Ok(Node::Seq(items, OnceCell::new()))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Node, E> {
+                Ok(Node::Unit)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Node, E> {
+                Ok(Node::Unit)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+                Node::deserialize(deserializer)
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Node, D::Error> {
+                Node::deserialize(deserializer)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+                let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Node::Seq(items, OnceCell::new()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Node::Map(entries))
+            }
+
+            // Formats such as YAML report enums on their own,
+            // they're kept the same way as in JSON: a map with a single entry.
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Node, A::Error> {
+                let (variant, value) = data.variant::<String>()?;
+                let value = value.newtype_variant()?;
+                Ok(Node::Map(vec![(
+                    Node::Str(variant, OnceCell::new()),
+                    value,
+                )]))
+            }
+        }
+
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+impl Node {
+    fn unexpected(&self) -> de::Unexpected<'_> {
+        match self {
+            Node::Unit => de::Unexpected::Unit,
+            Node::Bool(v) => de::Unexpected::Bool(*v),
+            Node::U64(v) => de::Unexpected::Unsigned(*v),
+            Node::I64(v) => de::Unexpected::Signed(*v),
+            Node::F64(v) => de::Unexpected::Float(*v),
+            Node::Str(v, _) => de::Unexpected::Str(v),
+            Node::Seq(..) => de::Unexpected::Seq,
+            Node::Map(_) => de::Unexpected::Map,
+        }
+    }
+
+    /// The binary data of a string given as a list of bytes.
+    fn str_bytes(&self) -> Result<&[u8], DeserializeError> {
+        let Node::Seq(items, bytes) = self else {
+            return Err(de::Error::invalid_type(
+                self.unexpected(),
+                &"a list of bytes",
+            ));
+        };
+        if let Some(bytes) = bytes.get() {
+            return Ok(bytes);
+        }
+
+        let decoded = items
+            .iter()
+            .map(|item| match item {
+                Node::U64(byte) => u8::try_from(*byte)
+                    .map_err(|_| de::Error::invalid_value(item.unexpected(), &"a byte")),
+                _ => Err(de::Error::invalid_type(item.unexpected(), &"a byte")),
+            })
+            .collect::<Result<_, DeserializeError>>()?;
+        Ok(bytes.get_or_init(|| decoded))
+    }
+
+    /// The binary data of a hex string.
+    fn hex_bytes(&self) -> Result<&[u8], DeserializeError> {
+        let Node::Str(hex, bytes) = self else {
+            return Err(de::Error::invalid_type(self.unexpected(), &"a hex string"));
+        };
+        if let Some(bytes) = bytes.get() {
+            return Ok(bytes);
+        }
+
+        let decoded = decode_hex(hex)?;
+        Ok(bytes.get_or_init(|| decoded))
+    }
+}
+
+impl<'de> Deserializer<'de> for &'de Node {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Unit => visitor.visit_unit(),
+            Node::Bool(v) => visitor.visit_bool(*v),
+            Node::U64(v) => visitor.visit_u64(*v),
+            Node::I64(v) => visitor.visit_i64(*v),
+            Node::F64(v) => visitor.visit_f64(*v),
+            Node::Str(v, _) => visitor.visit_borrowed_str(v),
+            Node::Seq(items, _) => visitor.visit_seq(NodeSeq(items.iter())),
+            Node::Map(entries) => visitor.visit_map(NodeMap {
+                entries: entries.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Unit => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match (name, self) {
+            (STR_NAME, Node::Seq(..)) => visitor.visit_borrowed_bytes(self.str_bytes()?),
+            (BYTES_NAME, _) => visitor.visit_borrowed_bytes(self.hex_bytes()?),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Str(..) => visitor.visit_enum(NodeEnum {
+                variant: self,
+                value: None,
+            }),
+            Node::Map(entries) if entries.len() == 1 => visitor.visit_enum(NodeEnum {
+                variant: &entries[0].0,
+                value: Some(&entries[0].1),
+            }),
+            _ => Err(de::Error::invalid_type(self.unexpected(), &"an enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct NodeSeq<'de>(std::slice::Iter<'de, Node>);
+
+impl<'de> SeqAccess<'de> for NodeSeq<'de> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0.next().map(|item| seed.deserialize(item)).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct NodeMap<'de> {
+    entries: std::slice::Iter<'de, (Node, Node)>,
+    value: Option<&'de Node>,
+}
+
+impl<'de> MapAccess<'de> for NodeMap<'de> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct NodeEnum<'de> {
+    variant: &'de Node,
+    value: Option<&'de Node>,
+}
+
+impl<'de> EnumAccess<'de> for NodeEnum<'de> {
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Self::Error> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for NodeEnum<'de> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            None | Some(Node::Unit) => Ok(()),
+            Some(value) => Err(de::Error::invalid_type(
+                value.unexpected(),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.value.unwrap_or(&Node::Unit))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.unwrap_or(&Node::Unit).deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.unwrap_or(&Node::Unit).deserialize_any(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SerdeBuffer;
+    use crate::test_data;
+    use crate::types::Tag;
+
+    fn from_json(json: &str) -> SerdeBuffer {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn round_trip_tags() {
+        for (swf_version, expected_tag, _) in test_data::tag_tests() {
+            let json = serde_json::to_string(&expected_tag).unwrap();
+            let buffer = from_json(&json);
+            let tag: Tag = buffer.deserialize().unwrap();
+            assert_eq!(
+                tag, expected_tag,
+                "Serde round trip failed for SWF version {swf_version}:\n{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn strings_and_bytes() {
+        let tag = Tag::DoAbc(b"\x10\xff");
+        let json = serde_json::to_string(&tag).unwrap();
+        assert_eq!(json, r#"{"DoAbc":"10ff"}"#);
+        assert_eq!(from_json(&json).deserialize::<Tag>().unwrap(), tag);
+
+        let tag = Tag::FrameLabel(crate::types::FrameLabel {
+            label: crate::SwfStr::from_bytes(b"caf\xe9"),
+            is_anchor: false,
+        });
+        let json = serde_json::to_string(&tag).unwrap();
+        assert_eq!(
+            json,
+            r#"{"FrameLabel":{"label":[99,97,102,233],"is_anchor":false}}"#
+        );
+        assert_eq!(from_json(&json).deserialize::<Tag>().unwrap(), tag);
+    }
+
+    #[test]
+    fn data_is_borrowed() {
+        // Strings without escapes can be borrowed from the input directly.
+        let json = r#"{"FrameLabel":{"label":"intro","is_anchor":true}}"#;
+        let tag: Tag = serde_json::from_str(json).unwrap();
+        assert!(matches!(tag, Tag::FrameLabel(label) if label.label.as_bytes() == b"intro"));
+
+        // Decoded data has to be owned by a buffer.
+        assert!(serde_json::from_str::<Tag>(r#"{"DoAbc":"10ff"}"#).is_err());
+    }
+}
@@ -66,6 +66,7 @@ pub struct SwfBuf {
 ///
 /// [SWF19 p.27](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=27)
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     pub compression: Compression,
     pub version: u8,
@@ -234,6 +235,7 @@ impl HeaderExt {
 /// The vast majority of SWFs will use zlib compression.
 /// [SWF19 p.27](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=27)
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Compression {
     None,
     Zlib,
@@ -241,6 +243,7 @@ pub enum Compression {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Language {
     Unknown = 0,
     Latin = 1,
@@ -261,6 +264,8 @@ bitflags! {
     ///
     /// [SWF19 pp.57-58 ClipEvent](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=47)
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct FileAttributes: u8 {
         /// Whether this SWF requests hardware acceleration to blit to the screen.
         const USE_DIRECT_BLIT = 1 << 6;
@@ -290,18 +295,24 @@ impl Default for FileAttributes {
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct FrameLabel<'a> {
     pub label: &'a SwfStr,
     pub is_anchor: bool,
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct DefineSceneAndFrameLabelData<'a> {
     pub scenes: Vec<FrameLabelData<'a>>,
     pub frame_labels: Vec<FrameLabelData<'a>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct FrameLabelData<'a> {
     pub frame_num: u32,
     pub label: &'a SwfStr,
@@ -311,6 +322,8 @@ pub type Depth = u16;
 pub type CharacterId = u16;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct PlaceObject<'a> {
     pub version: u8,
     pub action: PlaceObjectAction,
@@ -328,6 +341,7 @@ pub struct PlaceObject<'a> {
     pub has_image: bool,
     pub is_bitmap_cached: Option<bool>,
     pub is_visible: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::option_bytes"))]
     pub amf_data: Option<&'a [u8]>,
 }
 
@@ -354,6 +368,7 @@ bitflags! {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlaceObjectAction {
     Place(CharacterId),
     Modify,
@@ -361,6 +376,7 @@ pub enum PlaceObjectAction {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Filter {
     DropShadowFilter(Box<DropShadowFilter>),
     BlurFilter(Box<BlurFilter>),
@@ -373,6 +389,7 @@ pub enum Filter {
 }
 
 #[derive(Default, Clone, Copy, Debug, Eq, FromPrimitive, PartialEq, Enum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendMode {
     #[default]
     Normal = 0,
@@ -452,9 +469,12 @@ impl FromStr for BlendMode {
 ///
 /// [SWF19 pp.37-38 ClipActionRecord](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=39)
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct ClipAction<'a> {
     pub events: ClipEventFlag,
     pub key_code: Option<KeyCode>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub action_data: &'a [u8],
 }
 
@@ -463,6 +483,8 @@ bitflags! {
     ///
     /// [SWF19 pp.48-50 ClipEvent](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=50)
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct ClipEventFlag: u32 {
         const LOAD            = 1 << 0;
         const ENTER_FRAME     = 1 << 1;
@@ -502,6 +524,8 @@ pub type KeyCode = u8;
 ///
 // [SWF19 p.29](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=29)
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub enum Tag<'a> {
     ExportAssets(ExportAssets<'a>),
     ScriptLimits {
@@ -516,10 +540,12 @@ pub enum Tag<'a> {
     DefineBinaryData(DefineBinaryData<'a>),
     DefineBits {
         id: CharacterId,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
         jpeg_data: &'a [u8],
     },
     DefineBitsJpeg2 {
         id: CharacterId,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
         jpeg_data: &'a [u8],
     },
     DefineBitsJpeg3(DefineBitsJpeg3<'a>),
@@ -554,15 +580,17 @@ pub enum Tag<'a> {
     DefineText(Box<Text>),
     DefineText2(Box<Text>),
     DefineVideoStream(DefineVideoStream),
-    DoAbc(&'a [u8]),
+    DoAbc(#[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))] &'a [u8]),
     DoAbc2(DoAbc2<'a>),
-    DoAction(DoAction<'a>),
+    DoAction(#[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))] DoAction<'a>),
     DoInitAction {
         id: CharacterId,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
         action_data: &'a [u8],
     },
     EnableDebugger(&'a SwfStr),
     EnableTelemetry {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
         password_hash: &'a [u8],
     },
     End,
@@ -571,14 +599,19 @@ pub enum Tag<'a> {
         url: &'a SwfStr,
         imports: Vec<ExportedAsset<'a>>,
     },
-    JpegTables(JpegTables<'a>),
+    JpegTables(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))] JpegTables<'a>,
+    ),
     NameCharacter(NameCharacter<'a>),
     SetBackgroundColor(SetBackgroundColor),
     SetTabIndex {
         depth: Depth,
         tab_index: u16,
     },
-    SoundStreamBlock(SoundStreamBlock<'a>),
+    SoundStreamBlock(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
+        SoundStreamBlock<'a>,
+    ),
     SoundStreamHead(Box<SoundStreamHead>),
     SoundStreamHead2(Box<SoundStreamHead>),
     StartSound(StartSound),
@@ -599,6 +632,7 @@ pub enum Tag<'a> {
 
     Unknown {
         tag_code: u16,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
         data: &'a [u8],
     },
 }
@@ -606,12 +640,15 @@ pub enum Tag<'a> {
 pub type ExportAssets<'a> = Vec<ExportedAsset<'a>>;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct ExportedAsset<'a> {
     pub id: CharacterId,
     pub name: &'a SwfStr,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RemoveObject {
     pub depth: Depth,
     pub character_id: Option<CharacterId>,
@@ -620,12 +657,15 @@ pub struct RemoveObject {
 pub type SetBackgroundColor = Color;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct SymbolClassLink<'a> {
     pub id: CharacterId,
     pub class_name: &'a SwfStr,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShapeContext {
     pub swf_version: u8,
     pub shape_version: u8,
@@ -634,6 +674,7 @@ pub struct ShapeContext {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shape {
     pub version: u8,
     pub id: CharacterId,
@@ -646,6 +687,8 @@ pub struct Shape {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct ShapeFlag: u8 {
         const HAS_SCALING_STROKES     = 1 << 0;
         const HAS_NON_SCALING_STROKES = 1 << 1;
@@ -654,14 +697,18 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct Sound<'a> {
     pub id: CharacterId,
     pub format: SoundFormat,
     pub num_samples: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoundInfo {
     pub event: SoundEvent,
     pub in_sample: Option<u32>,
@@ -671,6 +718,7 @@ pub struct SoundInfo {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SoundEvent {
     Event = 0,
     Start = 1,
@@ -689,6 +737,7 @@ impl SoundEvent {
 pub type SoundEnvelope = Vec<SoundEnvelopePoint>;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoundEnvelopePoint {
     pub sample: u32,
     pub left_volume: f32,
@@ -696,25 +745,31 @@ pub struct SoundEnvelopePoint {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StartSound {
     pub id: CharacterId,
     pub sound_info: Box<SoundInfo>,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct Sprite<'a> {
     pub id: CharacterId,
     pub num_frames: u16,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub tags: Vec<Tag<'a>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShapeStyles {
     pub fill_styles: Vec<FillStyle>,
     pub line_styles: Vec<LineStyle>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeRecord {
     StyleChange(Box<StyleChangeData>),
     StraightEdge {
@@ -728,6 +783,8 @@ pub enum ShapeRecord {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct ShapeRecordFlag: u8 {
         const MOVE_TO      = 1 << 0;
         const FILL_STYLE_0 = 1 << 1;
@@ -738,6 +795,7 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleChangeData {
     pub move_to: Option<Point<Twips>>,
     pub fill_style_0: Option<u32>,
@@ -747,6 +805,7 @@ pub struct StyleChangeData {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillStyle {
     Color(Color),
     LinearGradient(Gradient),
@@ -764,6 +823,7 @@ pub enum FillStyle {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gradient {
     pub matrix: Matrix,
     pub spread: GradientSpread,
@@ -772,6 +832,7 @@ pub struct Gradient {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq, Enum, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GradientSpread {
     Pad = 0,
     Reflect = 1,
@@ -790,6 +851,7 @@ impl GradientSpread {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GradientInterpolation {
     Rgb = 0,
     LinearRgb = 1,
@@ -807,12 +869,14 @@ impl GradientInterpolation {
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientRecord {
     pub ratio: u8,
     pub color: Color,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineStyle {
     pub(crate) width: Twips,
     pub(crate) fill_style: FillStyle,
@@ -967,6 +1031,8 @@ impl Default for LineStyle {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct LineStyleFlag: u16 {
         // First byte.
         const PIXEL_HINTING = 1 << 0;
@@ -995,6 +1061,7 @@ impl Default for LineStyleFlag {
 }
 
 #[derive(Default, Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineCapStyle {
     #[default]
     Round = 0,
@@ -1010,6 +1077,7 @@ impl LineCapStyle {
 }
 
 #[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineJoinStyle {
     #[default]
     Round,
@@ -1018,6 +1086,7 @@ pub enum LineJoinStyle {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AudioCompression {
     UncompressedUnknownEndian = 0,
     Adpcm = 1,
@@ -1037,6 +1106,7 @@ impl AudioCompression {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoundFormat {
     pub compression: AudioCompression,
     pub sample_rate: u16,
@@ -1045,6 +1115,7 @@ pub struct SoundFormat {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SoundStreamHead {
     pub stream_format: SoundFormat,
     pub playback_format: SoundFormat,
@@ -1055,14 +1126,18 @@ pub struct SoundStreamHead {
 pub type SoundStreamBlock<'a> = &'a [u8];
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct Button<'a> {
     pub id: CharacterId,
     pub is_track_as_menu: bool,
     pub records: Vec<ButtonRecord>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub actions: Vec<ButtonAction<'a>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonRecord {
     pub states: ButtonState,
     pub id: CharacterId,
@@ -1075,6 +1150,8 @@ pub struct ButtonRecord {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct ButtonState: u8 {
         const UP       = 1 << 0;
         const OVER     = 1 << 1;
@@ -1084,12 +1161,14 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonColorTransform {
     pub id: CharacterId,
     pub color_transforms: Vec<ColorTransform>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonSounds {
     pub id: CharacterId,
     pub over_to_up_sound: Option<ButtonSound>,
@@ -1101,8 +1180,11 @@ pub struct ButtonSounds {
 pub type ButtonSound = (CharacterId, SoundInfo);
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct ButtonAction<'a> {
     pub conditions: ButtonActionCondition,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub action_data: &'a [u8],
 }
 
@@ -1114,6 +1196,8 @@ impl ButtonAction<'_> {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct ButtonActionCondition: u16 {
         const IDLE_TO_OVER_UP       = 1 << 0;
         const OVER_UP_TO_IDLE       = 1 << 1;
@@ -1148,6 +1232,7 @@ impl ButtonActionCondition {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefineMorphShape {
     pub version: u8,
     pub id: CharacterId,
@@ -1158,6 +1243,8 @@ pub struct DefineMorphShape {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct DefineMorphShapeFlag: u8 {
         const HAS_SCALING_STROKES     = 1 << 0;
         const HAS_NON_SCALING_STROKES = 1 << 1;
@@ -1165,6 +1252,7 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MorphShape {
     pub shape_bounds: Rectangle<Twips>,
     pub edge_bounds: Rectangle<Twips>,
@@ -1174,12 +1262,15 @@ pub struct MorphShape {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontV1 {
     pub id: CharacterId,
     pub glyphs: Vec<Vec<ShapeRecord>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct Font<'a> {
     pub version: u8,
     pub id: CharacterId,
@@ -1192,6 +1283,8 @@ pub struct Font<'a> {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct FontFlag: u8 {
         const IS_BOLD = 1 << 0;
         const IS_ITALIC = 1 << 1;
@@ -1205,15 +1298,19 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct Font4<'a> {
     pub id: CharacterId,
     pub is_italic: bool,
     pub is_bold: bool,
     pub name: &'a SwfStr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::option_bytes"))]
     pub data: Option<&'a [u8]>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Glyph {
     pub shape_records: Vec<ShapeRecord>,
     pub code: u16,
@@ -1222,6 +1319,7 @@ pub struct Glyph {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontLayout {
     pub ascent: u16,
     pub descent: u16,
@@ -1230,6 +1328,7 @@ pub struct FontLayout {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KerningRecord {
     pub left_code: u16,
     pub right_code: u16,
@@ -1237,6 +1336,8 @@ pub struct KerningRecord {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct FontInfo<'a> {
     pub id: CharacterId,
     pub version: u8,
@@ -1248,6 +1349,8 @@ pub struct FontInfo<'a> {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct FontInfoFlag: u8 {
         const HAS_WIDE_CODES = 1 << 0;
         const IS_BOLD = 1 << 1;
@@ -1259,12 +1362,16 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct DefineBinaryData<'a> {
     pub id: CharacterId,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text {
     pub id: CharacterId,
     pub bounds: Rectangle<Twips>,
@@ -1273,6 +1380,7 @@ pub struct Text {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextRecord {
     pub font_id: Option<CharacterId>,
     pub color: Option<Color>,
@@ -1283,12 +1391,15 @@ pub struct TextRecord {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphEntry {
     pub index: u32,
     pub advance: i32,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct EditText<'a> {
     pub(crate) id: CharacterId,
     pub(crate) bounds: Rectangle<Twips>,
@@ -1602,6 +1713,8 @@ impl Default for EditText<'_> {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct EditTextFlag: u16 {
         const HAS_FONT = 1 << 0;
         const HAS_MAX_LENGTH = 1 << 1;
@@ -1624,6 +1737,7 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextLayout {
     pub align: TextAlign,
     pub left_margin: Twips,
@@ -1633,6 +1747,7 @@ pub struct TextLayout {
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextAlign {
     #[default]
     Left = 0,
@@ -1648,6 +1763,7 @@ impl TextAlign {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontAlignZone {
     // TODO(Herschel): Read these as f16s.
     pub left: i16,
@@ -1657,6 +1773,7 @@ pub struct FontAlignZone {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontThickness {
     Thin = 0,
     Medium = 1,
@@ -1670,6 +1787,7 @@ impl FontThickness {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CsmTextSettings {
     pub id: CharacterId,
     pub use_advanced_rendering: bool,
@@ -1679,6 +1797,7 @@ pub struct CsmTextSettings {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextGridFit {
     None = 0,
     Pixel = 1,
@@ -1692,16 +1811,20 @@ impl TextGridFit {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct DefineBitsLossless<'a> {
     pub version: u8,
     pub id: CharacterId,
     pub format: BitmapFormat,
     pub width: u16,
     pub height: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: Cow<'a, [u8]>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitmapFormat {
     ColorMap8 { num_colors: u8 },
     Rgb15,
@@ -1709,6 +1832,7 @@ pub enum BitmapFormat {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefineVideoStream {
     pub id: CharacterId,
     pub num_frames: u16,
@@ -1720,6 +1844,7 @@ pub struct DefineVideoStream {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VideoDeblocking {
     UseVideoPacketValue = 0,
     None = 1,
@@ -1736,6 +1861,7 @@ impl VideoDeblocking {
 }
 
 #[derive(Clone, Copy, Debug, Eq, FromPrimitive, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VideoCodec {
     None = 0,
     H263 = 2,
@@ -1753,30 +1879,42 @@ impl VideoCodec {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct VideoFrame<'a> {
     pub stream_id: CharacterId,
     pub frame_num: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct DefineBitsJpeg3<'a> {
     pub id: CharacterId,
     pub version: u8,
     pub deblocking: Fixed8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub alpha_data: &'a [u8],
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct DoAbc2<'a> {
     pub flags: DoAbc2Flag,
     pub name: &'a SwfStr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::bytes"))]
     pub data: &'a [u8],
 }
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct DoAbc2Flag: u32 {
         const LAZY_INITIALIZE = 1 << 0;
     }
@@ -1791,6 +1929,7 @@ pub type JpegTables<'a> = &'a [u8];
 /// Not documented in the SWF19 reference. Emitted by mxmlc.
 /// See <http://wahlers.com.br/claus/blog/undocumented-swf-tags-written-by-mxmlc/>
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProductInfo {
     pub product_id: u32,
     pub edition: u32,
@@ -1807,6 +1946,8 @@ pub type DebugId = [u8; 16];
 /// This seems to have no effect in the official Flash Player.
 /// Superseded by the PlaceObject2 tag.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'a")))]
 pub struct NameCharacter<'a> {
     pub id: CharacterId,
     pub name: &'a SwfStr,
@@ -195,6 +195,22 @@ macro_rules! define_fixed {
             }
         }
 
+        // Serialized as a float for readability; every fixed-point value is
+        // exactly representable as an `f64`.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $type_name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_f64(self.to_f64())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $type_name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                f64::deserialize(deserializer).map(Self::from_f64)
+            }
+        }
+
         impl Neg for $type_name {
             type Output = Self;
             #[inline]
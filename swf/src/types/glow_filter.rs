@@ -2,6 +2,7 @@ use crate::{BlurFilter, BlurFilterFlags, Color, Fixed16, Fixed8, Rectangle, Twip
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlowFilter {
     pub color: Color,
     pub blur_x: Fixed16,
@@ -52,6 +53,8 @@ impl GlowFilter {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct GlowFilterFlags: u8 {
         const INNER_GLOW       = 1 << 7;
         const KNOCKOUT         = 1 << 6;
@@ -2,6 +2,7 @@ use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord};
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientFilter {
     pub colors: Vec<GradientRecord>,
     pub blur_x: Fixed16,
@@ -50,6 +51,8 @@ impl GradientFilter {
 
 bitflags! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct GradientFilterFlags: u8 {
         const INNER_SHADOW     = 1 << 7;
         const KNOCKOUT         = 1 << 6;
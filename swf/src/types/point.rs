@@ -28,6 +28,7 @@ impl Coordinate for Twips {
 
 /// A 2D position defined by x and y coordinates.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point<T: Coordinate> {
     pub x: T,
     pub y: T,
@@ -145,6 +146,7 @@ impl<T: Coordinate> fmt::Display for Point<T> {
 
 /// A difference between two 2D points.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointDelta<T: Coordinate> {
     pub dx: T,
     pub dy: T,
@@ -0,0 +1,108 @@
+//! Benchmarks running AVM2 SWFs in a headless Ruffle instance.
+//!
+//! Every SWF in `benches/swfs/avm2` does its work in an `enterFrame` handler,
+//! and is measured both with and without AVM2 inline caches.
+//! Run with `cargo bench -p tests --bench avm2`.
+
+use libtest_mimic::{Arguments, Failed, Measurement, Trial};
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::{Player, PlayerBuilder};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Frames run before measuring, so that every method has been verified.
+const WARMUP_FRAMES: usize = 2;
+
+/// Frames measured for each benchmark.
+const MEASURED_FRAMES: usize = 20;
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let root = Path::new("benches/swfs/avm2");
+    let mut benches = Vec::new();
+    for entry in std::fs::read_dir(root).expect("Benchmark directory should exist") {
+        let path = entry
+            .expect("Benchmark directory should be readable")
+            .path();
+        let Some(name) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        let swf_path = path.join("test.swf");
+        if !swf_path.is_file() {
+            continue;
+        }
+
+        for inline_caches in [true, false] {
+            let swf_path = swf_path.clone();
+            let kind = if inline_caches {
+                "inline caches"
+            } else {
+                "no inline caches"
+            };
+            benches.push(
+                Trial::bench(format!("avm2/{name}"), move |test_mode| {
+                    run_bench(&swf_path, inline_caches, test_mode)
+                })
+                .with_kind(kind),
+            );
+        }
+    }
+
+    benches.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+
+    libtest_mimic::run(&args, benches).exit()
+}
+
+fn run_bench(
+    swf_path: &Path,
+    inline_caches: bool,
+    test_mode: bool,
+) -> Result<Option<Measurement>, Failed> {
+    let movie = SwfMovie::from_path(swf_path, None).map_err(|e| e.to_string())?;
+    let player = PlayerBuilder::new()
+        .with_movie(movie)
+        .with_autoplay(true)
+        .with_avm2_inline_caches_enabled(inline_caches)
+        .build();
+
+    while !player
+        .lock()
+        .unwrap()
+        .preload(&mut ExecutionLimit::exhausted())
+    {}
+
+    if test_mode {
+        run_frame(&player);
+        return Ok(None);
+    }
+
+    for _ in 0..WARMUP_FRAMES {
+        run_frame(&player);
+    }
+
+    let mut samples = Vec::with_capacity(MEASURED_FRAMES);
+    for _ in 0..MEASURED_FRAMES {
+        let start = Instant::now();
+        run_frame(&player);
+        samples.push(start.elapsed().as_nanos() as u64);
+    }
+
+    let avg = samples.iter().sum::<u64>() / samples.len() as u64;
+    let variance = samples
+        .iter()
+        .map(|sample| sample.abs_diff(avg).pow(2))
+        .sum::<u64>()
+        / samples.len() as u64;
+
+    Ok(Some(Measurement { avg, variance }))
+}
+
+fn run_frame(player: &Arc<Mutex<Player>>) {
+    player.lock().unwrap().run_frame();
+}
@@ -0,0 +1,28 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Reads and writes dynamic properties of plain objects, which
+	// are never traits and always need a full lookup.
+	public class Test extends Sprite {
+		private var points = [];
+
+		public function Test() {
+			for (var i = 0; i < 2000; i++) {
+				points.push({x: i, y: -i, hits: 0});
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event) {
+			for (var step = 0; step < 30; step++) {
+				for (var i = 0; i < points.length; i++) {
+					var point = points[i];
+					point.x = point.x + point.y * 0.01;
+					point.y = point.y - point.x * 0.01;
+					point.hits++;
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,46 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Moves untyped particles around, like a typical particle system
+	// that keeps its particles in an Array.
+	public class Test extends Sprite {
+		private var particles = [];
+
+		public function Test() {
+			for (var i = 0; i < 2000; i++) {
+				var p = new Particle();
+				p.x = i % 550;
+				p.y = i % 400;
+				p.vx = (i % 7) - 3;
+				p.vy = (i % 5) - 2;
+				particles.push(p);
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event) {
+			for (var step = 0; step < 50; step++) {
+				for (var i = 0; i < particles.length; i++) {
+					var p = particles[i];
+					p.vy += 0.1;
+					p.x += p.vx;
+					p.y += p.vy;
+					if (p.y > 400) {
+						p.y = 400;
+						p.vy = -p.vy * 0.8;
+					}
+					p.age++;
+				}
+			}
+		}
+	}
+}
+
+class Particle {
+	public var x = 0;
+	public var y = 0;
+	public var vx = 0;
+	public var vy = 0;
+	public var age = 0;
+}
@@ -0,0 +1,77 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Accesses the same names on objects of a few different classes
+	// from a single call site.
+	public class Test extends Sprite {
+		private var shapes = [];
+
+		public function Test() {
+			for (var i = 0; i < 1000; i++) {
+				switch (i % 3) {
+					case 0:
+						shapes.push(new Circle(i % 10 + 1));
+						break;
+					case 1:
+						shapes.push(new Rect(i % 7 + 1, i % 5 + 1));
+						break;
+					default:
+						shapes.push(new Square(i % 9 + 1));
+				}
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event) {
+			var total = 0;
+			for (var step = 0; step < 50; step++) {
+				for (var i = 0; i < shapes.length; i++) {
+					var shape = shapes[i];
+					total += shape.area() * shape.scale;
+					shape.scale = (shape.scale + 1) % 4;
+				}
+			}
+		}
+	}
+}
+
+class Shape {
+	public var scale = 1;
+
+	public function area() {
+		return 0;
+	}
+}
+
+class Circle extends Shape {
+	public var radius;
+
+	public function Circle(radius) {
+		this.radius = radius;
+	}
+
+	override public function area() {
+		return 3.14159 * radius * radius;
+	}
+}
+
+class Rect extends Shape {
+	public var width;
+	public var height;
+
+	public function Rect(width, height) {
+		this.width = width;
+		this.height = height;
+	}
+
+	override public function area() {
+		return width * height;
+	}
+}
+
+class Square extends Rect {
+	public function Square(side) {
+		super(side, side);
+	}
+}
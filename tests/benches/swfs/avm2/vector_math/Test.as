@@ -0,0 +1,71 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Calls methods and reads fields through untyped references,
+	// like the vector math in physics engine ports.
+	public class Test extends Sprite {
+		private var bodies = [];
+
+		public function Test() {
+			for (var i = 0; i < 500; i++) {
+				bodies.push(new Body(new Vec2(i, i * 2), new Vec2(1, -1)));
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event) {
+			var gravity = new Vec2(0, 0.5);
+			for (var step = 0; step < 20; step++) {
+				for (var i = 0; i < bodies.length; i++) {
+					var body = bodies[i];
+					body.velocity.addScaled(gravity, 1 / 60);
+					body.position.addScaled(body.velocity, 1 / 60);
+					if (body.position.lengthSquared() > 1000000) {
+						body.position.set(0, 0);
+					}
+					body.energy = 0.5 * body.velocity.dot(body.velocity);
+				}
+			}
+		}
+	}
+}
+
+class Vec2 {
+	public var x;
+	public var y;
+
+	public function Vec2(x, y) {
+		this.x = x;
+		this.y = y;
+	}
+
+	public function set(x, y) {
+		this.x = x;
+		this.y = y;
+	}
+
+	public function addScaled(other, scale) {
+		this.x += other.x * scale;
+		this.y += other.y * scale;
+	}
+
+	public function dot(other) {
+		return this.x * other.x + this.y * other.y;
+	}
+
+	public function lengthSquared() {
+		return this.x * this.x + this.y * this.y;
+	}
+}
+
+class Body {
+	public var position;
+	public var velocity;
+	public var energy = 0;
+
+	public function Body(position, velocity) {
+		this.position = position;
+		this.velocity = velocity;
+	}
+}
@@ -2,8 +2,10 @@ mod audio;
 mod log;
 mod navigator;
 mod ui;
+mod worker;
 
 pub use audio::TestAudioBackend;
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
 pub use ui::TestUiBackend;
+pub use worker::TestWorkerBackend;
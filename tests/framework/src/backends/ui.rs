@@ -4,6 +4,7 @@ use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
     FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
 };
+use ruffle_core::swf::{Rectangle, Twips};
 use url::Url;
 
 /// A simulated file dialog response, for use in tests
@@ -99,6 +100,10 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
+    fn set_mouse_lock(&mut self, _locked: bool) -> bool {
+        true
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.clone()
     }
@@ -111,6 +116,10 @@ impl UiBackend for TestUiBackend {
         Ok(())
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     fn display_root_movie_download_failed_message(&self, _invalid_swf: bool) {}
 
     fn message(&self, _message: &str) {}
@@ -119,6 +128,10 @@ impl UiBackend for TestUiBackend {
 
     fn close_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, _enabled: bool) {}
+
+    fn set_ime_cursor_area(&mut self, _area: Rectangle<Twips>) {}
+
     fn language(&self) -> LanguageIdentifier {
         US_ENGLISH.clone()
     }
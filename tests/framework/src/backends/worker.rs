@@ -0,0 +1,19 @@
+use ruffle_core::backend::worker::{Job, WorkerBackend};
+use std::thread;
+
+/// Runs every job on a new thread.
+///
+/// The player always waits for results, so tests stay deterministic no matter
+/// when the threads finish.
+#[derive(Default)]
+pub struct TestWorkerBackend;
+
+impl WorkerBackend for TestWorkerBackend {
+    fn spawn(&self, job: Job) {
+        thread::spawn(job);
+    }
+
+    fn waits_for_results(&self) -> bool {
+        true
+    }
+}
@@ -1,4 +1,4 @@
-use crate::backends::TestAudioBackend;
+use crate::backends::{TestAudioBackend, TestWorkerBackend};
 use crate::environment::{Environment, RenderInterface};
 use crate::image_trigger::ImageTrigger;
 use crate::util::write_image;
@@ -6,6 +6,7 @@ use anyhow::{anyhow, Result};
 use approx::relative_eq;
 use image::ImageFormat;
 use regex::Regex;
+use ruffle_core::deterministic::DeterministicOptions;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{PlayerBuilder, PlayerRuntime, ViewportDimensions};
 use ruffle_render::backend::RenderBackend;
@@ -149,7 +150,11 @@ pub struct PlayerOptions {
     with_renderer: Option<RenderOptions>,
     with_audio: bool,
     with_video: bool,
+    with_workers: bool,
     runtime: PlayerRuntime,
+    timezone: Option<String>,
+    deterministic: Option<DeterministicTestOptions>,
+    record_input: bool,
 }
 
 impl PlayerOptions {
@@ -172,8 +177,30 @@ impl PlayerOptions {
             player_builder = player_builder.with_audio(TestAudioBackend::default());
         }
 
+        if self.with_workers {
+            player_builder = player_builder.with_workers(TestWorkerBackend);
+        }
+
         player_builder = player_builder.with_player_runtime(self.runtime);
 
+        if let Some(timezone) = &self.timezone {
+            let timezone = timezone
+                .parse()
+                .map_err(|e| anyhow!("Invalid timezone '{}': {}", timezone, e))?;
+            player_builder = player_builder.with_timezone(timezone);
+        }
+
+        if let Some(deterministic) = &self.deterministic {
+            player_builder = player_builder.with_deterministic_options(DeterministicOptions {
+                seed: deterministic.seed,
+                ..Default::default()
+            });
+        }
+
+        if self.record_input {
+            player_builder = player_builder.with_input_recording(true);
+        }
+
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
             {
@@ -236,6 +263,12 @@ impl PlayerOptions {
     }
 }
 
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeterministicTestOptions {
+    seed: u64,
+}
+
 #[derive(Deserialize, Default, Clone, Debug)]
 #[serde(default, deny_unknown_fields)]
 pub struct ImageComparison {
@@ -9,15 +9,11 @@ use anyhow::{anyhow, Error, Result};
 use image::ImageFormat;
 use pretty_assertions::Comparison;
 use ruffle_core::backend::navigator::NullExecutor;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
-use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
+use ruffle_core::input_log;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
-use ruffle_input_format::{
-    AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
-};
+use ruffle_input_format::{AutomatedEvent, InputInjector};
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
 use std::collections::HashMap;
@@ -54,6 +50,7 @@ impl TestRunner {
         test: &Test,
         movie: SwfMovie,
         injector: InputInjector,
+        input_replay: Option<InputInjector>,
         socket_events: Option<Vec<SocketEvent>>,
         renderer: Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)>,
         viewport_dimensions: ViewportDimensions,
@@ -94,6 +91,15 @@ impl TestRunner {
                 viewport_dimensions.scale_factor,
             );
 
+        #[cfg(feature = "jit")]
+        if test.force_jit {
+            builder = builder.with_avm2_jit_mode(ruffle_core::JitMode::Always);
+        }
+
+        if let Some(replay) = input_replay {
+            builder = builder.with_input_replay(replay);
+        }
+
         let render_interface = if let Some((interface, backend)) = renderer {
             builder = builder.with_boxed_renderer(backend);
             Some(interface)
@@ -203,96 +209,24 @@ impl TestRunner {
                 return;
             }
 
-            let handled = player.handle_event(match evt {
-                AutomatedEvent::MouseDown {
-                    pos, btn, index, ..
-                } => PlayerEvent::MouseDown {
-                    x: pos.0,
-                    y: pos.1,
-                    button: match btn {
-                        InputMouseButton::Left => RuffleMouseButton::Left,
-                        InputMouseButton::Middle => RuffleMouseButton::Middle,
-                        InputMouseButton::Right => RuffleMouseButton::Right,
-                    },
+            let event = match input_log::player_event(evt) {
+                Some(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button,
+                    index,
+                }) => PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button,
                     // None here means that the core will compute index automatically,
                     // however we do not want that in tests.
                     index: Some(index.unwrap_or_default()),
                 },
-                AutomatedEvent::MouseMove { pos } => PlayerEvent::MouseMove { x: pos.0, y: pos.1 },
-                AutomatedEvent::MouseUp { pos, btn } => PlayerEvent::MouseUp {
-                    x: pos.0,
-                    y: pos.1,
-                    button: match btn {
-                        InputMouseButton::Left => RuffleMouseButton::Left,
-                        InputMouseButton::Middle => RuffleMouseButton::Middle,
-                        InputMouseButton::Right => RuffleMouseButton::Right,
-                    },
-                },
-                AutomatedEvent::MouseWheel { lines, pixels } => PlayerEvent::MouseWheel {
-                    delta: match (lines, pixels) {
-                        (Some(lines), None) => MouseWheelDelta::Lines(*lines),
-                        (None, Some(pixels)) => MouseWheelDelta::Pixels(*pixels),
-                        _ => panic!("MouseWheel: expected only one of 'lines' or 'pixels'"),
-                    },
-                },
-                AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
-                    key_code: KeyCode::from_code(*key_code),
-                    key_char: None,
-                },
-                AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
-                    key_code: KeyCode::from_code(*key_code),
-                    key_char: None,
-                },
-                AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
-                    codepoint: *codepoint,
-                },
-                AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
-                    code: match code {
-                        InputTextControlCode::MoveLeft => RuffleTextControlCode::MoveLeft,
-                        InputTextControlCode::MoveLeftWord => RuffleTextControlCode::MoveLeftWord,
-                        InputTextControlCode::MoveLeftLine => RuffleTextControlCode::MoveLeftLine,
-                        InputTextControlCode::MoveLeftDocument => {
-                            RuffleTextControlCode::MoveLeftDocument
-                        }
-                        InputTextControlCode::MoveRight => RuffleTextControlCode::MoveRight,
-                        InputTextControlCode::MoveRightWord => RuffleTextControlCode::MoveRightWord,
-                        InputTextControlCode::MoveRightLine => RuffleTextControlCode::MoveRightLine,
-                        InputTextControlCode::MoveRightDocument => {
-                            RuffleTextControlCode::MoveRightDocument
-                        }
-                        InputTextControlCode::SelectLeft => RuffleTextControlCode::SelectLeft,
-                        InputTextControlCode::SelectLeftWord => {
-                            RuffleTextControlCode::SelectLeftWord
-                        }
-                        InputTextControlCode::SelectLeftLine => {
-                            RuffleTextControlCode::SelectLeftLine
-                        }
-                        InputTextControlCode::SelectLeftDocument => {
-                            RuffleTextControlCode::SelectLeftDocument
-                        }
-                        InputTextControlCode::SelectRight => RuffleTextControlCode::SelectRight,
-                        InputTextControlCode::SelectRightWord => {
-                            RuffleTextControlCode::SelectRightWord
-                        }
-                        InputTextControlCode::SelectRightLine => {
-                            RuffleTextControlCode::SelectRightLine
-                        }
-                        InputTextControlCode::SelectRightDocument => {
-                            RuffleTextControlCode::SelectRightDocument
-                        }
-                        InputTextControlCode::SelectAll => RuffleTextControlCode::SelectAll,
-                        InputTextControlCode::Copy => RuffleTextControlCode::Copy,
-                        InputTextControlCode::Paste => RuffleTextControlCode::Paste,
-                        InputTextControlCode::Cut => RuffleTextControlCode::Cut,
-                        InputTextControlCode::Backspace => RuffleTextControlCode::Backspace,
-                        InputTextControlCode::Enter => RuffleTextControlCode::Enter,
-                        InputTextControlCode::Delete => RuffleTextControlCode::Delete,
-                    },
-                },
-                AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
-                AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
-                AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
-            });
+                Some(event) => event,
+                None => panic!("Invalid input event: {evt:?}"),
+            };
+            let handled = player.handle_event(event);
 
             #[allow(clippy::single_match)]
             match evt {
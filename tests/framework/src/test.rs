@@ -23,6 +23,10 @@ pub struct Test {
     pub output_path: VfsPath,
     pub root_path: VfsPath,
     pub name: String,
+
+    /// Whether AVM2 methods are compiled the first time they're called.
+    /// This only has an effect with the `jit` feature.
+    pub force_jit: bool,
 }
 
 impl Test {
@@ -40,10 +44,31 @@ impl Test {
             output_path,
             root_path: test_dir,
             name,
+            force_jit: false,
         })
     }
 
     pub fn create_test_runner(&self, environment: &impl Environment) -> Result<TestRunner> {
+        let injector = self.input_injector()?;
+        self.create_runner(environment, injector, None)
+    }
+
+    /// Creates a runner which replays the given input log in the player itself,
+    /// before each frame, instead of sending `input.json` from the host.
+    pub fn create_replay_test_runner(
+        &self,
+        environment: &impl Environment,
+        replay: InputInjector,
+    ) -> Result<TestRunner> {
+        self.create_runner(environment, InputInjector::empty(), Some(replay))
+    }
+
+    fn create_runner(
+        &self,
+        environment: &impl Environment,
+        injector: InputInjector,
+        input_replay: Option<InputInjector>,
+    ) -> Result<TestRunner> {
         let movie = self.movie()?;
         let viewport_dimensions = self.options.player_options.viewport_dimensions(&movie);
         let renderer = self
@@ -51,12 +76,12 @@ impl Test {
             .player_options
             .create_renderer(environment, viewport_dimensions);
 
-        let injector = self.input_injector()?;
         let socket_events = self.socket_events()?;
         let runner = TestRunner::new(
             self,
             movie,
             injector,
+            input_replay,
             socket_events,
             renderer,
             viewport_dimensions,
@@ -43,8 +43,29 @@ pub enum TextControlCode {
     Paste,
     Cut,
     Backspace,
+    BackspaceWord,
     Enter,
     Delete,
+    DeleteWord,
+}
+
+/// Which gamepad button is being pressed or released.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
 }
 
 /// All automated event types supported by FlashTAS.
@@ -75,6 +96,12 @@ pub enum AutomatedEvent {
         btn: MouseButton,
     },
 
+    /// Move the mouse outside of the stage.
+    MouseLeave,
+
+    /// Relative motion of a locked mouse, in pixels.
+    MouseMotion { dx: f64, dy: f64 },
+
     /// Mouse scroll.
     MouseWheel {
         lines: Option<f64>,
@@ -82,10 +109,22 @@ pub enum AutomatedEvent {
     },
 
     /// Press a key
-    KeyDown { key_code: u32 },
+    KeyDown {
+        key_code: u32,
+        key_char: Option<char>,
+    },
 
     /// Release a key
-    KeyUp { key_code: u32 },
+    KeyUp {
+        key_code: u32,
+        key_char: Option<char>,
+    },
+
+    /// Press a gamepad button
+    GamepadButtonDown { button: GamepadButton },
+
+    /// Release a gamepad button
+    GamepadButtonUp { button: GamepadButton },
 
     /// Input a character code
     TextInput { codepoint: char },
@@ -93,9 +132,35 @@ pub enum AutomatedEvent {
     /// Input a control character code
     TextControl { code: TextControlCode },
 
+    /// Update the composition string of the input method editor
+    ImePreedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+
+    /// Finish the composition of the input method editor
+    ImeCommit { text: String },
+
     /// Populate clipboard with the given text
     SetClipboardText { text: String },
 
+    /// Put a finger on the screen.
+    TouchBegin {
+        id: i32,
+        pos: MousePosition,
+        pressure: Option<f64>,
+    },
+
+    /// Move a finger which is on the screen.
+    TouchMove {
+        id: i32,
+        pos: MousePosition,
+        pressure: Option<f64>,
+    },
+
+    /// Lift a finger from the screen.
+    TouchEnd { id: i32, pos: MousePosition },
+
     /// Inform the player that the focus has been gained (i.e. the window has been focused).
     FocusGained,
 
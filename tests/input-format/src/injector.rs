@@ -70,6 +70,16 @@ impl InputInjector {
         })
     }
 
+    /// Construct an input injector from a list of events, such as one
+    /// captured while recording a session.
+    pub fn from_events(items: Vec<AutomatedEvent>) -> Self {
+        Self {
+            items,
+            pos: 0,
+            buttons: MouseButtons::empty(),
+        }
+    }
+
     /// Create an empty input injector with no input to inject.
     ///
     /// Useful to represent a missing input file in cases where providing one
@@ -82,6 +92,11 @@ impl InputInjector {
         }
     }
 
+    /// Whether every event has already been injected.
+    pub fn is_finished(&self) -> bool {
+        self.pos >= self.items.len()
+    }
+
     /// Run the next frame's worth of events.
     pub fn next<Sink>(&mut self, mut event_sink: Sink)
     where
@@ -95,12 +110,21 @@ impl InputInjector {
                 match event {
                     AutomatedEvent::Wait => break,
                     AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::MouseLeave
+                    | AutomatedEvent::MouseMotion { .. }
                     | AutomatedEvent::KeyDown { .. }
                     | AutomatedEvent::KeyUp { .. }
                     | AutomatedEvent::TextInput { .. }
+                    | AutomatedEvent::GamepadButtonDown { .. }
+                    | AutomatedEvent::GamepadButtonUp { .. }
                     | AutomatedEvent::TextControl { .. }
+                    | AutomatedEvent::ImePreedit { .. }
+                    | AutomatedEvent::ImeCommit { .. }
                     | AutomatedEvent::SetClipboardText { .. }
                     | AutomatedEvent::MouseWheel { .. }
+                    | AutomatedEvent::TouchBegin { .. }
+                    | AutomatedEvent::TouchMove { .. }
+                    | AutomatedEvent::TouchEnd { .. }
                     | AutomatedEvent::FocusGained
                     | AutomatedEvent::FocusLost => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
@@ -0,0 +1,52 @@
+use ruffle_core::input_log::InputInjector;
+use ruffle_test_framework::environment::Environment;
+use ruffle_test_framework::options::TestOptions;
+use ruffle_test_framework::runner::{TestRunner, TestStatus};
+use ruffle_test_framework::test::Test;
+use ruffle_test_framework::vfs::{PhysicalFS, VfsPath};
+use std::thread::sleep;
+
+pub fn input_replay_avm2(environment: &impl Environment) -> Result<(), libtest_mimic::Failed> {
+    // Test deterministic replays. Run an SWF with input from the host while
+    // recording it, then replay the recording and verify that both runs
+    // produce the same output.
+    let root = VfsPath::new(PhysicalFS::new("tests/swfs/avm2/deterministic_mode/"));
+    let test = &Test::from_options(
+        TestOptions::read(&root.join("test.toml")?)?,
+        root,
+        "input_replay_avm2".to_string(),
+    )?;
+
+    // Initial run; input is sent by the host after each tick.
+    let mut runner = test.create_test_runner(environment)?;
+    run(&mut runner)?;
+
+    let mut log = Vec::new();
+    runner
+        .player()
+        .lock()
+        .unwrap()
+        .input_recorder()
+        .expect("Input should be recorded")
+        .write(&mut log)?;
+
+    // Replay the recorded input, which the player injects before each frame.
+    let mut runner =
+        test.create_replay_test_runner(environment, InputInjector::from_reader(&log[..])?)?;
+    run(&mut runner)?;
+
+    Ok(())
+}
+
+fn run(runner: &mut TestRunner) -> Result<(), libtest_mimic::Failed> {
+    loop {
+        runner.tick();
+        match runner.test()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => break,
+        }
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@
 
 use crate::environment::NativeEnvironment;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
+use crate::input_replay::input_replay_avm2;
 use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
 use anyhow::Context;
 use anyhow::Result;
@@ -19,6 +20,7 @@ use std::thread::sleep;
 
 mod environment;
 mod external_interface;
+mod input_replay;
 mod shared_object;
 
 const TEST_TOML_NAME: &str = "test.toml";
@@ -85,25 +87,26 @@ fn main() {
 
     let root = Path::new("tests/swfs");
     let mut tests: Vec<Trial> = if filter_exact {
-        look_up_test(root, &args).map_or_else(Vec::new, |trial| vec![trial])
+        look_up_test(root, &args).unwrap_or_default()
     } else {
         walkdir::WalkDir::new(root)
             .into_iter()
             .map(Result::unwrap)
             .filter(|entry| entry.file_type().is_file() && entry.file_name() == TEST_TOML_NAME)
-            .filter_map(|file| {
-                let name = file
-                    .path()
-                    .parent()?
+            .flat_map(|file| {
+                let Some(dir) = file.path().parent() else {
+                    return Vec::new();
+                };
+                let name = dir
                     .strip_prefix(root)
                     .context("Couldn't strip root prefix from test dir")
                     .unwrap()
                     .to_string_lossy()
                     .replace('\\', "/");
                 if is_candidate(&args, &name) {
-                    Some(run_test(&args, file.path(), &name))
+                    run_tests(&args, file.path(), &name)
                 } else {
-                    None
+                    Vec::new()
                 }
             })
             .collect()
@@ -125,13 +128,16 @@ fn main() {
     tests.push(Trial::test("external_interface_avm2", || {
         external_interface_avm2(&NativeEnvironment)
     }));
+    tests.push(Trial::test("input_replay_avm2", || {
+        input_replay_avm2(&NativeEnvironment)
+    }));
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 
     libtest_mimic::run(&args, tests).exit()
 }
 
-fn look_up_test(root: &Path, args: &Arguments) -> Option<Trial> {
+fn look_up_test(root: &Path, args: &Arguments) -> Option<Vec<Trial>> {
     let name = filter_to_test_name(args.filter.as_ref().unwrap());
     let absolute_root = std::fs::canonicalize(root).unwrap();
     let path = absolute_root
@@ -147,15 +153,25 @@ fn look_up_test(root: &Path, args: &Arguments) -> Option<Trial> {
         return None;
     }
     if path.is_file() {
-        Some(run_test(args, &path, &name))
+        Some(run_tests(args, &path, &name))
     } else {
         None
     }
 }
 
-fn run_test(args: &Arguments, file: &Path, name: &str) -> Trial {
+/// Creates the trials for a test: one running it normally, and with the `jit`
+/// feature, one running AVM2 tests with every supported method compiled.
+fn run_tests(args: &Arguments, file: &Path, name: &str) -> Vec<Trial> {
+    let mut trials = vec![run_test(args, file, name, false)];
+    if cfg!(feature = "jit") && name.starts_with("avm2/") {
+        trials.push(run_test(args, file, name, true).with_kind("jit"));
+    }
+    trials
+}
+
+fn run_test(args: &Arguments, file: &Path, name: &str, force_jit: bool) -> Trial {
     let root = VfsPath::new(PhysicalFS::new(file.parent().unwrap()));
-    let test = Test::from_options(
+    let mut test = Test::from_options(
         TestOptions::read(&root.join("test.toml").unwrap())
             .context("Couldn't load test options")
             .unwrap(),
@@ -164,6 +180,7 @@ fn run_test(args: &Arguments, file: &Path, name: &str) -> Trial {
     )
     .with_context(|| format!("Couldn't create test {name}"))
     .unwrap();
+    test.force_jit = force_jit;
 
     let ignore = !test.should_run(!args.list, &NativeEnvironment);
 
@@ -0,0 +1,21 @@
+// Instants on either side of the 2020 transitions of Europe/London.
+d = new Date(Date.UTC(2020, 2, 29, 0, 59, 59));
+trace(d.getTime() + ": " + d.toString() + " offset=" + d.getTimezoneOffset() + " hours=" + d.getHours());
+d = new Date(Date.UTC(2020, 2, 29, 1, 0, 0));
+trace(d.getTime() + ": " + d.toString() + " offset=" + d.getTimezoneOffset() + " hours=" + d.getHours());
+d = new Date(Date.UTC(2020, 9, 25, 0, 59, 59));
+trace(d.getTime() + ": " + d.toString() + " offset=" + d.getTimezoneOffset() + " hours=" + d.getHours());
+d = new Date(Date.UTC(2020, 9, 25, 1, 0, 0));
+trace(d.getTime() + ": " + d.toString() + " offset=" + d.getTimezoneOffset() + " hours=" + d.getHours());
+
+// Local times around the transitions.
+trace("before spring forward: " + new Date(2020, 2, 29, 0, 30).getTime());
+trace("skipped by spring forward: " + new Date(2020, 2, 29, 1, 30).getTime());
+trace("after spring forward: " + new Date(2020, 2, 29, 2, 30).getTime());
+trace("repeated by fall back: " + new Date(2020, 9, 25, 1, 30).getTime());
+trace("after fall back: " + new Date(2020, 9, 25, 2, 30).getTime());
+
+// Setting the day keeps the local time of day across a transition.
+d = new Date(2020, 2, 28, 12, 0);
+d.setDate(30);
+trace("setDate across spring forward: " + d.toString() + " hours=" + d.getHours());
@@ -0,0 +1,29 @@
+package {
+	import flash.display.Sprite;
+
+	public class Test extends Sprite {
+		public function Test() {
+			// Instants on either side of the 2020 transitions of Europe/London.
+			describe(new Date(Date.UTC(2020, 2, 29, 0, 59, 59)));
+			describe(new Date(Date.UTC(2020, 2, 29, 1, 0, 0)));
+			describe(new Date(Date.UTC(2020, 9, 25, 0, 59, 59)));
+			describe(new Date(Date.UTC(2020, 9, 25, 1, 0, 0)));
+
+			// Local times around the transitions.
+			trace("before spring forward: " + new Date(2020, 2, 29, 0, 30).time);
+			trace("skipped by spring forward: " + new Date(2020, 2, 29, 1, 30).time);
+			trace("after spring forward: " + new Date(2020, 2, 29, 2, 30).time);
+			trace("repeated by fall back: " + new Date(2020, 9, 25, 1, 30).time);
+			trace("after fall back: " + new Date(2020, 9, 25, 2, 30).time);
+
+			// Setting the day keeps the local time of day across a transition.
+			var date:Date = new Date(2020, 2, 28, 12, 0);
+			date.date = 30;
+			trace("setDate across spring forward: " + date + " hours=" + date.hours);
+		}
+
+		private function describe(date:Date):void {
+			trace(date.time + ": " + date + " offset=" + date.timezoneOffset + " hours=" + date.hours);
+		}
+	}
+}
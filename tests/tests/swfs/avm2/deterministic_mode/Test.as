@@ -0,0 +1,57 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.KeyboardEvent;
+	import flash.events.TimerEvent;
+	import flash.utils.Timer;
+	import flash.utils.getTimer;
+
+	// Runs at 20fps, so the virtual clock advances by exactly 50ms per frame.
+	public class Test extends Sprite {
+		private var startDate:Number;
+		private var timer:Timer;
+
+		public function Test() {
+			trace("// Math.random() with seed 42");
+			for (var i:int = 0; i < 3; i++) {
+				trace(randomInt());
+			}
+
+			startDate = new Date().time;
+			trace("// Starting a 100ms timer at getTimer() = " + getTimer());
+			timer = new Timer(100, 3);
+			timer.addEventListener(TimerEvent.TIMER, onTimer);
+			timer.addEventListener(TimerEvent.TIMER_COMPLETE, onTimerComplete);
+			timer.start();
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+			stage.addEventListener(KeyboardEvent.KEY_DOWN, onKeyDown);
+		}
+
+		// Math.random() returns multiples of 2^-31, so this is the raw generated value.
+		private function randomInt():int {
+			return int(Math.random() * 0x80000000);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			var time:int = getTimer();
+			// Only the first frames are traced, skipping the one the SWF started in.
+			if (time == 0 || time > 200) {
+				return;
+			}
+			trace("enterFrame: getTimer() = " + time + ", Date advanced by " + (new Date().time - startDate));
+		}
+
+		private function onTimer(e:TimerEvent):void {
+			trace("timer " + timer.currentCount + ": getTimer() = " + getTimer());
+		}
+
+		private function onTimerComplete(e:TimerEvent):void {
+			trace("timer complete: getTimer() = " + getTimer());
+		}
+
+		private function onKeyDown(e:KeyboardEvent):void {
+			trace("keyDown " + e.keyCode + ": getTimer() = " + getTimer() + ", Math.random() = " + randomInt());
+		}
+	}
+}
@@ -0,0 +1,57 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IMEEvent;
+    import flash.events.KeyboardEvent;
+    import flash.events.TextEvent;
+    import flash.system.IME;
+    import flash.system.IMEConversionMode;
+    import flash.system.System;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+
+    public class Test extends Sprite {
+        private var field:TextField;
+
+        public function Test() {
+            trace("IME.enabled: " + IME.enabled);
+            IME.enabled = false;
+            trace("IME.enabled after disabling: " + IME.enabled);
+            IME.enabled = true;
+
+            trace("IME.conversionMode: " + IME.conversionMode);
+            IME.conversionMode = IMEConversionMode.JAPANESE_HIRAGANA;
+            trace("IME.conversionMode after setting: " + IME.conversionMode);
+            try {
+                IME.conversionMode = "INVALID";
+            } catch (e:ArgumentError) {
+                trace(e);
+            }
+            trace("IME.conversionMode after an invalid mode: " + IME.conversionMode);
+
+            field = new TextField();
+            field.type = TextFieldType.INPUT;
+            field.width = 200;
+            field.height = 50;
+            field.text = "ab";
+            addChild(field);
+
+            stage.focus = field;
+            field.setSelection(1, 1);
+
+            field.addEventListener(TextEvent.TEXT_INPUT, function(event:TextEvent):void {
+                trace("textInput: " + event.text);
+            });
+            field.addEventListener(Event.CHANGE, function(event:Event):void {
+                trace("change: " + field.text);
+            });
+            System.ime.addEventListener(IMEEvent.IME_COMPOSITION, function(event:IMEEvent):void {
+                trace("imeComposition: " + event.text);
+            });
+            stage.addEventListener(KeyboardEvent.KEY_DOWN, function(event:KeyboardEvent):void {
+                trace("text: " + field.text + " (length " + field.length + ")"
+                    + " selection: " + field.selectionBeginIndex + "," + field.selectionEndIndex);
+            });
+        }
+    }
+}
@@ -0,0 +1,130 @@
+package {
+	import flash.display.Sprite;
+
+	public class Test extends Sprite {
+	}
+}
+
+class A {
+	public var x = "A.x";
+
+	public function describe() {
+		return "A.describe";
+	}
+}
+
+class B extends A {
+	override public function describe() {
+		return "B.describe";
+	}
+}
+
+class WithGetter {
+	public function get x() {
+		return "WithGetter.x";
+	}
+
+	public function set x(value) {
+		trace("WithGetter.x set to " + value);
+	}
+}
+
+class WithFunctionSlot {
+	public var describe = function() {
+		return "WithFunctionSlot.describe";
+	};
+}
+
+dynamic class Dyn {
+}
+
+class M1 { public var x = "M1.x"; }
+class M2 { public var x = "M2.x"; }
+class M3 { public var x = "M3.x"; }
+class M4 { public var x = "M4.x"; }
+class M5 { public var x = "M5.x"; }
+class M6 { public var x = "M6.x"; }
+
+function getX(o) {
+	return o.x;
+}
+
+function setX(o, value) {
+	o.x = value;
+}
+
+function callDescribe(o) {
+	return o.describe();
+}
+
+function callDescribeVoid(o) {
+	o.describe();
+}
+
+trace("// monomorphic");
+for (var i = 0; i < 3; i++) {
+	trace(getX(new A()));
+}
+
+trace("// polymorphic");
+var receivers = [new A(), new B(), new WithGetter(), {x: "Object.x"}, new A()];
+for each (var receiver in receivers) {
+	trace(getX(receiver));
+}
+
+trace("// methods");
+trace(callDescribe(new A()));
+trace(callDescribe(new B()));
+trace(callDescribe(new WithFunctionSlot()));
+trace(callDescribe({describe: function() { return "Object.describe"; }}));
+callDescribeVoid(new A());
+
+trace("// setters");
+var a = new A();
+setX(a, "A.x changed");
+trace(getX(a));
+setX(new WithGetter(), "value");
+var obj = {};
+setX(obj, "Object.x set");
+trace(getX(obj));
+
+trace("// dynamic properties");
+var dyn = new Dyn();
+trace(getX(dyn));
+dyn.x = "own x";
+trace(getX(dyn));
+delete dyn.x;
+trace(getX(dyn));
+
+trace("// prototype edits");
+trace(getX(dyn));
+Dyn.prototype.x = "Dyn.prototype.x";
+trace(getX(dyn));
+dyn.x = "own x";
+trace(getX(dyn));
+delete dyn.x;
+trace(getX(dyn));
+Object.prototype.x = "Object.prototype.x";
+trace(getX(dyn));
+delete Dyn.prototype.x;
+trace(getX(dyn));
+trace(getX(new A()));
+delete Object.prototype.x;
+trace(getX(dyn));
+
+trace("// prototype methods");
+trace(callDescribe(new B()));
+Dyn.prototype.describe = function() { return "Dyn.prototype.describe"; };
+trace(callDescribe(dyn));
+Dyn.prototype.describe = function() { return "Dyn.prototype.describe replaced"; };
+trace(callDescribe(dyn));
+
+trace("// megamorphic");
+var many = [new M1(), new M2(), new M3(), new M4(), new M5(), new M6()];
+for (var round = 0; round < 2; round++) {
+	for each (var m in many) {
+		trace(getX(m));
+	}
+}
+trace(getX(new A()));
+trace(getX(dyn));
@@ -0,0 +1,120 @@
+package {
+    import flash.display.Sprite;
+
+    // Every method here only uses numbers and booleans, so the JIT can compile them.
+    // Each one is called repeatedly, so that it runs both interpreted and
+    // compiled when the JIT compiles hot methods.
+    public class Test extends Sprite {
+        public function Test() {
+            for (var i:int = 0; i < 20; i++) {
+                var last:Boolean = i == 19;
+                check(last, "sumTo(100)", sumTo(100));
+                check(last, "sumTo(-5)", sumTo(-5));
+                check(last, "mix(10, 0.5)", mix(10, 0.5));
+                check(last, "mix(-7, -1.25)", mix(-7, -1.25));
+                check(last, "uintOps(4294967295)", uintOps(4294967295));
+                check(last, "uintOps(12345)", uintOps(12345));
+                check(last, "compare(1, 2)", compare(1, 2));
+                check(last, "compare(2, 2)", compare(2, 2));
+                check(last, "compare(NaN, 2)", compare(NaN, 2));
+                check(last, "overflow(2147483647, 1)", overflow(2147483647, 1));
+                check(last, "overflow(-2147483648, -1)", overflow(-2147483648, -1));
+                check(last, "overflowToInt(2147483647, 1)", overflowToInt(2147483647, 1));
+                check(last, "product(65536, 65537)", product(65536, 65537));
+                check(last, "productI(65536, 65537)", productI(65536, 65537));
+                check(last, "isEven(7)", isEven(7));
+                check(last, "isEven(-4)", isEven(-4));
+                check(last, "1 / negate(0)", 1 / negate(0));
+                check(last, "toInt(3.75e10)", toInt(3.75e10));
+                check(last, "toInt(NaN)", toInt(NaN));
+                check(last, "toUint(-1.5)", toUint(-1.5));
+                check(last, "modulo(-7.5, 2)", modulo(-7.5, 2));
+                check(last, "nothing(3)", nothing(3));
+            }
+        }
+
+        private function check(last:Boolean, name:String, value:*):void {
+            if (last) {
+                trace(name + ": " + value);
+            }
+        }
+
+        private function sumTo(n:int):int {
+            var sum:int = 0;
+            for (var i:int = 0; i < n; i++) {
+                sum += i;
+            }
+            return sum;
+        }
+
+        private function mix(a:int, b:Number):Number {
+            return a / 3 + b * 2 - (a % 7);
+        }
+
+        private function uintOps(x:uint):uint {
+            return (x >>> 3) ^ (x << 5);
+        }
+
+        private function compare(a:Number, b:Number):int {
+            var result:int = 0;
+            if (a < b) {
+                result |= 1;
+            }
+            if (a >= b) {
+                result |= 2;
+            }
+            if (!(a < b)) {
+                result |= 4;
+            }
+            if (a == b) {
+                result |= 8;
+            }
+            if (a != b) {
+                result |= 16;
+            }
+            return result;
+        }
+
+        private function overflow(a:int, b:int):Number {
+            return a + b;
+        }
+
+        private function overflowToInt(a:int, b:int):int {
+            return a + b;
+        }
+
+        private function product(a:int, b:int):Number {
+            return a * b;
+        }
+
+        private function productI(a:int, b:int):int {
+            return a * b;
+        }
+
+        private function isEven(x:int):Boolean {
+            return (x & 1) == 0;
+        }
+
+        private function negate(x:int):Number {
+            return -x;
+        }
+
+        private function toInt(x:Number):int {
+            var result:int = x;
+            return result;
+        }
+
+        private function toUint(x:Number):uint {
+            var result:uint = x;
+            return result;
+        }
+
+        private function modulo(a:Number, b:Number):Number {
+            return a % b;
+        }
+
+        private function nothing(x:int):void {
+            x++;
+        }
+    }
+}
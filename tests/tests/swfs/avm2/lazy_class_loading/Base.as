@@ -0,0 +1,19 @@
+package {
+    public class Base {
+        {
+            trace("Base class init");
+        }
+
+        public var kind:String = "base";
+
+        public function Base() {
+            trace("Base constructor");
+        }
+
+        public function describe():String {
+            return "I am " + kind;
+        }
+    }
+}
+
+trace("Base.as script init");
@@ -0,0 +1,18 @@
+package {
+    public class Derived extends Base {
+        {
+            trace("Derived class init");
+        }
+
+        public function Derived() {
+            kind = "derived";
+            trace("Derived constructor");
+        }
+
+        override public function describe():String {
+            return super.describe() + " (via Derived)";
+        }
+    }
+}
+
+trace("Derived.as script init");
@@ -0,0 +1,29 @@
+package {
+    import flash.display.Sprite;
+    import flash.system.ApplicationDomain;
+    import flash.utils.describeType;
+    import flash.utils.getDefinitionByName;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var domain:ApplicationDomain = ApplicationDomain.currentDomain;
+            var names:Vector.<String> = domain.getQualifiedDefinitionNames();
+            names.sort(0);
+            trace("definitions: " + names);
+
+            trace("looking up Derived");
+            var derivedClass:Class = getDefinitionByName("Derived") as Class;
+            var description:XML = describeType(derivedClass);
+            trace("describeType name: " + description.@name);
+            trace("describeType extends: " + description.factory.extendsClass[0].@type);
+
+            var derived:Object = new derivedClass();
+            trace(derived.describe());
+            trace("derived is Base: " + (derived is Base));
+
+            trace("looking up Base");
+            var base:Base = new Base();
+            trace(base.describe());
+        }
+    }
+}
@@ -0,0 +1,9 @@
+package {
+    public class Unreferenced {
+        {
+            trace("Unreferenced class init");
+        }
+    }
+}
+
+trace("Unreferenced.as script init");
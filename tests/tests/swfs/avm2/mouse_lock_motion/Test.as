@@ -0,0 +1,22 @@
+package {
+    import flash.display.Sprite;
+    import flash.display.StageDisplayState;
+    import flash.events.MouseEvent;
+
+    public class Test extends Sprite {
+        public function Test() {
+            stage.displayState = StageDisplayState.FULL_SCREEN_INTERACTIVE;
+            trace("displayState: " + stage.displayState);
+
+            stage.mouseLock = true;
+            trace("mouseLock: " + stage.mouseLock);
+
+            stage.addEventListener(MouseEvent.MOUSE_MOVE, onMouseMove);
+        }
+
+        private function onMouseMove(event:MouseEvent):void {
+            trace("mouseMove: target=" + event.target
+                + " movement=" + event.movementX + "," + event.movementY);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.ui.Mouse;
+	import flash.ui.MouseCursor;
+	import flash.ui.MouseCursorData;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("Mouse.cursor: " + Mouse.cursor);
+			setCursor(MouseCursor.HAND);
+			setCursor("custom");
+
+			trace("// Registered cursor");
+			Mouse.registerCursor("custom", cursorData(new BitmapData(16, 16, true, 0xFFFF0000)));
+			setCursor("custom");
+
+			trace("// Unregistered cursor in use");
+			Mouse.unregisterCursor("custom");
+			trace("Mouse.cursor: " + Mouse.cursor);
+			setCursor("custom");
+
+			trace("// Cursor without data");
+			Mouse.registerCursor("nodata", new MouseCursorData());
+			setCursor("nodata");
+
+			trace("// Cursor with empty data");
+			Mouse.registerCursor("empty", cursorData());
+			setCursor("empty");
+
+			trace("// Cursor with disposed data");
+			var disposed:BitmapData = new BitmapData(16, 16);
+			disposed.dispose();
+			Mouse.registerCursor("disposed", cursorData(disposed));
+			setCursor("disposed");
+
+			trace("// Native cursor replaced by a registered one");
+			Mouse.registerCursor(MouseCursor.ARROW, cursorData(new BitmapData(16, 16)));
+			setCursor(MouseCursor.ARROW);
+			Mouse.unregisterCursor(MouseCursor.ARROW);
+			trace("Mouse.cursor: " + Mouse.cursor);
+			setCursor(MouseCursor.AUTO);
+		}
+
+		private function cursorData(... frames):MouseCursorData {
+			var data:Vector.<BitmapData> = new Vector.<BitmapData>();
+			for each (var frame:BitmapData in frames) {
+				data.push(frame);
+			}
+			var cursor:MouseCursorData = new MouseCursorData();
+			cursor.data = data;
+			return cursor;
+		}
+
+		private function setCursor(name:String):void {
+			try {
+				Mouse.cursor = name;
+				trace("Mouse.cursor = " + name + ": " + Mouse.cursor);
+			} catch (e:ArgumentError) {
+				trace("Mouse.cursor = " + name + ": " + e);
+			}
+		}
+	}
+}
@@ -0,0 +1,70 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+
+	public class Test extends Sprite {
+		// audio.flv has audio tags every 250ms from 0 to 1000ms.
+		// video.flv has an audio tag at 0ms, keyframes at 200ms and 600ms,
+		// interframes at 400ms and 800ms, and a final audio tag at 1000ms.
+		private var files:Array = [
+			["audio.flv", [0.6, 0.3, 2, 0.5, 0.1]],
+			["video.flv", [0.3, 0.1, 0.15, 0.7, 0.65]]
+		];
+		private var nc:NetConnection;
+		private var ns:NetStream;
+		private var seeks:Array;
+
+		public function Test() {
+			nc = new NetConnection();
+			nc.connect(null);
+			playNext();
+		}
+
+		private function playNext():void {
+			if (files.length == 0) {
+				return;
+			}
+
+			var file:Array = files.shift();
+			seeks = file[1];
+			trace("// " + file[0]);
+			ns = new NetStream(nc);
+			ns.client = {onMetaData: function(info:Object):void {}};
+			ns.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+			ns.play(file[0]);
+		}
+
+		private function onNetStatus(event:NetStatusEvent):void {
+			var stream:NetStream = event.target as NetStream;
+			if (stream != ns) {
+				return;
+			}
+
+			switch (event.info.code) {
+				case "NetStream.Buffer.Full":
+					// Keep the stream paused so that ns.time only reflects seeks.
+					ns.pause();
+					seekNext();
+					break;
+				case "NetStream.Seek.Complete":
+					trace("time: " + ns.time);
+					seekNext();
+					break;
+			}
+		}
+
+		private function seekNext():void {
+			if (seeks.length == 0) {
+				ns = null;
+				playNext();
+				return;
+			}
+
+			var offset:Number = seeks.shift();
+			trace("ns.seek(" + offset + ")");
+			ns.seek(offset);
+		}
+	}
+}
@@ -0,0 +1,32 @@
+package {
+    import flash.display.Sprite;
+    import flash.display.StageDisplayState;
+    import flash.geom.Rectangle;
+
+    public class Test extends Sprite {
+        public function Test() {
+            // Only the red and blue rectangles are inside the source rectangle.
+            fill(0x00FF00, 0, 0, 50, 100);
+            fill(0xFF0000, 50, 0, 25, 50);
+            fill(0x0000FF, 75, 0, 25, 50);
+            fill(0x00FF00, 50, 50, 150, 50);
+            fill(0x00FF00, 100, 0, 100, 50);
+
+            trace("fullScreenWidth: " + stage.fullScreenWidth);
+            trace("fullScreenHeight: " + stage.fullScreenHeight);
+            trace("fullScreenSourceRect: " + stage.fullScreenSourceRect);
+
+            stage.fullScreenSourceRect = new Rectangle(50, 0, 50, 50);
+            trace("fullScreenSourceRect: " + stage.fullScreenSourceRect);
+
+            stage.displayState = StageDisplayState.FULL_SCREEN;
+            trace("displayState: " + stage.displayState);
+        }
+
+        private function fill(color:uint, x:Number, y:Number, width:Number, height:Number):void {
+            graphics.beginFill(color);
+            graphics.drawRect(x, y, width, height);
+            graphics.endFill();
+        }
+    }
+}
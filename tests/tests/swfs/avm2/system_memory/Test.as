@@ -0,0 +1,24 @@
+package {
+    import flash.display.Sprite;
+    import flash.system.System;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("totalMemory > 0: " + (System.totalMemory > 0));
+            trace("totalMemoryNumber close to totalMemory: " + (Math.abs(System.totalMemoryNumber - System.totalMemory) < 1024 * 1024));
+            trace("privateMemory > 0: " + (System.privateMemory > 0));
+            trace("freeMemory >= 0: " + (System.freeMemory >= 0));
+
+            var before:Number = System.totalMemoryNumber;
+            var bytes:ByteArray = new ByteArray();
+            bytes.length = 16 * 1024 * 1024;
+            trace("ByteArray counted: " + (System.totalMemoryNumber - before >= 16 * 1024 * 1024));
+
+            System.pauseForGCIfCollectionImminent();
+            System.pauseForGCIfCollectionImminent(NaN);
+            System.gc();
+            trace("gc() returned");
+        }
+    }
+}
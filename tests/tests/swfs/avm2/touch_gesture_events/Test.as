@@ -0,0 +1,68 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.TouchEvent;
+    import flash.events.TransformGestureEvent;
+    import flash.ui.Multitouch;
+    import flash.ui.MultitouchInputMode;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var box:Sprite = new Sprite();
+            box.name = "box";
+            box.graphics.beginFill(0xFF0000);
+            box.graphics.drawRect(0, 0, 200, 200);
+            box.graphics.endFill();
+            addChild(box);
+
+            Multitouch.inputMode = MultitouchInputMode.TOUCH_POINT;
+            trace("inputMode: " + Multitouch.inputMode);
+
+            var touchEvents:Array = [
+                TouchEvent.TOUCH_BEGIN,
+                TouchEvent.TOUCH_MOVE,
+                TouchEvent.TOUCH_END,
+                TouchEvent.TOUCH_TAP,
+                TouchEvent.TOUCH_OVER,
+                TouchEvent.TOUCH_OUT,
+                TouchEvent.TOUCH_ROLL_OVER,
+                TouchEvent.TOUCH_ROLL_OUT
+            ];
+            for each (var type:String in touchEvents) {
+                box.addEventListener(type, onTouch);
+            }
+
+            var gestureEvents:Array = [
+                TransformGestureEvent.GESTURE_ZOOM,
+                TransformGestureEvent.GESTURE_PAN,
+                TransformGestureEvent.GESTURE_ROTATE,
+                TransformGestureEvent.GESTURE_SWIPE
+            ];
+            for each (type in gestureEvents) {
+                box.addEventListener(type, onGesture);
+            }
+        }
+
+        private function onTouch(event:TouchEvent):void {
+            trace(event.type + ": target=" + event.target.name
+                + " id=" + event.touchPointID
+                + " primary=" + event.isPrimaryTouchPoint
+                + " local=" + event.localX + "," + event.localY
+                + " stage=" + event.stageX + "," + event.stageY);
+
+            if (event.type == TouchEvent.TOUCH_TAP) {
+                Multitouch.inputMode = MultitouchInputMode.GESTURE;
+                trace("inputMode: " + Multitouch.inputMode);
+            }
+        }
+
+        private function onGesture(event:TransformGestureEvent):void {
+            trace(event.type + ": target=" + event.target.name
+                + " phase=" + event.phase
+                + " local=" + event.localX + "," + event.localY
+                + " scale=" + event.scaleX + "," + event.scaleY
+                + " rotation=" + event.rotation
+                + " offset=" + event.offsetX + "," + event.offsetY);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+package {
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var ints:Vector.<int> = new Vector.<int>(4);
+            fillInts(ints);
+            trace("ints: " + ints);
+            trace("sumInts: " + sumInts(ints));
+
+            setIntsLoose(ints, 3.75, -2147483649, "12", true);
+            trace("ints coerced: " + ints);
+
+            ints[0] = 268435456;
+            trace("ints[0]: " + ints[0] + " is int: " + (ints[0] is int));
+
+            ints[4] = 7;
+            trace("ints grown: " + ints.length + " " + ints[4]);
+
+            try {
+                trace(ints[10]);
+            } catch (e:RangeError) {
+                trace("get out of range: " + e.errorID);
+            }
+
+            try {
+                ints[10] = 1;
+            } catch (e:RangeError) {
+                trace("set out of range: " + e.errorID);
+            }
+
+            var removed:Vector.<int> = ints.splice(1, 2, 100, 200, 300);
+            trace("removed: " + removed);
+            trace("spliced: " + ints);
+            trace("converted: " + Vector.<int>([1.9, "2", -3]));
+
+            var uints:Vector.<uint> = new Vector.<uint>(3);
+            setUints(uints, -1);
+            trace("uints: " + uints);
+            trace("uints sum: " + (uints[0] + uints[1]));
+            uints.reverse();
+            trace("uints reversed: " + uints);
+
+            var minusOne:Number = -1;
+            var nums:Vector.<Number> = new Vector.<Number>();
+            nums.push(0.1, NaN, minusOne * 0);
+            nums[3] = 1 / 3;
+            trace("nums: " + nums);
+            trace("1 / nums[2]: " + (1 / nums[2]));
+            nums.length = 6;
+            trace("nums hole: " + nums[5]);
+            trace("sumNumbers: " + sumNumbers(new <Number>[0.5, 1.25, 2]));
+
+            var fixed:Vector.<Number> = new Vector.<Number>(2, true);
+            fixed[1] = 1.5;
+            trace("fixed: " + fixed);
+            try {
+                fixed[2] = 3;
+            } catch (e:RangeError) {
+                trace("fixed set: " + e.errorID);
+            }
+            try {
+                fixed.push(3);
+            } catch (e:RangeError) {
+                trace("fixed push: " + e.errorID);
+            }
+
+            var bools:Vector.<Boolean> = new Vector.<Boolean>();
+            bools.length = 2;
+            trace("bools hole: " + bools[0]);
+            setBools(bools, 0);
+            bools.push("x");
+            trace("bools: " + bools);
+
+            var untyped:* = ints;
+            untyped[0] = 2.5;
+            untyped[1] = 9;
+            trace("untyped: " + untyped[0] + " " + untyped[1]);
+
+            var objects:Vector.<Object> = new Vector.<Object>(2);
+            objects[0] = 1.5;
+            trace("objects: " + objects[0] + " " + objects[1]);
+        }
+
+        private function fillInts(v:Vector.<int>):void {
+            for (var i:int = 0; i < v.length; i++) {
+                v[i] = i * 3 - 4;
+            }
+        }
+
+        private function sumInts(v:Vector.<int>):int {
+            var sum:int = 0;
+            for (var i:int = 0; i < v.length; i++) {
+                sum += v[i];
+            }
+            return sum;
+        }
+
+        private function setIntsLoose(v:Vector.<int>, n:Number, big:Number, s:String, b:Boolean):void {
+            v[0] = n;
+            v[1] = big;
+            v[2] = s;
+            v[3] = b;
+        }
+
+        private function setUints(v:Vector.<uint>, negative:int):void {
+            v[0] = 4294967295;
+            v[1] = negative;
+            v[2] = 2147483648;
+        }
+
+        private function sumNumbers(v:Vector.<Number>):Number {
+            var sum:Number = 0;
+            for (var i:uint = 0; i < v.length; i++) {
+                sum += v[i];
+            }
+            return sum;
+        }
+
+        private function setBools(v:Vector.<Boolean>, zero:int):void {
+            v[0] = true;
+            v[1] = zero;
+        }
+    }
+}
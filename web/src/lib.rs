@@ -539,13 +539,32 @@ impl RuffleHandle {
                 false,
                 move |js_event: PointerEvent| {
                     let _ = ruffle.with_instance(move |instance| {
-                        let event = PlayerEvent::MouseMove {
-                            x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                            y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                        let pointer_locked = web_sys::window()
+                            .and_then(|window| window.document())
+                            .and_then(|document| document.pointer_lock_element())
+                            .is_some();
+                        let event = if pointer_locked {
+                            // The pointer doesn't move while locked, only its motion is reported.
+                            PlayerEvent::MouseMotion {
+                                dx: f64::from(js_event.movement_x()) * instance.device_pixel_ratio,
+                                dy: f64::from(js_event.movement_y()) * instance.device_pixel_ratio,
+                            }
+                        } else {
+                            PlayerEvent::MouseMove {
+                                x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
+                                y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                            }
                         };
                         let _ = instance.with_core_mut(|core| {
                             core.handle_event(event);
                         });
+                        if let Some((id, x, y, pressure)) =
+                            touch_point(&js_event, instance.device_pixel_ratio)
+                        {
+                            let _ = instance.with_core_mut(|core| {
+                                core.handle_event(PlayerEvent::TouchMove { id, x, y, pressure });
+                            });
+                        }
                         if instance.has_focus {
                             js_event.prevent_default();
                         }
@@ -610,7 +629,7 @@ impl RuffleHandle {
                             index: None,
                         };
                         let handled = instance
-                            .with_core_mut(|core| core.handle_event(event))
+                            .with_core_mut(|core| core.handle_event(event.clone()))
                             .unwrap_or_default();
 
                         if handled
@@ -625,6 +644,14 @@ impl RuffleHandle {
                             js_player_callback.suppress_context_menu();
                         }
 
+                        if let Some((id, x, y, pressure)) =
+                            touch_point(&js_event, device_pixel_ratio)
+                        {
+                            let _ = instance.with_core_mut(|core| {
+                                core.handle_event(PlayerEvent::TouchBegin { id, x, y, pressure });
+                            });
+                        }
+
                         js_event.prevent_default();
                     });
                 },
@@ -655,6 +682,13 @@ impl RuffleHandle {
                         let _ = instance.with_core_mut(|core| {
                             core.handle_event(event);
                         });
+                        if let Some((id, x, y, _)) =
+                            touch_point(&js_event, instance.device_pixel_ratio)
+                        {
+                            let _ = instance.with_core_mut(|core| {
+                                core.handle_event(PlayerEvent::TouchEnd { id, x, y });
+                            });
+                        }
 
                         if instance.has_focus {
                             js_event.prevent_default();
@@ -1191,6 +1225,24 @@ fn parse_movie_parameters(input: &JsValue) -> Vec<(String, String)> {
     params
 }
 
+/// Returns the ID, position and pressure of the touch point of a pointer event,
+/// or `None` if the event was not caused by touch input.
+fn touch_point(
+    js_event: &PointerEvent,
+    device_pixel_ratio: f64,
+) -> Option<(i32, f64, f64, Option<f64>)> {
+    if js_event.pointer_type() != "touch" {
+        return None;
+    }
+
+    Some((
+        js_event.pointer_id(),
+        f64::from(js_event.offset_x()) * device_pixel_ratio,
+        f64::from(js_event.offset_y()) * device_pixel_ratio,
+        Some(f64::from(js_event.pressure())),
+    ))
+}
+
 #[wasm_bindgen(start)]
 fn global_init() {
     // Redirect Log to Tracing
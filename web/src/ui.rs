@@ -6,6 +6,7 @@ use ruffle_core::backend::ui::{
 use ruffle_core::backend::ui::{
     FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
 };
+use ruffle_core::swf::{Rectangle, Twips};
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
 use url::Url;
@@ -190,6 +191,8 @@ impl WebUiBackend {
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
+                // TODO: Custom cursors could be supported using a data URL.
+                MouseCursor::Custom(_) => "auto",
             }
         } else {
             "none"
@@ -220,6 +223,16 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
+    fn set_mouse_lock(&mut self, locked: bool) -> bool {
+        // The browser grants pointer lock asynchronously, it can still be refused later.
+        if locked {
+            self.canvas.request_pointer_lock();
+        } else if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.exit_pointer_lock();
+        }
+        true
+    }
+
     fn clipboard_content(&mut self) -> String {
         // On web, clipboard content is not directly accessible due to security restrictions,
         // but pasting from the clipboard is supported via the JS `paste` event
@@ -289,6 +302,16 @@ impl UiBackend for WebUiBackend {
         }
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        let window = web_sys::window()?;
+        let screen = window.screen().ok()?;
+        // The screen size is in CSS pixels, while the viewport is in device pixels.
+        let scale_factor = window.device_pixel_ratio();
+        let width = f64::from(screen.width().ok()?) * scale_factor;
+        let height = f64::from(screen.height().ok()?) * scale_factor;
+        Some((width.round() as u32, height.round() as u32))
+    }
+
     fn display_root_movie_download_failed_message(&self, invalid_swf: bool) {
         self.js_player
             .display_root_movie_download_failed_message(invalid_swf)
@@ -306,6 +329,13 @@ impl UiBackend for WebUiBackend {
         self.js_player.close_virtual_keyboard()
     }
 
+    fn set_ime_enabled(&mut self, _enabled: bool) {
+        // The browser handles composition in the virtual keyboard element,
+        // committed text reaches us as regular key events.
+    }
+
+    fn set_ime_cursor_area(&mut self, _area: Rectangle<Twips>) {}
+
     fn language(&self) -> LanguageIdentifier {
         self.language.clone()
     }